@@ -115,6 +115,8 @@ pub enum BinaryOperator {
     Remainder,
     /// `%=`
     RemainderAssign,
+    /// `**`
+    Power,
 }
 
 /// Associativity kind of binary operators
@@ -178,6 +180,7 @@ impl Operator {
             Operator::Asterisk => Some((Multiply, Left)),
             Operator::Slash => Some((Divide, Left)),
             Operator::Percent => Some((Remainder, Left)),
+            Operator::AsteriskAsterisk => Some((Power, Right)),
             _ => None,
         }
     }
@@ -203,7 +206,8 @@ impl Operator {
             LessLess | GreaterGreater => 10,
             Plus | Minus => 11,
             Asterisk | Slash | Percent => 12,
-            Tilde | Bang | PlusPlus | MinusMinus | OpenParen => 13,
+            AsteriskAsterisk => 13,
+            Tilde | Bang | PlusPlus | MinusMinus | OpenParen => 14,
         }
     }
 }
@@ -291,6 +295,12 @@ pub enum SyntaxError {
     /// Other error in operator usage
     #[error("invalid use of operator")]
     InvalidOperator,
+    /// Expression nested deeper than the limit
+    #[error("expression too complex")]
+    TooComplex,
+    /// Operator that is not portable
+    #[error("non-portable operator")]
+    NonPortableOperator,
 }
 
 /// Description of an error that occurred during expansion
@@ -311,11 +321,52 @@ impl From<crate::token::Error> for Error {
     }
 }
 
+/// Limit on the nesting depth of an expression
+///
+/// The parser counts the depth of recursion it makes to parse nested
+/// expressions as well as the height of the resulting AST. Either exceeding the
+/// maximum results in [`SyntaxError::TooComplex`].
+#[derive(Clone, Copy, Debug)]
+struct Depth {
+    /// Current depth of recursion
+    current: usize,
+    /// Maximum depth allowed
+    max: usize,
+}
+
+impl Depth {
+    /// Returns the depth one level deeper than `self`.
+    ///
+    /// Returns an error at `location` if the new depth exceeds the maximum.
+    fn deeper(self, location: &Range<usize>) -> Result<Self, Error> {
+        let current = self.current + 1;
+        self.check(current, location)?;
+        Ok(Depth { current, ..self })
+    }
+
+    /// Checks if the given height of an AST is within the maximum.
+    fn check(self, height: usize, location: &Range<usize>) -> Result<(), Error> {
+        if height <= self.max {
+            Ok(())
+        } else {
+            Err(Error {
+                cause: SyntaxError::TooComplex,
+                location: location.clone(),
+            })
+        }
+    }
+}
+
 /// Parses postfix operators
+///
+/// The `height` is that of the operand tree. This function returns the height
+/// of the tree including the parsed operators.
 fn parse_postfix<'a>(
     tokens: &mut PeekableTokens<'a>,
+    mut height: usize,
+    depth: Depth,
     result: &mut Vec<Ast<'a>>,
-) -> Result<(), Error> {
+) -> Result<usize, Error> {
     while let &Ok(Token {
         value: TokenValue::Operator(operator),
         ..
@@ -326,9 +377,11 @@ fn parse_postfix<'a>(
             None => break,
         };
         let location = tokens.next().unwrap().location;
+        height += 1;
+        depth.check(height, &location)?;
         result.push(Ast::Postfix { operator, location });
     }
-    Ok(())
+    Ok(height)
 }
 
 /// Parses a closing parenthesis `")"`.
@@ -358,18 +411,24 @@ fn parse_close_paren(
 ///
 /// A leaf expression is a term or parenthesized expression, optionally modified
 /// by unary operators.
-fn parse_leaf<'a>(tokens: &mut PeekableTokens<'a>, result: &mut Vec<Ast<'a>>) -> Result<(), Error> {
+///
+/// Returns the height of the parsed tree.
+fn parse_leaf<'a>(
+    tokens: &mut PeekableTokens<'a>,
+    depth: Depth,
+    result: &mut Vec<Ast<'a>>,
+) -> Result<usize, Error> {
     let token = tokens.next()?;
     match token.value {
         TokenValue::Term(term) => {
             result.push(Ast::Term(term));
-            parse_postfix(tokens, result)
+            parse_postfix(tokens, 1, depth, result)
         }
 
         TokenValue::Operator(Operator::OpenParen) => {
-            parse_tree(tokens, 1, result)?;
+            let height = parse_tree(tokens, 1, depth.deeper(&token.location)?, result)?;
             parse_close_paren(tokens, token.location)?;
-            parse_postfix(tokens, result)
+            parse_postfix(tokens, height, depth, result)
         }
 
         TokenValue::Operator(operator) => {
@@ -382,12 +441,13 @@ fn parse_leaf<'a>(tokens: &mut PeekableTokens<'a>, result: &mut Vec<Ast<'a>>) ->
                     });
                 }
             };
-            parse_leaf(tokens, result)?;
+            let height = parse_leaf(tokens, depth.deeper(&token.location)?, result)? + 1;
+            depth.check(height, &token.location)?;
             result.push(Ast::Prefix {
                 operator,
                 location: token.location,
             });
-            Ok(())
+            Ok(height)
         }
 
         TokenValue::EndOfInput => Err(Error {
@@ -399,33 +459,43 @@ fn parse_leaf<'a>(tokens: &mut PeekableTokens<'a>, result: &mut Vec<Ast<'a>>) ->
 
 /// Parses the right-hand-side operand of a binary operation and pushes the
 /// operator to the result.
+///
+/// The `lhs_height` is the height of the left-hand-side operand tree. This
+/// function returns the height of the tree rooted at the binary operator.
 fn parse_binary_rhs<'a>(
     tokens: &mut PeekableTokens<'a>,
     operator: BinaryOperator,
     location: Range<usize>,
     min_precedence: u8,
+    lhs_height: usize,
+    depth: Depth,
     result: &mut Vec<Ast<'a>>,
-) -> Result<(), Error> {
+) -> Result<usize, Error> {
     let old_len = result.len();
-    parse_tree(tokens, min_precedence, result)?;
+    let rhs_height = parse_tree(tokens, min_precedence, depth.deeper(&location)?, result)?;
+    let height = lhs_height.max(rhs_height) + 1;
+    depth.check(height, &location)?;
     result.push(Ast::Binary {
         operator,
         rhs_len: result.len() - old_len,
         location,
     });
-    Ok(())
+    Ok(height)
 }
 
 /// Parses a expression that may contain binary and ternary operators.
 ///
 /// This function consumes binary operators with precedence equal to or greater
 /// than the given minimum precedence, which must be greater than 0.
+///
+/// Returns the height of the parsed tree.
 fn parse_tree<'a>(
     tokens: &mut PeekableTokens<'a>,
     min_precedence: u8,
+    depth: Depth,
     result: &mut Vec<Ast<'a>>,
-) -> Result<(), Error> {
-    parse_leaf(tokens, result)?;
+) -> Result<usize, Error> {
+    let mut height = parse_leaf(tokens, depth, result)?;
 
     while let &Ok(Token {
         value: TokenValue::Operator(operator),
@@ -442,7 +512,7 @@ fn parse_tree<'a>(
         use Operator::*;
         if operator == Question {
             let then_index = result.len();
-            parse_tree(tokens, 1, result)?;
+            let then_height = parse_tree(tokens, 1, depth.deeper(&location)?, result)?;
 
             // Skip the colon operator
             let token = tokens.next()?;
@@ -456,8 +526,10 @@ fn parse_tree<'a>(
             }
 
             let else_index = result.len();
-            parse_tree(tokens, precedence, result)?;
+            let else_height = parse_tree(tokens, precedence, depth.deeper(&location)?, result)?;
 
+            height = height.max(then_height).max(else_height) + 1;
+            depth.check(height, &location)?;
             result.push(Ast::Conditional {
                 then_len: else_index - then_index,
                 else_len: result.len() - else_index,
@@ -475,9 +547,17 @@ fn parse_tree<'a>(
                 });
             }
         };
-        parse_binary_rhs(tokens, operator, location, rhs_precedence, result)?
+        height = parse_binary_rhs(
+            tokens,
+            operator,
+            location,
+            rhs_precedence,
+            height,
+            depth,
+            result,
+        )?;
     }
-    Ok(())
+    Ok(height)
 }
 
 /// Ensures there is no more token.
@@ -504,20 +584,50 @@ fn parse_end_of_input(tokens: &mut PeekableTokens) -> Result<(), Error> {
 ///
 /// A successful parse is returned as a non-empty vector of `Ast` nodes, where
 /// the last node is the root.
-pub fn parse(mut tokens: PeekableTokens) -> Result<Vec<Ast>, Error> {
+///
+/// The `max_depth` limits the nesting depth of the expression. If the
+/// expression is nested deeper, this function returns
+/// [`SyntaxError::TooComplex`] so that neither parsing nor evaluating the
+/// expression overflows the stack.
+pub fn parse(mut tokens: PeekableTokens, max_depth: usize) -> Result<Vec<Ast>, Error> {
     let mut result = Vec::new();
-    parse_tree(&mut tokens, 1, &mut result)?;
+    let depth = Depth {
+        current: 0,
+        max: max_depth,
+    };
+    parse_tree(&mut tokens, 1, depth, &mut result)?;
     parse_end_of_input(&mut tokens)?;
     Ok(result)
 }
 
+/// Ensures the parsed expression contains no non-portable operators.
+///
+/// Currently, the `**` operator is the only operator rejected by this
+/// function.
+pub fn reject_non_portable(ast: &[Ast]) -> Result<(), Error> {
+    for node in ast {
+        if let Ast::Binary {
+            operator: BinaryOperator::Power,
+            location,
+            ..
+        } = node
+        {
+            return Err(Error {
+                cause: SyntaxError::NonPortableOperator,
+                location: location.clone(),
+            });
+        }
+    }
+    Ok(())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use crate::token::Value;
 
     fn parse_str(source: &str) -> Result<Vec<Ast<'_>>, Error> {
-        parse(PeekableTokens::from(source))
+        parse(PeekableTokens::from(source), crate::DEFAULT_MAX_DEPTH)
     }
 
     #[test]
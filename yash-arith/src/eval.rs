@@ -35,18 +35,15 @@ pub enum EvalError<E1, E2> {
     /// A variable value that is not a valid number
     #[error("invalid variable value: {0:?}")]
     InvalidVariableValue(String),
-    /// Result out of bounds
-    #[error("overflow")]
-    Overflow,
     /// Division by zero
     #[error("division by zero")]
     DivisionByZero,
-    /// Left bit-shifting with a negative left-hand-side operand
-    #[error("left-shifting a negative integer")]
-    LeftShiftingNegative,
     /// Bit-shifting with a negative right-hand-side operand
     #[error("negative shift width")]
     ReverseShifting,
+    /// Exponentiation with a negative right-hand-side operand
+    #[error("negative exponent")]
+    NegativeExponent,
     /// Assignment with a left-hand-side operand not being a variable
     #[error("assignment to a non-variable")]
     AssignmentToValue,
@@ -125,17 +122,6 @@ fn require_variable<'a, E1, E2>(
     }
 }
 
-/// Extracts a successful computation result or returns an overflow error.
-fn unwrap_or_overflow<T, E1, E2>(
-    checked_computation: Option<T>,
-    location: &Range<usize>,
-) -> Result<T, Error<E1, E2>> {
-    checked_computation.ok_or_else(|| Error {
-        cause: EvalError::Overflow,
-        location: location.clone(),
-    })
-}
-
 /// Assigns a value to a variable and returns the value.
 fn assign<E: Env>(
     name: &str,
@@ -164,8 +150,7 @@ fn apply_prefix<E: Env>(
             let (name, location) = require_variable(term, op_location)?;
             match expand_variable(name, &location, env)? {
                 Value::Integer(value) => {
-                    let new_value =
-                        Value::Integer(unwrap_or_overflow(value.checked_add(1), op_location)?);
+                    let new_value = Value::Integer(value.wrapping_add(1));
                     assign(name, new_value, location, env)
                 }
             }
@@ -174,21 +159,14 @@ fn apply_prefix<E: Env>(
             let (name, location) = require_variable(term, op_location)?;
             match expand_variable(name, &location, env)? {
                 Value::Integer(value) => {
-                    let new_value =
-                        Value::Integer(unwrap_or_overflow(value.checked_sub(1), op_location)?);
+                    let new_value = Value::Integer(value.wrapping_sub(1));
                     assign(name, new_value, location, env)
                 }
             }
         }
         PrefixOperator::NumericCoercion => into_value(term, env),
         PrefixOperator::NumericNegation => match into_value(term, env)? {
-            Value::Integer(value) => match value.checked_neg() {
-                Some(result) => Ok(Value::Integer(result)),
-                None => Err(Error {
-                    cause: EvalError::Overflow,
-                    location: op_location.clone(),
-                }),
-            },
+            Value::Integer(value) => Ok(Value::Integer(value.wrapping_neg())),
         },
         PrefixOperator::LogicalNegation => match into_value(term, env)? {
             Value::Integer(value) => Ok(Value::Integer((value == 0) as _)),
@@ -209,21 +187,41 @@ fn apply_postfix<E: Env>(
     let (name, location) = require_variable(term, op_location)?;
     match expand_variable(name, &location, env)? {
         old_value @ Value::Integer(value) => {
-            let result = match operator {
-                PostfixOperator::Increment => value.checked_add(1),
-                PostfixOperator::Decrement => value.checked_sub(1),
-            };
-            let new_value = Value::Integer(unwrap_or_overflow(result, op_location)?);
+            let new_value = Value::Integer(match operator {
+                PostfixOperator::Increment => value.wrapping_add(1),
+                PostfixOperator::Decrement => value.wrapping_sub(1),
+            });
             assign(name, new_value, location, env)?;
             Ok(old_value)
         }
     }
 }
 
+/// Computes `base` raised to the power of `exponent`, wrapping around on
+/// overflow.
+///
+/// Returns `None` if `exponent` is negative.
+fn wrapping_pow(base: i64, exponent: i64) -> Option<i64> {
+    let mut exponent = u64::try_from(exponent).ok()?;
+    let mut base = base;
+    let mut result = 1i64;
+    while exponent > 0 {
+        if exponent & 1 != 0 {
+            result = result.wrapping_mul(base);
+        }
+        base = base.wrapping_mul(base);
+        exponent >>= 1;
+    }
+    Some(result)
+}
+
 /// Computes the result value of a binary operator.
 ///
 /// If `operator` is a compound assignment operator, this function only computes
 /// the result value without performing assignment.
+///
+/// Arithmetic is performed on signed 64-bit integers with two's complement
+/// wrapping on overflow. The shift width of `<<` and `>>` is taken modulo 64.
 fn binary_result<E1, E2>(
     lhs: Value,
     rhs: Value,
@@ -231,14 +229,16 @@ fn binary_result<E1, E2>(
     op_location: &Range<usize>,
 ) -> Result<Value, Error<E1, E2>> {
     fn require_non_negative<E1, E2>(v: i64, location: &Range<usize>) -> Result<u32, Error<E1, E2>> {
-        v.try_into().map_err(|_| Error {
-            cause: if v < 0 {
-                EvalError::ReverseShifting
-            } else {
-                EvalError::Overflow
-            },
-            location: location.clone(),
-        })
+        if v >= 0 {
+            // Truncation preserves the value modulo 64, which is all that
+            // matters to the wrapping shift operations.
+            Ok(v as u32)
+        } else {
+            Err(Error {
+                cause: EvalError::ReverseShifting,
+                location: location.clone(),
+            })
+        }
     }
     fn require_non_zero<E1, E2>(v: i64, location: &Range<usize>) -> Result<(), Error<E1, E2>> {
         if v != 0 {
@@ -255,46 +255,42 @@ fn binary_result<E1, E2>(
     let Value::Integer(rhs) = rhs;
     use BinaryOperator::*;
     let result = match operator {
-        LogicalOr => Some((lhs != 0 || rhs != 0) as _),
-        LogicalAnd => Some((lhs != 0 && rhs != 0) as _),
-        BitwiseOr | BitwiseOrAssign => Some(lhs | rhs),
-        BitwiseXor | BitwiseXorAssign => Some(lhs ^ rhs),
-        BitwiseAnd | BitwiseAndAssign => Some(lhs & rhs),
-        EqualTo => Some((lhs == rhs) as _),
-        NotEqualTo => Some((lhs != rhs) as _),
-        LessThan => Some((lhs < rhs) as _),
-        GreaterThan => Some((lhs > rhs) as _),
-        LessThanOrEqualTo => Some((lhs <= rhs) as _),
-        GreaterThanOrEqualTo => Some((lhs >= rhs) as _),
+        LogicalOr => (lhs != 0 || rhs != 0) as _,
+        LogicalAnd => (lhs != 0 && rhs != 0) as _,
+        BitwiseOr | BitwiseOrAssign => lhs | rhs,
+        BitwiseXor | BitwiseXorAssign => lhs ^ rhs,
+        BitwiseAnd | BitwiseAndAssign => lhs & rhs,
+        EqualTo => (lhs == rhs) as _,
+        NotEqualTo => (lhs != rhs) as _,
+        LessThan => (lhs < rhs) as _,
+        GreaterThan => (lhs > rhs) as _,
+        LessThanOrEqualTo => (lhs <= rhs) as _,
+        GreaterThanOrEqualTo => (lhs >= rhs) as _,
         ShiftLeft | ShiftLeftAssign => {
-            if lhs < 0 {
-                return Err(Error {
-                    cause: EvalError::LeftShiftingNegative,
-                    location: op_location.clone(),
-                });
-            }
             let rhs = require_non_negative(rhs, op_location)?;
-            lhs.checked_shl(rhs)
-                .filter(|&result| result >= 0 && result >> rhs == lhs)
+            lhs.wrapping_shl(rhs)
         }
         ShiftRight | ShiftRightAssign => {
             let rhs = require_non_negative(rhs, op_location)?;
-            lhs.checked_shr(rhs)
+            lhs.wrapping_shr(rhs)
         }
-        Add | AddAssign => lhs.checked_add(rhs),
-        Subtract | SubtractAssign => lhs.checked_sub(rhs),
-        Multiply | MultiplyAssign => lhs.checked_mul(rhs),
+        Add | AddAssign => lhs.wrapping_add(rhs),
+        Subtract | SubtractAssign => lhs.wrapping_sub(rhs),
+        Multiply | MultiplyAssign => lhs.wrapping_mul(rhs),
         Divide | DivideAssign => {
             require_non_zero(rhs, op_location)?;
-            lhs.checked_div(rhs)
+            lhs.wrapping_div(rhs)
         }
         Remainder | RemainderAssign => {
             require_non_zero(rhs, op_location)?;
-            lhs.checked_rem(rhs)
+            lhs.wrapping_rem(rhs)
         }
-        Assign => Some(rhs),
+        Power => wrapping_pow(lhs, rhs).ok_or_else(|| Error {
+            cause: EvalError::NegativeExponent,
+            location: op_location.clone(),
+        })?,
+        Assign => rhs,
     };
-    let result = unwrap_or_overflow(result, op_location)?;
     Ok(Value::Integer(result))
 }
 
@@ -310,7 +306,7 @@ fn apply_binary<'a, E: Env>(
     match operator {
         LogicalOr | LogicalAnd | BitwiseOr | BitwiseXor | BitwiseAnd | EqualTo | NotEqualTo
         | LessThan | GreaterThan | LessThanOrEqualTo | GreaterThanOrEqualTo | ShiftLeft
-        | ShiftRight | Add | Subtract | Multiply | Divide | Remainder => {
+        | ShiftRight | Add | Subtract | Multiply | Divide | Remainder | Power => {
             let lhs = into_value(lhs, env)?;
             let rhs = into_value(rhs, env)?;
             binary_result(lhs, rhs, operator, op_location)
@@ -480,7 +476,7 @@ mod tests {
     }
 
     #[test]
-    fn apply_prefix_increment_overflow() {
+    fn apply_prefix_increment_wraps_around() {
         let env = &mut HashMap::new();
         env.insert("i".to_string(), "9223372036854775807".to_string());
         assert_eq!(
@@ -493,11 +489,9 @@ mod tests {
                 &(3..5),
                 env
             ),
-            Err(Error {
-                cause: EvalError::Overflow,
-                location: 3..5,
-            })
+            Ok(Value::Integer(i64::MIN))
         );
+        assert_eq!(env["i"], "-9223372036854775808");
     }
 
     #[test]
@@ -551,7 +545,7 @@ mod tests {
     }
 
     #[test]
-    fn apply_prefix_decrement_overflow() {
+    fn apply_prefix_decrement_wraps_around() {
         let env = &mut HashMap::new();
         env.insert("i".to_string(), "-9223372036854775808".to_string());
         assert_eq!(
@@ -564,11 +558,9 @@ mod tests {
                 &(3..5),
                 env
             ),
-            Err(Error {
-                cause: EvalError::Overflow,
-                location: 3..5,
-            })
+            Ok(Value::Integer(i64::MAX))
         );
+        assert_eq!(env["i"], "9223372036854775807");
     }
 
     #[test]
@@ -639,7 +631,7 @@ mod tests {
     }
 
     #[test]
-    fn apply_prefix_numeric_negation_overflow() {
+    fn apply_prefix_numeric_negation_wraps_around() {
         let env = &mut HashMap::new();
         assert_eq!(
             apply_prefix(
@@ -648,10 +640,7 @@ mod tests {
                 &(3..4),
                 env
             ),
-            Err(Error {
-                cause: EvalError::Overflow,
-                location: 3..4,
-            })
+            Ok(Value::Integer(i64::MIN))
         );
     }
 
@@ -739,7 +728,7 @@ mod tests {
     }
 
     #[test]
-    fn apply_postfix_increment_overflow() {
+    fn apply_postfix_increment_wraps_around() {
         let env = &mut HashMap::new();
         env.insert("i".to_string(), "9223372036854775807".to_string());
         assert_eq!(
@@ -752,11 +741,9 @@ mod tests {
                 &(3..5),
                 env
             ),
-            Err(Error {
-                cause: EvalError::Overflow,
-                location: 3..5,
-            })
+            Ok(Value::Integer(i64::MAX))
         );
+        assert_eq!(env["i"], "-9223372036854775808");
     }
 
     #[test]
@@ -810,7 +797,7 @@ mod tests {
     }
 
     #[test]
-    fn apply_postfix_decrement_overflow() {
+    fn apply_postfix_decrement_wraps_around() {
         let env = &mut HashMap::new();
         env.insert("i".to_string(), "-9223372036854775808".to_string());
         assert_eq!(
@@ -823,11 +810,9 @@ mod tests {
                 &(3..5),
                 env
             ),
-            Err(Error {
-                cause: EvalError::Overflow,
-                location: 3..5,
-            })
+            Ok(Value::Integer(i64::MIN))
         );
+        assert_eq!(env["i"], "9223372036854775807");
     }
 
     #[test]
@@ -1062,17 +1047,11 @@ mod tests {
 
     #[test]
     fn binary_result_shift_left_negative_lhs() {
-        let lhs = Value::Integer(-1);
-        let rhs = Value::Integer(0);
+        let lhs = Value::Integer(-3);
+        let rhs = Value::Integer(1);
         for operator in [BinaryOperator::ShiftLeft, BinaryOperator::ShiftLeftAssign] {
             let result = binary_result::<Infallible, Infallible>(lhs, rhs, operator, &(3..4));
-            assert_eq!(
-                result,
-                Err(Error {
-                    cause: EvalError::LeftShiftingNegative,
-                    location: 3..4,
-                })
-            );
+            assert_eq!(result, Ok(Value::Integer(-6)));
         }
     }
 
@@ -1093,52 +1072,34 @@ mod tests {
     }
 
     #[test]
-    fn binary_result_shift_left_too_large_rhs() {
-        let lhs = Value::Integer(0);
-        for rhs in [i64::BITS as i64, i64::MAX] {
+    fn binary_result_shift_left_width_modulo_64() {
+        let lhs = Value::Integer(1);
+        for (rhs, expected) in [(64, 1), (65, 2), (i64::MAX, i64::MIN)] {
             let rhs = Value::Integer(rhs);
             for operator in [BinaryOperator::ShiftLeft, BinaryOperator::ShiftLeftAssign] {
                 let result = binary_result::<Infallible, Infallible>(lhs, rhs, operator, &(3..4));
-                assert_eq!(
-                    result,
-                    Err(Error {
-                        cause: EvalError::Overflow,
-                        location: 3..4,
-                    })
-                );
+                assert_eq!(result, Ok(Value::Integer(expected)));
             }
         }
     }
 
     #[test]
-    fn binary_result_shift_left_overflow_to_sign_bit() {
+    fn binary_result_shift_left_into_sign_bit() {
         let lhs = Value::Integer(0x4000_0000_0000_0000);
         let rhs = Value::Integer(1);
         for operator in [BinaryOperator::ShiftLeft, BinaryOperator::ShiftLeftAssign] {
             let result = binary_result::<Infallible, Infallible>(lhs, rhs, operator, &(3..4));
-            assert_eq!(
-                result,
-                Err(Error {
-                    cause: EvalError::Overflow,
-                    location: 3..4,
-                })
-            );
+            assert_eq!(result, Ok(Value::Integer(i64::MIN)));
         }
     }
 
     #[test]
-    fn binary_result_shift_left_overflow_beyond_sign_bit() {
+    fn binary_result_shift_left_beyond_sign_bit() {
         let lhs = Value::Integer(0x4000_0000_0000_0000);
         let rhs = Value::Integer(2);
         for operator in [BinaryOperator::ShiftLeft, BinaryOperator::ShiftLeftAssign] {
             let result = binary_result::<Infallible, Infallible>(lhs, rhs, operator, &(3..4));
-            assert_eq!(
-                result,
-                Err(Error {
-                    cause: EvalError::Overflow,
-                    location: 3..4,
-                })
-            );
+            assert_eq!(result, Ok(Value::Integer(0)));
         }
     }
 
@@ -1169,18 +1130,12 @@ mod tests {
     }
 
     #[test]
-    fn binary_result_shift_right_too_large_rhs() {
-        let lhs = Value::Integer(0);
-        let rhs = Value::Integer(i64::BITS as _);
+    fn binary_result_shift_right_width_modulo_64() {
+        let lhs = Value::Integer(0x100);
+        let rhs = Value::Integer(i64::BITS as i64 + 4);
         for operator in [BinaryOperator::ShiftRight, BinaryOperator::ShiftRightAssign] {
             let result = binary_result::<Infallible, Infallible>(lhs, rhs, operator, &(3..4));
-            assert_eq!(
-                result,
-                Err(Error {
-                    cause: EvalError::Overflow,
-                    location: 3..4,
-                })
-            );
+            assert_eq!(result, Ok(Value::Integer(0x10)));
         }
     }
 
@@ -1195,18 +1150,12 @@ mod tests {
     }
 
     #[test]
-    fn binary_result_add_overflow() {
+    fn binary_result_add_wraps_around() {
         let lhs = Value::Integer(i64::MIN);
         let rhs = Value::Integer(-1);
         for operator in [BinaryOperator::Add, BinaryOperator::AddAssign] {
             let result = binary_result::<Infallible, Infallible>(lhs, rhs, operator, &(3..4));
-            assert_eq!(
-                result,
-                Err(Error {
-                    cause: EvalError::Overflow,
-                    location: 3..4,
-                })
-            );
+            assert_eq!(result, Ok(Value::Integer(i64::MAX)));
         }
     }
 
@@ -1221,18 +1170,12 @@ mod tests {
     }
 
     #[test]
-    fn binary_result_subtract_overflow() {
+    fn binary_result_subtract_wraps_around() {
         let lhs = Value::Integer(i64::MAX);
         let rhs = Value::Integer(-1);
         for operator in [BinaryOperator::Subtract, BinaryOperator::SubtractAssign] {
             let result = binary_result::<Infallible, Infallible>(lhs, rhs, operator, &(3..4));
-            assert_eq!(
-                result,
-                Err(Error {
-                    cause: EvalError::Overflow,
-                    location: 3..4,
-                })
-            );
+            assert_eq!(result, Ok(Value::Integer(i64::MIN)));
         }
     }
 
@@ -1247,18 +1190,12 @@ mod tests {
     }
 
     #[test]
-    fn binary_result_multiply_overflow() {
+    fn binary_result_multiply_wraps_around() {
         let lhs = Value::Integer(0x4000_0000_0000_0000);
         let rhs = Value::Integer(2);
         for operator in [BinaryOperator::Multiply, BinaryOperator::MultiplyAssign] {
             let result = binary_result::<Infallible, Infallible>(lhs, rhs, operator, &(3..4));
-            assert_eq!(
-                result,
-                Err(Error {
-                    cause: EvalError::Overflow,
-                    location: 3..4,
-                })
-            );
+            assert_eq!(result, Ok(Value::Integer(i64::MIN)));
         }
     }
 
@@ -1273,18 +1210,12 @@ mod tests {
     }
 
     #[test]
-    fn binary_result_divide_overflow() {
+    fn binary_result_divide_wraps_around() {
         let lhs = Value::Integer(i64::MIN);
         let rhs = Value::Integer(-1);
         for operator in [BinaryOperator::Divide, BinaryOperator::DivideAssign] {
             let result = binary_result::<Infallible, Infallible>(lhs, rhs, operator, &(3..4));
-            assert_eq!(
-                result,
-                Err(Error {
-                    cause: EvalError::Overflow,
-                    location: 3..4,
-                })
-            );
+            assert_eq!(result, Ok(Value::Integer(i64::MIN)));
         }
     }
 
@@ -1315,18 +1246,12 @@ mod tests {
     }
 
     #[test]
-    fn binary_result_remainder_overflow() {
+    fn binary_result_remainder_wraps_around() {
         let lhs = Value::Integer(i64::MIN);
         let rhs = Value::Integer(-1);
         for operator in [BinaryOperator::Remainder, BinaryOperator::RemainderAssign] {
             let result = binary_result::<Infallible, Infallible>(lhs, rhs, operator, &(3..4));
-            assert_eq!(
-                result,
-                Err(Error {
-                    cause: EvalError::Overflow,
-                    location: 3..4,
-                })
-            );
+            assert_eq!(result, Ok(Value::Integer(0)));
         }
     }
 
@@ -1346,6 +1271,43 @@ mod tests {
         }
     }
 
+    #[test]
+    fn binary_result_power() {
+        for (lhs, rhs, expected) in [(3, 4, 81), (-2, 3, -8), (5, 0, 1), (0, 0, 1)] {
+            let (lhs, rhs) = (Value::Integer(lhs), Value::Integer(rhs));
+            let result =
+                binary_result::<Infallible, Infallible>(lhs, rhs, BinaryOperator::Power, &(3..5));
+            assert_eq!(result, Ok(Value::Integer(expected)));
+        }
+    }
+
+    #[test]
+    fn binary_result_power_wraps_around() {
+        let lhs = Value::Integer(2);
+        for (rhs, expected) in [(63, i64::MIN), (64, 0), (i64::MAX, 0)] {
+            let rhs = Value::Integer(rhs);
+            let result =
+                binary_result::<Infallible, Infallible>(lhs, rhs, BinaryOperator::Power, &(3..5));
+            assert_eq!(result, Ok(Value::Integer(expected)));
+        }
+    }
+
+    #[test]
+    fn binary_result_power_negative_exponent() {
+        for (lhs, rhs) in [(2, -1), (1, -5), (-1, -3), (0, -1), (0, i64::MIN)] {
+            let (lhs, rhs) = (Value::Integer(lhs), Value::Integer(rhs));
+            let result =
+                binary_result::<Infallible, Infallible>(lhs, rhs, BinaryOperator::Power, &(3..5));
+            assert_eq!(
+                result,
+                Err(Error {
+                    cause: EvalError::NegativeExponent,
+                    location: 3..5,
+                })
+            );
+        }
+    }
+
     #[test]
     fn apply_binary_add() {
         let env = &mut HashMap::new();
@@ -1358,20 +1320,14 @@ mod tests {
     }
 
     #[test]
-    fn apply_binary_add_overflow() {
+    fn apply_binary_add_wraps_around() {
         let env = &mut HashMap::new();
         let lhs = Term::Value(Value::Integer(i64::MAX));
         let rhs = Term::Value(Value::Integer(1));
         let operator = BinaryOperator::Add;
         let op_location = 4..5;
         let result = apply_binary(lhs, rhs, operator, &op_location, env);
-        assert_eq!(
-            result,
-            Err(Error {
-                cause: EvalError::Overflow,
-                location: 4..5,
-            })
-        );
+        assert_eq!(result, Ok(Value::Integer(i64::MIN)));
     }
 
     #[test]
@@ -1386,20 +1342,14 @@ mod tests {
     }
 
     #[test]
-    fn apply_binary_subtract_overflow() {
+    fn apply_binary_subtract_wraps_around() {
         let env = &mut HashMap::new();
         let lhs = Term::Value(Value::Integer(i64::MIN));
         let rhs = Term::Value(Value::Integer(1));
         let operator = BinaryOperator::Subtract;
         let op_location = 4..5;
         let result = apply_binary(lhs, rhs, operator, &op_location, env);
-        assert_eq!(
-            result,
-            Err(Error {
-                cause: EvalError::Overflow,
-                location: 4..5,
-            })
-        );
+        assert_eq!(result, Ok(Value::Integer(i64::MAX)));
     }
 
     #[test]
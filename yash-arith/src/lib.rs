@@ -99,13 +99,100 @@ impl<E1, E2> From<eval::Error<E1, E2>> for Error<E1, E2> {
     }
 }
 
+/// Default limit on the nesting depth of an expression
+///
+/// This is the limit used by [`eval()`]. Use [`eval_with_max_depth`] to
+/// specify another limit.
+pub const DEFAULT_MAX_DEPTH: usize = 256;
+
+/// Configuration for [`eval_with_config`]
+///
+/// The default configuration has [`DEFAULT_MAX_DEPTH`] as the limit on the
+/// nesting depth and allows non-portable operators.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+#[non_exhaustive]
+pub struct Config {
+    /// Limit on the nesting depth of the expression
+    ///
+    /// See [`eval_with_max_depth`] for details.
+    pub max_depth: usize,
+    /// Whether non-portable operators are rejected
+    ///
+    /// If `true`, operators that are not required by POSIX, such as `**`,
+    /// cause [`SyntaxError::NonPortableOperator`].
+    pub portable: bool,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Config {
+            max_depth: DEFAULT_MAX_DEPTH,
+            portable: false,
+        }
+    }
+}
+
 /// Performs arithmetic expansion
+///
+/// This function is equivalent to [`eval_with_max_depth`] with
+/// [`DEFAULT_MAX_DEPTH`].
 pub fn eval<E: Env>(
     expression: &str,
     env: &mut E,
+) -> Result<Value, Error<E::GetVariableError, E::AssignVariableError>> {
+    eval_with_max_depth(expression, env, DEFAULT_MAX_DEPTH)
+}
+
+/// Performs arithmetic expansion with a limit on the nesting depth
+///
+/// Parenthesized expressions, operators, and their operands nest in an
+/// expression. If the expression is nested deeper than `max_depth`, this
+/// function fails with [`SyntaxError::TooComplex`] instead of overflowing the
+/// stack.
+///
+/// ```
+/// use std::collections::HashMap;
+/// use yash_arith::{ErrorCause, SyntaxError, Value, eval_with_max_depth};
+/// let mut env = HashMap::new();
+/// let result = eval_with_max_depth("((1))", &mut env, 2);
+/// assert_eq!(result, Ok(Value::Integer(1)));
+/// let result = eval_with_max_depth("(((1)))", &mut env, 2);
+/// assert_eq!(result.unwrap_err().cause, ErrorCause::SyntaxError(SyntaxError::TooComplex));
+/// ```
+pub fn eval_with_max_depth<E: Env>(
+    expression: &str,
+    env: &mut E,
+    max_depth: usize,
+) -> Result<Value, Error<E::GetVariableError, E::AssignVariableError>> {
+    let config = Config {
+        max_depth,
+        ..Config::default()
+    };
+    eval_with_config(expression, env, config)
+}
+
+/// Performs arithmetic expansion with the given configuration
+///
+/// ```
+/// use std::collections::HashMap;
+/// use yash_arith::{Config, ErrorCause, SyntaxError, Value, eval_with_config};
+/// let mut env = HashMap::new();
+/// let mut config = Config::default();
+/// assert_eq!(eval_with_config("2 ** 3", &mut env, config), Ok(Value::Integer(8)));
+/// config.portable = true;
+/// let result = eval_with_config("2 ** 3", &mut env, config);
+/// assert_eq!(result.unwrap_err().cause, ErrorCause::SyntaxError(SyntaxError::NonPortableOperator));
+/// ```
+pub fn eval_with_config<E: Env>(
+    expression: &str,
+    env: &mut E,
+    config: Config,
 ) -> Result<Value, Error<E::GetVariableError, E::AssignVariableError>> {
     let tokens = PeekableTokens::from(expression);
-    let ast = ast::parse(tokens)?;
+    let ast = ast::parse(tokens, config.max_depth)?;
+    if config.portable {
+        ast::reject_non_portable(&ast)?;
+    }
     let term = eval::eval(&ast, env)?;
     let value = eval::into_value(term, env)?;
     Ok(value)
@@ -406,67 +493,34 @@ mod tests {
     }
 
     #[test]
-    fn overflow_in_bit_shifting() {
+    fn bit_shifting_beyond_width() {
         let env = &mut HashMap::new();
         assert_eq!(
             eval("0x4000000000000000<<1", env),
-            Err(Error {
-                cause: EvalError::Overflow.into(),
-                location: 18..20,
-            })
-        );
-        assert_eq!(
-            eval("0<<1000", env),
-            Err(Error {
-                cause: EvalError::Overflow.into(),
-                location: 1..3,
-            })
-        );
-        assert_eq!(
-            eval("0<<0x100000000", env),
-            Err(Error {
-                cause: EvalError::Overflow.into(),
-                location: 1..3,
-            })
-        );
-
-        assert_eq!(
-            eval("0>>1000", env),
-            Err(Error {
-                cause: EvalError::Overflow.into(),
-                location: 1..3,
-            })
-        );
-        assert_eq!(
-            eval("0>>0x100000000", env),
-            Err(Error {
-                cause: EvalError::Overflow.into(),
-                location: 1..3,
-            })
+            Ok(Value::Integer(i64::MIN))
         );
+        assert_eq!(eval("0x4000000000000000<<2", env), Ok(Value::Integer(0)));
+
+        // The shift width is taken modulo 64.
+        assert_eq!(eval("1<<64", env), Ok(Value::Integer(1)));
+        assert_eq!(eval("1<<1000", env), Ok(Value::Integer(1 << (1000 % 64))));
+        assert_eq!(eval("3<<0x100000000", env), Ok(Value::Integer(3)));
+        assert_eq!(eval("8>>65", env), Ok(Value::Integer(4)));
+        assert_eq!(eval("8>>0x100000000", env), Ok(Value::Integer(8)));
     }
 
     #[test]
     fn bit_shifting_of_negative_values() {
         let env = &mut HashMap::new();
 
-        // Left-shifting a negative value is undefined in C.
-        assert_eq!(
-            eval("-1<<1", env),
-            Err(Error {
-                cause: EvalError::LeftShiftingNegative.into(),
-                location: 2..4,
-            })
-        );
+        // Left-shifting works on the two's complement representation.
+        assert_eq!(eval("-1<<1", env), Ok(Value::Integer(-2)));
         assert_eq!(
             eval("(-0x7FFFFFFFFFFFFFFF-1)<<1", env),
-            Err(Error {
-                cause: EvalError::LeftShiftingNegative.into(),
-                location: 23..25,
-            })
+            Ok(Value::Integer(0))
         );
 
-        // Right-shifting a negative value is implementation-defined in C.
+        // Right-shifting is arithmetic.
         assert_eq!(eval("-4>>1", env), Ok(Value::Integer(-4 >> 1)));
         assert_eq!(eval("-1>>1", env), Ok(Value::Integer(-1 >> 1)));
     }
@@ -500,14 +554,11 @@ mod tests {
     }
 
     #[test]
-    fn overflow_in_addition() {
+    fn addition_wraps_around() {
         let env = &mut HashMap::new();
         assert_eq!(
             eval("9223372036854775807+1", env),
-            Err(Error {
-                cause: EvalError::Overflow.into(),
-                location: 19..20,
-            })
+            Ok(Value::Integer(i64::MIN))
         );
     }
 
@@ -520,14 +571,11 @@ mod tests {
     }
 
     #[test]
-    fn overflow_in_subtraction() {
+    fn subtraction_wraps_around() {
         let env = &mut HashMap::new();
         assert_eq!(
             eval("0-9223372036854775807-2", env),
-            Err(Error {
-                cause: EvalError::Overflow.into(),
-                location: 21..22,
-            })
+            Ok(Value::Integer(i64::MAX))
         );
     }
 
@@ -540,14 +588,11 @@ mod tests {
     }
 
     #[test]
-    fn overflow_in_multiplication() {
+    fn multiplication_wraps_around() {
         let env = &mut HashMap::new();
         assert_eq!(
             eval("0x100000000 * 0x80000000", env),
-            Err(Error {
-                cause: EvalError::Overflow.into(),
-                location: 12..13,
-            })
+            Ok(Value::Integer(i64::MIN))
         );
     }
 
@@ -586,14 +631,11 @@ mod tests {
     }
 
     #[test]
-    fn overflow_in_division() {
+    fn division_wraps_around() {
         let env = &mut HashMap::new();
         assert_eq!(
             eval("(-0x7FFFFFFFFFFFFFFF-1)/-1", env),
-            Err(Error {
-                cause: EvalError::Overflow.into(),
-                location: 23..24,
-            })
+            Ok(Value::Integer(i64::MIN))
         );
     }
 
@@ -632,17 +674,76 @@ mod tests {
     }
 
     #[test]
-    fn overflow_in_remainder() {
+    fn remainder_wraps_around() {
         let env = &mut HashMap::new();
         assert_eq!(
             eval("(-0x7FFFFFFFFFFFFFFF-1)%-1", env),
+            Ok(Value::Integer(0))
+        );
+    }
+
+    #[test]
+    fn power_operator() {
+        let env = &mut HashMap::new();
+        assert_eq!(eval("2**10", env), Ok(Value::Integer(1024)));
+        assert_eq!(eval(" 3 ** 0 ", env), Ok(Value::Integer(1)));
+        // right associative
+        assert_eq!(eval(" 2 ** 3 ** 2 ", env), Ok(Value::Integer(512)));
+        // higher precedence than multiplication, lower than prefix operators
+        assert_eq!(eval(" 3 * 2 ** 2 ", env), Ok(Value::Integer(12)));
+        assert_eq!(eval(" -2 ** 2 ", env), Ok(Value::Integer(4)));
+    }
+
+    #[test]
+    fn power_wraps_around() {
+        let env = &mut HashMap::new();
+        assert_eq!(eval("2**63", env), Ok(Value::Integer(i64::MIN)));
+        assert_eq!(eval("2**64", env), Ok(Value::Integer(0)));
+        assert_eq!(
+            eval("3**41", env),
+            Ok(Value::Integer(3i64.wrapping_pow(41)))
+        );
+    }
+
+    #[test]
+    fn negative_exponent() {
+        let env = &mut HashMap::new();
+        assert_eq!(
+            eval("2**-1", env),
             Err(Error {
-                cause: EvalError::Overflow.into(),
-                location: 23..24,
+                cause: EvalError::NegativeExponent.into(),
+                location: 1..3,
+            })
+        );
+        assert_eq!(
+            eval("0**-1", env),
+            Err(Error {
+                cause: EvalError::NegativeExponent.into(),
+                location: 1..3,
             })
         );
     }
 
+    #[test]
+    fn power_operator_in_portable_mode() {
+        let env = &mut HashMap::new();
+        let config = Config {
+            portable: true,
+            ..Config::default()
+        };
+        assert_eq!(
+            eval_with_config("1 + 2 ** 3", env, config),
+            Err(Error {
+                cause: SyntaxError::NonPortableOperator.into(),
+                location: 6..8,
+            })
+        );
+        assert_eq!(
+            eval_with_config("1 + 2 * 3", env, config),
+            Ok(Value::Integer(7))
+        );
+    }
+
     #[test]
     fn plus_prefix_operator() {
         let env = &mut HashMap::new();
@@ -661,7 +762,7 @@ mod tests {
     }
 
     #[test]
-    fn overflow_in_numeric_negation() {
+    fn numeric_negation_wraps_around() {
         let env = &mut HashMap::new();
         assert_eq!(
             eval("-0x7FFFFFFFFFFFFFFF-1", env),
@@ -669,10 +770,7 @@ mod tests {
         );
         assert_eq!(
             eval(" - (-0x7FFFFFFFFFFFFFFF-1)", env),
-            Err(Error {
-                cause: EvalError::Overflow.into(),
-                location: 1..2
-            })
+            Ok(Value::Integer(i64::MIN))
         );
     }
 
@@ -716,16 +814,11 @@ mod tests {
     }
 
     #[test]
-    fn overflow_in_increment() {
+    fn increment_wraps_around() {
         let env = &mut HashMap::new();
         env.insert("i".to_string(), "9223372036854775807".to_string());
-        assert_eq!(
-            eval("  ++ i", env),
-            Err(Error {
-                cause: EvalError::Overflow.into(),
-                location: 2..4,
-            })
-        );
+        assert_eq!(eval("  ++ i", env), Ok(Value::Integer(i64::MIN)));
+        assert_eq!(env["i"], "-9223372036854775808");
     }
 
     #[test]
@@ -738,16 +831,11 @@ mod tests {
     }
 
     #[test]
-    fn overflow_in_decrement() {
+    fn decrement_wraps_around() {
         let env = &mut HashMap::new();
         env.insert("i".to_string(), "-9223372036854775808".to_string());
-        assert_eq!(
-            eval(" -- i", env),
-            Err(Error {
-                cause: EvalError::Overflow.into(),
-                location: 1..3,
-            })
-        );
+        assert_eq!(eval(" -- i", env), Ok(Value::Integer(i64::MAX)));
+        assert_eq!(env["i"], "9223372036854775807");
     }
 
     #[test]
@@ -792,6 +880,38 @@ mod tests {
         assert_eq!(eval("a", env), Ok(Value::Integer(-3)));
     }
 
+    #[test]
+    fn increment_and_decrement_of_existing_value() {
+        let env = &mut HashMap::new();
+        env.insert("x".to_string(), "5".to_string());
+        assert_eq!(eval("x++", env), Ok(Value::Integer(5)));
+        assert_eq!(env["x"], "6");
+        assert_eq!(eval("++x", env), Ok(Value::Integer(7)));
+        assert_eq!(env["x"], "7");
+        assert_eq!(eval("x--", env), Ok(Value::Integer(7)));
+        assert_eq!(env["x"], "6");
+        assert_eq!(eval("--x", env), Ok(Value::Integer(5)));
+        assert_eq!(env["x"], "5");
+    }
+
+    #[test]
+    fn prefix_increment_of_existing_value() {
+        let env = &mut HashMap::new();
+        env.insert("x".to_string(), "5".to_string());
+        assert_eq!(eval("++x", env), Ok(Value::Integer(6)));
+        assert_eq!(env["x"], "6");
+    }
+
+    #[test]
+    fn increment_in_compound_expression() {
+        let env = &mut HashMap::new();
+        env.insert("x".to_string(), "5".to_string());
+        env.insert("y".to_string(), "5".to_string());
+        assert_eq!(eval("x++ + ++y", env), Ok(Value::Integer(11)));
+        assert_eq!(env["x"], "6");
+        assert_eq!(env["y"], "6");
+    }
+
     #[test]
     fn postfix_decrementing_non_variable() {
         let env = &mut HashMap::new();
@@ -858,4 +978,48 @@ mod tests {
             })
         );
     }
+
+    #[test]
+    fn nesting_up_to_default_max_depth() {
+        let env = &mut HashMap::new();
+        let depth = DEFAULT_MAX_DEPTH - 1;
+        let expression = format!("{}1{}", "(".repeat(depth), ")".repeat(depth));
+        assert_eq!(eval(&expression, env), Ok(Value::Integer(1)));
+        let expression = format!("{}1", "1+".repeat(depth));
+        assert_eq!(eval(&expression, env), Ok(Value::Integer(256)));
+        let expression = format!("{}1", "- ".repeat(depth));
+        assert_eq!(eval(&expression, env), Ok(Value::Integer(-1)));
+    }
+
+    #[test]
+    fn too_deeply_nested_parentheses() {
+        let env = &mut HashMap::new();
+        let expression = format!("{}1{}", "(".repeat(100_000), ")".repeat(100_000));
+        let result = eval(&expression, env);
+        let error = result.unwrap_err();
+        assert_eq!(
+            error.cause,
+            ErrorCause::SyntaxError(SyntaxError::TooComplex)
+        );
+        assert_eq!(error.location, DEFAULT_MAX_DEPTH..DEFAULT_MAX_DEPTH + 1);
+    }
+
+    #[test]
+    fn too_complex_expressions() {
+        let env = &mut HashMap::new();
+        for expression in [
+            format!("{}1", "1+".repeat(100_000)),
+            format!("{}1", "1**".repeat(100_000)),
+            format!("{}1", "!".repeat(100_000)),
+            format!("a{}", "++".repeat(100_000)),
+            format!("{}0", "1?0:".repeat(100_000)),
+        ] {
+            let result = eval(&expression, env);
+            assert_eq!(
+                result.unwrap_err().cause,
+                ErrorCause::SyntaxError(SyntaxError::TooComplex),
+                "{expression:.20}"
+            );
+        }
+    }
 }
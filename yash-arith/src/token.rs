@@ -115,6 +115,8 @@ pub enum Operator {
     Asterisk,
     /// `*=`
     AsteriskEqual,
+    /// `**`
+    AsteriskAsterisk,
     /// `/`
     Slash,
     /// `/=`
@@ -207,6 +209,7 @@ const OPERATORS: &[(&str, Operator)] = &[
     ("--", Operator::MinusMinus),
     ("-", Operator::Minus),
     ("*=", Operator::AsteriskEqual),
+    ("**", Operator::AsteriskAsterisk),
     ("*", Operator::Asterisk),
     ("/=", Operator::SlashEqual),
     ("/", Operator::Slash),
@@ -760,6 +763,13 @@ mod tests {
                 location: 0..2,
             }))
         );
+        assert_eq!(
+            Tokens::new("**").next(),
+            Some(Ok(Token {
+                value: TokenValue::Operator(Operator::AsteriskAsterisk),
+                location: 0..2,
+            }))
+        );
         assert_eq!(
             Tokens::new("/").next(),
             Some(Ok(Token {
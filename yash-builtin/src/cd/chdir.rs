@@ -16,12 +16,13 @@
 
 //! Part of the cd built-in that invokes the underlying system call
 
-use crate::common::report::prepare_report_message_and_divert;
+use crate::common::report::{prepare_report_and_divert, prepare_report_message_and_divert};
 use std::borrow::Cow;
 use std::ffi::CString;
 use std::ffi::NulError;
 use thiserror::Error;
 use yash_env::Env;
+use yash_env::io::print_report;
 use yash_env::path::Path;
 use yash_env::semantics::Field;
 use yash_env::source::Location;
@@ -69,24 +70,33 @@ pub fn failure_message<S: Isatty>(
     path: &Path,
     error: &Error,
 ) -> (String, yash_env::semantics::Result) {
-    let location = operand
+    let location = failure_location(env, operand);
+    let report = failure_report(&location, path, error);
+    prepare_report_message_and_divert(env, report)
+}
+
+/// Returns the location to be annotated in the failure message.
+fn failure_location<'a, S>(env: &'a Env<S>, operand: Option<&'a Field>) -> Cow<'a, Location> {
+    operand
         .or_else(|| env.stack.current_builtin().map(|builtin| &builtin.name))
         .map(|field| Cow::Borrowed(&field.origin))
-        .unwrap_or_else(|| Cow::Owned(Location::dummy("")));
+        .unwrap_or_else(|| Cow::Owned(Location::dummy("")))
+}
+
+/// Creates a report that describes the failure.
+fn failure_report<'a>(location: &'a Location, path: &Path, error: &Error) -> Report<'a> {
     let label = Cow::Owned(format!("{path:?}: {error}"));
     let mut report = Report::new();
     report.r#type = ReportType::Error;
     report.title = "cannot change the working directory".into();
-    report.snippets = Snippet::with_primary_span(&location, label);
-    prepare_report_message_and_divert(env, report)
+    report.snippets = Snippet::with_primary_span(location, label);
+    report
 }
 
 /// Prints an error message to the standard error.
 ///
-/// This function constructs a message with [`failure_message`] and prints it
-/// with [`WriteAll::print_error`].
-///
-/// [`WriteAll::print_error`]: yash_env::system::concurrency::WriteAll::print_error
+/// This function constructs the same message as [`failure_message`] and
+/// prints it with [`print_report`].
 pub async fn report_failure<S>(
     env: &mut Env<S>,
     operand: Option<&Field>,
@@ -96,7 +106,9 @@ pub async fn report_failure<S>(
 where
     S: Isatty + WriteAll,
 {
-    let (message, divert) = failure_message(env, operand, path, error);
-    env.system.print_error(&message).await;
+    let location = failure_location(env, operand);
+    let report = failure_report(&location, path, error);
+    let (report, divert) = prepare_report_and_divert(env, report);
+    print_report(env, &report).await;
     crate::Result::with_exit_status_and_divert(super::EXIT_STATUS_CHDIR_ERROR, divert)
 }
@@ -17,9 +17,10 @@
 //! Part of the cd built-in that prints the new working directory
 
 use super::target::Origin;
-use crate::common::report::prepare_report_message_and_divert;
+use crate::common::report::prepare_report_and_divert;
 use yash_env::Env;
 use yash_env::io::Fd;
+use yash_env::io::print_report;
 use yash_env::path::Path;
 use yash_env::source::pretty::{Report, ReportType};
 use yash_env::system::concurrency::WriteAll;
@@ -62,6 +63,6 @@ where
     let mut report = Report::new();
     report.r#type = ReportType::Warning;
     report.title = format!("cannot print new $PWD: {errno}").into();
-    let (message, _divert) = prepare_report_message_and_divert(env, report);
-    env.system.print_error(&message).await;
+    let (report, _divert) = prepare_report_and_divert(env, report);
+    print_report(env, &report).await;
 }
@@ -105,6 +105,7 @@ where
         + Exit
         + Fork
         + GetPid
+        + IsExecutableFile
         + Isatty
         + Open
         + RunBlocking
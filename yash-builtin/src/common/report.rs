@@ -31,36 +31,29 @@ use yash_env::stack::Stack;
 use yash_env::system::Isatty;
 use yash_env::system::concurrency::WriteAll;
 
-/// Convenience function for constructing an error report and a divert value.
+/// Convenience function for annotating an error report and computing a divert
+/// value.
 ///
 /// If the environment is currently executing a built-in
 /// ([`Stack::current_builtin`]), an annotation indicating the built-in name is
-/// appended to the given report. The report is then converted into a string
-/// using [`yash_env::io::report_to_string`] and returned with an appropriate
-/// divert value.
+/// appended to the given report. The annotated report is returned with an
+/// appropriate divert value.
 ///
 /// The [`Divert`] value indicates whether the caller should divert the
 /// execution flow. If the current built-in is a special built-in, the second
 /// return value is `Break(Divert::Interrupt(None))`; otherwise, `Continue(())`.
 ///
-/// You should always use this function (or another function defined in this
-/// module which calls this function) to construct an error or warning message
-/// in a built-in. This ensures that the message contains the built-in name
-/// in a unified format.
-///
-/// Use [`WriteAll::print_error`] to print the returned message and
+/// Use [`yash_env::io::print_report`] to print the returned report and
 /// [`crate::Result::with_exit_status_and_divert`] to return the divert value
-/// along with an exit status.
-///
-/// [`WriteAll::print_error`]: yash_env::system::concurrency::WriteAll::print_error
-#[must_use = "returned message should be printed"]
-pub fn prepare_report_message_and_divert<'e, 'r, S>(
+/// along with an exit status. See also [`prepare_report_message_and_divert`],
+/// which converts the report into a string.
+#[must_use = "returned report should be printed"]
+pub fn prepare_report_and_divert<'e, 'r, S>(
     env: &'e Env<S>,
     mut report: Report<'r>,
-) -> (String, yash_env::semantics::Result)
+) -> (Report<'r>, yash_env::semantics::Result)
 where
     'e: 'r,
-    S: Isatty,
 {
     let is_special_builtin;
 
@@ -82,22 +75,54 @@ where
         is_special_builtin = false;
     }
 
-    let text = yash_env::io::report_to_string(env, &report);
     let divert = if is_special_builtin {
         Break(Divert::Interrupt(None))
     } else {
         Continue(())
     };
+    (report, divert)
+}
+
+/// Convenience function for constructing an error report and a divert value.
+///
+/// The report is annotated by [`prepare_report_and_divert`] and then converted
+/// into a string using [`yash_env::io::report_to_string`]. The string is
+/// returned with an appropriate divert value.
+///
+/// You should always use this function (or another function defined in this
+/// module which calls this function or [`prepare_report_and_divert`]) to
+/// construct an error or warning message in a built-in. This ensures that the
+/// message contains the built-in name in a unified format.
+///
+/// Use [`WriteAll::print_error`] to print the returned message and
+/// [`crate::Result::with_exit_status_and_divert`] to return the divert value
+/// along with an exit status. Note that the message printed this way bypasses
+/// a [`ReportSink`](yash_env::io::ReportSink) installed in the environment.
+/// Prefer [`prepare_report_and_divert`] unless you need the message as a
+/// string.
+///
+/// [`WriteAll::print_error`]: yash_env::system::concurrency::WriteAll::print_error
+#[must_use = "returned message should be printed"]
+pub fn prepare_report_message_and_divert<'e, 'r, S>(
+    env: &'e Env<S>,
+    report: Report<'r>,
+) -> (String, yash_env::semantics::Result)
+where
+    'e: 'r,
+    S: Isatty,
+{
+    let (report, divert) = prepare_report_and_divert(env, report);
+    let text = yash_env::io::report_to_string(env, &report);
     (text, divert)
 }
 
 /// Reports a message with the given exit status.
 ///
 /// This is a convenience function for reporting a message with a specific exit
-/// status. The message is converted to a string and [`Divert`] using
-/// [`prepare_report_message_and_divert`], and then printed to the standard
-/// error. The returned result contains the given exit status and the divert
-/// value.
+/// status. The message is annotated and [`Divert`] is computed using
+/// [`prepare_report_and_divert`], and then the message is printed using
+/// [`yash_env::io::print_report`]. The returned result contains the given exit
+/// status and the divert value.
 ///
 /// When the exit status is [`ExitStatus::FAILURE`] or [`ExitStatus::ERROR`],
 /// you can use [`report_failure`] or [`report_error`] instead of this function,
@@ -106,24 +131,24 @@ where
 /// This function requires a mutable borrow of the environment to print the
 /// message, so it is only usable when the `report` argument does not contain
 /// any borrows from the environment. Otherwise, directly call
-/// [`prepare_report_message_and_divert`], which only requires an immutable
-/// borrow of the environment, to construct the message and divert value, and
-/// then print the message yourself:
+/// [`prepare_report_and_divert`], which only requires an immutable borrow of
+/// the environment, to construct the report and divert value, and then print
+/// the report yourself:
 ///
 /// ```
 /// # use futures_util::future::FutureExt as _;
-/// # use yash_builtin::common::report::prepare_report_message_and_divert;
+/// # use yash_builtin::common::report::prepare_report_and_divert;
 /// # use yash_env::builtin::Result;
+/// # use yash_env::io::print_report;
 /// # use yash_env::semantics::ExitStatus;
 /// # use yash_env::source::pretty::{Report, ReportType, Snippet};
-/// # use yash_env::system::concurrency::WriteAll as _;
 /// # async {
-/// # let mut env = yash_env::Env::new_virtual();
+/// # let env = yash_env::Env::new_virtual();
 /// # let mut report = Report::new();
 /// # report.r#type = ReportType::Error;
 /// # report.title = "cannot assign to read-only variable".into();
-/// let (message, divert) = prepare_report_message_and_divert(&env, report);
-/// env.system.print_error(&message).await;
+/// let (report, divert) = prepare_report_and_divert(&env, report);
+/// print_report(&env, &report).await;
 /// Result::with_exit_status_and_divert(ExitStatus::FAILURE, divert)
 /// # }.now_or_never().unwrap();
 /// ```
@@ -142,8 +167,8 @@ where
         report: Report<'_>,
         exit_status: ExitStatus,
     ) -> yash_env::builtin::Result {
-        let (message, divert) = prepare_report_message_and_divert(env, report);
-        env.system.print_error(&message).await;
+        let (report, divert) = prepare_report_and_divert(env, report);
+        yash_env::io::print_report(env, &report).await;
         yash_env::builtin::Result::with_exit_status_and_divert(exit_status, divert)
     }
     inner(env, report.into(), exit_status).await
@@ -177,7 +202,7 @@ where
 ///
 /// This function constructs a [`Report`] with the given title and prints it
 /// using [`report`]. The message has no annotations except for the built-in
-/// name which is added by [`prepare_report_message_and_divert`].
+/// name which is added by [`prepare_report_and_divert`].
 ///
 /// When the exit status is [`ExitStatus::FAILURE`] or [`ExitStatus::ERROR`],
 /// you can use [`report_simple_failure`] or [`report_simple_error`] instead of
@@ -262,6 +287,10 @@ where
 #[cfg(test)]
 mod tests {
     use super::*;
+    use futures_util::FutureExt as _;
+    use std::cell::RefCell;
+    use std::rc::Rc;
+    use yash_env::io::ReportSink;
     use yash_env::semantics::Field;
     use yash_env::stack::Builtin;
     use yash_env::stack::Frame;
@@ -297,6 +326,23 @@ mod tests {
         assert_eq!(divert, Continue(()));
     }
 
+    #[test]
+    fn report_is_passed_to_sink() {
+        let mut env = Env::new_virtual();
+        let titles = Rc::new(RefCell::new(Vec::new()));
+        let titles_2 = Rc::clone(&titles);
+        env.any
+            .insert(Box::new(ReportSink(Rc::new(move |report: &Report<'_>| {
+                titles_2.borrow_mut().push(report.title.to_string())
+            }))));
+
+        let result = report_simple_failure(&mut env, "some failure")
+            .now_or_never()
+            .unwrap();
+        assert_eq!(result.exit_status(), ExitStatus::FAILURE);
+        assert_eq!(*titles.borrow(), ["some failure"]);
+    }
+
     #[test]
     fn merge_reports_with_common_code() {
         // In this test, report1 and report2 have snippets with overlapping spans in
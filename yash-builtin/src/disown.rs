@@ -0,0 +1,355 @@
+// This file is part of yash, an extended POSIX shell.
+// Copyright (C) 2026 WATANABE Yuki
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+//! Disown built-in
+//!
+//! This module implements the [`disown` built-in], which removes jobs from the
+//! job list so that the shell no longer manages them.
+//!
+//! [`disown` built-in]: https://magicant.github.io/yash-rs/builtins/disown.html
+//!
+//! # Implementation notes
+//!
+//! With the `-h` option, the built-in does not remove the jobs but sets the
+//! [`no_sighup`] flag of the jobs instead.
+//!
+//! [`no_sighup`]: yash_env::job::Job::no_sighup
+
+use crate::common::report::{
+    merge_reports, report_error, report_failure, report_simple_failure, syntax_error,
+};
+use crate::common::syntax::{Mode, OptionSpec, parse_arguments};
+use std::fmt::Display;
+use thiserror::Error;
+use yash_env::Env;
+use yash_env::job::ProcessState;
+use yash_env::job::id::FindError;
+use yash_env::job::id::ParseError;
+use yash_env::job::id::parse;
+use yash_env::semantics::Field;
+use yash_env::source::pretty::{Report, ReportType, Snippet};
+use yash_env::system::Isatty;
+use yash_env::system::concurrency::WriteAll;
+
+/// List of options accepted by the `disown` built-in
+pub const OPTION_SPECS: &[OptionSpec<'static>] = &[
+    OptionSpec::new().short('a').long("all"),
+    OptionSpec::new().short('h').long("no-sighup"),
+    OptionSpec::new().short('r').long("running"),
+];
+
+/// Errors that may occur when processing an operand
+#[derive(Clone, Debug, Error, Eq, PartialEq)]
+enum OperandErrorKind {
+    /// The operand is not a job ID.
+    #[error(transparent)]
+    InvalidJobId(#[from] ParseError),
+    /// The job ID does not specify a single job.
+    #[error(transparent)]
+    UnidentifiedJob(#[from] FindError),
+}
+
+/// An operand and the error that occurred when processing it
+#[derive(Clone, Debug, Error, Eq, PartialEq)]
+struct OperandError(Field, OperandErrorKind);
+
+impl Display for OperandError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}: {}", self.0.value, self.1)
+    }
+}
+
+impl OperandError {
+    /// Converts the error to a [`Report`].
+    #[must_use]
+    pub fn to_report(&self) -> Report<'_> {
+        let mut report = Report::new();
+        report.r#type = ReportType::Error;
+        report.title = "cannot disown job".into();
+        let label = format!("{}: {}", self.0.value, self.1).into();
+        report.snippets = Snippet::with_primary_span(&self.0.origin, label);
+        report
+    }
+}
+
+impl<'a> From<&'a OperandError> for Report<'a> {
+    #[inline]
+    fn from(error: &'a OperandError) -> Self {
+        error.to_report()
+    }
+}
+
+/// Finds the index of the job specified by the operand.
+fn find_job<S>(env: &Env<S>, job_id: &str) -> Result<usize, OperandErrorKind> {
+    Ok(parse(job_id)?.find(&env.jobs)?)
+}
+
+/// Disowns the job at the specified index.
+///
+/// If `no_sighup` is true, the job is only flagged as one that should not be
+/// sent `SIGHUP`. Otherwise, the job is removed from the job list.
+fn disown<S>(env: &mut Env<S>, index: usize, no_sighup: bool) {
+    if no_sighup {
+        if let Some(mut job) = env.jobs.get_mut(index) {
+            job.set_no_sighup();
+        }
+    } else {
+        env.jobs.remove(index);
+    }
+}
+
+/// Entry point of the `disown` built-in
+pub async fn main<S>(env: &mut Env<S>, args: Vec<Field>) -> crate::Result
+where
+    S: Isatty + WriteAll,
+{
+    let (options, operands) = match parse_arguments(OPTION_SPECS, Mode::with_env(env), args) {
+        Ok(result) => result,
+        Err(error) => return report_error(env, &error).await,
+    };
+
+    let mut all = false;
+    let mut no_sighup = false;
+    let mut running_only = false;
+    for option in &options {
+        match option.spec.get_short() {
+            Some('a') => all = true,
+            Some('h') => no_sighup = true,
+            Some('r') => running_only = true,
+            _ => unreachable!("unhandled option: {:?}", option),
+        }
+    }
+
+    if all || running_only {
+        if let Some(operand) = operands.first() {
+            let message = "no operand is allowed with the -a or -r option";
+            return syntax_error(env, message, &operand.origin).await;
+        }
+
+        let indexes = env
+            .jobs
+            .iter()
+            .filter(|(_, job)| !running_only || job.state == ProcessState::Running)
+            .map(|(index, _)| index)
+            .collect::<Vec<_>>();
+        for index in indexes {
+            disown(env, index, no_sighup);
+        }
+        crate::Result::default()
+    } else if operands.is_empty() {
+        if let Some(index) = env.jobs.current_job() {
+            disown(env, index, no_sighup);
+            crate::Result::default()
+        } else {
+            report_simple_failure(env, "there is no job").await
+        }
+    } else {
+        let mut indexes = Vec::new();
+        let mut errors = Vec::new();
+        for operand in operands {
+            match find_job(env, &operand.value) {
+                Ok(index) => indexes.push(index),
+                Err(error) => errors.push(OperandError(operand, error)),
+            }
+        }
+        for index in indexes {
+            disown(env, index, no_sighup);
+        }
+        match merge_reports(&errors) {
+            None => crate::Result::default(),
+            Some(report) => report_failure(env, report).await,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use futures_util::FutureExt as _;
+    use std::rc::Rc;
+    use yash_env::VirtualSystem;
+    use yash_env::job::Job;
+    use yash_env::job::Pid;
+    use yash_env::semantics::ExitStatus;
+    use yash_env::system::Concurrent;
+    use yash_env::system::r#virtual::SIGSTOP;
+    use yash_env::test_helper::assert_stderr;
+    use yash_env::test_helper::assert_stdout;
+
+    fn job_named(pid: i32, name: &str) -> Job {
+        let mut job = Job::new(Pid(pid));
+        job.name = name.to_string();
+        job
+    }
+
+    #[test]
+    fn disowned_job_is_not_listed_by_jobs() {
+        let system = VirtualSystem::new();
+        let state = Rc::clone(&system.state);
+        let mut env = Env::with_system(Rc::new(Concurrent::new(system)));
+        env.jobs.insert(job_named(10, "sleep 1"));
+        env.jobs.insert(job_named(20, "sleep 2"));
+
+        let result = main(&mut env, Field::dummies(["%1"]))
+            .now_or_never()
+            .unwrap();
+        assert_eq!(result, crate::Result::default());
+        assert_eq!(env.jobs.len(), 1);
+
+        let result = crate::jobs::main(&mut env, vec![]).now_or_never().unwrap();
+        assert_eq!(result, crate::Result::default());
+        assert_stdout(&state, |stdout| {
+            assert_eq!(stdout, "[2] + Running              sleep 2\n")
+        });
+    }
+
+    #[test]
+    fn no_operands_disowns_current_job() {
+        let mut env = Env::new_virtual();
+        let index1 = env.jobs.insert(Job::new(Pid(10)));
+        let index2 = env.jobs.insert(Job::new(Pid(20)));
+        env.jobs.set_current_job(index2).unwrap();
+
+        let result = main(&mut env, vec![]).now_or_never().unwrap();
+        assert_eq!(result, crate::Result::default());
+        assert!(env.jobs.get(index1).is_some());
+        assert!(env.jobs.get(index2).is_none());
+    }
+
+    #[test]
+    fn no_operands_without_jobs() {
+        let system = VirtualSystem::new();
+        let state = Rc::clone(&system.state);
+        let mut env = Env::with_system(Rc::new(Concurrent::new(system)));
+
+        let result = main(&mut env, vec![]).now_or_never().unwrap();
+        assert_eq!(result, crate::Result::from(ExitStatus::FAILURE));
+        assert_stderr(&state, |stderr| {
+            assert!(stderr.contains("there is no job"), "stderr = {stderr:?}")
+        });
+    }
+
+    #[test]
+    fn h_option_keeps_job_and_sets_flag() {
+        let system = VirtualSystem::new();
+        let state = Rc::clone(&system.state);
+        let mut env = Env::with_system(Rc::new(Concurrent::new(system)));
+        let index = env.jobs.insert(job_named(10, "sleep 1"));
+
+        let result = main(&mut env, Field::dummies(["-h", "%1"]))
+            .now_or_never()
+            .unwrap();
+        assert_eq!(result, crate::Result::default());
+        assert!(env.jobs[index].no_sighup);
+
+        let result = crate::jobs::main(&mut env, vec![]).now_or_never().unwrap();
+        assert_eq!(result, crate::Result::default());
+        assert_stdout(&state, |stdout| {
+            assert_eq!(stdout, "[1] + Running              sleep 1\n")
+        });
+    }
+
+    #[test]
+    fn a_option_disowns_all_jobs() {
+        let mut env = Env::new_virtual();
+        env.jobs.insert(Job::new(Pid(10)));
+        let mut job = Job::new(Pid(20));
+        job.state = ProcessState::stopped(SIGSTOP);
+        env.jobs.insert(job);
+
+        let result = main(&mut env, Field::dummies(["-a"]))
+            .now_or_never()
+            .unwrap();
+        assert_eq!(result, crate::Result::default());
+        assert_eq!(env.jobs.len(), 0);
+    }
+
+    #[test]
+    fn r_option_disowns_running_jobs_only() {
+        let mut env = Env::new_virtual();
+        let running = env.jobs.insert(Job::new(Pid(10)));
+        let mut job = Job::new(Pid(20));
+        job.state = ProcessState::stopped(SIGSTOP);
+        let stopped = env.jobs.insert(job);
+        let mut job = Job::new(Pid(30));
+        job.state = ProcessState::exited(ExitStatus::SUCCESS);
+        let exited = env.jobs.insert(job);
+
+        let result = main(&mut env, Field::dummies(["-r"]))
+            .now_or_never()
+            .unwrap();
+        assert_eq!(result, crate::Result::default());
+        assert!(env.jobs.get(running).is_none());
+        assert!(env.jobs.get(stopped).is_some());
+        assert!(env.jobs.get(exited).is_some());
+    }
+
+    #[test]
+    fn a_and_h_options_flag_all_jobs() {
+        let mut env = Env::new_virtual();
+        let index1 = env.jobs.insert(Job::new(Pid(10)));
+        let index2 = env.jobs.insert(Job::new(Pid(20)));
+
+        let result = main(&mut env, Field::dummies(["-ah"]))
+            .now_or_never()
+            .unwrap();
+        assert_eq!(result, crate::Result::default());
+        assert!(env.jobs[index1].no_sighup);
+        assert!(env.jobs[index2].no_sighup);
+    }
+
+    #[test]
+    fn operand_with_a_option_is_error() {
+        let mut env = Env::new_virtual();
+        let index = env.jobs.insert(Job::new(Pid(10)));
+
+        let result = main(&mut env, Field::dummies(["-a", "%1"]))
+            .now_or_never()
+            .unwrap();
+        assert_eq!(result, crate::Result::from(ExitStatus::ERROR));
+        assert!(env.jobs.get(index).is_some());
+    }
+
+    #[test]
+    fn non_existing_job_is_error() {
+        let system = VirtualSystem::new();
+        let state = Rc::clone(&system.state);
+        let mut env = Env::with_system(Rc::new(Concurrent::new(system)));
+        let index = env.jobs.insert(Job::new(Pid(10)));
+
+        let result = main(&mut env, Field::dummies(["%2", "%1"]))
+            .now_or_never()
+            .unwrap();
+        assert_eq!(result, crate::Result::from(ExitStatus::FAILURE));
+        // The valid operand is still processed.
+        assert!(env.jobs.get(index).is_none());
+        assert_stderr(&state, |stderr| {
+            assert!(stderr.contains("job not found"), "stderr = {stderr:?}")
+        });
+    }
+
+    #[test]
+    fn invalid_job_id_is_error() {
+        let mut env = Env::new_virtual();
+        let index = env.jobs.insert(Job::new(Pid(10)));
+
+        let result = main(&mut env, Field::dummies(["1"]))
+            .now_or_never()
+            .unwrap();
+        assert_eq!(result, crate::Result::from(ExitStatus::FAILURE));
+        assert!(env.jobs.get(index).is_some());
+    }
+}
@@ -40,7 +40,9 @@ use std::ops::ControlFlow::Break;
 use yash_env::Env;
 use yash_env::builtin::Result;
 use yash_env::semantics::command::search::search_path;
-use yash_env::semantics::command::{ReplaceCurrentProcessError, replace_current_process};
+use yash_env::semantics::command::{
+    NoExternalUtilities, ReplaceCurrentProcessError, replace_current_process,
+};
 use yash_env::semantics::{Divert::Abort, ExitStatus, Field};
 use yash_env::source::Location;
 use yash_env::source::pretty::{Report, ReportType, Snippet};
@@ -69,6 +71,12 @@ where
             result.set_divert(Break(Abort(None)));
         }
 
+        if NoExternalUtilities::is_set(env) {
+            let _ = report_failure(env, Disabled(name)).await;
+            result.set_exit_status(ExitStatus::NOEXEC);
+            return result;
+        }
+
         let path = if name.value.contains('/') {
             CString::new(name.value.clone()).ok()
         } else {
@@ -125,6 +133,20 @@ impl<'a> From<NotFound<'a>> for Report<'a> {
     }
 }
 
+#[derive(Debug)]
+struct Disabled<'a>(&'a Field);
+
+impl<'a> From<Disabled<'a>> for Report<'a> {
+    fn from(value: Disabled<'a>) -> Self {
+        let mut report = Report::new();
+        report.r#type = ReportType::Error;
+        report.title = format!("cannot execute external utility {:?}", value.0.value).into();
+        report.snippets =
+            Snippet::with_primary_span(&value.0.origin, "external utilities are disabled".into());
+        report
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -137,6 +159,7 @@ mod tests {
     use yash_env::option::State::On;
     use yash_env::system::r#virtual::{FileBody, Inode};
     use yash_env::system::{Concurrent, Mode};
+    use yash_env::test_helper::assert_stderr;
     use yash_env::variable::{PATH, Scope};
 
     fn dummy_file(is_native_executable: bool) -> Inode {
@@ -330,4 +353,28 @@ mod tests {
         assert_eq!(result.exit_status(), ExitStatus::NOEXEC);
         assert_eq!(result.divert(), Continue(()));
     }
+
+    #[test]
+    fn utility_not_executed_if_externals_disabled() {
+        let system = VirtualSystem::new();
+        let mut env = Env::with_system(Rc::new(Concurrent::new(system.clone())));
+        env.any.insert(Box::new(NoExternalUtilities));
+        system
+            .state
+            .borrow_mut()
+            .file_system
+            .save("/bin/echo", Rc::new(RefCell::new(executable_file())))
+            .unwrap();
+
+        let args = Field::dummies(["/bin/echo"]);
+        let result = main(&mut env, args).now_or_never().unwrap();
+        assert_eq!(result.exit_status(), ExitStatus::NOEXEC);
+        assert_eq!(result.divert(), Break(Abort(None)));
+
+        let process = &system.current_process();
+        assert_eq!(process.last_exec(), &None);
+        assert_stderr(&system.state, |stderr| {
+            assert!(stderr.contains("disabled"), "{stderr}")
+        });
+    }
 }
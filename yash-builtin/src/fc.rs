@@ -0,0 +1,471 @@
+// This file is part of yash, an extended POSIX shell.
+// Copyright (C) 2026 WATANABE Yuki
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+//! Fc built-in
+//!
+//! This module implements the [`fc` built-in], which lists or re-executes
+//! entries of the [command history](yash_env::history).
+//!
+//! [`fc` built-in]: https://magicant.github.io/yash-rs/builtins/fc.html
+//!
+//! # Implementation notes
+//!
+//! Re-executing an entry requires a [`RunReadEvalLoop`] instance to be
+//! available in the environment's [`any`](yash_env::Env::any) storage.
+//!
+//! Editing entries with an editor (that is, invoking the built-in without the
+//! `-l` or `-s` option) is not supported yet.
+//!
+//! In an interactive shell, the newest history entry is the command line that
+//! invoked the built-in. That entry is excluded from the entries the operands
+//! refer to, so that `-1` refers to the previous command line.
+
+use crate::common::output;
+use crate::common::report::{report_error, report_failure};
+use std::cell::RefCell;
+use std::ops::RangeInclusive;
+use std::rc::Rc;
+use thiserror::Error;
+use yash_env::Env;
+use yash_env::history::{History, replace_last};
+use yash_env::input::Memory;
+use yash_env::option::{Interactive, On};
+use yash_env::parser::Config;
+use yash_env::semantics::{Field, RunReadEvalLoop};
+use yash_env::source::pretty::{Report, ReportType, Snippet};
+use yash_env::source::{Location, Source};
+use yash_env::system::concurrency::WriteAll;
+use yash_env::system::{Close, Isatty, Open};
+
+pub mod syntax;
+
+/// Number of entries listed by default
+pub const DEFAULT_LIST_COUNT: usize = 16;
+
+/// Abstract command line arguments of the `fc` built-in
+///
+/// An instance of this enum is created by parsing command line arguments
+/// using the [`syntax`] module.
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[non_exhaustive]
+pub enum Command {
+    /// Listing entries (the `-l` option)
+    List {
+        /// Operand that specifies the first entry to list
+        first: Option<Field>,
+        /// Operand that specifies the last entry to list
+        last: Option<Field>,
+        /// Whether entry numbers are printed (false with the `-n` option)
+        numbered: bool,
+        /// Whether entries are listed from the newest (the `-r` option)
+        reverse: bool,
+    },
+
+    /// Re-executing an entry (the `-s` option)
+    Reexecute {
+        /// `old=new` operand that replaces the first occurrence of `old` with
+        /// `new` in the entry
+        substitution: Option<(String, String)>,
+        /// Operand that specifies the entry to re-execute
+        first: Option<Field>,
+    },
+}
+
+/// Error in selecting history entries
+#[derive(Clone, Debug, Eq, Error, PartialEq)]
+#[non_exhaustive]
+pub enum Error {
+    /// The history has no entries to select.
+    #[error("the command history is empty")]
+    EmptyHistory,
+
+    /// No entry starts with the string given as an operand.
+    #[error("no such history entry")]
+    NoSuchEntry(Field),
+}
+
+impl Error {
+    /// Converts this error to a report.
+    #[must_use]
+    pub fn to_report(&self) -> Report<'_> {
+        let snippets = match self {
+            Self::EmptyHistory => vec![],
+            Self::NoSuchEntry(operand) => Snippet::with_primary_span(
+                &operand.origin,
+                format!("no entry starts with {:?}", operand.value).into(),
+            ),
+        };
+
+        let mut report = Report::new();
+        report.r#type = ReportType::Error;
+        report.title = self.to_string().into();
+        report.snippets = snippets;
+        report
+    }
+}
+
+impl<'a> From<&'a Error> for Report<'a> {
+    #[inline]
+    fn from(error: &'a Error) -> Self {
+        error.to_report()
+    }
+}
+
+/// Returns the range of entry numbers that operands can refer to.
+///
+/// If the shell is interactive, the newest entry is excluded as it is the
+/// command line that invoked the built-in.
+fn selectable_range<S>(env: &Env<S>) -> Result<RangeInclusive<usize>, Error> {
+    let history = &env.history;
+    let mut end = history.next_number();
+    if env.options.get(Interactive) == On && !history.is_empty() {
+        end -= 1;
+    }
+    if end == history.first_number() {
+        return Err(Error::EmptyHistory);
+    }
+    Ok(history.first_number()..=end - 1)
+}
+
+/// Resolves an operand to an entry number.
+///
+/// A positive number is an absolute entry number. A negative number is an
+/// offset from the end of the range, `-1` being the last. Zero is the same as
+/// `-1`. Numbers out of the range are clamped into the range. Any other
+/// operand selects the newest entry that starts with the operand.
+fn resolve(
+    history: &History,
+    range: &RangeInclusive<usize>,
+    operand: &Field,
+) -> Result<usize, Error> {
+    match operand.value.parse::<isize>() {
+        Ok(number) if number > 0 => Ok(number.unsigned_abs().clamp(*range.start(), *range.end())),
+        Ok(number) => {
+            let offset = number.unsigned_abs().saturating_sub(1);
+            Ok(range.end().saturating_sub(offset).max(*range.start()))
+        }
+        Err(_) => history
+            .range(range.clone())
+            .rev()
+            .find(|(_, line)| line.starts_with(&operand.value))
+            .map(|(number, _)| number)
+            .ok_or_else(|| Error::NoSuchEntry(operand.clone())),
+    }
+}
+
+/// Lists history entries.
+fn list<S>(
+    env: &Env<S>,
+    first: Option<&Field>,
+    last: Option<&Field>,
+    numbered: bool,
+    mut reverse: bool,
+) -> Result<String, Error> {
+    let range = selectable_range(env)?;
+    let history = &env.history;
+    let mut first = match first {
+        None => range
+            .end()
+            .saturating_sub(DEFAULT_LIST_COUNT - 1)
+            .max(*range.start()),
+        Some(first) => resolve(history, &range, first)?,
+    };
+    let mut last = match last {
+        None => *range.end(),
+        Some(last) => resolve(history, &range, last)?,
+    };
+    if first > last {
+        std::mem::swap(&mut first, &mut last);
+        reverse = !reverse;
+    }
+
+    let entries = history.range(first..=last);
+    let entries: Box<dyn Iterator<Item = _>> = if reverse {
+        Box::new(entries.rev())
+    } else {
+        Box::new(entries)
+    };
+    let mut result = String::new();
+    for (number, line) in entries {
+        if numbered {
+            result.push_str(&number.to_string());
+        }
+        result.push('\t');
+        result.push_str(line);
+        result.push('\n');
+    }
+    Ok(result)
+}
+
+/// Selects the entry to re-execute and applies the substitution.
+fn select_command<S>(
+    env: &Env<S>,
+    substitution: Option<&(String, String)>,
+    first: Option<&Field>,
+) -> Result<String, Error> {
+    let range = selectable_range(env)?;
+    let number = match first {
+        None => *range.end(),
+        Some(first) => resolve(&env.history, &range, first)?,
+    };
+    let command = env.history.get(number).unwrap_or_default();
+    Ok(match substitution {
+        None => command.to_owned(),
+        Some((old, new)) => command.replacen(old.as_str(), new, 1),
+    })
+}
+
+/// Re-executes the command.
+///
+/// In an interactive shell, the entry for the command line that invoked the
+/// built-in is replaced with the re-executed command.
+async fn reexecute<S>(env: &mut Env<S>, command: String) -> crate::Result
+where
+    S: Close + Isatty + Open + WriteAll + 'static,
+{
+    if env.options.get(Interactive) == On {
+        replace_last(env, &command).await;
+    }
+
+    let result = output(env, &format!("{command}\n")).await;
+    if result != crate::Result::default() {
+        return result;
+    }
+
+    let RunReadEvalLoop(run_read_eval_loop) = *env
+        .any
+        .get()
+        .expect("`RunReadEvalLoop` should be in `env.any`");
+    let original = env.stack.current_builtin().map_or_else(
+        || Location::dummy("fc"),
+        |builtin| builtin.name.origin.clone(),
+    );
+    let mut config = Config::with_input(Box::new(Memory::new(&command)));
+    config.source = Some(Rc::new(Source::Eval { original }));
+    let divert = run_read_eval_loop(&RefCell::new(env), config).await;
+    crate::Result::with_exit_status_and_divert(env.exit_status, divert)
+}
+
+/// Entry point of the `fc` built-in
+///
+/// When re-executing an entry, this function requires a [`RunReadEvalLoop`]
+/// instance to be available in the environment's [`any`](Env::any) storage.
+/// If the instance is not found, the function **panics**.
+pub async fn main<S>(env: &mut Env<S>, args: Vec<Field>) -> crate::Result
+where
+    S: Close + Isatty + Open + WriteAll + 'static,
+{
+    let command = match syntax::parse(env, args) {
+        Ok(command) => command,
+        Err(error) => return report_error(env, &error).await,
+    };
+
+    match command {
+        Command::List {
+            first,
+            last,
+            numbered,
+            reverse,
+        } => match list(env, first.as_ref(), last.as_ref(), numbered, reverse) {
+            Ok(content) => output(env, &content).await,
+            Err(error) => report_failure(env, &error).await,
+        },
+
+        Command::Reexecute {
+            substitution,
+            first,
+        } => match select_command(env, substitution.as_ref(), first.as_ref()) {
+            Ok(command) => reexecute(env, command).await,
+            Err(error) => report_failure(env, &error).await,
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use assert_matches::assert_matches;
+    use futures_util::FutureExt as _;
+    use std::ops::ControlFlow::Continue;
+    use yash_env::VirtualSystem;
+    use yash_env::input::Context;
+    use yash_env::semantics::ExitStatus;
+    use yash_env::system::Concurrent;
+    use yash_env::system::r#virtual::{FileBody, Inode, SystemState};
+    use yash_env::test_helper::{assert_stderr, assert_stdout};
+    use yash_env::variable::{HISTFILE, Scope};
+
+    fn fixture(lines: &[&str]) -> (Env<Rc<Concurrent<VirtualSystem>>>, Rc<RefCell<SystemState>>) {
+        let system = VirtualSystem::new();
+        let state = Rc::clone(&system.state);
+        let mut env = Env::with_system(Rc::new(Concurrent::new(system)));
+        for line in lines {
+            env.history.add(line.to_string());
+        }
+        (env, state)
+    }
+
+    #[test]
+    fn listing_recent_entries_with_numbers() {
+        let (mut env, state) = fixture(&["echo 1", "echo 2", "echo 3"]);
+
+        let result = main(&mut env, Field::dummies(["-l"]))
+            .now_or_never()
+            .unwrap();
+        assert_eq!(result, crate::Result::default());
+        assert_stdout(&state, |stdout| {
+            assert_eq!(stdout, "1\techo 1\n2\techo 2\n3\techo 3\n")
+        });
+    }
+
+    #[test]
+    fn listing_at_most_default_count() {
+        let lines = (1..=20).map(|i| format!("echo {i}")).collect::<Vec<_>>();
+        let lines = lines.iter().map(String::as_str).collect::<Vec<_>>();
+        let (mut env, state) = fixture(&lines);
+
+        let result = main(&mut env, Field::dummies(["-l"]))
+            .now_or_never()
+            .unwrap();
+        assert_eq!(result, crate::Result::default());
+        assert_stdout(&state, |stdout| {
+            let listed = stdout.lines().collect::<Vec<_>>();
+            assert_eq!(listed.len(), DEFAULT_LIST_COUNT);
+            assert_eq!(listed.first(), Some(&"5\techo 5"));
+            assert_eq!(listed.last(), Some(&"20\techo 20"));
+        });
+    }
+
+    #[test]
+    fn listing_range_reversed_without_numbers() {
+        let (mut env, state) = fixture(&["a", "b", "c", "d"]);
+
+        let args = Field::dummies(["-lnr", "-3", "c"]);
+        let result = main(&mut env, args).now_or_never().unwrap();
+        assert_eq!(result, crate::Result::default());
+        assert_stdout(&state, |stdout| assert_eq!(stdout, "\tc\n\tb\n"));
+    }
+
+    #[test]
+    fn listing_in_interactive_shell_excludes_current_command() {
+        let (mut env, state) = fixture(&["echo 1", "echo 2", "fc -l"]);
+        env.options.set(Interactive, On);
+
+        let result = main(&mut env, Field::dummies(["-l"]))
+            .now_or_never()
+            .unwrap();
+        assert_eq!(result, crate::Result::default());
+        assert_stdout(&state, |stdout| {
+            assert_eq!(stdout, "1\techo 1\n2\techo 2\n")
+        });
+    }
+
+    #[test]
+    fn listing_empty_history() {
+        let (mut env, state) = fixture(&[]);
+
+        let result = main(&mut env, Field::dummies(["-l"]))
+            .now_or_never()
+            .unwrap();
+        assert_eq!(result.exit_status(), ExitStatus::FAILURE);
+        assert_stdout(&state, |stdout| assert_eq!(stdout, ""));
+        assert_stderr(&state, |stderr| assert_ne!(stderr, ""));
+    }
+
+    #[test]
+    fn no_entry_with_prefix() {
+        let (mut env, state) = fixture(&["echo 1"]);
+
+        let args = Field::dummies(["-l", "foo"]);
+        let result = main(&mut env, args).now_or_never().unwrap();
+        assert_eq!(result.exit_status(), ExitStatus::FAILURE);
+        assert_stderr(&state, |stderr| assert_ne!(stderr, ""));
+    }
+
+    /// Stub of the read-eval loop that saves the command in the `ran` variable
+    fn reexecution_fixture(
+        lines: &[&str],
+    ) -> (Env<Rc<Concurrent<VirtualSystem>>>, Rc<RefCell<SystemState>>) {
+        let (mut env, state) = fixture(lines);
+        env.any
+            .insert(Box::new(RunReadEvalLoop::<Rc<Concurrent<VirtualSystem>>>(
+                |env, mut config| {
+                    Box::pin(async move {
+                        let context = Context::default();
+                        let line = config.input.next_line(&context).await.unwrap();
+                        let mut env = env.borrow_mut();
+                        let mut var = env.variables.get_or_new("ran", Scope::Global);
+                        var.assign(line, None).unwrap();
+                        env.exit_status = ExitStatus(7);
+                        Continue(())
+                    })
+                },
+            )));
+        (env, state)
+    }
+
+    #[test]
+    fn reexecuting_specific_entry() {
+        let (mut env, state) = reexecution_fixture(&["echo 1", "echo 2", "echo 3"]);
+
+        let result = main(&mut env, Field::dummies(["-s", "2"]))
+            .now_or_never()
+            .unwrap();
+        assert_eq!(result.exit_status(), ExitStatus(7));
+        assert_eq!(env.variables.get_scalar("ran"), Some("echo 2"));
+        assert_stdout(&state, |stdout| assert_eq!(stdout, "echo 2\n"));
+    }
+
+    #[test]
+    fn reexecuting_previous_entry_with_substitution() {
+        let (mut env, state) = reexecution_fixture(&["echo foo", "fc -s foo=bar"]);
+        env.options.set(Interactive, On);
+
+        let result = main(&mut env, Field::dummies(["-s", "foo=bar"]))
+            .now_or_never()
+            .unwrap();
+        assert_eq!(result.exit_status(), ExitStatus(7));
+        assert_eq!(env.variables.get_scalar("ran"), Some("echo bar"));
+        assert_stdout(&state, |stdout| assert_eq!(stdout, "echo bar\n"));
+        let history = env.history.iter().collect::<Vec<_>>();
+        assert_eq!(history, ["echo foo", "echo bar"]);
+    }
+
+    #[test]
+    fn reexecuted_command_replaces_fc_command_in_histfile() {
+        let (mut env, state) = reexecution_fixture(&["echo foo", "fc -s foo=bar"]);
+        env.options.set(Interactive, On);
+        let file = Rc::new(RefCell::new(Inode::new("echo foo\nfc -s foo=bar\n")));
+        state
+            .borrow_mut()
+            .file_system
+            .save("/history", file)
+            .unwrap();
+        env.variables
+            .get_or_new(HISTFILE, Scope::Global)
+            .assign("/history", None)
+            .unwrap();
+
+        let result = main(&mut env, Field::dummies(["-s", "foo=bar"]))
+            .now_or_never()
+            .unwrap();
+        assert_eq!(result.exit_status(), ExitStatus(7));
+        let file = state.borrow().file_system.get("/history").unwrap();
+        assert_matches!(&file.borrow().body, FileBody::Regular { content, .. } => {
+            assert_eq!(std::str::from_utf8(content), Ok("echo foo\necho bar\n"));
+        });
+    }
+}
@@ -0,0 +1,277 @@
+// This file is part of yash, an extended POSIX shell.
+// Copyright (C) 2026 WATANABE Yuki
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+//! Command line argument parser for the fc built-in
+
+use super::Command;
+use crate::common::syntax::ConflictingOptionError;
+use crate::common::syntax::Mode;
+use crate::common::syntax::OptionSpec;
+use crate::common::syntax::parse_arguments;
+use thiserror::Error;
+use yash_env::Env;
+use yash_env::semantics::Field;
+use yash_env::source::pretty::{Footnote, FootnoteType, Report, ReportType, Snippet};
+
+/// Error in parsing command line arguments
+#[derive(Clone, Debug, Eq, Error, PartialEq)]
+#[non_exhaustive]
+pub enum Error {
+    /// An error occurred in the common parser.
+    #[error(transparent)]
+    CommonError(#[from] crate::common::syntax::ParseError<'static>),
+
+    /// The `-l` and `-s` options are used together.
+    #[error(transparent)]
+    ConflictingOption(#[from] ConflictingOptionError<'static>),
+
+    /// Neither the `-l` nor `-s` option is given.
+    ///
+    /// Editing commands with an editor is not supported yet.
+    #[error("editing history entries is not supported")]
+    EditingUnsupported,
+
+    /// Too many operands are given.
+    #[error("too many operands")]
+    TooManyOperands(Field),
+}
+
+impl Error {
+    /// Converts this error to a report.
+    #[must_use]
+    pub fn to_report(&self) -> Report<'_> {
+        let snippets = match self {
+            Self::CommonError(inner) => return inner.to_report(),
+            Self::ConflictingOption(inner) => return inner.to_report(),
+
+            Self::EditingUnsupported => vec![],
+
+            Self::TooManyOperands(operand) => {
+                Snippet::with_primary_span(&operand.origin, "unexpected operand".into())
+            }
+        };
+
+        let mut report = Report::new();
+        report.r#type = ReportType::Error;
+        report.title = self.to_string().into();
+        report.snippets = snippets;
+        if let Self::EditingUnsupported = self {
+            report.footnotes.push(Footnote {
+                r#type: FootnoteType::Suggestion,
+                label: "use the -l option to list or -s to re-execute entries".into(),
+            });
+        }
+        report
+    }
+}
+
+impl<'a> From<&'a Error> for Report<'a> {
+    #[inline]
+    fn from(error: &'a Error) -> Self {
+        error.to_report()
+    }
+}
+
+const OPTION_SPECS: &[OptionSpec] = &[
+    OptionSpec::new().short('l').long("list"),
+    OptionSpec::new().short('n').long("no-numbers"),
+    OptionSpec::new().short('r').long("reverse"),
+    OptionSpec::new().short('s').long("silent"),
+];
+
+/// Tests whether the argument is a negative number like `-5`.
+fn is_negative_number(field: &Field) -> bool {
+    field
+        .value
+        .strip_prefix('-')
+        .is_some_and(|digits| !digits.is_empty() && digits.bytes().all(|b| b.is_ascii_digit()))
+}
+
+/// Parses command line arguments.
+///
+/// A negative number like `-5` is accepted as an operand even if it is not
+/// preceded by `--`.
+pub fn parse<S>(env: &Env<S>, mut args: Vec<Field>) -> Result<Command, Error> {
+    // Split off the first negative number and the following arguments so
+    // that they are not parsed as options.
+    let split = args
+        .iter()
+        .position(|arg| is_negative_number(arg) || !arg.value.starts_with('-') || arg.value == "--")
+        .filter(|&index| is_negative_number(&args[index]))
+        .map(|index| args.split_off(index));
+
+    let mode = Mode::with_env(env);
+    let (options, mut operands) = parse_arguments(OPTION_SPECS, mode, args)?;
+    operands.extend(split.into_iter().flatten());
+
+    let mut l_option = None;
+    let mut s_option = None;
+    let mut numbered = true;
+    let mut reverse = false;
+    for (index, option) in options.iter().enumerate() {
+        match option.spec.get_short() {
+            Some('l') => l_option = Some(index),
+            Some('n') => numbered = false,
+            Some('r') => reverse = true,
+            Some('s') => s_option = Some(index),
+            _ => unreachable!(),
+        }
+    }
+
+    let mut operands = operands.into_iter();
+    match (l_option, s_option) {
+        (Some(l_pos), Some(s_pos)) => {
+            Err(ConflictingOptionError::pick_from_indexes(options, [l_pos, s_pos]).into())
+        }
+
+        (Some(_), None) => {
+            let first = operands.next();
+            let last = operands.next();
+            if let Some(operand) = operands.next() {
+                return Err(Error::TooManyOperands(operand));
+            }
+            Ok(Command::List {
+                first,
+                last,
+                numbered,
+                reverse,
+            })
+        }
+
+        (None, Some(_)) => {
+            let mut operand = operands.next();
+            let substitution = match operand.take_if(|operand| operand.value.contains('=')) {
+                None => None,
+                Some(field) => {
+                    let (old, new) = field.value.split_once('=').unwrap();
+                    let substitution = (old.to_owned(), new.to_owned());
+                    operand = operands.next();
+                    Some(substitution)
+                }
+            };
+            if let Some(operand) = operands.next() {
+                return Err(Error::TooManyOperands(operand));
+            }
+            Ok(Command::Reexecute {
+                substitution,
+                first: operand,
+            })
+        }
+
+        (None, None) => Err(Error::EditingUnsupported),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use assert_matches::assert_matches;
+
+    #[test]
+    fn list_without_operands() {
+        let env = Env::new_virtual();
+        let result = parse(&env, Field::dummies(["-l"]));
+        assert_eq!(
+            result,
+            Ok(Command::List {
+                first: None,
+                last: None,
+                numbered: true,
+                reverse: false,
+            })
+        );
+    }
+
+    #[test]
+    fn list_with_options_and_operands() {
+        let env = Env::new_virtual();
+        let result = parse(&env, Field::dummies(["-nrl", "3", "echo"]));
+        assert_eq!(
+            result,
+            Ok(Command::List {
+                first: Some(Field::dummy("3")),
+                last: Some(Field::dummy("echo")),
+                numbered: false,
+                reverse: true,
+            })
+        );
+    }
+
+    #[test]
+    fn negative_numbers_are_operands() {
+        let env = Env::new_virtual();
+        let result = parse(&env, Field::dummies(["-l", "-5", "-2"]));
+        assert_eq!(
+            result,
+            Ok(Command::List {
+                first: Some(Field::dummy("-5")),
+                last: Some(Field::dummy("-2")),
+                numbered: true,
+                reverse: false,
+            })
+        );
+    }
+
+    #[test]
+    fn reexecute_without_operands() {
+        let env = Env::new_virtual();
+        let result = parse(&env, Field::dummies(["-s"]));
+        assert_eq!(
+            result,
+            Ok(Command::Reexecute {
+                substitution: None,
+                first: None,
+            })
+        );
+    }
+
+    #[test]
+    fn reexecute_with_substitution_and_operand() {
+        let env = Env::new_virtual();
+        let result = parse(&env, Field::dummies(["-s", "foo=bar", "-3"]));
+        assert_eq!(
+            result,
+            Ok(Command::Reexecute {
+                substitution: Some(("foo".to_owned(), "bar".to_owned())),
+                first: Some(Field::dummy("-3")),
+            })
+        );
+    }
+
+    #[test]
+    fn list_and_reexecute_conflict() {
+        let env = Env::new_virtual();
+        let result = parse(&env, Field::dummies(["-l", "-s"]));
+        assert_matches!(result, Err(Error::ConflictingOption(_)));
+    }
+
+    #[test]
+    fn editing_is_unsupported() {
+        let env = Env::new_virtual();
+        let result = parse(&env, Field::dummies(["1"]));
+        assert_eq!(result, Err(Error::EditingUnsupported));
+    }
+
+    #[test]
+    fn too_many_operands() {
+        let env = Env::new_virtual();
+        let result = parse(&env, Field::dummies(["-l", "1", "2", "3"]));
+        assert_eq!(result, Err(Error::TooManyOperands(Field::dummy("3"))));
+
+        let result = parse(&env, Field::dummies(["-s", "1", "2"]));
+        assert_eq!(result, Err(Error::TooManyOperands(Field::dummy("2"))));
+    }
+}
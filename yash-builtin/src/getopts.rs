@@ -135,6 +135,12 @@ where
 #[cfg(test)]
 mod tests {
     use super::*;
+    use futures_util::FutureExt as _;
+    use std::rc::Rc;
+    use yash_env::VirtualSystem;
+    use yash_env::system::Concurrent;
+    use yash_env::variable::OPTARG;
+    use yash_env::variable::Scope;
 
     fn non_zero(i: usize) -> NonZeroUsize {
         NonZeroUsize::new(i).unwrap()
@@ -174,4 +180,37 @@ mod tests {
         assert_eq!(indexes_to_optind(non_zero(2), non_zero(4)), "2:4");
         assert_eq!(indexes_to_optind(non_zero(10), non_zero(13)), "10:13");
     }
+
+    fn set_optind(env: &mut Env<Rc<Concurrent<VirtualSystem>>>, value: &str) {
+        env.get_or_create_variable(OPTIND, Scope::Global)
+            .assign(value, None)
+            .unwrap();
+    }
+
+    fn getopts(env: &mut Env<Rc<Concurrent<VirtualSystem>>>) -> crate::Result {
+        let args = Field::dummies(["a:b", "opt", "-a", "foo", "-b", "bar"]);
+        main(env, args).now_or_never().unwrap()
+    }
+
+    #[test]
+    fn resetting_optind_restarts_parsing() {
+        let mut env = Env::new_virtual();
+        set_optind(&mut env, "1");
+
+        assert_eq!(getopts(&mut env), crate::Result::default());
+        assert_eq!(env.variables.get_scalar("opt"), Some("a"));
+        assert_eq!(env.variables.get_scalar(OPTARG), Some("foo"));
+        assert_eq!(env.variables.get_scalar(OPTIND), Some("3"));
+
+        assert_eq!(getopts(&mut env), crate::Result::default());
+        assert_eq!(env.variables.get_scalar("opt"), Some("b"));
+        assert_eq!(env.variables.get_scalar(OPTARG), None);
+        assert_eq!(env.variables.get_scalar(OPTIND), Some("4"));
+
+        set_optind(&mut env, "1");
+        assert_eq!(getopts(&mut env), crate::Result::default());
+        assert_eq!(env.variables.get_scalar("opt"), Some("a"));
+        assert_eq!(env.variables.get_scalar(OPTARG), Some("foo"));
+        assert_eq!(env.variables.get_scalar(OPTIND), Some("3"));
+    }
 }
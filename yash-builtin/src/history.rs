@@ -0,0 +1,124 @@
+// This file is part of yash, an extended POSIX shell.
+// Copyright (C) 2026 WATANABE Yuki
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+//! History built-in
+//!
+//! This module implements the [`history` built-in], which prints entries of
+//! the [command history](yash_env::history) with their numbers. Unlike
+//! [`fc -l`](crate::fc), the listing includes the command line that invoked
+//! the built-in in an interactive shell.
+//!
+//! [`history` built-in]: https://magicant.github.io/yash-rs/builtins/history.html
+
+use crate::Result;
+use crate::common::output;
+use crate::common::report::{report_error, syntax_error};
+use crate::common::syntax::{Mode, parse_arguments};
+use yash_env::Env;
+use yash_env::semantics::Field;
+use yash_env::system::Isatty;
+use yash_env::system::concurrency::WriteAll;
+
+/// Entry point of the `history` built-in
+///
+/// If an operand is given, only that many newest entries are printed.
+/// Otherwise, all entries are printed.
+pub async fn main<S>(env: &mut Env<S>, args: Vec<Field>) -> Result
+where
+    S: Isatty + WriteAll,
+{
+    let args = match parse_arguments(&[], Mode::with_env(env), args) {
+        Ok((_options, operands)) => operands,
+        Err(error) => return report_error(env, &error).await,
+    };
+
+    if let Some(arg) = args.get(1) {
+        return syntax_error(env, "too many operands", &arg.origin).await;
+    }
+
+    let count = match args.first() {
+        None => usize::MAX,
+        Some(arg) => match arg.value.parse() {
+            Ok(count) => count,
+            Err(e) => {
+                let message = format!("non-integral operand: {e}");
+                return syntax_error(env, &message, &arg.origin).await;
+            }
+        },
+    };
+
+    let history = &env.history;
+    let skip = history.len().saturating_sub(count);
+    let mut content = String::new();
+    for (number, line) in history.iter_numbered().skip(skip) {
+        content.push_str(&format!("{number}\t{line}\n"));
+    }
+    output(env, &content).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use futures_util::FutureExt as _;
+    use std::rc::Rc;
+    use yash_env::VirtualSystem;
+    use yash_env::semantics::ExitStatus;
+    use yash_env::system::Concurrent;
+    use yash_env::test_helper::{assert_stderr, assert_stdout};
+
+    #[test]
+    fn printing_all_entries() {
+        let system = VirtualSystem::new();
+        let state = Rc::clone(&system.state);
+        let mut env = Env::with_system(Rc::new(Concurrent::new(system)));
+        env.history.add("echo 1".to_string());
+        env.history.add("history".to_string());
+
+        let result = main(&mut env, vec![]).now_or_never().unwrap();
+        assert_eq!(result, Result::default());
+        assert_stdout(&state, |stdout| {
+            assert_eq!(stdout, "1\techo 1\n2\thistory\n")
+        });
+    }
+
+    #[test]
+    fn printing_newest_entries() {
+        let system = VirtualSystem::new();
+        let state = Rc::clone(&system.state);
+        let mut env = Env::with_system(Rc::new(Concurrent::new(system)));
+        for line in ["a", "b", "c", "d"] {
+            env.history.add(line.to_string());
+        }
+        env.history.trim(3);
+
+        let args = Field::dummies(["2"]);
+        let result = main(&mut env, args).now_or_never().unwrap();
+        assert_eq!(result, Result::default());
+        assert_stdout(&state, |stdout| assert_eq!(stdout, "3\tc\n4\td\n"));
+    }
+
+    #[test]
+    fn invalid_operand() {
+        let system = VirtualSystem::new();
+        let state = Rc::clone(&system.state);
+        let mut env = Env::with_system(Rc::new(Concurrent::new(system)));
+
+        let args = Field::dummies(["x"]);
+        let result = main(&mut env, args).now_or_never().unwrap();
+        assert_eq!(result.exit_status(), ExitStatus::ERROR);
+        assert_stderr(&state, |stderr| assert_ne!(stderr, ""));
+    }
+}
@@ -107,11 +107,12 @@ where
     let result = output(env, &accumulator.print).await;
 
     // Remove finished jobs and mark reported jobs as reported
-    // only if there was no error
+    // only if there was no error. Finished jobs whose process ID has been
+    // expanded as `$!` are kept so that the user can wait for them.
     if result.exit_status().is_successful() {
         for index in accumulator.indices_reported {
             if let Some(mut job) = env.jobs.get_mut(index) {
-                if job.state.is_alive() {
+                if job.state.is_alive() || job.pid_expanded {
                     job.state_reported();
                 } else {
                     env.jobs.remove(index);
@@ -128,20 +129,26 @@ mod tests {
     use super::*;
     use assert_matches::assert_matches;
     use futures_util::future::FutureExt as _;
+    use std::ops::ControlFlow::Continue;
     use std::rc::Rc;
     use yash_env::io::Fd;
     use yash_env::job::Job;
     use yash_env::job::Pid;
     use yash_env::job::ProcessResult;
     use yash_env::job::ProcessState;
+    use yash_env::job::handle_job_status;
+    use yash_env::option::{Monitor, On};
     use yash_env::semantics::ExitStatus;
     use yash_env::stack::Builtin;
     use yash_env::stack::Frame;
+    use yash_env::subshell::Config;
     use yash_env::system::Concurrent;
     use yash_env::system::r#virtual::VirtualSystem;
     use yash_env::system::r#virtual::{SIGINT, SIGQUIT, SIGSTOP, SIGTSTP};
+    use yash_env::system::{GetPid as _, SendSignal as _};
     use yash_env::test_helper::assert_stderr;
     use yash_env::test_helper::assert_stdout;
+    use yash_env::test_helper::{in_virtual_system, stub_tty};
 
     #[test]
     fn no_operands_no_jobs() {
@@ -176,6 +183,31 @@ mod tests {
         });
     }
 
+    #[test]
+    fn stopped_foreground_job_is_listed() {
+        in_virtual_system(|mut env, state| async move {
+            env.options.set(Monitor, On);
+            stub_tty(&state);
+
+            let (pid, subshell_result) = Config::foreground()
+                .start_and_wait(&mut env, async |env, _| {
+                    let pid = env.system.getpid();
+                    env.system.kill(pid, Some(SIGSTOP)).await.unwrap();
+                })
+                .await
+                .unwrap();
+            let exit_status =
+                handle_job_status(&mut env, pid, subshell_result, || "sleep 10".to_string());
+            assert_eq!(exit_status, Continue(ExitStatus::from(SIGSTOP)));
+
+            let result = main(&mut env, vec![]).await;
+            assert_eq!(result, Result::new(ExitStatus::SUCCESS));
+            assert_stdout(&state, |stdout| {
+                assert_eq!(stdout, "[1] + Stopped(SIGSTOP)     sleep 10\n")
+            });
+        })
+    }
+
     #[test]
     fn finished_jobs_are_removed_with_no_operands() {
         let mut env = Env::new_virtual();
@@ -226,6 +258,33 @@ mod tests {
         assert_matches!(env.jobs.get(i16), None);
     }
 
+    #[test]
+    fn finished_job_referenced_by_last_async_pid_is_kept() {
+        let mut env = Env::new_virtual();
+
+        let mut job = Job::new(Pid(11));
+        job.name = "echo never referenced".to_string();
+        let i11 = env.jobs.insert(job);
+
+        let mut job = Job::new(Pid(12));
+        job.name = "echo referenced".to_string();
+        let i12 = env.jobs.insert(job);
+        env.jobs.set_last_async_pid(Pid(12));
+        assert_eq!(env.jobs.expand_last_async_pid(), Pid(12));
+
+        env.jobs.update_status(Pid(11), ProcessState::exited(0));
+        env.jobs.update_status(Pid(12), ProcessState::exited(3));
+
+        let result = main(&mut env, vec![]).now_or_never().unwrap();
+        assert_eq!(result, Result::new(ExitStatus::SUCCESS));
+
+        assert_matches!(env.jobs.get(i11), None);
+        assert_matches!(env.jobs.get(i12), Some(job) => {
+            assert_eq!(job.state, ProcessState::exited(3));
+            assert!(!job.state_changed);
+        });
+    }
+
     #[test]
     fn specifying_valid_job_ids() {
         let system = VirtualSystem::new();
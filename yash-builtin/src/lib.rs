@@ -44,7 +44,7 @@
 //!   [`RunFunction`](yash_env::semantics::command::RunFunction) instance to
 //!   invoke shell functions, and an [`IsKeyword`](yash_env::parser::IsKeyword)
 //!   instance to check for reserved words.
-//! - The `eval` and `source` built-ins require a
+//! - The `eval`, `fc`, and `source` built-ins require a
 //!   [`RunReadEvalLoop`](yash_env::semantics::RunReadEvalLoop) instance to run
 //!   the read-eval loop for executing commands.
 //! - The `read` built-in requires a [`GetPrompt`](yash_env::prompt::GetPrompt)
@@ -63,15 +63,19 @@ pub mod colon;
 pub mod command;
 pub mod common;
 pub mod r#continue;
+pub mod disown;
 pub mod eval;
 pub mod exec;
 pub mod exit;
 pub mod export;
 pub mod r#false;
+pub mod fc;
 pub mod fg;
 pub mod getopts;
+pub mod history;
 pub mod jobs;
 pub mod kill;
+pub mod mapfile;
 pub mod pwd;
 pub mod read;
 pub mod readonly;
@@ -100,7 +104,7 @@ use yash_env::job::{RunBlocking, RunUnblocking};
 #[cfg(doc)]
 use yash_env::stack::{Frame, Stack};
 use yash_env::subshell::BlockSignals;
-use yash_env::system::concurrency::{WaitForSignals, WriteAll};
+use yash_env::system::concurrency::{Sleep, WaitForSignals, WriteAll};
 use yash_env::system::resource::{GetRlimit, SetRlimit};
 use yash_env::system::{
     Chdir, Clock, Close, Dup, Exec, Exit, Fcntl, Fork, Fstat, GetCwd, GetPid, GetPw, GetUid,
@@ -147,6 +151,7 @@ where
         + SetRlimit
         + ShellPath
         + SignalSystem
+        + Sleep
         + Sysconf
         + TcGetPgrp
         + TcSetPgrp
@@ -194,6 +199,16 @@ where
             "continue",
             Builtin::new(Special, |env, args| Box::pin(r#continue::main(env, args))),
         ),
+        ("declare", {
+            let mut builtin =
+                Builtin::new(Elective, |env, args| Box::pin(typeset::main(env, args)));
+            builtin.is_declaration_utility = Some(true);
+            builtin
+        }),
+        (
+            "disown",
+            Builtin::new(Elective, |env, args| Box::pin(disown::main(env, args))),
+        ),
         ("eval", {
             let mut builtin = Builtin::new(Special, |env, args| Box::pin(eval::main(env, args)));
             builtin.handles_signals_internally = true;
@@ -216,6 +231,10 @@ where
             "false",
             Builtin::new(Substitutive, |env, args| Box::pin(r#false::main(env, args))),
         ),
+        (
+            "fc",
+            Builtin::new(Mandatory, |env, args| Box::pin(fc::main(env, args))),
+        ),
         ("fg", {
             let mut builtin = Builtin::new(Mandatory, |env, args| Box::pin(fg::main(env, args)));
             builtin.handles_signals_internally = true;
@@ -225,6 +244,10 @@ where
             "getopts",
             Builtin::new(Mandatory, |env, args| Box::pin(getopts::main(env, args))),
         ),
+        (
+            "history",
+            Builtin::new(Elective, |env, args| Box::pin(history::main(env, args))),
+        ),
         (
             "jobs",
             Builtin::new(Mandatory, |env, args| Box::pin(jobs::main(env, args))),
@@ -233,6 +256,16 @@ where
             "kill",
             Builtin::new(Mandatory, |env, args| Box::pin(kill::main(env, args))),
         ),
+        ("local", {
+            let mut builtin =
+                Builtin::new(Elective, |env, args| Box::pin(typeset::main(env, args)));
+            builtin.is_declaration_utility = Some(true);
+            builtin
+        }),
+        (
+            "mapfile",
+            Builtin::new(Elective, |env, args| Box::pin(mapfile::main(env, args))),
+        ),
         (
             "pwd",
             Builtin::new(Substitutive, |env, args| Box::pin(pwd::main(env, args))),
@@ -241,6 +274,10 @@ where
             "read",
             Builtin::new(Mandatory, |env, args| Box::pin(read::main(env, args))),
         ),
+        (
+            "readarray",
+            Builtin::new(Elective, |env, args| Box::pin(mapfile::main(env, args))),
+        ),
         ("readonly", {
             let mut builtin =
                 Builtin::new(Special, |env, args| Box::pin(readonly::main(env, args)));
@@ -0,0 +1,291 @@
+// This file is part of yash, an extended POSIX shell.
+// Copyright (C) 2026 WATANABE Yuki
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+//! Mapfile built-in
+//!
+//! This module implements the [`mapfile` built-in], which reads lines from the
+//! standard input into an array variable. The built-in is also available as
+//! `readarray`.
+//!
+//! [`mapfile` built-in]: https://magicant.github.io/yash-rs/builtins/mapfile.html
+//!
+//! # Implementation notes
+//!
+//! Like the [`read`](crate::read) built-in, this built-in reads the input byte
+//! by byte so that it does not consume more lines than requested.
+
+use crate::common::report::{report_error, report_failure, report_simple_failure};
+use crate::typeset::AssignReadOnlyError;
+use yash_env::Env;
+use yash_env::io::Fd;
+use yash_env::semantics::Field;
+use yash_env::system::concurrency::WriteAll;
+use yash_env::system::{Errno, Isatty, Read};
+use yash_env::variable::{Scope, Value};
+
+pub mod syntax;
+
+/// Name of the variable assigned when no operand is given
+pub const DEFAULT_VARIABLE: &str = "MAPFILE";
+
+/// Abstract command line arguments of the `mapfile` built-in
+///
+/// An instance of this struct is created by parsing command line arguments
+/// using the [`syntax`] module.
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[non_exhaustive]
+pub struct Command {
+    /// Maximum number of lines to read, specified by the `-n` option
+    ///
+    /// `None` means all lines are read.
+    pub max_count: Option<usize>,
+
+    /// Index of the first array element to assign, specified by the `-O`
+    /// option
+    ///
+    /// If `None`, the array is cleared before assignment and the first line is
+    /// assigned to index 0. Otherwise, existing elements before the index are
+    /// retained.
+    pub origin: Option<usize>,
+
+    /// Whether the `-t` option is specified
+    ///
+    /// If true, the trailing newline is removed from each line.
+    pub trim_newline: bool,
+
+    /// Name of the array variable to assign
+    pub variable: Field,
+}
+
+/// Reads one line from the standard input.
+///
+/// The result includes the trailing newline if any. An empty vector is
+/// returned at the end of the input.
+async fn read_line<S: Read>(env: &mut Env<S>) -> Result<Vec<u8>, Errno> {
+    let mut line = Vec::new();
+    loop {
+        let mut byte = 0;
+        let count = env
+            .system
+            .read(Fd::STDIN, std::slice::from_mut(&mut byte))
+            .await?;
+        if count == 0 {
+            break;
+        }
+        line.push(byte);
+        if byte == b'\n' {
+            break;
+        }
+    }
+    Ok(line)
+}
+
+impl Command {
+    /// Reads lines from the standard input.
+    async fn read_lines<S: Read>(&self, env: &mut Env<S>) -> Result<Vec<String>, Errno> {
+        let mut lines = Vec::new();
+        while self
+            .max_count
+            .is_none_or(|max_count| lines.len() < max_count)
+        {
+            let mut line = read_line(env).await?;
+            if line.is_empty() {
+                break;
+            }
+            if self.trim_newline && line.last() == Some(&b'\n') {
+                line.pop();
+            }
+            let line = String::from_utf8(line).map_err(|_| Errno::EILSEQ)?;
+            lines.push(line);
+        }
+        Ok(lines)
+    }
+
+    /// Assigns the lines to the array variable.
+    fn assign<S>(self, env: &mut Env<S>, lines: Vec<String>) -> Result<(), AssignReadOnlyError> {
+        let mut var = env.get_or_create_variable(self.variable.value.clone(), Scope::Global);
+
+        let values = match self.origin {
+            None => lines,
+            Some(origin) => {
+                let mut values = match &var.value {
+                    None => vec![],
+                    Some(Value::Scalar(value)) => vec![value.clone()],
+                    Some(Value::Array(values)) => values.clone(),
+                };
+                if values.len() < origin {
+                    values.resize_with(origin, String::new);
+                }
+                for (index, line) in (origin..).zip(lines) {
+                    if index < values.len() {
+                        values[index] = line;
+                    } else {
+                        values.push(line);
+                    }
+                }
+                values
+            }
+        };
+
+        match var.assign(Value::Array(values), self.variable.origin) {
+            Ok(_) => Ok(()),
+            Err(e) => Err(AssignReadOnlyError {
+                name: self.variable.value,
+                new_value: e.new_value,
+                assigned_location: e.assigned_location.unwrap(),
+                read_only_location: e.read_only_location,
+            }),
+        }
+    }
+}
+
+/// Entry point of the `mapfile` built-in
+pub async fn main<S>(env: &mut Env<S>, args: Vec<Field>) -> crate::Result
+where
+    S: Isatty + Read + WriteAll,
+{
+    let command = match syntax::parse(env, args) {
+        Ok(command) => command,
+        Err(error) => return report_error(env, &error).await,
+    };
+
+    let lines = match command.read_lines(env).await {
+        Ok(lines) => lines,
+        Err(errno) => {
+            let message = format!("error reading from the standard input: {errno}");
+            return report_simple_failure(env, &message).await;
+        }
+    };
+
+    match command.assign(env, lines) {
+        Ok(()) => crate::Result::default(),
+        Err(error) => report_failure(env, &error).await,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cell::RefCell;
+    use yash_env::semantics::ExitStatus;
+    use yash_env::system::r#virtual::FileBody;
+    use yash_env::system::r#virtual::SystemState;
+    use yash_env::test_helper::in_virtual_system;
+
+    fn set_stdin<B: Into<Vec<u8>>>(system: &RefCell<SystemState>, bytes: B) {
+        let state = system.borrow_mut();
+        let stdin = state.file_system.get("/dev/stdin").unwrap();
+        stdin.borrow_mut().body = FileBody::new(bytes);
+    }
+
+    fn array(values: &[&str]) -> Value {
+        Value::array(values.iter().copied())
+    }
+
+    #[test]
+    fn reading_three_lines_keeping_newlines() {
+        in_virtual_system(|mut env, system| async move {
+            set_stdin(&system, "foo\nbar\nbaz\n");
+
+            let result = main(&mut env, Field::dummies(["lines"])).await;
+            assert_eq!(result, crate::Result::default());
+            let var = env.variables.get("lines").unwrap();
+            assert_eq!(var.value, Some(array(&["foo\n", "bar\n", "baz\n"])));
+        })
+    }
+
+    #[test]
+    fn reading_three_lines_trimming_newlines() {
+        in_virtual_system(|mut env, system| async move {
+            set_stdin(&system, "foo\nbar\nbaz");
+
+            let result = main(&mut env, Field::dummies(["-t", "lines"])).await;
+            assert_eq!(result, crate::Result::default());
+            let var = env.variables.get("lines").unwrap();
+            assert_eq!(var.value, Some(array(&["foo", "bar", "baz"])));
+        })
+    }
+
+    #[test]
+    fn default_variable() {
+        in_virtual_system(|mut env, system| async move {
+            set_stdin(&system, "foo\n");
+
+            let result = main(&mut env, vec![]).await;
+            assert_eq!(result, crate::Result::default());
+            let var = env.variables.get(DEFAULT_VARIABLE).unwrap();
+            assert_eq!(var.value, Some(array(&["foo\n"])));
+        })
+    }
+
+    #[test]
+    fn max_count_leaves_rest_unread() {
+        in_virtual_system(|mut env, system| async move {
+            set_stdin(&system, "1\n2\n3\n");
+
+            let result = main(&mut env, Field::dummies(["-t", "-n", "2", "a"])).await;
+            assert_eq!(result, crate::Result::default());
+            let result = main(&mut env, Field::dummies(["-t", "b"])).await;
+            assert_eq!(result, crate::Result::default());
+            assert_eq!(
+                env.variables.get("a").unwrap().value,
+                Some(array(&["1", "2"]))
+            );
+            assert_eq!(env.variables.get("b").unwrap().value, Some(array(&["3"])));
+        })
+    }
+
+    #[test]
+    fn origin_retains_preceding_elements() {
+        in_virtual_system(|mut env, system| async move {
+            set_stdin(&system, "x\ny\n");
+            env.variables
+                .get_or_new("a", Scope::Global)
+                .assign(array(&["1", "2", "3", "4"]), None)
+                .unwrap();
+
+            let result = main(&mut env, Field::dummies(["-t", "-O", "1", "a"])).await;
+            assert_eq!(result, crate::Result::default());
+            let var = env.variables.get("a").unwrap();
+            assert_eq!(var.value, Some(array(&["1", "x", "y", "4"])));
+        })
+    }
+
+    #[test]
+    fn origin_beyond_end_pads_with_empty_strings() {
+        in_virtual_system(|mut env, system| async move {
+            set_stdin(&system, "x\n");
+
+            let result = main(&mut env, Field::dummies(["-t", "-O", "2", "a"])).await;
+            assert_eq!(result, crate::Result::default());
+            let var = env.variables.get("a").unwrap();
+            assert_eq!(var.value, Some(array(&["", "", "x"])));
+        })
+    }
+
+    #[test]
+    fn assigning_to_read_only_variable() {
+        in_virtual_system(|mut env, system| async move {
+            set_stdin(&system, "foo\n");
+            let mut var = env.variables.get_or_new("a", Scope::Global);
+            var.assign("ro", None).unwrap();
+            var.make_read_only(yash_env::source::Location::dummy("ro"));
+
+            let result = main(&mut env, Field::dummies(["a"])).await;
+            assert_eq!(result.exit_status(), ExitStatus::FAILURE);
+        })
+    }
+}
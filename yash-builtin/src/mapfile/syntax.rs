@@ -0,0 +1,221 @@
+// This file is part of yash, an extended POSIX shell.
+// Copyright (C) 2026 WATANABE Yuki
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+//! Command line argument parser for the mapfile built-in
+
+use super::{Command, DEFAULT_VARIABLE};
+use crate::common::syntax::Mode;
+use crate::common::syntax::OptionArgumentSpec;
+use crate::common::syntax::OptionSpec;
+use crate::common::syntax::parse_arguments;
+use std::num::ParseIntError;
+use thiserror::Error;
+use yash_env::Env;
+use yash_env::semantics::Field;
+use yash_env::source::Location;
+use yash_env::source::pretty::{Report, ReportType, Snippet};
+
+/// Error in parsing command line arguments
+#[derive(Clone, Debug, Eq, Error, PartialEq)]
+#[non_exhaustive]
+pub enum Error {
+    /// An error occurred in the common parser.
+    #[error(transparent)]
+    CommonError(#[from] crate::common::syntax::ParseError<'static>),
+
+    /// An option argument is not a non-negative decimal integer.
+    #[error("invalid number {0}: {1}")]
+    InvalidNumber(Field, ParseIntError),
+
+    /// More than one operand is given.
+    #[error("too many operands")]
+    TooManyOperands(Field),
+
+    /// The operand is not a valid variable name.
+    #[error("invalid variable name")]
+    InvalidVariableName(Field),
+}
+
+impl Error {
+    /// Converts this error to a report.
+    #[must_use]
+    pub fn to_report(&self) -> Report<'_> {
+        let snippets = match self {
+            Self::CommonError(parse_error) => return parse_error.to_report(),
+
+            Self::InvalidNumber(field, _) => {
+                Snippet::with_primary_span(&field.origin, self.to_string().into())
+            }
+
+            Self::TooManyOperands(operand) => Snippet::with_primary_span(
+                &operand.origin,
+                "only one variable name is allowed".into(),
+            ),
+
+            Self::InvalidVariableName(name) => Snippet::with_primary_span(
+                &name.origin,
+                format!("variable name {:?} is not valid", name.value).into(),
+            ),
+        };
+
+        let mut report = Report::new();
+        report.r#type = ReportType::Error;
+        report.title = self.to_string().into();
+        report.snippets = snippets;
+        report
+    }
+}
+
+impl<'a> From<&'a Error> for Report<'a> {
+    #[inline]
+    fn from(error: &'a Error) -> Self {
+        error.to_report()
+    }
+}
+
+const OPTION_SPECS: &[OptionSpec] = &[
+    OptionSpec::new()
+        .short('n')
+        .long("max-count")
+        .argument(OptionArgumentSpec::Required),
+    OptionSpec::new()
+        .short('O')
+        .long("origin")
+        .argument(OptionArgumentSpec::Required),
+    OptionSpec::new().short('t').long("trim-newline"),
+];
+
+fn parse_number(field: Field) -> Result<usize, Error> {
+    match field.value.parse() {
+        Ok(number) => Ok(number),
+        Err(error) => Err(Error::InvalidNumber(field, error)),
+    }
+}
+
+/// Parses command line arguments.
+pub fn parse<S>(env: &Env<S>, args: Vec<Field>) -> Result<Command, Error> {
+    let mode = Mode::with_env(env);
+    let (options, operands) = parse_arguments(OPTION_SPECS, mode, args)?;
+
+    // Parse options
+    let mut max_count = None;
+    let mut origin = None;
+    let mut trim_newline = false;
+    for option in options {
+        match option.spec.get_short() {
+            Some('n') => match parse_number(option.argument.unwrap())? {
+                0 => max_count = None,
+                count => max_count = Some(count),
+            },
+            Some('O') => origin = Some(parse_number(option.argument.unwrap())?),
+            Some('t') => trim_newline = true,
+            _ => unreachable!(),
+        }
+    }
+
+    // Parse operands
+    let mut operands = operands.into_iter();
+    let variable = operands.next();
+    if let Some(operand) = operands.next() {
+        return Err(Error::TooManyOperands(operand));
+    }
+    let variable = match variable {
+        None => Field {
+            value: DEFAULT_VARIABLE.to_owned(),
+            origin: env.stack.current_builtin().map_or_else(
+                || Location::dummy(""),
+                |builtin| builtin.name.origin.clone(),
+            ),
+        },
+        Some(name) if name.value.is_empty() || name.value.contains('=') => {
+            return Err(Error::InvalidVariableName(name));
+        }
+        Some(name) => name,
+    };
+
+    Ok(Command {
+        max_count,
+        origin,
+        trim_newline,
+        variable,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use assert_matches::assert_matches;
+
+    #[test]
+    fn no_arguments() {
+        let env = Env::new_virtual();
+        let command = parse(&env, vec![]).unwrap();
+        assert_eq!(command.max_count, None);
+        assert_eq!(command.origin, None);
+        assert!(!command.trim_newline);
+        assert_eq!(command.variable.value, DEFAULT_VARIABLE);
+    }
+
+    #[test]
+    fn all_options_and_operand() {
+        let env = Env::new_virtual();
+        let args = Field::dummies(["-t", "-n", "3", "-O", "2", "lines"]);
+        assert_eq!(
+            parse(&env, args),
+            Ok(Command {
+                max_count: Some(3),
+                origin: Some(2),
+                trim_newline: true,
+                variable: Field::dummy("lines"),
+            })
+        );
+    }
+
+    #[test]
+    fn zero_max_count_means_unlimited() {
+        let env = Env::new_virtual();
+        let command = parse(&env, Field::dummies(["-n", "0"])).unwrap();
+        assert_eq!(command.max_count, None);
+    }
+
+    #[test]
+    fn invalid_number() {
+        let env = Env::new_virtual();
+        let result = parse(&env, Field::dummies(["-n", "x"]));
+        assert_matches!(result, Err(Error::InvalidNumber(field, _)) => {
+            assert_eq!(field.value, "x");
+        });
+
+        let result = parse(&env, Field::dummies(["-O", "-1"]));
+        assert_matches!(result, Err(Error::InvalidNumber(field, _)) => {
+            assert_eq!(field.value, "-1");
+        });
+    }
+
+    #[test]
+    fn too_many_operands() {
+        let env = Env::new_virtual();
+        let result = parse(&env, Field::dummies(["a", "b"]));
+        assert_eq!(result, Err(Error::TooManyOperands(Field::dummy("b"))));
+    }
+
+    #[test]
+    fn invalid_variable_name() {
+        let env = Env::new_virtual();
+        let result = parse(&env, Field::dummies(["a=b"]));
+        assert_eq!(result, Err(Error::InvalidVariableName(Field::dummy("a=b"))));
+    }
+}
@@ -22,20 +22,28 @@
 //!
 //! # Implementation notes
 //!
-//! The built-in reads the input byte by byte. This is inefficient, but it is
-//! necessary not to read past the delimiter.
-//! (TODO: Use a buffered reader if the input is seekable)
+//! If the standard input is seekable, the built-in reads the input in bulk and
+//! seeks back to just after the delimiter. Otherwise, the built-in reads the
+//! input byte by byte. This is inefficient, but it is necessary not to read
+//! past the delimiter. Embedders that know the built-in is the only reader of
+//! the input can store [`BufferedInput`](input::BufferedInput) in the
+//! environment's [`any`](yash_env::Env::any) storage to read non-seekable
+//! input in bulk as well.
+//!
+//! The `-t` option is implemented by racing the input against a
+//! [`Sleep`] future, so partial input read before the timeout is discarded.
 //!
 //! Prompting requires a [`GetPrompt`](yash_env::prompt::GetPrompt) instance to
 //! be available in the environment's [`any`](yash_env::Env::any) storage. If no
 //! such instance is found, the built-in will **panic**.
 
 use crate::common::report::{merge_reports, report, report_simple};
+use std::time::Duration;
 use yash_env::Env;
 use yash_env::semantics::ExitStatus;
 use yash_env::semantics::Field;
-use yash_env::system::concurrency::WriteAll;
-use yash_env::system::{Isatty, Read};
+use yash_env::system::concurrency::{Sleep, WriteAll};
+use yash_env::system::{Fstat, Isatty, Read, Seek};
 
 pub mod assigning;
 pub mod input;
@@ -56,6 +64,25 @@ pub const EXIT_STATUS_READ_ERROR: ExitStatus = ExitStatus(3);
 /// Exit status on a command line syntax error
 pub const EXIT_STATUS_SYNTAX_ERROR: ExitStatus = ExitStatus(4);
 
+/// Exit status when the built-in times out before reading a complete line
+///
+/// This value is greater than 128 so that it can be distinguished from the
+/// other exit statuses. It is the same value as used by bash.
+pub const EXIT_STATUS_TIMEOUT: ExitStatus = ExitStatus(142);
+
+/// Limit on the number of characters to read
+///
+/// This is specified by the `-n` or `-N` option.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum CharCount {
+    /// Read at most the specified number of characters, stopping at the
+    /// delimiter if found earlier (`-n`)
+    UpToDelimiter(usize),
+    /// Read exactly the specified number of characters unless the end of input
+    /// is reached, ignoring the delimiter (`-N`)
+    Exact(usize),
+}
+
 /// Abstract command line arguments of the `read` built-in
 ///
 /// An instance of this struct is created by parsing command line arguments
@@ -73,6 +100,30 @@ pub struct Command {
     /// If this field is `true`, backslashes are not interpreted.
     pub is_raw: bool,
 
+    /// Limit specified by the `-n` or `-N` option
+    ///
+    /// If both options are specified, the last one is effective.
+    pub char_count: Option<CharCount>,
+
+    /// Prompt specified by the `-p` option
+    ///
+    /// If this field is `Some`, the string is displayed before reading the
+    /// first line if the shell is interactive and the standard input is a
+    /// terminal.
+    pub prompt: Option<String>,
+
+    /// Timeout specified by the `-t` option
+    ///
+    /// If this field is `Some`, the built-in gives up reading if no complete
+    /// line is read before the duration elapses.
+    pub timeout: Option<Duration>,
+
+    /// Whether the `-a` option is specified
+    ///
+    /// If this field is `true`, the last variable is assigned an array of the
+    /// remaining fields instead of a string.
+    pub is_array: bool,
+
     /// Names of variables to be assigned, except the last one
     pub variables: Vec<Field>,
 
@@ -86,15 +137,28 @@ pub struct Command {
 /// Entry point of the `read` built-in
 pub async fn main<S>(env: &mut Env<S>, args: Vec<Field>) -> crate::Result
 where
-    S: Isatty + Read + WriteAll + 'static,
+    S: Clone + Fstat + Isatty + Read + Seek + Sleep + WriteAll + 'static,
 {
     let command = match syntax::parse(env, args) {
         Ok(command) => command,
         Err(error) => return report(env, &error, EXIT_STATUS_SYNTAX_ERROR).await,
     };
 
-    let (input, newline_found) = match input::read(env, command.delimiter, command.is_raw).await {
-        Ok(input) => input,
+    let delimiter = command.delimiter;
+    let is_raw = command.is_raw;
+    let limit = command.char_count;
+    let prompt = command.prompt.as_deref();
+    let result = match command.timeout {
+        None => input::read(env, delimiter, is_raw, limit, prompt)
+            .await
+            .map(Some),
+        Some(timeout) => {
+            input::read_with_timeout(env, delimiter, is_raw, limit, prompt, timeout).await
+        }
+    };
+    let (input, newline_found) = match result {
+        Ok(Some(input)) => input,
+        Ok(None) => return EXIT_STATUS_TIMEOUT.into(),
         Err(error) => return report(env, &error, EXIT_STATUS_READ_ERROR).await,
     };
 
@@ -102,7 +166,13 @@ where
         return report_simple(env, "input contains a nul byte", EXIT_STATUS_READ_ERROR).await;
     }
 
-    let errors = assigning::assign(env, &input, command.variables, command.last_variable);
+    let variables = command.variables;
+    let last_variable = command.last_variable;
+    let errors = if command.is_array {
+        assigning::assign_array(env, &input, variables, last_variable)
+    } else {
+        assigning::assign(env, &input, variables, last_variable)
+    };
     match merge_reports(&errors) {
         None if newline_found => EXIT_STATUS_SUCCESS.into(),
         None => EXIT_STATUS_EOF.into(),
@@ -16,6 +16,7 @@
 
 //! Assigning the input to variables
 
+use std::ops::Range;
 use yash_env::Env;
 use yash_env::semantics::Field;
 use yash_env::semantics::expansion::attr::AttrChar;
@@ -25,6 +26,7 @@ use yash_env::semantics::expansion::split::Class;
 use yash_env::semantics::expansion::split::Ifs;
 use yash_env::variable::IFS;
 use yash_env::variable::Scope;
+use yash_env::variable::Value;
 
 pub use crate::typeset::AssignReadOnlyError as Error;
 
@@ -44,23 +46,13 @@ pub fn assign<S>(
     variables: Vec<Field>,
     last_variable: Field,
 ) -> Vec<Error> {
-    let ifs = env
-        .variables
-        .get_scalar(IFS)
-        .unwrap_or(Ifs::DEFAULT)
-        .to_owned();
+    let ifs = current_ifs(env);
     let ifs = Ifs::new(&ifs);
 
     let mut ranges = ifs.ranges(text.iter().copied());
 
     // Assign variables but the last
-    let mut errors = variables
-        .into_iter()
-        .filter_map(|var_name| {
-            let value = ranges.next().map(|r| &text[r]).unwrap_or_default();
-            assign_one(env, var_name, value).err()
-        })
-        .collect::<Vec<_>>();
+    let mut errors = assign_leading(env, text, &mut ranges, variables);
 
     // Assign the last
     let range = match ranges.next() {
@@ -83,10 +75,74 @@ pub fn assign<S>(
     errors
 }
 
+/// Assigns the text to variables and an array.
+///
+/// This function is similar to [`assign`], but the last variable is assigned
+/// an array containing all the remaining fields, one field per element. Field
+/// separators are not included in the array. If there are no remaining
+/// fields, the array is empty.
+pub fn assign_array<S>(
+    env: &mut Env<S>,
+    text: &[AttrChar],
+    variables: Vec<Field>,
+    array_variable: Field,
+) -> Vec<Error> {
+    let ifs = current_ifs(env);
+    let ifs = Ifs::new(&ifs);
+
+    let mut ranges = ifs.ranges(text.iter().copied());
+
+    let mut errors = assign_leading(env, text, &mut ranges, variables);
+
+    let values = ranges.map(|range| field_value(&text[range]));
+    let array_result = assign_value(env, array_variable, Value::array(values));
+    errors.extend(array_result.err());
+
+    errors
+}
+
+/// Returns the current value of `$IFS`.
+fn current_ifs<S>(env: &Env<S>) -> String {
+    env.variables
+        .get_scalar(IFS)
+        .unwrap_or(Ifs::DEFAULT)
+        .to_owned()
+}
+
+/// Assigns the first fields to variables.
+///
+/// This function consumes as many ranges as there are variables.
+fn assign_leading<S, I>(
+    env: &mut Env<S>,
+    text: &[AttrChar],
+    ranges: &mut I,
+    variables: Vec<Field>,
+) -> Vec<Error>
+where
+    I: Iterator<Item = Range<usize>>,
+{
+    variables
+        .into_iter()
+        .filter_map(|var_name| {
+            let value = ranges.next().map(|r| &text[r]).unwrap_or_default();
+            assign_one(env, var_name, value).err()
+        })
+        .collect()
+}
+
+/// Performs quote removal and attribute stripping on a field.
+fn field_value(value: &[AttrChar]) -> String {
+    let value = value.iter().copied();
+    skip_quotes(value).strip().collect()
+}
+
 /// Assigns one field to a variable.
 fn assign_one<S>(env: &mut Env<S>, name: Field, value: &[AttrChar]) -> Result<(), Error> {
-    let value = value.iter().copied();
-    let value = skip_quotes(value).strip().collect::<String>();
+    assign_value(env, name, field_value(value).into())
+}
+
+/// Assigns a value to a variable.
+fn assign_value<S>(env: &mut Env<S>, name: Field, value: Value) -> Result<(), Error> {
     let mut var = env.get_or_create_variable(name.value.clone(), Scope::Global);
     match var.assign(value, name.origin) {
         Ok(_old_value) => Ok(()),
@@ -105,7 +161,6 @@ mod tests {
     use assert_matches::assert_matches;
     use yash_env::semantics::expansion::attr::Origin;
     use yash_env::source::Location;
-    use yash_env::variable::Value;
     use yash_env::variable::Variable;
     use yash_env::variable::VariableSet;
 
@@ -403,4 +458,79 @@ mod tests {
         });
         assert_variable(&env.variables, "second", "222");
     }
+
+    fn assert_array(vars: &VariableSet, name: &str, values: &[&str]) {
+        assert_matches!(
+            vars.get(name),
+            Some(Variable { value: Some(Value::Array(v)), ..  }) if v == values,
+            "expected ${name}={values:?}",
+        );
+    }
+
+    #[test]
+    fn array_from_empty_text() {
+        let mut env = Env::new_virtual();
+
+        let errors = assign_array(&mut env, &[], vec![], Field::dummy("a"));
+
+        assert_eq!(errors, []);
+        assert_array(&env.variables, "a", &[]);
+    }
+
+    #[test]
+    fn array_with_default_ifs() {
+        let mut env = Env::new_virtual();
+        let text = attr_chars(" 1 222  33 ");
+
+        let errors = assign_array(&mut env, &text, vec![], Field::dummy("a"));
+
+        assert_eq!(errors, []);
+        assert_array(&env.variables, "a", &["1", "222", "33"]);
+    }
+
+    #[test]
+    fn array_with_custom_ifs() {
+        let mut env = Env::new_virtual();
+        env.get_or_create_variable(IFS, Scope::Global)
+            .assign(":", None)
+            .unwrap();
+        let text = attr_chars("1:2 2::33");
+
+        let errors = assign_array(&mut env, &text, vec![], Field::dummy("a"));
+
+        assert_eq!(errors, []);
+        assert_array(&env.variables, "a", &["1", "2 2", "", "33"]);
+    }
+
+    #[test]
+    fn variables_before_array() {
+        let mut env = Env::new_virtual();
+        let text = attr_chars("1 2 3 4");
+
+        let errors = assign_array(&mut env, &text, Field::dummies(["x"]), Field::dummy("a"));
+
+        assert_eq!(errors, []);
+        assert_variable(&env.variables, "x", "1");
+        assert_array(&env.variables, "a", &["2", "3", "4"]);
+    }
+
+    #[test]
+    fn read_only_array() {
+        let mut env = Env::new_virtual();
+        env.get_or_create_variable("a", Scope::Global)
+            .make_read_only(Location::dummy("read-only"));
+        let text = attr_chars("1 2");
+
+        let errors = assign_array(&mut env, &text, vec![], Field::dummy("a"));
+
+        assert_eq!(
+            errors,
+            [Error {
+                name: "a".into(),
+                new_value: Value::array(["1", "2"]),
+                assigned_location: Location::dummy("a"),
+                read_only_location: Location::dummy("read-only"),
+            }]
+        );
+    }
 }
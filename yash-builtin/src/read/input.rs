@@ -16,6 +16,11 @@
 
 //! Reading input
 
+use super::CharCount;
+use futures_util::future::{Either, select};
+use std::io::SeekFrom;
+use std::pin::pin;
+use std::time::Duration;
 use thiserror::Error;
 use yash_env::Env;
 use yash_env::io::Fd;
@@ -23,8 +28,8 @@ use yash_env::prompt::GetPrompt;
 use yash_env::semantics::expansion::attr::AttrChar;
 use yash_env::semantics::expansion::attr::Origin;
 use yash_env::source::pretty::{Report, ReportType};
-use yash_env::system::concurrency::WriteAll;
-use yash_env::system::{Errno, Isatty, Read};
+use yash_env::system::concurrency::{Sleep, WriteAll};
+use yash_env::system::{Errno, Fstat, Isatty, Read, Seek, Stat as _};
 
 /// Error reading from the standard input
 ///
@@ -98,29 +103,83 @@ fn plain(value: char) -> AttrChar {
 /// [`GetPrompt`] instance to be available in the environment's
 /// [`any`](Env::any) storage.
 ///
+/// If `prompt` is `Some`, the string is displayed before reading the first
+/// line under the same condition as the `PS2` prompt.
+///
+/// If `limit` is `Some`, this function stops reading after the specified
+/// number of characters. Characters are counted after backslash processing,
+/// so a quoting backslash or a line continuation is not counted. With
+/// [`CharCount::Exact`], the `delimiter` is not recognized and is read as an
+/// ordinary character. Input after the limit is left unread for the next read.
+///
 /// If successful, this function returns a vector of [`AttrChar`]s representing
 /// the line read and a boolean value indicating whether the line was terminated
-/// by a delimiter. If the end of the input is reached before finding a
-/// delimiter, the boolean value is `false`.
+/// by a delimiter or the character limit. If the end of the input is reached
+/// before that, the boolean value is `false`.
+///
+/// If the standard input is seekable and not a terminal, this function reads
+/// the input in bulk and then moves the file offset back to just after the
+/// last character consumed, so that the next read starts right after the line
+/// read by this function. If the standard input is neither seekable nor a
+/// terminal and a [`BufferedInput`] instance is in the environment's
+/// [`any`](Env::any) storage, the input is read in bulk and the bytes after
+/// the line are kept in the instance for the next call. Otherwise, the input
+/// is read byte by byte so that no bytes beyond the line are consumed.
 pub async fn read<S>(
     env: &mut Env<S>,
     delimiter: u8,
     is_raw: bool,
+    limit: Option<CharCount>,
+    prompt: Option<&str>,
+) -> Result<(Vec<AttrChar>, bool), Error>
+where
+    S: Fstat + Isatty + Read + Seek + WriteAll + 'static,
+{
+    if let Some(prompt) = prompt
+        && should_prompt(env)
+    {
+        env.system.print_error(prompt).await;
+    }
+
+    let mut reader = Reader::new(env);
+    let result = read_line(env, &mut reader, delimiter, is_raw, limit).await;
+    let finish_result = reader.finish(env);
+    let line = result?;
+    finish_result?;
+    Ok(line)
+}
+
+/// Body of [`read`]
+async fn read_line<S>(
+    env: &mut Env<S>,
+    reader: &mut Reader,
+    delimiter: u8,
+    is_raw: bool,
+    limit: Option<CharCount>,
 ) -> Result<(Vec<AttrChar>, bool), Error>
 where
     S: Isatty + Read + WriteAll + 'static,
 {
+    let (max_chars, delimiter) = match limit {
+        None => (usize::MAX, Some(char::from(delimiter))),
+        Some(CharCount::UpToDelimiter(count)) => (count, Some(char::from(delimiter))),
+        Some(CharCount::Exact(count)) => (count, None),
+    };
+    let mut char_count = 0;
     let mut result = Vec::new();
 
     let newline_found = loop {
-        // TODO Read in bulk if the standard input is seekable
-        match read_char(env).await? {
+        if char_count >= max_chars {
+            break true;
+        }
+
+        match reader.read_char(env).await? {
             None => break false,
-            Some(c) if c == delimiter.into() => break true,
+            Some(c) if Some(c) == delimiter => break true,
 
             // Backslash escape
             Some('\\') if !is_raw => {
-                let c = read_char(env).await?;
+                let c = reader.read_char(env).await?;
                 if c == Some('\n') {
                     // Line continuation
                     print_prompt(env).await;
@@ -136,61 +195,251 @@ where
             // Plain character
             Some(c) => result.push(plain(c)),
         }
+        char_count += 1;
     };
 
     Ok((result, newline_found))
 }
 
-/// Reads one character from the standard input.
+/// Reads a line from the standard input with a timeout.
 ///
-/// This function reads a single UTF-8-encoded character from the standard
-/// input. If the standard input is empty, this function returns `Ok(None)`.
-/// If the input is not a valid UTF-8 sequence, this function returns an error.
-async fn read_char<S>(env: &mut Env<S>) -> Result<Option<char>, Error>
+/// This function is similar to [`read`], but gives up reading if no complete
+/// line is read before the specified `timeout` elapses. In that case, this
+/// function returns `Ok(None)` and any partial input read so far is discarded.
+///
+/// If `timeout` is zero, this function does not wait for input at all: it
+/// returns `Ok(Some(_))` only if the input is immediately available.
+pub async fn read_with_timeout<S>(
+    env: &mut Env<S>,
+    delimiter: u8,
+    is_raw: bool,
+    limit: Option<CharCount>,
+    prompt: Option<&str>,
+    timeout: Duration,
+) -> Result<Option<(Vec<AttrChar>, bool)>, Error>
 where
-    S: Isatty + Read + WriteAll,
+    S: Clone + Fstat + Isatty + Read + Seek + Sleep + WriteAll + 'static,
 {
-    // Any character is at most 4 bytes in UTF-8.
-    let mut buffer = [0; 4];
-    let mut len = 0;
-    loop {
-        // Read from the standard input byte by byte so that we don't consume
-        // more than one character.
-        let byte = std::slice::from_mut(&mut buffer[len]);
-        let count = env.system.read(Fd::STDIN, byte).await?;
-        if count == 0 {
-            // End of input
-            return if len == 0 {
-                Ok(None)
+    let system = env.system.clone();
+    let read_fut = pin!(read(env, delimiter, is_raw, limit, prompt));
+    let timeout_fut = pin!(system.sleep(timeout));
+    match select(read_fut, timeout_fut).await {
+        Either::Left((result, _timeout_fut)) => result.map(Some),
+        Either::Right(((), _read_fut)) => Ok(None),
+    }
+}
+
+/// Size of the buffer used to read the standard input in bulk
+const BUFFER_SIZE: usize = 1024;
+
+/// Configuration that enables reading a non-seekable input in bulk
+///
+/// By default, the `read` built-in reads a standard input that is not seekable,
+/// such as a pipe, byte by byte so that it does not consume any bytes after
+/// the line read. This is slow for a large input. If an instance of this type
+/// is in the environment's [`any`](Env::any) storage, the built-in reads such
+/// input in bulk and keeps the bytes read past the line in the instance. The
+/// next `read` built-in consumes the kept bytes first if the standard input is
+/// still the same file.
+///
+/// The kept bytes are not available to other utilities reading the same
+/// input, so this configuration should only be used when the `read` built-in
+/// is the only reader of the input. A terminal is always read byte by byte
+/// regardless of this configuration.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct BufferedInput {
+    /// Bytes read from the standard input but not consumed yet
+    buffer: Vec<u8>,
+    /// Device ID and inode number of the file `buffer` was read from
+    source: Option<(u64, u64)>,
+}
+
+/// Strategy of the [`Reader`] for reading the standard input
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+enum Mode {
+    /// Reads byte by byte so that no bytes beyond the line are consumed.
+    Unbuffered,
+    /// Reads in bulk and seeks back to just after the line.
+    Seekable,
+    /// Reads in bulk and keeps the unconsumed bytes in [`BufferedInput`].
+    Buffered {
+        /// Device ID and inode number of the standard input
+        source: (u64, u64),
+    },
+}
+
+/// Reader of the standard input
+///
+/// This reader reads the standard input in bulk or byte by byte depending on
+/// the [`Mode`]. When the reader has read bytes that have not been consumed,
+/// [`finish`](Self::finish) must be called to move the file offset back or
+/// keep the bytes for the next read.
+#[derive(Debug)]
+struct Reader {
+    /// Bytes read from the standard input
+    buffer: Vec<u8>,
+    /// Index of the first byte in `buffer` that has not been consumed
+    position: usize,
+    /// How the standard input is read
+    mode: Mode,
+}
+
+impl Reader {
+    /// Creates a new reader.
+    ///
+    /// This function chooses the [`Mode`] depending on the type of the
+    /// standard input. A terminal is never regarded as seekable because some
+    /// systems allow seeking a terminal even though it does not behave like a
+    /// regular file.
+    ///
+    /// In the buffered mode, the bytes kept in the [`BufferedInput`] are
+    /// moved to the reader if they were read from the current standard input.
+    fn new<S: Fstat + Isatty + Seek>(env: &mut Env<S>) -> Self {
+        let mode = if env.system.isatty(Fd::STDIN) {
+            Mode::Unbuffered
+        } else if env.system.lseek(Fd::STDIN, SeekFrom::Current(0)).is_ok() {
+            Mode::Seekable
+        } else if env.any.get::<BufferedInput>().is_some()
+            && let Ok(stat) = env.system.fstat(Fd::STDIN)
+        {
+            Mode::Buffered {
+                source: (stat.dev(), stat.ino()),
+            }
+        } else {
+            Mode::Unbuffered
+        };
+
+        let mut buffer = Vec::new();
+        if let Mode::Buffered { source } = mode
+            && let Some(config) = env.any.get_mut::<BufferedInput>()
+        {
+            let kept = std::mem::take(&mut config.buffer);
+            if config.source.take() == Some(source) {
+                buffer = kept;
+            }
+        }
+
+        Reader {
+            buffer,
+            position: 0,
+            mode,
+        }
+    }
+
+    /// Reads one byte from the standard input.
+    ///
+    /// If the buffer has been exhausted, this function reads more bytes from
+    /// the standard input. If the standard input is empty, this function
+    /// returns `Ok(None)`.
+    async fn read_byte<S: Read>(&mut self, env: &mut Env<S>) -> Result<Option<u8>, Error> {
+        if self.position == self.buffer.len() {
+            // Read byte by byte unless unconsumed bytes can be given back so
+            // that we don't consume more than we need.
+            let size = if self.mode == Mode::Unbuffered {
+                1
             } else {
-                // The input ended in the middle of a UTF-8 sequence.
-                Err(Errno::EILSEQ.into())
+                BUFFER_SIZE
             };
+            self.buffer.resize(size, 0);
+            self.position = 0;
+            let count = (env.system.read(Fd::STDIN, &mut self.buffer).await)
+                .inspect_err(|_| self.buffer.clear())?;
+            self.buffer.truncate(count);
+            if count == 0 {
+                return Ok(None);
+            }
         }
-        debug_assert_eq!(count, 1);
-        len += 1;
-
-        match std::str::from_utf8(&buffer[..len]) {
-            Ok(s) => {
-                let mut chars = s.chars();
-                // Since the buffer is not empty, there must be a character.
-                let c = chars.next().unwrap();
-                // And it must be the only character.
-                debug_assert_eq!(chars.next(), None);
-                return Ok(Some(c));
+
+        let byte = self.buffer[self.position];
+        self.position += 1;
+        Ok(Some(byte))
+    }
+
+    /// Reads one character from the standard input.
+    ///
+    /// This function reads a single UTF-8-encoded character from the standard
+    /// input. If the standard input is empty, this function returns `Ok(None)`.
+    /// If the input is not a valid UTF-8 sequence, this function returns an
+    /// error.
+    async fn read_char<S: Read>(&mut self, env: &mut Env<S>) -> Result<Option<char>, Error> {
+        // Any character is at most 4 bytes in UTF-8.
+        let mut buffer = [0; 4];
+        let mut len = 0;
+        loop {
+            let Some(byte) = self.read_byte(env).await? else {
+                // End of input
+                return if len == 0 {
+                    Ok(None)
+                } else {
+                    // The input ended in the middle of a UTF-8 sequence.
+                    Err(Errno::EILSEQ.into())
+                };
+            };
+            buffer[len] = byte;
+            len += 1;
+
+            match std::str::from_utf8(&buffer[..len]) {
+                Ok(s) => {
+                    let mut chars = s.chars();
+                    // Since the buffer is not empty, there must be a character.
+                    let c = chars.next().unwrap();
+                    // And it must be the only character.
+                    debug_assert_eq!(chars.next(), None);
+                    return Ok(Some(c));
+                }
+                Err(e) => match e.error_len() {
+                    None => {
+                        // The bytes in the buffer are incomplete for a UTF-8
+                        // character. Read more bytes.
+                        continue;
+                    }
+                    Some(_) => return Err(Errno::EILSEQ.into()),
+                },
             }
-            Err(e) => match e.error_len() {
-                None => {
-                    // The bytes in the buffer are incomplete for a UTF-8
-                    // character. Read more bytes.
-                    continue;
+        }
+    }
+
+    /// Gives back the bytes read but not consumed.
+    ///
+    /// In the seekable mode, this function moves the file offset of the
+    /// standard input back by the number of the unconsumed bytes. In the
+    /// buffered mode, this function keeps the unconsumed bytes in the
+    /// [`BufferedInput`] for the next read.
+    fn finish<S: Seek>(&mut self, env: &mut Env<S>) -> Result<(), Error> {
+        let mut buffer = std::mem::take(&mut self.buffer);
+        let position = std::mem::take(&mut self.position);
+        match self.mode {
+            Mode::Unbuffered => Ok(()),
+
+            Mode::Seekable => {
+                let unconsumed = buffer.len() - position;
+                if unconsumed > 0 {
+                    let offset = -i64::try_from(unconsumed).map_err(|_| Errno::EOVERFLOW)?;
+                    env.system.lseek(Fd::STDIN, SeekFrom::Current(offset))?;
                 }
-                Some(_) => return Err(Errno::EILSEQ.into()),
-            },
+                Ok(())
+            }
+
+            Mode::Buffered { source } => {
+                if let Some(config) = env.any.get_mut::<BufferedInput>() {
+                    buffer.drain(..position);
+                    config.buffer = buffer;
+                    config.source = Some(source);
+                }
+                Ok(())
+            }
         }
     }
 }
 
+/// Tests if the built-in should display a prompt.
+///
+/// A prompt is displayed only if the shell is interactive and the standard
+/// input is a terminal.
+fn should_prompt<S: Isatty>(env: &Env<S>) -> bool {
+    env.is_interactive() && env.system.isatty(Fd::STDIN)
+}
+
 /// Prints the prompt string for the continuation line.
 ///
 /// This function prints the value of the `PS2` variable as a prompt for the
@@ -204,7 +453,7 @@ async fn print_prompt<S>(env: &mut Env<S>)
 where
     S: Isatty + WriteAll + 'static,
 {
-    if !env.is_interactive() || !env.system.isatty(Fd::STDIN) {
+    if !should_prompt(env) {
         return;
     }
 
@@ -222,8 +471,15 @@ where
 mod tests {
     use super::*;
     use std::cell::RefCell;
+    use std::rc::Rc;
+    use std::time::Instant;
+    use yash_env::VirtualSystem;
+    use yash_env::option::Option::Interactive;
+    use yash_env::option::State::On;
     use yash_env::system::r#virtual::FileBody;
     use yash_env::system::r#virtual::SystemState;
+    use yash_env::system::{Clock as _, Close as _, Concurrent, Dup as _, Pipe as _, Write as _};
+    use yash_env::test_helper::assert_stderr;
     use yash_env::test_helper::in_virtual_system;
 
     fn set_stdin<B: Into<Vec<u8>>>(system: &RefCell<SystemState>, bytes: B) {
@@ -239,7 +495,7 @@ mod tests {
     #[test]
     fn empty_input() {
         in_virtual_system(|mut env, _| async move {
-            let result = read(&mut env, b'\n', false).await;
+            let result = read(&mut env, b'\n', false, None, None).await;
             assert_eq!(result, Ok((vec![], false)));
         })
     }
@@ -249,13 +505,13 @@ mod tests {
         in_virtual_system(|mut env, system| async move {
             set_stdin(&system, "foo\nbar\n");
 
-            let result = read(&mut env, b'\n', false).await;
+            let result = read(&mut env, b'\n', false, None, None).await;
             assert_eq!(result, Ok((attr_chars("foo"), true)));
 
-            let result = read(&mut env, b'\n', false).await;
+            let result = read(&mut env, b'\n', false, None, None).await;
             assert_eq!(result, Ok((attr_chars("bar"), true)));
 
-            let result = read(&mut env, b'\n', false).await;
+            let result = read(&mut env, b'\n', false, None, None).await;
             assert_eq!(result, Ok((vec![], false)));
         })
     }
@@ -265,10 +521,10 @@ mod tests {
         in_virtual_system(|mut env, system| async move {
             set_stdin(&system, "newline");
 
-            let result = read(&mut env, b'\n', false).await;
+            let result = read(&mut env, b'\n', false, None, None).await;
             assert_eq!(result, Ok((attr_chars("newline"), false)));
 
-            let result = read(&mut env, b'\n', false).await;
+            let result = read(&mut env, b'\n', false, None, None).await;
             assert_eq!(result, Ok((vec![], false)));
         })
     }
@@ -278,7 +534,7 @@ mod tests {
         in_virtual_system(|mut env, system| async move {
             set_stdin(&system, "©⁉😀\n");
 
-            let result = read(&mut env, b'\n', false).await;
+            let result = read(&mut env, b'\n', false, None, None).await;
             assert_eq!(result, Ok((attr_chars("©⁉😀"), true)));
         })
     }
@@ -288,13 +544,13 @@ mod tests {
         in_virtual_system(|mut env, system| async move {
             set_stdin(&system, "foo\0bar\0");
 
-            let result = read(&mut env, b'\0', false).await;
+            let result = read(&mut env, b'\0', false, None, None).await;
             assert_eq!(result, Ok((attr_chars("foo"), true)));
 
-            let result = read(&mut env, b'\0', false).await;
+            let result = read(&mut env, b'\0', false, None, None).await;
             assert_eq!(result, Ok((attr_chars("bar"), true)));
 
-            let result = read(&mut env, b'\0', false).await;
+            let result = read(&mut env, b'\0', false, None, None).await;
             assert_eq!(result, Ok((vec![], false)));
         })
     }
@@ -304,20 +560,43 @@ mod tests {
         in_virtual_system(|mut env, system| async move {
             set_stdin(&system, "foo\nbar\n");
 
-            let result = read(&mut env, b'a', false).await;
+            let result = read(&mut env, b'a', false, None, None).await;
             assert_eq!(result, Ok((attr_chars("foo\nb"), true)));
 
-            let result = read(&mut env, b'a', false).await;
+            let result = read(&mut env, b'a', false, None, None).await;
             assert_eq!(result, Ok((attr_chars("r\n"), false)));
         })
     }
 
+    #[test]
+    fn colon_delimiter() {
+        in_virtual_system(|mut env, system| async move {
+            set_stdin(&system, "/bin:/usr/bin\n");
+
+            let result = read(&mut env, b':', false, None, None).await;
+            assert_eq!(result, Ok((attr_chars("/bin"), true)));
+
+            let result = read(&mut env, b':', false, None, None).await;
+            assert_eq!(result, Ok((attr_chars("/usr/bin\n"), false)));
+        })
+    }
+
+    #[test]
+    fn end_of_input_before_nul_delimiter() {
+        in_virtual_system(|mut env, system| async move {
+            set_stdin(&system, "foo\nbar");
+
+            let result = read(&mut env, b'\0', false, None, None).await;
+            assert_eq!(result, Ok((attr_chars("foo\nbar"), false)));
+        })
+    }
+
     #[test]
     fn raw_mode() {
         in_virtual_system(|mut env, system| async move {
             set_stdin(&system, "\\foo\\\nbar\\\nbaz\n");
 
-            let result = read(&mut env, b'\n', true).await;
+            let result = read(&mut env, b'\n', true, None, None).await;
             assert_eq!(result, Ok((attr_chars("\\foo\\"), true)));
         })
     }
@@ -327,7 +606,7 @@ mod tests {
         in_virtual_system(|mut env, system| async move {
             set_stdin(&system, "\\foo\\\nbar\\\nbaz\n");
 
-            let result = read(&mut env, b'\n', false).await;
+            let result = read(&mut env, b'\n', false, None, None).await;
             assert_eq!(
                 result,
                 Ok((
@@ -354,7 +633,7 @@ mod tests {
         in_virtual_system(|mut env, system| async move {
             set_stdin(&system, "foo\\");
 
-            let result = read(&mut env, b'\n', false).await;
+            let result = read(&mut env, b'\n', false, None, None).await;
             assert_eq!(
                 result,
                 Ok((
@@ -370,24 +649,326 @@ mod tests {
         in_virtual_system(|mut env, system| async move {
             set_stdin(&system, *b"\xFF");
 
-            let result = read(&mut env, b'\n', false).await;
+            let result = read(&mut env, b'\n', false, None, None).await;
             assert_eq!(result, Err(Errno::EILSEQ.into()));
         });
 
         in_virtual_system(|mut env, system| async move {
             set_stdin(&system, *b"\xCF\xD0");
 
-            let result = read(&mut env, b'\n', false).await;
+            let result = read(&mut env, b'\n', false, None, None).await;
             assert_eq!(result, Err(Errno::EILSEQ.into()));
         });
 
         in_virtual_system(|mut env, system| async move {
             set_stdin(&system, *b"\xCF");
 
-            let result = read(&mut env, b'\n', false).await;
+            let result = read(&mut env, b'\n', false, None, None).await;
             assert_eq!(result, Err(Errno::EILSEQ.into()));
         });
     }
 
-    // TODO Test PS2 prompt
+    #[test]
+    fn large_seekable_input() {
+        in_virtual_system(|mut env, system| async move {
+            let lines = (0..10_000).map(|i| format!("line {i}\n"));
+            set_stdin(&system, lines.clone().collect::<String>());
+
+            let result = read(&mut env, b'\n', false, None, None).await;
+            assert_eq!(result, Ok((attr_chars("line 0"), true)));
+            // The file offset is just after the first line.
+            let offset = env.system.lseek(Fd::STDIN, SeekFrom::Current(0));
+            assert_eq!(offset, Ok("line 0\n".len() as u64));
+
+            for line in lines.skip(1) {
+                let result = read(&mut env, b'\n', false, None, None).await;
+                let expected = attr_chars(line.strip_suffix('\n').unwrap());
+                assert_eq!(result, Ok((expected, true)));
+            }
+            let result = read(&mut env, b'\n', false, None, None).await;
+            assert_eq!(result, Ok((vec![], false)));
+        })
+    }
+
+    #[test]
+    fn non_seekable_input_is_not_read_past_delimiter() {
+        in_virtual_system(|mut env, _| async move {
+            let writer = set_up_blocking_stdin(&mut env);
+            env.system.write(writer, b"foo\nbar\n").await.unwrap();
+
+            let result = read(&mut env, b'\n', false, None, None).await;
+            assert_eq!(result, Ok((attr_chars("foo"), true)));
+
+            let mut buffer = [0; 16];
+            let count = env.system.read(Fd::STDIN, &mut buffer).await.unwrap();
+            assert_eq!(&buffer[..count], b"bar\n");
+        })
+    }
+
+    #[test]
+    fn buffered_input_reads_pipe_in_bulk() {
+        in_virtual_system(|mut env, _| async move {
+            env.any.insert(Box::new(BufferedInput::default()));
+            let writer = set_up_blocking_stdin(&mut env);
+            let lines = (0..100).map(|i| format!("{i:09}\n")).collect::<Vec<_>>();
+            env.system
+                .write(writer, lines.concat().as_bytes())
+                .await
+                .unwrap();
+            env.system.close(writer).unwrap();
+
+            let result = read(&mut env, b'\n', false, None, None).await;
+            assert_eq!(result, Ok((attr_chars(lines[0].trim_end()), true)));
+            // The whole input has been consumed by a single read from the pipe.
+            let mut buffer = [0; 16];
+            let count = env.system.read(Fd::STDIN, &mut buffer).await.unwrap();
+            assert_eq!(count, 0);
+
+            for line in &lines[1..] {
+                let result = read(&mut env, b'\n', false, None, None).await;
+                assert_eq!(result, Ok((attr_chars(line.trim_end()), true)));
+            }
+            let result = read(&mut env, b'\n', false, None, None).await;
+            assert_eq!(result, Ok((vec![], false)));
+        })
+    }
+
+    #[test]
+    fn buffered_input_is_not_used_for_another_file() {
+        in_virtual_system(|mut env, _| async move {
+            env.any.insert(Box::new(BufferedInput::default()));
+            let writer = set_up_blocking_stdin(&mut env);
+            env.system.write(writer, b"foo\nbar\n").await.unwrap();
+            let result = read(&mut env, b'\n', false, None, None).await;
+            assert_eq!(result, Ok((attr_chars("foo"), true)));
+
+            let writer = set_up_blocking_stdin(&mut env);
+            env.system.write(writer, b"baz\n").await.unwrap();
+            let result = read(&mut env, b'\n', false, None, None).await;
+            assert_eq!(result, Ok((attr_chars("baz"), true)));
+        })
+    }
+
+    #[test]
+    fn buffered_input_is_not_used_for_terminal() {
+        in_virtual_system(|mut env, system| async move {
+            env.any.insert(Box::new(BufferedInput::default()));
+            let stdin = system.borrow().file_system.get("/dev/stdin").unwrap();
+            let content = b"foo\nbar\n".to_vec();
+            stdin.borrow_mut().body = FileBody::Terminal { content };
+
+            let result = read(&mut env, b'\n', false, None, None).await;
+            assert_eq!(result, Ok((attr_chars("foo"), true)));
+            let config = env.any.get::<BufferedInput>().unwrap();
+            assert_eq!(*config, BufferedInput::default());
+        })
+    }
+
+    fn set_up_prompt(env: &mut Env<Rc<Concurrent<VirtualSystem>>>) {
+        env.any
+            .insert(Box::new(GetPrompt::<Rc<Concurrent<VirtualSystem>>>(
+                |_env, context| {
+                    let prompt = if context.is_first_line() { "$ " } else { "> " };
+                    Box::pin(async move { prompt.to_string() })
+                },
+            )));
+    }
+
+    #[test]
+    fn ps2_prompt_for_line_continuation_on_terminal() {
+        in_virtual_system(|mut env, system| async move {
+            let stdin = system.borrow().file_system.get("/dev/stdin").unwrap();
+            let content = b"foo\\\nbar\n".to_vec();
+            stdin.borrow_mut().body = FileBody::Terminal { content };
+            env.options.set(Interactive, On);
+            set_up_prompt(&mut env);
+
+            let result = read(&mut env, b'\n', false, None, None).await;
+            assert_eq!(result, Ok((attr_chars("foobar"), true)));
+            assert_stderr(&system, |stderr| assert_eq!(stderr, "> "));
+        })
+    }
+
+    #[test]
+    fn prompt_before_first_line_on_terminal() {
+        in_virtual_system(|mut env, system| async move {
+            let stdin = system.borrow().file_system.get("/dev/stdin").unwrap();
+            let content = b"foo\\\nbar\n".to_vec();
+            stdin.borrow_mut().body = FileBody::Terminal { content };
+            env.options.set(Interactive, On);
+            set_up_prompt(&mut env);
+
+            let result = read(&mut env, b'\n', false, None, Some("Name? ")).await;
+            assert_eq!(result, Ok((attr_chars("foobar"), true)));
+            assert_stderr(&system, |stderr| assert_eq!(stderr, "Name? > "));
+        })
+    }
+
+    #[test]
+    fn no_prompt_before_first_line_if_not_terminal() {
+        in_virtual_system(|mut env, system| async move {
+            set_stdin(&system, "foo\n");
+            env.options.set(Interactive, On);
+            set_up_prompt(&mut env);
+
+            let result = read(&mut env, b'\n', false, None, Some("Name? ")).await;
+            assert_eq!(result, Ok((attr_chars("foo"), true)));
+            assert_stderr(&system, |stderr| assert_eq!(stderr, ""));
+        })
+    }
+
+    #[test]
+    fn no_ps2_prompt_if_not_terminal() {
+        in_virtual_system(|mut env, system| async move {
+            set_stdin(&system, "foo\\\nbar\n");
+            env.options.set(Interactive, On);
+            set_up_prompt(&mut env);
+
+            let result = read(&mut env, b'\n', false, None, None).await;
+            assert_eq!(result, Ok((attr_chars("foobar"), true)));
+            assert_stderr(&system, |stderr| assert_eq!(stderr, ""));
+        })
+    }
+
+    /// Replaces the standard input with the reading end of an empty pipe.
+    ///
+    /// Returns the writing end of the pipe, which must be kept open to make
+    /// reading from the standard input block.
+    fn set_up_blocking_stdin(env: &mut Env<Rc<Concurrent<VirtualSystem>>>) -> Fd {
+        let (reader, writer) = env.system.pipe().unwrap();
+        env.system.dup2(reader, Fd::STDIN).unwrap();
+        writer
+    }
+
+    #[test]
+    fn line_read_before_timeout() {
+        in_virtual_system(|mut env, system| async move {
+            system.borrow_mut().now = Some(Instant::now());
+            set_stdin(&system, "foo\n");
+
+            let timeout = Duration::from_secs(5);
+            let result = read_with_timeout(&mut env, b'\n', false, None, None, timeout).await;
+            assert_eq!(result, Ok(Some((attr_chars("foo"), true))));
+        })
+    }
+
+    #[test]
+    fn timeout_without_complete_line() {
+        in_virtual_system(|mut env, system| async move {
+            let start = Instant::now();
+            system.borrow_mut().now = Some(start);
+            let writer = set_up_blocking_stdin(&mut env);
+            env.system.write(writer, b"partial").await.unwrap();
+
+            let timeout = Duration::from_secs(5);
+            let result = read_with_timeout(&mut env, b'\n', false, None, None, timeout).await;
+            assert_eq!(result, Ok(None));
+            assert_eq!(env.system.now(), start + timeout);
+        })
+    }
+
+    #[test]
+    fn zero_timeout_with_available_input() {
+        in_virtual_system(|mut env, system| async move {
+            system.borrow_mut().now = Some(Instant::now());
+            set_stdin(&system, "foo\n");
+
+            let result =
+                read_with_timeout(&mut env, b'\n', false, None, None, Duration::ZERO).await;
+            assert_eq!(result, Ok(Some((attr_chars("foo"), true))));
+        })
+    }
+
+    #[test]
+    fn zero_timeout_without_available_input() {
+        in_virtual_system(|mut env, system| async move {
+            let start = Instant::now();
+            system.borrow_mut().now = Some(start);
+            let _writer = set_up_blocking_stdin(&mut env);
+
+            let result =
+                read_with_timeout(&mut env, b'\n', false, None, None, Duration::ZERO).await;
+            assert_eq!(result, Ok(None));
+            assert_eq!(env.system.now(), start);
+        })
+    }
+
+    #[test]
+    fn char_limit_before_delimiter() {
+        in_virtual_system(|mut env, system| async move {
+            set_stdin(&system, "foobar\n");
+
+            let limit = Some(CharCount::UpToDelimiter(3));
+            let result = read(&mut env, b'\n', false, limit, None).await;
+            assert_eq!(result, Ok((attr_chars("foo"), true)));
+
+            // The rest of the line remains for the next read.
+            let result = read(&mut env, b'\n', false, None, None).await;
+            assert_eq!(result, Ok((attr_chars("bar"), true)));
+        })
+    }
+
+    #[test]
+    fn delimiter_before_char_limit() {
+        in_virtual_system(|mut env, system| async move {
+            set_stdin(&system, "a\nbcd\n");
+
+            let limit = Some(CharCount::UpToDelimiter(3));
+            let result = read(&mut env, b'\n', false, limit, None).await;
+            assert_eq!(result, Ok((attr_chars("a"), true)));
+        })
+    }
+
+    #[test]
+    fn exact_char_count_ignores_delimiter() {
+        in_virtual_system(|mut env, system| async move {
+            set_stdin(&system, "a\nbcd\n");
+
+            let limit = Some(CharCount::Exact(3));
+            let result = read(&mut env, b'\n', false, limit, None).await;
+            assert_eq!(result, Ok((attr_chars("a\nb"), true)));
+
+            let result = read(&mut env, b'\n', false, None, None).await;
+            assert_eq!(result, Ok((attr_chars("cd"), true)));
+        })
+    }
+
+    #[test]
+    fn exact_char_count_until_end_of_input() {
+        in_virtual_system(|mut env, system| async move {
+            set_stdin(&system, "ab");
+
+            let limit = Some(CharCount::Exact(3));
+            let result = read(&mut env, b'\n', false, limit, None).await;
+            assert_eq!(result, Ok((attr_chars("ab"), false)));
+        })
+    }
+
+    #[test]
+    fn char_limit_counts_characters_not_bytes() {
+        in_virtual_system(|mut env, system| async move {
+            set_stdin(&system, "©⁉😀x\n");
+
+            let limit = Some(CharCount::UpToDelimiter(3));
+            let result = read(&mut env, b'\n', false, limit, None).await;
+            assert_eq!(result, Ok((attr_chars("©⁉😀"), true)));
+        })
+    }
+
+    #[test]
+    fn char_limit_does_not_count_quoting_backslash() {
+        in_virtual_system(|mut env, system| async move {
+            set_stdin(&system, "\\ab\\\ncd\n");
+
+            let limit = Some(CharCount::UpToDelimiter(3));
+            let result = read(&mut env, b'\n', false, limit, None).await;
+            assert_eq!(
+                result,
+                Ok((
+                    vec![quoting('\\'), quoted('a'), plain('b'), plain('c')],
+                    true
+                ))
+            );
+        })
+    }
 }
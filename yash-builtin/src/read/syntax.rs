@@ -16,11 +16,13 @@
 
 //! Command line argument parser for the read built-in
 
+use super::CharCount;
 use super::Command;
 use crate::common::syntax::Mode;
 use crate::common::syntax::OptionArgumentSpec;
 use crate::common::syntax::OptionSpec;
 use crate::common::syntax::parse_arguments;
+use std::time::Duration;
 use thiserror::Error;
 use yash_env::Env;
 use yash_env::semantics::Field;
@@ -39,6 +41,14 @@ pub enum Error {
     #[error("multibyte delimiter is not supported")]
     MultibyteDelimiter { delimiter: Field },
 
+    /// The count specified by the `-n` or `-N` option is not a valid number.
+    #[error("invalid character count")]
+    InvalidCharCount { count: Field },
+
+    /// The timeout specified by the `-t` option is not a valid number.
+    #[error("invalid timeout")]
+    InvalidTimeout { timeout: Field },
+
     /// No operand is given.
     #[error("missing operand")]
     MissingOperand,
@@ -65,6 +75,20 @@ impl Error {
                 .into(),
             ),
 
+            Self::InvalidCharCount { count } => Snippet::with_primary_span(
+                &count.origin,
+                format!("count {:?} is not a non-negative integer", count.value).into(),
+            ),
+
+            Self::InvalidTimeout { timeout } => Snippet::with_primary_span(
+                &timeout.origin,
+                format!(
+                    "timeout {:?} is not a non-negative decimal number",
+                    timeout.value
+                )
+                .into(),
+            ),
+
             Self::MissingOperand => vec![],
 
             Self::InvalidVariableName { name } => Snippet::with_primary_span(
@@ -89,11 +113,29 @@ impl<'a> From<&'a Error> for Report<'a> {
 }
 
 const OPTION_SPECS: &[OptionSpec] = &[
+    OptionSpec::new().short('A'),
+    OptionSpec::new().short('a').long("array"),
     OptionSpec::new()
         .short('d')
         .long("delimiter")
         .argument(OptionArgumentSpec::Required),
+    OptionSpec::new()
+        .short('n')
+        .long("max-chars")
+        .argument(OptionArgumentSpec::Required),
+    OptionSpec::new()
+        .short('N')
+        .long("exact-chars")
+        .argument(OptionArgumentSpec::Required),
+    OptionSpec::new()
+        .short('p')
+        .long("prompt")
+        .argument(OptionArgumentSpec::Required),
     OptionSpec::new().short('r').long("raw-mode"),
+    OptionSpec::new()
+        .short('t')
+        .long("timeout")
+        .argument(OptionArgumentSpec::Required),
 ];
 
 /// Parses command line arguments.
@@ -102,10 +144,15 @@ pub fn parse<S>(env: &Env<S>, args: Vec<Field>) -> Result<Command, Error> {
     let (options, operands) = parse_arguments(OPTION_SPECS, mode, args)?;
 
     // Parse options
+    let mut is_array = false;
     let mut delimiter = b'\n';
     let mut is_raw = false;
+    let mut char_count = None;
+    let mut prompt = None;
+    let mut timeout = None;
     for option in options {
         match option.spec.get_short() {
+            Some('A' | 'a') => is_array = true,
             Some('d') => {
                 let arg = option.argument.unwrap();
                 match arg.value.len() {
@@ -114,7 +161,23 @@ pub fn parse<S>(env: &Env<S>, args: Vec<Field>) -> Result<Command, Error> {
                     _ => return Err(Error::MultibyteDelimiter { delimiter: arg }),
                 }
             }
+            Some('n') => {
+                let count = parse_char_count(option.argument.unwrap())?;
+                char_count = Some(CharCount::UpToDelimiter(count));
+            }
+            Some('N') => {
+                let count = parse_char_count(option.argument.unwrap())?;
+                char_count = Some(CharCount::Exact(count));
+            }
+            Some('p') => prompt = Some(option.argument.unwrap().value),
             Some('r') => is_raw = true,
+            Some('t') => {
+                let arg = option.argument.unwrap();
+                match parse_timeout(&arg.value) {
+                    Some(duration) => timeout = Some(duration),
+                    None => return Err(Error::InvalidTimeout { timeout: arg }),
+                }
+            }
             _ => unreachable!(),
         }
     }
@@ -126,11 +189,42 @@ pub fn parse<S>(env: &Env<S>, args: Vec<Field>) -> Result<Command, Error> {
     Ok(Command {
         delimiter,
         is_raw,
+        char_count,
+        prompt,
+        timeout,
+        is_array,
         variables,
         last_variable,
     })
 }
 
+/// Parses the argument to the `-n` or `-N` option.
+fn parse_char_count(arg: Field) -> Result<usize, Error> {
+    if arg.value.bytes().all(|b| b.is_ascii_digit())
+        && let Ok(count) = arg.value.parse()
+    {
+        Ok(count)
+    } else {
+        Err(Error::InvalidCharCount { count: arg })
+    }
+}
+
+/// Parses the argument to the `-t` option.
+///
+/// The argument must be a non-negative decimal number of seconds, optionally
+/// with a fractional part. Returns `None` if the argument is not valid.
+fn parse_timeout(value: &str) -> Option<Duration> {
+    let (integral, fractional) = value.split_once('.').unwrap_or((value, ""));
+    let is_digits = |s: &str| s.bytes().all(|b| b.is_ascii_digit());
+    let is_valid = !(integral.is_empty() && fractional.is_empty())
+        && is_digits(integral)
+        && is_digits(fractional);
+    if !is_valid {
+        return None;
+    }
+    Duration::try_from_secs_f64(value.parse().ok()?).ok()
+}
+
 /// Tests if all the variable names are valid.
 ///
 /// If all the variable names are valid, this function returns `names` as is.
@@ -156,6 +250,10 @@ mod tests {
             Ok(Command {
                 delimiter: b'\n',
                 is_raw: false,
+                char_count: None,
+                prompt: None,
+                timeout: None,
+                is_array: false,
                 variables: vec![],
                 last_variable: Field::dummy("var"),
             })
@@ -170,12 +268,162 @@ mod tests {
             Ok(Command {
                 delimiter: b'\n',
                 is_raw: true,
+                char_count: None,
+                prompt: None,
+                timeout: None,
+                is_array: false,
+                variables: vec![],
+                last_variable: Field::dummy("var"),
+            })
+        );
+    }
+
+    #[test]
+    fn prompt() {
+        let env = Env::new_virtual();
+        assert_eq!(
+            parse(&env, Field::dummies(["-p", "Name? ", "var"])),
+            Ok(Command {
+                delimiter: b'\n',
+                is_raw: false,
+                char_count: None,
+                prompt: Some("Name? ".to_string()),
+                timeout: None,
+                is_array: false,
+                variables: vec![],
+                last_variable: Field::dummy("var"),
+            })
+        );
+    }
+
+    #[test]
+    fn char_count() {
+        let env = Env::new_virtual();
+        assert_eq!(
+            parse(&env, Field::dummies(["-n", "3", "var"])),
+            Ok(Command {
+                delimiter: b'\n',
+                is_raw: false,
+                char_count: Some(CharCount::UpToDelimiter(3)),
+                prompt: None,
+                timeout: None,
+                is_array: false,
+                variables: vec![],
+                last_variable: Field::dummy("var"),
+            })
+        );
+
+        assert_eq!(
+            parse(&env, Field::dummies(["-n", "3", "-N", "5", "var"])),
+            Ok(Command {
+                delimiter: b'\n',
+                is_raw: false,
+                char_count: Some(CharCount::Exact(5)),
+                prompt: None,
+                timeout: None,
+                is_array: false,
+                variables: vec![],
+                last_variable: Field::dummy("var"),
+            })
+        );
+    }
+
+    #[test]
+    fn invalid_char_count() {
+        let env = Env::new_virtual();
+        for count in ["", "-1", "+1", "x", "99999999999999999999999"] {
+            assert_eq!(
+                parse(&env, Field::dummies(["-n", count, "var"])),
+                Err(Error::InvalidCharCount {
+                    count: Field::dummy(count)
+                }),
+                "{count:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn timeout() {
+        let env = Env::new_virtual();
+        assert_eq!(
+            parse(&env, Field::dummies(["-t", "2.5", "var"])),
+            Ok(Command {
+                delimiter: b'\n',
+                is_raw: false,
+                char_count: None,
+                prompt: None,
+                timeout: Some(Duration::from_millis(2500)),
+                is_array: false,
+                variables: vec![],
+                last_variable: Field::dummy("var"),
+            })
+        );
+
+        assert_eq!(
+            parse(&env, Field::dummies(["-t", "0", "var"])),
+            Ok(Command {
+                delimiter: b'\n',
+                is_raw: false,
+                char_count: None,
+                prompt: None,
+                timeout: Some(Duration::ZERO),
+                is_array: false,
                 variables: vec![],
                 last_variable: Field::dummy("var"),
             })
         );
     }
 
+    #[test]
+    fn invalid_timeout() {
+        let env = Env::new_virtual();
+        for timeout in ["", ".", "-1", "1e3", "inf", "3s"] {
+            assert_eq!(
+                parse(&env, Field::dummies(["-t", timeout, "var"])),
+                Err(Error::InvalidTimeout {
+                    timeout: Field::dummy(timeout)
+                }),
+                "{timeout:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn array() {
+        let env = Env::new_virtual();
+        assert_eq!(
+            parse(&env, Field::dummies(["-a", "foo", "bar"])),
+            Ok(Command {
+                delimiter: b'\n',
+                is_raw: false,
+                char_count: None,
+                prompt: None,
+                timeout: None,
+                is_array: true,
+                variables: Field::dummies(["foo"]),
+                last_variable: Field::dummy("bar"),
+            })
+        );
+    }
+
+    #[test]
+    fn array_with_uppercase_option() {
+        let env = Env::new_virtual();
+        assert_eq!(
+            parse(&env, Field::dummies(["-A", "foo"])),
+            Ok(Command {
+                delimiter: b'\n',
+                is_raw: false,
+                char_count: None,
+                prompt: None,
+                timeout: None,
+                is_array: true,
+                variables: vec![],
+                last_variable: Field::dummy("foo"),
+            })
+        );
+    }
+
     #[test]
     fn nul_delimiter() {
         let env = Env::new_virtual();
@@ -184,6 +432,10 @@ mod tests {
             Ok(Command {
                 delimiter: b'\0',
                 is_raw: false,
+                char_count: None,
+                prompt: None,
+                timeout: None,
+                is_array: false,
                 variables: vec![],
                 last_variable: Field::dummy("var"),
             })
@@ -198,6 +450,10 @@ mod tests {
             Ok(Command {
                 delimiter: b':',
                 is_raw: false,
+                char_count: None,
+                prompt: None,
+                timeout: None,
+                is_array: false,
                 variables: vec![],
                 last_variable: Field::dummy("var"),
             })
@@ -230,6 +486,10 @@ mod tests {
             Ok(Command {
                 delimiter: b'\n',
                 is_raw: false,
+                char_count: None,
+                prompt: None,
+                timeout: None,
+                is_array: false,
                 variables: Field::dummies(["foo"]),
                 last_variable: Field::dummy("bar"),
             })
@@ -240,6 +500,10 @@ mod tests {
             Ok(Command {
                 delimiter: b'\n',
                 is_raw: false,
+                char_count: None,
+                prompt: None,
+                timeout: None,
+                is_array: false,
                 variables: Field::dummies(["first", "second"]),
                 last_variable: Field::dummy("third"),
             })
@@ -36,6 +36,10 @@
 //!   use it as the exit status of the function or script. However, if the
 //!   built-in is invoked in a trap executed in the function or script, the
 //!   caller should use the value of `$?` before entering trap.
+//!
+//! In the POSIXly-correct mode, a leading `--` is not recognized as a
+//! separator between options and operands. It is treated as an operand, which
+//! is an invalid exit status.
 
 use crate::common::report::{report_error, syntax_error};
 use crate::common::syntax::{Mode, OptionSpec, parse_arguments};
@@ -43,6 +47,8 @@ use std::num::ParseIntError;
 use std::ops::ControlFlow::Break;
 use yash_env::Env;
 use yash_env::builtin::Result;
+use yash_env::option::Option::PosixlyCorrect;
+use yash_env::option::State::On;
 use yash_env::semantics::Divert;
 use yash_env::semantics::ExitStatus;
 use yash_env::semantics::Field;
@@ -72,9 +78,17 @@ pub async fn main<S>(env: &mut Env<S>, args: Vec<Field>) -> Result
 where
     S: Isatty + WriteAll,
 {
-    let (options, operands) = match parse_arguments(OPTION_SPECS, Mode::with_env(env), args) {
-        Ok(result) => result,
-        Err(error) => return report_error(env, &error).await,
+    // In the POSIXly-correct mode, the "--" separator is not recognized and is
+    // treated as an operand.
+    let (options, operands) = if env.options.get(PosixlyCorrect) == On
+        && args.first().is_some_and(|arg| arg.value == "--")
+    {
+        (vec![], args)
+    } else {
+        match parse_arguments(OPTION_SPECS, Mode::with_env(env), args) {
+            Ok(result) => result,
+            Err(error) => return report_error(env, &error).await,
+        }
     };
 
     // TODO Reject returning from an interactive session
@@ -89,9 +103,6 @@ where
     }
 
     // Parse operands
-    if let Some(arg) = operands.get(1) {
-        return syntax_error(env, "too many operands", &arg.origin).await;
-    }
     let exit_status = match operands.first() {
         None => None,
         Some(arg) => match arg.value.parse() {
@@ -100,6 +111,9 @@ where
             Err(e) => return operand_parse_error(env, &arg.origin, e).await,
         },
     };
+    if let Some(arg) = operands.get(1) {
+        return syntax_error(env, "too many operands", &arg.origin).await;
+    }
 
     if no_return {
         Result::new(exit_status.unwrap_or(env.exit_status))
@@ -240,6 +254,45 @@ mod tests {
         });
     }
 
+    fn assert_operand_error(operand: &str, message: &str) {
+        let system = VirtualSystem::new();
+        let state = Rc::clone(&system.state);
+        let mut env = Env::with_system(Rc::new(Concurrent::new(system)));
+        let mut env = env.push_frame(Frame::Builtin(Builtin {
+            name: Field::dummy("return"),
+            is_special: true,
+        }));
+        let args = Field::dummies(["--", operand]);
+
+        let actual_result = main(&mut env, args).now_or_never().unwrap();
+        let expected_result =
+            Result::with_exit_status_and_divert(ExitStatus::ERROR, Break(Divert::Interrupt(None)));
+        assert_eq!(actual_result, expected_result);
+        assert_stderr(&state, |stderr| {
+            assert!(stderr.contains(message), "stderr = {stderr:?}")
+        });
+    }
+
+    #[test]
+    fn return_with_non_numeric_operand_after_separator() {
+        assert_operand_error("x", "invalid digit");
+    }
+
+    #[test]
+    fn return_with_negative_operand_after_separator() {
+        assert_operand_error("-5", "negative exit status");
+    }
+
+    #[test]
+    fn return_with_operand_exceeding_i32() {
+        assert_operand_error("99999999999", "too large");
+    }
+
+    #[test]
+    fn return_with_trailing_garbage_in_operand() {
+        assert_operand_error("12x", "invalid digit");
+    }
+
     #[test]
     fn option_operand_separator() {
         let mut env = Env::new_virtual();
@@ -248,6 +301,68 @@ mod tests {
         assert_eq!(result, Result::new(ExitStatus(12)));
     }
 
+    #[test]
+    fn separator_followed_by_operand_in_posix_mode() {
+        let system = VirtualSystem::new();
+        let state = Rc::clone(&system.state);
+        let mut env = Env::with_system(Rc::new(Concurrent::new(system)));
+        env.options.set(PosixlyCorrect, On);
+        let mut env = env.push_frame(Frame::Builtin(Builtin {
+            name: Field::dummy("return"),
+            is_special: true,
+        }));
+        let args = Field::dummies(["--", "3"]);
+
+        let actual_result = main(&mut env, args).now_or_never().unwrap();
+        let expected_result =
+            Result::with_exit_status_and_divert(ExitStatus::ERROR, Break(Divert::Interrupt(None)));
+        assert_eq!(actual_result, expected_result);
+        assert_stderr(&state, |stderr| {
+            assert!(stderr.contains("invalid digit"), "stderr = {stderr:?}")
+        });
+    }
+
+    #[test]
+    fn separator_alone_in_posix_mode() {
+        let mut env = Env::new_virtual();
+        env.options.set(PosixlyCorrect, On);
+        let mut env = env.push_frame(Frame::Builtin(Builtin {
+            name: Field::dummy("return"),
+            is_special: true,
+        }));
+        let args = Field::dummies(["--"]);
+
+        let actual_result = main(&mut env, args).now_or_never().unwrap();
+        let expected_result =
+            Result::with_exit_status_and_divert(ExitStatus::ERROR, Break(Divert::Interrupt(None)));
+        assert_eq!(actual_result, expected_result);
+    }
+
+    #[test]
+    fn separator_followed_by_operand_in_non_posix_mode() {
+        let mut env = Env::new_virtual();
+        let args = Field::dummies(["--", "3"]);
+        let actual_result = main(&mut env, args).now_or_never().unwrap();
+        let expected_result = Result::with_exit_status_and_divert(
+            ExitStatus::SUCCESS,
+            Break(Divert::Return(Some(ExitStatus(3)))),
+        );
+        assert_eq!(actual_result, expected_result);
+    }
+
+    #[test]
+    fn operand_without_separator_in_posix_mode() {
+        let mut env = Env::new_virtual();
+        env.options.set(PosixlyCorrect, On);
+        let args = Field::dummies(["3"]);
+        let actual_result = main(&mut env, args).now_or_never().unwrap();
+        let expected_result = Result::with_exit_status_and_divert(
+            ExitStatus::SUCCESS,
+            Break(Divert::Return(Some(ExitStatus(3)))),
+        );
+        assert_eq!(actual_result, expected_result);
+    }
+
     #[test]
     fn return_with_too_many_operands() {
         let system = VirtualSystem::new();
@@ -280,6 +280,7 @@ errexit          off
 exec             on
 glob             on
 hashondefinition off
+huponexit        off
 ignoreeof        off
 interactive      off
 log              on
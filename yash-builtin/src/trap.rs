@@ -411,6 +411,26 @@ mod tests {
         });
     }
 
+    #[test]
+    fn printing_pseudo_traps_after_signal_traps() {
+        let system = VirtualSystem::new();
+        let state = Rc::clone(&system.state);
+        let mut env = Env::with_system(Rc::new(Concurrent::new(system)));
+        let args = Field::dummies(["echo e", "ERR", "DEBUG"]);
+        let _ = main(&mut env, args).now_or_never().unwrap();
+        let args = Field::dummies(["echo t", "TERM"]);
+        let _ = main(&mut env, args).now_or_never().unwrap();
+
+        let result = main(&mut env, vec![]).now_or_never().unwrap();
+        assert_eq!(result, Result::new(ExitStatus::SUCCESS));
+        assert_stdout(&state, |stdout| {
+            assert_eq!(
+                stdout,
+                "trap -- 'echo t' TERM\ntrap -- 'echo e' DEBUG\ntrap -- 'echo e' ERR\n"
+            )
+        });
+    }
+
     #[test]
     fn printing_initially_ignored_trap() {
         let system = VirtualSystem::new();
@@ -88,8 +88,9 @@ impl<'a> From<&'a Error> for Report<'a> {
 /// On success, returns the parsed `Condition` and the original `Field`.
 /// On failure, returns `Error::UnknownCondition`.
 ///
-/// A condition can be `0` or `EXIT` for [`Condition::Exit`], or a signal
-/// name/number for [`Condition::Signal`].
+/// A condition can be `0` or `EXIT` for [`Condition::Exit`], `DEBUG` for
+/// [`Condition::Debug`], `ERR` for [`Condition::Err`], or a signal name/number
+/// for [`Condition::Signal`].
 fn parse_condition<S: Signals>(field: Field, system: &S) -> Result<(Condition, Field), Error> {
     // TODO Case-insensitive parse
     // TODO Allow SIG prefix
@@ -100,6 +101,8 @@ fn parse_condition<S: Signals>(field: Field, system: &S) -> Result<(Condition, F
             None => Err(Error::UnknownCondition(field)),
         },
         Err(_) if field.value == "EXIT" => Ok((Condition::Exit, field)),
+        Err(_) if field.value == "DEBUG" => Ok((Condition::Debug, field)),
+        Err(_) if field.value == "ERR" => Ok((Condition::Err, field)),
         Err(_) => match system.str2sig(&field.value) {
             Some(number) => Ok((Condition::Signal(number), field)),
             None => Err(Error::UnknownCondition(field)),
@@ -199,6 +202,18 @@ mod tests {
         assert_eq!(result, Ok((Condition::Exit, field)));
     }
 
+    #[test]
+    fn parse_condition_debug_and_err() {
+        let system = VirtualSystem::new();
+        let field = Field::dummy("DEBUG");
+        let result = parse_condition(field.clone(), &system);
+        assert_eq!(result, Ok((Condition::Debug, field)));
+
+        let field = Field::dummy("ERR");
+        let result = parse_condition(field.clone(), &system);
+        assert_eq!(result, Ok((Condition::Err, field)));
+    }
+
     #[test]
     fn parse_condition_signal_by_name() {
         let system = VirtualSystem::new();
@@ -21,6 +21,10 @@
 //!
 //! [`typeset` built-in]: https://magicant.github.io/yash-rs/builtins/typeset.html
 //!
+//! The same implementation is also used for the `declare` built-in, which
+//! differs from the typeset built-in only in the name of the built-in printed
+//! in the commands that recreate variables and functions.
+//!
 //! # Implementation notes
 //!
 //! The implementation of this built-in is also used by the
@@ -92,7 +96,9 @@ pub enum Scope {
     /// the current function.
     ///
     /// When printing variables: Only variables defined in the local context of
-    /// the current function are printed.
+    /// the current function are printed. The `FUNCNAME`, `BASH_SOURCE`, and
+    /// `BASH_LINENO` variables, which the shell defines in every function
+    /// call, are not printed unless named by an operand.
     Local,
 }
 
@@ -100,6 +106,12 @@ pub enum Scope {
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub struct SetVariables {
     /// Names and optional values of the variables to be defined
+    ///
+    /// In the local scope, a field that is a single hyphen (`-`) does not
+    /// define a variable but makes the shell options local to the current
+    /// function. The current options are saved as
+    /// [`SavedOptions`](yash_env::option::SavedOptions) and restored when
+    /// the function returns. Such a field is ignored outside a function.
     pub variables: Vec<Field>,
     /// Attributes to be set on the variables
     pub attrs: Vec<(VariableAttr, State)>,
@@ -172,13 +184,13 @@ pub struct PrintContext<'a> {
 
     /// Whether the command that invokes the built-in should always be printed
     ///
-    /// The typeset built-in does not itself modify the attributes of variables
-    /// or functions when invoked simply with a name operand. If a separate
-    /// array assignment or function definition command is sufficient to
-    /// reproduce an array variable or function, the command that invokes the
-    /// typeset built-in may be omitted. This field indicates whether the
-    /// command should always be printed regardless of the attributes of the
-    /// variables or functions.
+    /// The typeset built-in does not itself modify the attributes of functions
+    /// when invoked simply with a name operand. If a separate function
+    /// definition command is sufficient to reproduce a function, the command
+    /// that invokes the typeset built-in may be omitted. This field indicates
+    /// whether the command should always be printed regardless of the
+    /// attributes of the functions. Variables are always printed with the
+    /// command regardless of this field.
     ///
     /// This field should be false for the typeset built-in to allow omitting,
     /// but it should be true for the export and readonly built-ins to force
@@ -200,6 +212,12 @@ pub const PRINT_CONTEXT: PrintContext<'static> = PrintContext {
     options_allowed: self::syntax::ALL_OPTIONS,
 };
 
+/// Printing context for the declare built-in
+pub const DECLARE_PRINT_CONTEXT: PrintContext<'static> = PrintContext {
+    builtin_name: "declare",
+    ..PRINT_CONTEXT
+};
+
 /// Set of information that defines the behavior of a single invocation of the
 /// typeset built-in
 ///
@@ -449,14 +467,25 @@ impl<'a> From<&'a ExecuteError> for Report<'a> {
     }
 }
 
-/// Entry point of the typeset built-in
+/// Entry point of the typeset and declare built-ins
+///
+/// The built-in name printed in the commands that recreate variables and
+/// functions is chosen according to the name of the [current
+/// built-in](yash_env::stack::Stack::current_builtin).
 pub async fn main<S>(env: &mut Env<S>, args: Vec<Field>) -> yash_env::builtin::Result
 where
     S: Isatty + WriteAll,
 {
+    let print_context = match env.stack.current_builtin() {
+        Some(builtin) if builtin.name.value == DECLARE_PRINT_CONTEXT.builtin_name => {
+            &DECLARE_PRINT_CONTEXT
+        }
+        _ => &PRINT_CONTEXT,
+    };
+
     match syntax::parse(syntax::ALL_OPTIONS, args) {
         Ok((options, operands)) => match syntax::interpret(options, operands) {
-            Ok(command) => match command.execute(env, &PRINT_CONTEXT) {
+            Ok(command) => match command.execute(env, print_context) {
                 Ok(result) => output(env, &result).await,
                 Err(errors) => report_failure(env, merge_reports(&errors).unwrap()).await,
             },
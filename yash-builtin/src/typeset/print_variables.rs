@@ -16,7 +16,13 @@
 
 use super::*;
 use std::fmt::Write as _;
-use yash_env::variable::{Value, VariableSet};
+use yash_env::variable::{BASH_LINENO, BASH_SOURCE, FUNCNAME, Value, VariableSet};
+
+/// Variables the shell defines locally in every function call
+///
+/// These variables are not printed when listing the variables of the local
+/// scope, since they are not defined by the user.
+const CALL_STACK_VARIABLES: [&str; 3] = [BASH_LINENO, BASH_SOURCE, FUNCNAME];
 
 impl PrintVariables {
     /// Executes the command.
@@ -29,7 +35,12 @@ impl PrintVariables {
         let mut errors = Vec::new();
 
         if self.variables.is_empty() {
-            let mut variables = variables.iter(self.scope.into()).collect::<Vec<_>>();
+            let mut variables = variables
+                .iter(self.scope.into())
+                .filter(|&(name, _)| {
+                    self.scope == Scope::Global || !CALL_STACK_VARIABLES.contains(&name)
+                })
+                .collect::<Vec<_>>();
             // TODO Honor the collation order in the locale.
             variables.sort_unstable_by_key(|&(name, _)| name);
             for (name, var) in variables {
@@ -96,16 +107,14 @@ fn print_one(
 
         Some(value @ Value::Array(_)) => {
             writeln!(output, "{}={}", quoted_name, value.quote()).unwrap();
-
-            let options = options.to_string();
-            if !options.is_empty() || context.builtin_is_significant {
-                writeln!(
-                    output,
-                    "{} {}{}{}",
-                    context.builtin_name, options, separator, quoted_name
-                )
-                .unwrap();
-            }
+            // Like scalar variables, array variables are always followed by
+            // the command that declares them.
+            writeln!(
+                output,
+                "{} {}{}{}",
+                context.builtin_name, options, separator, quoted_name
+            )
+            .unwrap();
         }
 
         None => writeln!(
@@ -202,7 +211,7 @@ mod tests {
         };
 
         let result = pv.execute(&vars, &PRINT_CONTEXT).unwrap();
-        assert_eq!(result, "a=(1 '2  2' 3)\n");
+        assert_eq!(result, "a=(1 '2  2' 3)\ntypeset a\n");
     }
 
     #[test]
@@ -239,7 +248,8 @@ mod tests {
             pv.execute(&vars, &PRINT_CONTEXT).unwrap(),
             "typeset 'valueless$'\n\
              typeset 'scalar$'='=;'\n\
-             'array$'=('~' \"'\" '*?')\n",
+             'array$'=('~' \"'\" '*?')\n\
+             typeset 'array$'\n",
         );
     }
 
@@ -268,6 +278,34 @@ mod tests {
         );
     }
 
+    #[test]
+    fn call_stack_variables_are_not_listed_in_local_scope() {
+        let mut outer = VariableSet::new();
+        let mut inner = outer.push_context(Context::default());
+        for name in [FUNCNAME, BASH_SOURCE, BASH_LINENO, "local"] {
+            inner
+                .get_or_new(name, Scope::Local.into())
+                .assign("value", None)
+                .unwrap();
+        }
+
+        let pv = PrintVariables {
+            variables: vec![],
+            attrs: vec![],
+            scope: Scope::Local,
+        };
+        let output = pv.execute(&inner, &PRINT_CONTEXT).unwrap();
+        assert_eq!(output, "typeset local=value\n");
+
+        let pv = PrintVariables {
+            variables: Field::dummies([FUNCNAME]),
+            attrs: vec![],
+            scope: Scope::Local,
+        };
+        let output = pv.execute(&inner, &PRINT_CONTEXT).unwrap();
+        assert_eq!(output, "typeset FUNCNAME=value\n");
+    }
+
     #[test]
     fn printing_local_variables_only() {
         let mut outer = VariableSet::new();
@@ -657,7 +695,7 @@ mod tests {
         }
 
         #[test]
-        fn builtin_is_significant() {
+        fn array_is_declared_regardless_of_builtin_significance() {
             let mut vars = VariableSet::new();
             vars.get_or_new("a", Scope::Global.into())
                 .assign(Value::array(["foo", "bar"]), None)
@@ -668,23 +706,16 @@ mod tests {
                 scope: Scope::Global,
             };
 
-            let context = PrintContext {
-                builtin_is_significant: false,
-                ..PRINT_CONTEXT
-            };
-            assert_eq!(
-                pv.clone().execute(&vars, &context).unwrap(),
-                "a=(foo bar)\n"
-            );
-
-            let context = PrintContext {
-                builtin_is_significant: true,
-                ..PRINT_CONTEXT
-            };
-            assert_eq!(
-                pv.clone().execute(&vars, &context).unwrap(),
-                "a=(foo bar)\ntypeset a\n"
-            );
+            for builtin_is_significant in [false, true] {
+                let context = PrintContext {
+                    builtin_is_significant,
+                    ..PRINT_CONTEXT
+                };
+                assert_eq!(
+                    pv.clone().execute(&vars, &context).unwrap(),
+                    "a=(foo bar)\ntypeset a\n"
+                );
+            }
         }
 
         #[test]
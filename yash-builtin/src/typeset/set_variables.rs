@@ -15,6 +15,7 @@
 // along with this program.  If not, see <https://www.gnu.org/licenses/>.
 
 use super::*;
+use yash_env::option::SavedOptions;
 use yash_env::variable::Value;
 
 impl From<Scope> for yash_env::variable::Scope {
@@ -32,6 +33,15 @@ impl SetVariables {
         let mut errors = Vec::new();
 
         'field: for mut field in self.variables {
+            // `local -` makes the shell options local to the current function.
+            if field.value == "-" && self.scope == Scope::Local {
+                let in_function = env.stack.functions().next().is_some();
+                if in_function && env.any.get::<SavedOptions>().is_none() {
+                    env.any.insert(Box::new(SavedOptions(env.options)));
+                }
+                continue;
+            }
+
             // Split the field into the name and the value.
             let mut value_to_assign = None;
             if let Some((name, value)) = field.value.split_once('=') {
@@ -90,7 +100,9 @@ mod tests {
     use super::*;
     use assert_matches::assert_matches;
     use yash_env::option::Option::AllExport;
+    use yash_env::option::State::On;
     use yash_env::source::Location;
+    use yash_env::stack::{Frame, Function as FrameFunction};
     use yash_env::variable::{Context, Variable};
 
     #[test]
@@ -132,6 +144,48 @@ mod tests {
         assert_eq!(outer.variables.get("baz"), None);
     }
 
+    #[test]
+    fn hyphen_saves_options_in_function() {
+        let mut env = Env::new_virtual();
+        let mut env = env.push_frame(Frame::Function(FrameFunction {
+            name: Field::dummy("f"),
+            definition: Location::dummy("f"),
+        }));
+        let mut env = env.push_context(Context::default());
+        let saved = env.options;
+        let sv = SetVariables {
+            variables: Field::dummies(["-"]),
+            attrs: vec![],
+            scope: Scope::Local,
+        };
+
+        let result = sv.clone().execute(&mut env);
+        assert_eq!(result, Ok("".to_string()));
+        assert_eq!(env.any.get::<SavedOptions>(), Some(&SavedOptions(saved)));
+        assert_eq!(env.variables.get("-"), None);
+
+        // The options saved first are retained.
+        env.options.set(AllExport, On);
+        let result = sv.execute(&mut env);
+        assert_eq!(result, Ok("".to_string()));
+        assert_eq!(env.any.get::<SavedOptions>(), Some(&SavedOptions(saved)));
+    }
+
+    #[test]
+    fn hyphen_is_ignored_outside_function() {
+        let mut env = Env::new_virtual();
+        let sv = SetVariables {
+            variables: Field::dummies(["-"]),
+            attrs: vec![],
+            scope: Scope::Local,
+        };
+
+        let result = sv.execute(&mut env);
+        assert_eq!(result, Ok("".to_string()));
+        assert_eq!(env.any.get::<SavedOptions>(), None);
+        assert_eq!(env.variables.get("-"), None);
+    }
+
     #[test]
     fn setting_global_variables() {
         let mut outer = Env::new_virtual();
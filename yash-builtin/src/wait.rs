@@ -58,6 +58,13 @@ pub struct Command {
     ///
     /// If empty, the built-in waits for all existing asynchronous jobs.
     pub jobs: Vec<JobSpec>,
+
+    /// Whether to wait for only the next job to finish (the `-n` option)
+    ///
+    /// If `true`, the built-in returns as soon as any one of the jobs
+    /// specified by [`jobs`](Self::jobs) (or any existing asynchronous job if
+    /// `jobs` is empty) finishes.
+    pub next: bool,
 }
 
 pub mod core;
@@ -97,6 +104,24 @@ impl Command {
         status::wait_while_running(env, &mut status::any_job_is_running(job_control)).await
     }
 
+    /// Waits for any one of the jobs specified by the indexes to finish.
+    ///
+    /// If `indexes` is empty, waits for any job to finish.
+    async fn await_next_job<S>(
+        env: &mut Env<S>,
+        indexes: Vec<Option<usize>>,
+    ) -> Result<ExitStatus, core::Error>
+    where
+        S: SignalSystem + Wait + WaitForSignals + 'static,
+    {
+        let job_control = Off; // See `await_jobs`
+
+        // Jobs that are not found are ignored
+        let indexes = (!indexes.is_empty()).then(|| indexes.into_iter().flatten().collect());
+        let mut job_status = status::next_job_finished(indexes, job_control);
+        status::wait_while_running(env, &mut job_status).await
+    }
+
     /// Executes the `wait` built-in.
     pub async fn execute<S>(self, env: &mut Env<S>) -> crate::Result
     where
@@ -112,7 +137,12 @@ impl Command {
         }
 
         // Await jobs specified by the indexes
-        match Self::await_jobs(env, indexes).await {
+        let result = if self.next {
+            Self::await_next_job(env, indexes).await
+        } else {
+            Self::await_jobs(env, indexes).await
+        };
+        match result {
             Ok(exit_status) => exit_status.into(),
             Err(core::Error::Trapped(signal, divert)) => {
                 crate::Result::with_exit_status_and_divert(ExitStatus::from(signal), divert)
@@ -137,6 +167,7 @@ where
 mod tests {
     use super::*;
     use futures_util::poll;
+    use std::future::pending;
     use std::pin::pin;
     use std::rc::Rc;
     use std::task::Poll;
@@ -186,4 +217,41 @@ mod tests {
             assert_eq!(poll, Poll::Pending);
         })
     }
+
+    #[test]
+    fn next_option_returns_after_first_job_finishes() {
+        in_virtual_system(|mut env, _| async move {
+            stub_run_signal_trap_if_caught(&mut env);
+
+            // Start a job that never exits and another that exits immediately.
+            let pid1 = Config::new()
+                .start(&mut env, async |_, _| pending().await)
+                .await
+                .unwrap()
+                .0;
+            let index1 = env.jobs.insert(Job::new(pid1));
+            let pid2 = Config::new()
+                .start(&mut env, async |env, _| env.exit_status = ExitStatus(42))
+                .await
+                .unwrap()
+                .0;
+            let index2 = env.jobs.insert(Job::new(pid2));
+
+            let result = main(&mut env, Field::dummies(["-n"])).await;
+            assert_eq!(result, crate::Result::from(ExitStatus(42)));
+            // Only the finished job is removed from the job list.
+            assert_eq!(env.jobs.get(index2), None);
+            assert_eq!(env.jobs[index1].pid, pid1);
+        })
+    }
+
+    #[test]
+    fn next_option_without_jobs() {
+        in_virtual_system(|mut env, _| async move {
+            stub_run_signal_trap_if_caught(&mut env);
+
+            let result = main(&mut env, Field::dummies(["-n"])).await;
+            assert_eq!(result, crate::Result::from(ExitStatus::NOT_FOUND));
+        })
+    }
 }
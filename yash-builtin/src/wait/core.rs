@@ -143,6 +143,7 @@ mod tests {
     use yash_env::subshell::Config;
     use yash_env::system::Concurrent;
     use yash_env::system::SendSignal as _;
+    use yash_env::system::concurrency::Select as _;
     use yash_env::system::r#virtual::{SIGINT, SIGSTOP, SIGTERM};
     use yash_env::test_helper::in_virtual_system;
     use yash_env::trap::Action;
@@ -268,6 +269,72 @@ mod tests {
         });
     }
 
+    #[test]
+    fn trap_runs_once_for_signal_caught_during_blocking_select() {
+        in_virtual_system(|mut env, state| async move {
+            type TestSystem = Rc<Concurrent<VirtualSystem>>;
+            env.any.insert(Box::new(RunSignalTrapIfCaught::<TestSystem>(
+                |env, signal| {
+                    Box::pin(
+                        async move { yash_semantics::trap::run_trap_if_caught(env, signal).await },
+                    )
+                },
+            )));
+
+            let system = VirtualSystem {
+                state,
+                process_id: env.main_pid,
+            };
+            let concurrent = Rc::clone(&env.system);
+
+            // Start a child process that never exits.
+            Config::new()
+                .start(&mut env, async |_, _| pending().await)
+                .await
+                .unwrap();
+
+            // Set a trap for SIGTERM that appends a digit each time it runs.
+            env.traps
+                .set_action(
+                    &env.system,
+                    SIGTERM,
+                    Action::Command("count=${count}1".into()),
+                    Location::dummy("somewhere"),
+                    false,
+                )
+                .await
+                .unwrap();
+
+            {
+                // The function blocks in `select` waiting for a signal.
+                let mut future = pin!(wait_for_any_job_or_trap(&mut env));
+                assert_eq!(poll!(&mut future), Poll::Pending);
+
+                // The signal interrupts the `select` and the trap runs.
+                _ = system.current_process_mut().raise_signal(SIGTERM);
+                let result = future.await;
+                assert_eq!(result, Err(Error::Trapped(SIGTERM, Continue(()))));
+            }
+            assert_eq!(
+                env.variables.get("count").unwrap().value,
+                Some(Value::scalar("1")),
+            );
+
+            {
+                // The signal has been consumed, so the next wait blocks again
+                // rather than returning for the same signal.
+                let mut future = pin!(wait_for_any_job_or_trap(&mut env));
+                assert_eq!(poll!(&mut future), Poll::Pending);
+                concurrent.peek();
+                assert_eq!(poll!(&mut future), Poll::Pending);
+            }
+            assert_eq!(
+                env.variables.get("count").unwrap().value,
+                Some(Value::scalar("1")),
+            );
+        });
+    }
+
     #[test]
     fn sigint_with_default_action_in_interactive_mode() {
         in_virtual_system(|mut env, state| async move {
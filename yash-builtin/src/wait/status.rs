@@ -21,9 +21,10 @@
 //! [`ControlFlow::Break`], `wait_while_running` stops waiting and returns the
 //! exit status.
 //!
-//! You can pass a closure that is created by [`job_status`] or
-//! [`any_job_is_running`] to `wait_while_running`. The former tests if a
-//! specific job has finished, and the latter tests if all jobs have finished.
+//! You can pass a closure that is created by [`job_status`],
+//! [`any_job_is_running`], or [`next_job_finished`] to `wait_while_running`.
+//! The first tests if a specific job has finished, the second tests if all
+//! jobs have finished, and the last tests if any one of jobs has finished.
 
 use super::core::Error;
 use super::core::wait_for_any_job_or_trap;
@@ -123,6 +124,46 @@ pub fn any_job_is_running(
     }
 }
 
+/// Returns a closure that tests if any one of the given jobs has finished.
+///
+/// The closure applies [`job_status`] to each job specified by `indexes`, or
+/// to every job in the job list if `indexes` is `None`. If any of the jobs has
+/// finished, the closure returns [`ControlFlow::Break`] with the job's exit
+/// status. If there is no job to test (that is, all the jobs are not found or
+/// disowned), the closure returns [`ControlFlow::Break`] having
+/// [`ExitStatus::NOT_FOUND`]. Otherwise, the closure returns
+/// [`ControlFlow::Continue`].
+pub fn next_job_finished(
+    indexes: Option<Vec<usize>>,
+    job_control: State,
+) -> impl FnMut(&mut JobList) -> ControlFlow<ExitStatus> {
+    move |jobs| {
+        let indexes = match &indexes {
+            Some(indexes) => indexes.clone(),
+            None => jobs.iter().map(|(index, _)| index).collect(),
+        };
+
+        let mut any_job_found = false;
+        for index in indexes {
+            let Some(job) = jobs.get(index) else { continue };
+            let is_owned = job.is_owned;
+            let status = job_status(index, job_control)(jobs);
+            if is_owned {
+                if status.is_break() {
+                    return status;
+                }
+                any_job_found = true;
+            }
+        }
+
+        if any_job_found {
+            ControlFlow::Continue(())
+        } else {
+            ControlFlow::Break(ExitStatus::NOT_FOUND)
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -332,4 +373,84 @@ mod tests {
 
         assert_eq!(any_job_is_running(On)(&mut jobs), ControlFlow::Continue(()));
     }
+
+    #[test]
+    fn next_job_finished_with_no_job() {
+        let mut jobs = JobList::new();
+        assert_eq!(
+            next_job_finished(None, Off)(&mut jobs),
+            ControlFlow::Break(ExitStatus::NOT_FOUND),
+        );
+        assert_eq!(
+            next_job_finished(Some(vec![0, 1]), Off)(&mut jobs),
+            ControlFlow::Break(ExitStatus::NOT_FOUND),
+        );
+    }
+
+    #[test]
+    fn next_job_finished_with_running_jobs() {
+        let mut jobs = JobList::new();
+        let index1 = jobs.insert(Job::new(Pid(123)));
+        let index2 = jobs.insert(Job::new(Pid(456)));
+
+        assert_eq!(
+            next_job_finished(None, Off)(&mut jobs),
+            ControlFlow::Continue(()),
+        );
+        assert_eq!(
+            next_job_finished(Some(vec![index1, index2]), Off)(&mut jobs),
+            ControlFlow::Continue(()),
+        );
+    }
+
+    #[test]
+    fn next_job_finished_with_one_finished_job() {
+        let mut jobs = JobList::new();
+        let index1 = jobs.insert(Job::new(Pid(123)));
+        let mut job = Job::new(Pid(456));
+        job.state = ProcessState::exited(7);
+        let index2 = jobs.insert(job);
+
+        assert_eq!(
+            next_job_finished(None, Off)(&mut jobs),
+            ControlFlow::Break(ExitStatus(7)),
+        );
+        // The finished job is removed, but the running job remains.
+        assert_eq!(jobs.get(index2), None);
+        assert_eq!(jobs[index1].pid, Pid(123));
+
+        assert_eq!(
+            next_job_finished(None, Off)(&mut jobs),
+            ControlFlow::Continue(()),
+        );
+    }
+
+    #[test]
+    fn next_job_finished_ignores_unspecified_jobs() {
+        let mut jobs = JobList::new();
+        let index1 = jobs.insert(Job::new(Pid(123)));
+        let mut job = Job::new(Pid(456));
+        job.state = ProcessState::exited(7);
+        let index2 = jobs.insert(job);
+
+        assert_eq!(
+            next_job_finished(Some(vec![index1]), Off)(&mut jobs),
+            ControlFlow::Continue(()),
+        );
+        assert_eq!(jobs[index2].pid, Pid(456));
+    }
+
+    #[test]
+    fn next_job_finished_ignores_disowned_jobs() {
+        let mut jobs = JobList::new();
+        let mut job = Job::new(Pid(123));
+        job.is_owned = false;
+        let index = jobs.insert(job);
+
+        assert_eq!(
+            next_job_finished(None, Off)(&mut jobs),
+            ControlFlow::Break(ExitStatus::NOT_FOUND),
+        );
+        assert_eq!(jobs.get(index), None);
+    }
 }
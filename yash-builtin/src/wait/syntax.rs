@@ -24,7 +24,10 @@ use yash_env::job::Pid;
 use yash_env::semantics::Field;
 use yash_env::source::pretty::{Report, ReportType, Snippet};
 
-use crate::common::syntax::{Mode, ParseError, parse_arguments};
+use crate::common::syntax::{Mode, OptionSpec, ParseError, parse_arguments};
+
+/// List of all options supported by the `wait` built-in
+pub const OPTION_SPECS: &[OptionSpec] = &[OptionSpec::new().short('n').long("next")];
 
 /// Errors that may occur while parsing command line arguments
 #[derive(Clone, Debug, Eq, Error, PartialEq)]
@@ -85,12 +88,18 @@ impl TryFrom<Field> for JobSpec {
 
 /// Parses command line arguments for the wait built-in.
 pub fn parse<S>(env: &Env<S>, args: Vec<Field>) -> Result<Command, Error> {
-    let (_, operands) = parse_arguments(&[], Mode::with_env(env), args)?;
+    let (options, operands) = parse_arguments(OPTION_SPECS, Mode::with_env(env), args)?;
+
+    for option in &options {
+        debug_assert_eq!(option.spec.get_short(), Some('n'));
+    }
+    let next = !options.is_empty();
+
     let jobs = operands
         .into_iter()
         .map(JobSpec::try_from)
         .collect::<Result<Vec<JobSpec>, Error>>()?;
-    Ok(Command { jobs })
+    Ok(Command { jobs, next })
 }
 
 #[cfg(test)]
@@ -140,4 +149,42 @@ mod tests {
         let result = JobSpec::try_from(Field::dummy("%"));
         assert_eq!(result, Ok(JobSpec::JobId(Field::dummy("%"))));
     }
+
+    #[test]
+    fn no_arguments() {
+        let env = Env::new_virtual();
+        let result = parse(&env, vec![]);
+        assert_eq!(
+            result,
+            Ok(Command {
+                jobs: vec![],
+                next: false,
+            })
+        );
+    }
+
+    #[test]
+    fn next_option() {
+        let env = Env::new_virtual();
+        let result = parse(&env, Field::dummies(["-n"]));
+        assert_eq!(
+            result,
+            Ok(Command {
+                jobs: vec![],
+                next: true,
+            })
+        );
+
+        let result = parse(&env, Field::dummies(["-n", "%1", "42"]));
+        assert_eq!(
+            result,
+            Ok(Command {
+                jobs: vec![
+                    JobSpec::JobId(Field::dummy("%1")),
+                    JobSpec::ProcessId(Pid(42)),
+                ],
+                next: true,
+            })
+        );
+    }
 }
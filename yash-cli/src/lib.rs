@@ -33,9 +33,11 @@ use std::ops::ControlFlow::{Break, Continue};
 use std::rc::Rc;
 use yash_env::Env;
 use yash_env::RealSystem;
+use yash_env::history;
+use yash_env::job::hang_up_jobs;
 use yash_env::option::{Interactive, On};
 use yash_env::semantics::{Divert, ExitStatus, exit_or_raise};
-use yash_env::system::concurrency::WriteAll;
+use yash_env::system::concurrency::{Sleep, WriteAll};
 use yash_env::system::resource::GetRlimit;
 use yash_env::system::{
     Chdir, Concurrent, Disposition, Errno, GetCwd, GetUid, Isatty, Sigaction as _, Signals as _,
@@ -65,6 +67,7 @@ where
         + GetRlimit
         + GetUid
         + Runtime
+        + Sleep
         + Sysconf
         + TcGetPgrp
         + Times
@@ -96,6 +99,11 @@ where
     // TODO run profile if login
     run_rcfile(env, work.rcfile).await;
 
+    // Restore the command history saved by previous sessions
+    if is_interactive {
+        history::load(env).await;
+    }
+
     // Prepare the input for the main read-eval loop
     let ref_env = RefCell::new(env);
     let lexer = match prepare_input(&ref_env, &work.source).await {
@@ -136,6 +144,8 @@ where
         | Break(Divert::Exit(_)) => run_exit_trap(env).await,
         Break(Divert::Abort(_)) => (),
     }
+
+    hang_up_jobs(env).await;
 }
 
 pub fn main() -> ! {
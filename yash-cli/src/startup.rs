@@ -27,7 +27,9 @@ use yash_env::option::State::On;
 use yash_env::parser::IsKeyword;
 use yash_env::parser::IsName;
 use yash_env::prompt::GetPrompt;
+use yash_env::semantics::command::CommandNotFoundHandlerConfig;
 use yash_env::semantics::command::RunFunction;
+use yash_env::system::concurrency::Sleep;
 use yash_env::system::resource::GetRlimit;
 use yash_env::system::{Chdir, GetCwd, GetUid, Isatty, Sysconf, TcGetPgrp, Times, Umask, Write};
 use yash_env::trap::RunSignalTrapIfCaught;
@@ -75,6 +77,7 @@ where
         + GetRlimit
         + GetUid
         + Runtime
+        + Sleep
         + Sysconf
         + TcGetPgrp
         + Times
@@ -106,6 +109,10 @@ where
             .enable_internal_dispositions_for_terminators(&env.system)
             .await
             .ok();
+        env.traps
+            .enable_internal_disposition_for_sigwinch(&env.system)
+            .await
+            .ok();
         if env.options.get(Monitor) == On {
             env.traps
                 .enable_internal_dispositions_for_stoppers(&env.system)
@@ -125,6 +132,9 @@ where
 
     // Prepare variables
     env.init_variables();
+    if env.options.get(Interactive) == On {
+        env.update_tty_size();
+    }
 
     // Inject dependencies
     inject_dependencies(env);
@@ -141,6 +151,8 @@ fn inject_dependencies<S: Runtime + 'static>(env: &mut Env<S>) {
     env.any.insert(Box::new(IgnoreEofConfig::with_message(
         "# Type `exit` to leave the shell when the ignore-eof option is on.\n",
     )));
+    env.any
+        .insert(Box::new(CommandNotFoundHandlerConfig::default()));
 
     env.any.insert(Box::new(IsKeyword::<S>(|_env, word| {
         yash_syntax::parser::lex::Keyword::from_str(word).is_ok()
@@ -187,3 +199,122 @@ fn inject_dependencies<S: Runtime + 'static>(env: &mut Env<S>) {
         })
     })));
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use futures_util::FutureExt as _;
+    use std::cell::RefCell;
+    use std::rc::Rc;
+    use yash_env::VirtualSystem;
+    use yash_env::system::r#virtual::{FileBody, Inode};
+    use yash_env::system::{Concurrent, Dup as _, Mode, OfdAccess, Open as _};
+
+    fn run(args: &[&str]) -> Run {
+        match args::parse(args.iter().copied()) {
+            Ok(args::Parse::Run(run)) => run,
+            other => panic!("unexpected parse result: {other:?}"),
+        }
+    }
+
+    /// Connects the given file descriptors to a virtual terminal.
+    fn connect_terminal(system: &VirtualSystem, fds: &[Fd]) {
+        let inode = Inode {
+            body: FileBody::Terminal { content: vec![] },
+            permissions: Mode::ALL_READ | Mode::ALL_WRITE,
+        };
+        system
+            .state
+            .borrow_mut()
+            .file_system
+            .save("/dev/tty", Rc::new(RefCell::new(inode)))
+            .unwrap();
+        let fd = system
+            .open(
+                c"/dev/tty",
+                OfdAccess::ReadWrite,
+                Default::default(),
+                Mode::empty(),
+            )
+            .now_or_never()
+            .unwrap()
+            .unwrap();
+        for &to in fds {
+            system.dup2(fd, to).unwrap();
+        }
+    }
+
+    #[test]
+    fn auto_interactive_with_terminals() {
+        let system = VirtualSystem::new();
+        connect_terminal(&system, &[Fd::STDIN, Fd::STDERR]);
+        assert!(auto_interactive(&system, &run(&["yash"])));
+        assert!(auto_interactive(&system, &run(&["yash", "-s", "foo"])));
+    }
+
+    #[test]
+    fn auto_interactive_without_terminals() {
+        let system = VirtualSystem::new();
+        assert!(!auto_interactive(&system, &run(&["yash"])));
+
+        connect_terminal(&system, &[Fd::STDIN]);
+        assert!(!auto_interactive(&system, &run(&["yash"])));
+    }
+
+    #[test]
+    fn auto_interactive_not_reading_stdin() {
+        let system = VirtualSystem::new();
+        connect_terminal(&system, &[Fd::STDIN, Fd::STDERR]);
+        assert!(!auto_interactive(&system, &run(&["yash", "-c", "echo"])));
+        assert!(!auto_interactive(&system, &run(&["yash", "script"])));
+    }
+
+    #[test]
+    fn auto_interactive_with_explicit_option() {
+        let system = VirtualSystem::new();
+        connect_terminal(&system, &[Fd::STDIN, Fd::STDERR]);
+        assert!(!auto_interactive(&system, &run(&["yash", "-i"])));
+        assert!(!auto_interactive(&system, &run(&["yash", "+i"])));
+    }
+
+    #[test]
+    fn interactive_option_in_arguments() {
+        let system = VirtualSystem::new();
+        let mut env = Env::with_system(Rc::new(Concurrent::new(system)));
+        let run = run(&["yash", "-i", "-c", "echo"]);
+        configure_environment(&mut env, run).now_or_never().unwrap();
+        assert!(env.is_interactive());
+    }
+
+    #[test]
+    fn interactive_option_auto_detected() {
+        let system = VirtualSystem::new();
+        connect_terminal(&system, &[Fd::STDIN, Fd::STDERR]);
+        let mut env = Env::with_system(Rc::new(Concurrent::new(system)));
+        configure_environment(&mut env, run(&["yash"]))
+            .now_or_never()
+            .unwrap();
+        assert!(env.is_interactive());
+    }
+
+    #[test]
+    fn interactive_option_not_auto_detected_without_terminals() {
+        let system = VirtualSystem::new();
+        let mut env = Env::with_system(Rc::new(Concurrent::new(system)));
+        configure_environment(&mut env, run(&["yash"]))
+            .now_or_never()
+            .unwrap();
+        assert!(!env.is_interactive());
+    }
+
+    #[test]
+    fn interactive_option_auto_detection_overridden() {
+        let system = VirtualSystem::new();
+        connect_terminal(&system, &[Fd::STDIN, Fd::STDERR]);
+        let mut env = Env::with_system(Rc::new(Concurrent::new(system)));
+        configure_environment(&mut env, run(&["yash", "+i"]))
+            .now_or_never()
+            .unwrap();
+        assert!(!env.is_interactive());
+    }
+}
@@ -32,15 +32,17 @@ use yash_env::Env;
 use yash_env::input::Echo;
 use yash_env::input::EofGuard;
 use yash_env::input::FdReader2;
+use yash_env::input::LineEditor;
+use yash_env::input::Recorder;
 use yash_env::input::Reporter;
 use yash_env::io::Fd;
 use yash_env::io::move_fd_internal;
 use yash_env::option::Option::Interactive;
-use yash_env::option::State::{Off, On};
+use yash_env::option::State::On;
 use yash_env::parser::Config;
 use yash_env::system::concurrency::WriteAll;
 use yash_env::system::{
-    Close, Dup, Errno, Fcntl, Fstat, Isatty, Mode, OfdAccess, Open, OpenFlag, Read, Signals,
+    Close, Dup, Errno, Fcntl, Fstat, Isatty, Mode, OfdAccess, Open, OpenFlag, Read, Signals, Wait,
 };
 use yash_prompt::Prompter;
 use yash_syntax::input::InputObject;
@@ -66,6 +68,9 @@ pub struct PrepareInputError<'a> {
 /// - If the source is read with a file descriptor, the [`Echo`] decorator is
 ///   applied to the input to implement the [`Verbose`] shell option.
 /// - If the [`Interactive`] option is enabled and the source is read with a
+///   file descriptor, the [`Recorder`] decorator is applied to the input to
+///   record command lines in the history.
+/// - If the [`Interactive`] option is enabled and the source is read with a
 ///   file descriptor, the [`Prompter`] decorator is applied to the input to
 ///   show the prompt.
 /// - If the [`Interactive`] option is enabled, the [`Reporter`] decorator is
@@ -87,7 +92,18 @@ pub async fn prepare_input<'s, 'i, 'e, S>(
 ) -> Result<Lexer<'i>, PrepareInputError<'e>>
 where
     's: 'i + 'e,
-    S: Clone + Close + Dup + Fcntl + Fstat + Isatty + Open + Read + Signals + WriteAll + 'static,
+    S: Clone
+        + Close
+        + Dup
+        + Fcntl
+        + Fstat
+        + Isatty
+        + Open
+        + Read
+        + Signals
+        + Wait
+        + WriteAll
+        + 'static,
 {
     fn lexer_with_input_and_source<'a>(
         input: Box<dyn InputObject + 'a>,
@@ -157,20 +173,22 @@ where
 ///
 /// This function creates an [`FdReader2`] object from the given file descriptor
 /// and wraps it with the [`Echo`] decorator. If the [`Interactive`] option is
-/// enabled, the [`Prompter`], [`Reporter`], and [`EofGuard`] decorators are
-/// applied to the input object.
+/// enabled, the input object is created by [`LineEditor::new_input`] instead,
+/// so that the line editor in `env.any` (if any) is used for a terminal, and
+/// the [`Recorder`], [`Prompter`], [`Reporter`], and [`EofGuard`] decorators
+/// are applied to the input object.
 fn prepare_fd_input<'i, S>(fd: Fd, ref_env: &'i RefCell<&mut Env<S>>) -> Box<dyn InputObject + 'i>
 where
-    S: Clone + Isatty + Read + Signals + WriteAll + 'static,
+    S: Clone + Close + Isatty + Open + Read + Signals + Wait + WriteAll + 'static,
 {
-    let env = ref_env.borrow();
-    let system = env.system.clone();
-
-    let basic_input = Echo::new(FdReader2::new(fd, system), ref_env);
+    let is_interactive = ref_env.borrow().options.get(Interactive) == On;
 
-    if env.options.get(Interactive) == Off {
-        Box::new(basic_input)
+    if !is_interactive {
+        let system = ref_env.borrow().system.clone();
+        Box::new(Echo::new(FdReader2::new(fd, system), ref_env))
     } else {
+        let basic_input = Echo::new(LineEditor::new_input(fd, ref_env), ref_env);
+        let basic_input = Recorder::new(basic_input, ref_env);
         // The order of these decorators is important. The prompt should be shown after
         // the job status is reported, and both should be shown again if an EOF is ignored.
         let prompter = Prompter::new(basic_input, ref_env);
@@ -261,11 +261,21 @@ fn for_loop() {
     run("for-p.sh")
 }
 
+#[test]
+fn for_loop_ex() {
+    run("for-y.sh")
+}
+
 #[test]
 fn function() {
     run("function-p.sh")
 }
 
+#[test]
+fn function_ex() {
+    run("function-y.sh")
+}
+
 #[test]
 fn getopts_builtin() {
     run("getopts-p.sh")
@@ -286,6 +296,11 @@ fn grouping_ex() {
     run("grouping-y.sh")
 }
 
+#[test]
+fn history_ex() {
+    run("history-y.sh")
+}
+
 #[test]
 fn if_command() {
     run("if-p.sh")
@@ -336,6 +351,11 @@ fn lineno() {
     run("lineno-p.sh")
 }
 
+#[test]
+fn mapfile_builtin_ex() {
+    run("mapfile-y.sh")
+}
+
 #[test]
 fn nop_builtins() {
     run("nop-p.sh")
@@ -356,6 +376,11 @@ fn parameter_expansion() {
     run("param-p.sh")
 }
 
+#[test]
+fn parameter_expansion_ex() {
+    run("param-y.sh")
+}
+
 // a.k.a. globbing
 #[test]
 fn pathname_expansion() {
@@ -417,6 +442,11 @@ fn return_builtin() {
     run("return-p.sh")
 }
 
+#[test]
+fn return_builtin_ex() {
+    run("return-y.sh")
+}
+
 #[test]
 fn set_builtin() {
     run("set-p.sh")
@@ -432,6 +462,11 @@ fn simple_command() {
     run("simple-p.sh")
 }
 
+#[test]
+fn simple_command_ex() {
+    run("simple-y.sh")
+}
+
 #[test]
 fn source_builtin() {
     run("source-p.sh")
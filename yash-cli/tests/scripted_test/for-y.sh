@@ -0,0 +1,92 @@
+# for-y.sh: yash-specific test of for loop
+
+test_oE 'arithmetic for loop'
+for ((i=0;i<3;i++)); do echo $i; done
+__IN__
+0
+1
+2
+__OUT__
+
+test_oE 'arithmetic for loop with blanks and newlines'
+for (( i = 3 ; i > 0 ; i -= 1 ))
+do
+    echo $i
+done
+__IN__
+3
+2
+1
+__OUT__
+
+test_oE 'arithmetic for loop with empty initialization and update'
+i=2
+for ((; i < 4;)) do echo $((i++)); done
+__IN__
+2
+3
+__OUT__
+
+test_oE 'expansions in arithmetic for loop'
+n=2
+for ((i=$n; i < $(echo 4); i++)) do echo $i; done
+__IN__
+2
+3
+__OUT__
+
+test_oE 'exit status of arithmetic for loop'
+for ((i=0; i<2; i++)) do (exit $((i+3))); done
+echo $?
+for ((i=0; i<0; i++)) do (exit 1); done
+echo $?
+__IN__
+4
+0
+__OUT__
+
+test_O -d -e 2 'unclosed arithmetic for loop header'
+for ((i=0; i<2)) do :; done
+__IN__
+
+test_O -d -e 2 'portable option rejects arithmetic for loop' -o portable
+for ((;;)) do :; done
+__IN__
+
+test_oE 'break in arithmetic for loop'
+for ((i=0; i<5; i++)) do
+    if [ $i -eq 2 ]; then break; fi
+    echo $i
+done
+echo i=$i
+__IN__
+0
+1
+i=2
+__OUT__
+
+test_oE 'continue in arithmetic for loop'
+for ((i=0; i<4; i++)) do
+    if [ $i -eq 1 ]; then continue; fi
+    echo $i
+done
+echo i=$i
+__IN__
+0
+2
+3
+i=4
+__OUT__
+
+test_oE 'break and continue in nested arithmetic for loops'
+for ((i=0; i<3; i++)) do
+    for ((j=0; j<3; j++)) do
+        if [ $j -eq 1 ]; then continue 2; fi
+        if [ $i -eq 2 ]; then break 2; fi
+        echo $i$j
+    done
+done
+__IN__
+00
+10
+__OUT__
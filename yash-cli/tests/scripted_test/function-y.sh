@@ -0,0 +1,45 @@
+# function-y.sh: yash-specific test of functions
+
+test_oE 'FUNCNAME lists call chain innermost first'
+outer() { inner; }
+inner() { echo "$FUNCNAME"; }
+outer
+__IN__
+inner outer
+__OUT__
+
+test_oE 'BASH_LINENO lists lines of function calls'
+outer() {
+    inner
+}
+inner() { echo "$BASH_LINENO"; }
+outer
+__IN__
+2 5
+__OUT__
+
+test_oE 'FUNCNAME is unset outside functions'
+f() { :; }
+f
+echo "${FUNCNAME-unset}"
+__IN__
+unset
+__OUT__
+
+test_oE 'global FUNCNAME is restored after function returns'
+FUNCNAME=global
+f() { echo "$FUNCNAME"; }
+f
+echo "$FUNCNAME"
+__IN__
+f
+global
+__OUT__
+
+test_oE 'FUNCNAME is not defined in POSIX mode'
+set -o posixlycorrect
+f() { echo "${FUNCNAME-unset}"; }
+f
+__IN__
+unset
+__OUT__
@@ -0,0 +1,46 @@
+# history-y.sh: yash-specific test of command history
+
+test_o 'command lines are appended to history file' -i +m
+HISTFILE=history
+echo foo
+
+cat history
+__IN__
+foo
+echo foo
+cat history
+__OUT__
+
+test_o 'fc -l lists previous commands with numbers' -i +m
+echo a
+echo b
+fc -l
+__IN__
+a
+b
+1	echo a
+2	echo b
+__OUT__
+
+test_o 'fc -s re-executes specified command' -i +m
+echo a
+echo b
+fc -s 1
+fc -l -2
+__IN__
+a
+b
+echo a
+a
+2	echo b
+3	echo a
+__OUT__
+
+test_o 'history prints all commands including itself' -i +m
+echo a
+history
+__IN__
+a
+1	echo a
+2	history
+__OUT__
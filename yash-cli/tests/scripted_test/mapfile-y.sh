@@ -0,0 +1,61 @@
+# mapfile-y.sh: yash-specific test of the mapfile built-in
+
+test_oE -e 0 'mapfile is an elective built-in'
+command -V mapfile readarray
+__IN__
+mapfile: elective built-in
+readarray: elective built-in
+__OUT__
+
+test_oE -e 0 'reading all lines into default variable'
+printf '%s\n' 1 2 3 | {
+    mapfile
+    printf '[%s]' "$MAPFILE"
+    echo
+}
+__IN__
+[1
+][2
+][3
+]
+__OUT__
+
+test_oE -e 0 'trimming newlines'
+printf '%s\n' foo bar | {
+    readarray -t a
+    printf '[%s]\n' "$a"
+}
+__IN__
+[foo]
+[bar]
+__OUT__
+
+test_oE -e 0 'remaining lines are left unread'
+printf '%s\n' 1 2 3 4 | {
+    mapfile -t -n 2 a
+    printf '[%s]\n' "$a"
+    cat
+}
+__IN__
+[1]
+[2]
+3
+4
+__OUT__
+
+test_oE -e 0 'assigning from specified origin'
+a=(a b c)
+echo x > input
+mapfile -t -O 1 a < input
+printf '[%s]\n' "$a"
+__IN__
+[a]
+[x]
+[c]
+__OUT__
+
+test_O -d -e 1 'assigning to read-only variable'
+readonly a=1
+echo x > input
+mapfile a < input
+__IN__
@@ -0,0 +1,82 @@
+# param-y.sh: yash-specific test of parameter expansion
+
+setup -d
+
+test_oE 'indirect expansion of variable'
+ref=foo foo=bar
+echo ${!ref}
+__IN__
+bar
+__OUT__
+
+test_oE 'indirect expansion of positional and special parameters'
+set a b c
+one=1 two=2 hash='#'
+echo ${!one} ${!two} ${!hash}
+__IN__
+a b 3
+__OUT__
+
+test_oE 'indirect expansion of unset variable'
+ref=foo
+unset foo
+echo "[${!ref}]"
+__IN__
+[]
+__OUT__
+
+test_O -d -e 2 'nounset applies to target of indirect expansion' -u
+ref=foo
+unset foo
+echo ${!ref}
+__IN__
+
+test_O -d -e 2 'indirect expansion of invalid name'
+ref='foo bar'
+echo ${!ref}
+__IN__
+
+test_O -d -e 2 'portable option rejects indirect expansion' -o portable
+echo ${!ref}
+__IN__
+
+test_oE 'variable names with prefix'
+pfx_b=1 pfx_a=2 pfx_c=3 pfx=4
+bracket "${!pfx_@}"
+bracket "${!pfx_*}"
+IFS=-
+bracket "${!pfx_*}"
+bracket ${!pfx_*}
+__IN__
+[pfx_a][pfx_b][pfx_c]
+[pfx_a pfx_b pfx_c]
+[pfx_a-pfx_b-pfx_c]
+[pfx_a][pfx_b][pfx_c]
+__OUT__
+
+test_oE 'no variable names with prefix'
+bracket "${!no_such_variable_@}"
+bracket "${!no_such_variable_*}"
+__IN__
+
+[]
+__OUT__
+
+test_oE 'indices of array'
+a=(x y z) s=foo
+bracket "${!a[@]}"
+bracket "${!a[*]}"
+bracket "${!s[@]}"
+__IN__
+[0][1][2]
+[0 1 2]
+[0]
+__OUT__
+
+test_oE 'indices of unset variable'
+bracket "${!no_such_variable[@]}"
+bracket "${!no_such_variable[*]}"
+__IN__
+
+[]
+__OUT__
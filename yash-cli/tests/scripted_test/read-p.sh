@@ -280,6 +280,22 @@ __IN__
 0 [A] [B] [C] [D E]
 __OUT__
 
+test_oE 'null delimiter'
+printf 'A B\nC\0D\0E' | {
+read -d '' a b
+echoraw $? "[${a-unset}]" "[${b-unset}]"
+read -d '' c
+echoraw $? "[${c-unset}]"
+read -d '' d
+echoraw $? "[${d-unset}]"
+}
+__IN__
+0 [A] [B
+C]
+0 [D]
+1 [E]
+__OUT__
+
 test_oE 'raw mode - backslash not in IFS'
 IFS=' -' read -r a b c d <<'END'
 A\A\\ B-C\- D\
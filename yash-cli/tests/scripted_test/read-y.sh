@@ -105,8 +105,7 @@ __IN__
 0 [] [-  -   -]
 __OUT__
 
-# TODO not yet implemented
-test_oE -f 'array - single operand - single field'
+test_oE 'array - single operand - single field'
 read -A a <<'END'
 A
 END
@@ -118,8 +117,7 @@ a=(A)
 typeset a
 __OUT__
 
-# TODO not yet implemented
-test_oE -f 'array - single operand - no field'
+test_oE 'array - single operand - no field'
 read -A a <<'END'
 
 END
@@ -131,8 +129,7 @@ a=()
 typeset a
 __OUT__
 
-# TODO not yet implemented
-test_oE -f 'array - many operands'
+test_oE 'array - many operands'
 read -A a b c <<'END'
 A B C
 END
@@ -146,8 +143,7 @@ c=(C)
 typeset c
 __OUT__
 
-# TODO not yet implemented
-test_oE -f 'array - too many fields'
+test_oE 'array - too many fields'
 IFS=' -' read -A a b c <<'END'
 A B C-D E\\E\
 E   
@@ -162,8 +158,7 @@ c=(C D 'E\EE')
 typeset c
 __OUT__
 
-# TODO not yet implemented
-test_oE -f 'array - too many variables'
+test_oE 'array - too many variables'
 read -A a b c d <<'END'
 A B
 END
@@ -178,8 +173,19 @@ d=()
 typeset d
 __OUT__
 
-# TODO not yet implemented
-test_oE -f 'array - long option'
+test_oE 'array - custom IFS'
+IFS=: read -a a <<'END'
+ A:B B::C 
+END
+echo $?
+typeset -p a
+__IN__
+0
+a=(' A' 'B B' '' 'C ')
+typeset a
+__OUT__
+
+test_oE 'array - long option'
 read --array a b c <<'END'
 A B C
 END
@@ -193,8 +199,7 @@ c=(C)
 typeset c
 __OUT__
 
-# TODO not yet implemented
-test_oE -f 'array - set -o allexport'
+test_oE 'array - set -o allexport'
 set -a
 read -A a b <<'END'
 A B C D
@@ -204,6 +209,37 @@ __IN__
 [A] [B:C:D]
 __OUT__
 
+test_oE 'reading at most n characters'
+printf 'abcdef\nghi\n' | { read -n 3 a; read -n 5 b; read c; echo $? "[$a]" "[$b]" "[$c]"; }
+__IN__
+0 [abc] [def] [ghi]
+__OUT__
+
+test_oE 'reading exactly n characters'
+printf 'ab\ncdef\n' | { read -N 4 a; read b; echo $? "[$a]" "[$b]"; }
+__IN__
+0 [ab
+c] [def]
+__OUT__
+
+test_oE 'reading exactly n characters up to end of input'
+printf 'ab' | { read -N 4 a; echo $? "[$a]"; }
+__IN__
+1 [ab]
+__OUT__
+
+test_oE 'line read before timeout'
+echo foo | { read -t 5 a; echo $? "[$a]"; }
+__IN__
+0 [foo]
+__OUT__
+
+test_oE 'timeout discards partial input'
+{ printf partial; sleep 1; echo; } | { read -t 0.1 a; echo $? "[${a-unset}]"; }
+__IN__
+142 [unset]
+__OUT__
+
 test_o -d 'assignment to read-only variable'
 readonly a
 echo A | {
@@ -227,6 +263,14 @@ test_O -d -e 4 'multi-character delimiter'
 read -d AB foo
 __IN__
 
+test_O -d -e 4 'invalid character count'
+read -n x foo
+__IN__
+
+test_O -d -e 4 'invalid timeout'
+read -t 1s foo
+__IN__
+
 test_O -d -e 4 'missing operand'
 read
 __IN__
@@ -34,3 +34,10 @@ hi
 hi
 bye
 __OUT__
+
+test_o -d 'process redirection is parsed but not yet executed'
+cat <(echo not reached) >(echo not reached)
+echo $?
+__IN__
+2
+__OUT__
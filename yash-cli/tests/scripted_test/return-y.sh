@@ -0,0 +1,33 @@
+# return-y.sh: yash-specific test of the return built-in
+
+test_OE -e 56 'separator preceding operand'
+fn() {
+    return -- 56
+    echo not reached
+}
+fn
+__IN__
+
+test_O -d -e 2 'separator is not recognized in POSIX mode' --posix
+fn() {
+    return -- 56
+    echo not reached
+}
+fn
+__IN__
+
+test_O -d -e 2 'separator without operand in POSIX mode' --posix
+fn() {
+    return --
+    echo not reached
+}
+fn
+__IN__
+
+test_OE -e 56 'operand without separator in POSIX mode' --posix
+fn() {
+    return 56
+    echo not reached
+}
+fn
+__IN__
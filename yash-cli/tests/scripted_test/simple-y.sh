@@ -0,0 +1,36 @@
+# simple-y.sh: yash-specific test of simple commands
+
+test_oE -e 0 'command_not_found_handle is called for unknown command'
+command_not_found_handle() {
+    printf 'not found: %s\n' "$@"
+}
+_no_such_command_ foo 'bar  baz'
+__IN__
+not found: _no_such_command_
+not found: foo
+not found: bar  baz
+__OUT__
+
+test_OE -e 42 'exit status of command_not_found_handle'
+command_not_found_handle() {
+    return 42
+}
+_no_such_command_
+__IN__
+
+test_o -e 0 'command_not_found_handle runs in current environment'
+command_not_found_handle() {
+    x=handled
+}
+_no_such_command_
+echo $x
+__IN__
+handled
+__OUT__
+
+test_O -d -e 127 'command_not_found_handle is ignored in POSIX mode' --posix
+command_not_found_handle() {
+    echo not reached
+}
+_no_such_command_
+__IN__
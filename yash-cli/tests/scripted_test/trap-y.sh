@@ -192,3 +192,63 @@ test_O -d -e 1 'printing to closed stream: printing specific traps (with -p)'
 trap '' USR1
 trap -p USR1 >&-
 __IN__
+
+test_oE 'DEBUG trap runs before each simple command'
+trap 'echo debug' DEBUG
+echo 1
+echo 2
+__IN__
+debug
+1
+debug
+2
+__OUT__
+
+test_oE 'ERR trap runs on failing command'
+trap 'echo err $?' ERR
+false
+true
+! false
+false || true
+echo done
+__IN__
+err 1
+done
+__OUT__
+
+test_oE -e 3 'ERR trap runs before errexit exits the shell'
+trap 'echo err $?' ERR
+set -e
+(exit 3)
+echo not reached
+__IN__
+err 3
+__OUT__
+
+test_oE 'DEBUG and ERR traps are printed after signal traps'
+trap '' USR1
+trap 'echo e' ERR DEBUG
+trap
+__IN__
+e
+trap -- '' USR1
+trap -- 'echo e' DEBUG
+trap -- 'echo e' ERR
+__OUT__
+
+test_oE 'alias defined in trap action applies to following lines of action'
+trap "alias x='echo aliased'
+x" USR1
+kill -s USR1 $$
+__IN__
+aliased
+__OUT__
+
+test_o -d -e 2 'syntax error in trap action is reported after preceding lines'
+trap 'echo first
+echo (' USR1
+kill -s USR1 $$
+echo not reached
+__IN__
+first
+__OUT__
@@ -107,6 +107,7 @@ is
 my
 array.
 a=(This is my array.)
+typeset a
 global
 __OUT__
 
@@ -184,6 +185,7 @@ typeset -x b
 typeset -p a b
 __IN__
 a=()
+typeset a
 b=(1 '2  2' 3)
 typeset -x b
 __OUT__
@@ -441,3 +443,59 @@ chmod a+x typeset
 PATH=$PWD:$PATH
 typeset --help
 __IN__
+
+test_oE 'declare prints read-only exported scalar reinputtably (-p)'
+export yash_declare_test=foo
+readonly yash_declare_test
+declare -p yash_declare_test >out
+cat out
+"$TESTEE" -c '. ./out && declare -p yash_declare_test'
+__IN__
+declare -r -x yash_declare_test=foo
+declare -r -x yash_declare_test=foo
+__OUT__
+
+test_oE 'declare prints array reinputtably (-p)'
+a=(1 '2  2' "'3'")
+declare -p a >out
+cat out
+"$TESTEE" -c '. ./out && declare -p a'
+__IN__
+a=(1 '2  2' "'3'")
+declare a
+a=(1 '2  2' "'3'")
+declare a
+__OUT__
+
+test_oE 'local - restores options when function returns'
+f() {
+    local -
+    set -x
+    case $- in (*x*) echo in function: on; esac
+}
+f 2>/dev/null
+case $- in (*x*) echo after function: on;; (*) echo after function: off; esac
+__IN__
+in function: on
+after function: off
+__OUT__
+
+test_oE 'local - in inner function does not affect outer function'
+inner() { local -; set -f; }
+outer() { local -; set -a; inner; case $- in (*f*) echo f;; (*) echo no f; esac; }
+outer
+case $- in (*a*) echo a;; (*) echo no a; esac
+__IN__
+no f
+no a
+__OUT__
+
+test_oE 'local defines local variable'
+a=global
+f() { local a=local; echo $a; }
+f
+echo $a
+__IN__
+local
+global
+__OUT__
@@ -21,6 +21,7 @@ use crate::alias::AliasSet;
 use crate::any::DataSet;
 use crate::builtin::Builtin;
 use crate::function::FunctionSet;
+use crate::history::History;
 use crate::io::Fd;
 use crate::job::{JobList, Pid};
 use crate::option::OptionSet;
@@ -45,6 +46,7 @@ pub struct ForkEnvState<S> {
     builtins: HashMap<&'static str, Builtin<S>>,
     exit_status: ExitStatus,
     functions: FunctionSet<S>,
+    history: History,
     jobs: JobList,
     main_pgid: Pid,
     main_pid: Pid,
@@ -76,6 +78,7 @@ impl<S> ForkEnvState<S> {
             builtins: take(&mut env.builtins),
             exit_status: env.exit_status,
             functions: take(&mut env.functions),
+            history: take(&mut env.history),
             jobs: take(&mut env.jobs),
             main_pgid: env.main_pgid,
             main_pid: env.main_pid,
@@ -102,6 +105,7 @@ impl<S> ForkEnvState<S> {
             builtins,
             exit_status,
             functions,
+            history,
             jobs,
             main_pgid,
             main_pid,
@@ -118,6 +122,7 @@ impl<S> ForkEnvState<S> {
         env.builtins = builtins;
         env.exit_status = exit_status;
         env.functions = functions;
+        env.history = history;
         env.jobs = jobs;
         env.main_pgid = main_pgid;
         env.main_pid = main_pid;
@@ -145,6 +150,7 @@ impl<S> ForkEnvState<S> {
             builtins: self.builtins,
             exit_status: self.exit_status,
             functions: self.functions,
+            history: self.history,
             jobs: self.jobs,
             main_pgid: self.main_pgid,
             main_pid: self.main_pid,
@@ -169,6 +175,7 @@ impl<S> Clone for ForkEnvState<S> {
             builtins: self.builtins.clone(),
             exit_status: self.exit_status,
             functions: self.functions.clone(),
+            history: self.history.clone(),
             jobs: self.jobs.clone(),
             main_pgid: self.main_pgid,
             main_pid: self.main_pid,
@@ -187,6 +194,7 @@ impl<S> Clone for ForkEnvState<S> {
         self.builtins.clone_from(&source.builtins);
         self.exit_status = source.exit_status;
         self.functions.clone_from(&source.functions);
+        self.history.clone_from(&source.history);
         self.jobs.clone_from(&source.jobs);
         self.main_pgid = source.main_pgid;
         self.main_pid = source.main_pid;
@@ -0,0 +1,494 @@
+// This file is part of yash, an extended POSIX shell.
+// Copyright (C) 2026 WATANABE Yuki
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+//! Command history
+//!
+//! This module defines the [`History`] struct, which stores the command lines
+//! entered in an interactive shell. The history is part of the shell
+//! environment as [`Env::history`].
+//!
+//! The [`record`] function adds a line to the history, honoring the
+//! [`HISTSIZE`] and [`HISTFILE`] variables. The interactive shell calls it
+//! (via the [`Recorder`](crate::input::Recorder) input decorator) for each
+//! command line read. The [`load`] function restores the history from the
+//! file named by [`HISTFILE`] on startup, and the [`save`] function writes the
+//! whole history back to the file. The [`replace_last`] function replaces the
+//! newest entry in both the history and the file.
+
+use crate::Env;
+use crate::system::concurrency::{ReadAll, WriteAll};
+use crate::system::{Close, Mode, OfdAccess, Open, OpenFlag};
+use crate::variable::{HISTFILE, HISTSIZE, VariableSet};
+use std::collections::VecDeque;
+use std::ffi::CString;
+use std::ops::RangeInclusive;
+
+/// Default maximum number of entries in the history
+///
+/// This value is used when the [`HISTSIZE`] variable is not set or is not a
+/// valid non-negative integer.
+pub const DEFAULT_SIZE: usize = 500;
+
+/// Collection of command lines entered in the shell
+///
+/// The entries are ordered from the oldest to the newest. Each entry is
+/// identified by a unique number that is assigned in ascending order starting
+/// from 1. The number of an entry does not change when older entries are
+/// removed by [`trim`](Self::trim).
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct History {
+    entries: VecDeque<String>,
+    first_number: usize,
+}
+
+impl Default for History {
+    fn default() -> Self {
+        Self {
+            entries: VecDeque::new(),
+            first_number: 1,
+        }
+    }
+}
+
+impl History {
+    /// Creates an empty history.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the number of entries in the history.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Returns `true` if the history has no entries.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Returns the number of the oldest entry.
+    ///
+    /// If the history is empty, this is the number that will be assigned to
+    /// the next entry added.
+    #[must_use]
+    pub fn first_number(&self) -> usize {
+        self.first_number
+    }
+
+    /// Returns the number that will be assigned to the next entry added.
+    #[must_use]
+    pub fn next_number(&self) -> usize {
+        self.first_number + self.entries.len()
+    }
+
+    /// Returns the entry with the given number.
+    #[must_use]
+    pub fn get(&self, number: usize) -> Option<&str> {
+        let index = number.checked_sub(self.first_number)?;
+        self.entries.get(index).map(String::as_str)
+    }
+
+    /// Appends a line to the history as the newest entry.
+    pub fn add(&mut self, line: String) {
+        self.entries.push_back(line);
+    }
+
+    /// Removes and returns the newest entry.
+    ///
+    /// The number of the removed entry will be reused for the next entry added.
+    pub fn pop(&mut self) -> Option<String> {
+        self.entries.pop_back()
+    }
+
+    /// Removes the oldest entries so that at most `max_len` entries remain.
+    pub fn trim(&mut self, max_len: usize) {
+        let excess = self.entries.len().saturating_sub(max_len);
+        self.entries.drain(..excess);
+        self.first_number += excess;
+    }
+
+    /// Removes all entries.
+    ///
+    /// Entries added after clearing continue to be numbered from where the
+    /// previous entries left off.
+    pub fn clear(&mut self) {
+        self.first_number = self.next_number();
+        self.entries.clear()
+    }
+
+    /// Returns an iterator over the entries, from the oldest to the newest.
+    pub fn iter(&self) -> impl DoubleEndedIterator<Item = &str> + ExactSizeIterator {
+        self.entries.iter().map(String::as_str)
+    }
+
+    /// Returns an iterator over the entries with their numbers, from the
+    /// oldest to the newest.
+    pub fn iter_numbered(
+        &self,
+    ) -> impl DoubleEndedIterator<Item = (usize, &str)> + ExactSizeIterator {
+        self.range(self.first_number..=usize::MAX)
+    }
+
+    /// Returns an iterator over the entries whose numbers are in the given
+    /// range, from the oldest to the newest.
+    ///
+    /// Numbers in the range that do not correspond to any entry are ignored.
+    pub fn range(
+        &self,
+        range: RangeInclusive<usize>,
+    ) -> impl DoubleEndedIterator<Item = (usize, &str)> + ExactSizeIterator {
+        let (start, end) = range.into_inner();
+        let start = start.saturating_sub(self.first_number).min(self.len());
+        let end = end
+            .saturating_add(1)
+            .saturating_sub(self.first_number)
+            .clamp(start, self.len());
+        let first_number = self.first_number + start;
+        self.entries
+            .range(start..end)
+            .enumerate()
+            .map(move |(index, line)| (first_number + index, line.as_str()))
+    }
+}
+
+/// Returns the maximum number of history entries.
+///
+/// The value is taken from the [`HISTSIZE`] variable. If the variable is not
+/// set or its value is not a non-negative integer, [`DEFAULT_SIZE`] is
+/// returned.
+#[must_use]
+pub fn size_limit(variables: &VariableSet) -> usize {
+    variables
+        .get_scalar(HISTSIZE)
+        .and_then(|value| value.parse().ok())
+        .unwrap_or(DEFAULT_SIZE)
+}
+
+/// Returns the path of the history file, if any.
+///
+/// The path is the value of the [`HISTFILE`] variable. If the variable is not
+/// set or empty, `None` is returned.
+fn history_file(variables: &VariableSet) -> Option<CString> {
+    let path = variables.get_scalar(HISTFILE)?;
+    if path.is_empty() {
+        return None;
+    }
+    CString::new(path).ok()
+}
+
+/// Records a command line in the history.
+///
+/// The trailing newline of the line is removed before the line is added. Lines
+/// that contain only whitespace are not recorded. After adding the line, the
+/// oldest entries are removed so that the history does not exceed the
+/// [size limit](size_limit).
+///
+/// If the [`HISTFILE`] variable names a file, the line is also appended to the
+/// file. If the oldest entries have been removed from the history, the file is
+/// [rewritten](save) instead so that it does not grow beyond the size limit.
+/// Errors in writing to the file are ignored.
+pub async fn record<S>(env: &mut Env<S>, line: &str)
+where
+    S: Close + Open + WriteAll,
+{
+    let line = line.strip_suffix('\n').unwrap_or(line);
+    if line.trim().is_empty() {
+        return;
+    }
+
+    env.history.add(line.to_owned());
+    let len = env.history.len();
+    env.history.trim(size_limit(&env.variables));
+
+    if env.history.len() < len {
+        save(env).await;
+    } else {
+        write_file(env, OpenFlag::Append, &format!("{line}\n")).await;
+    }
+}
+
+/// Replaces the newest entry of the history with a command line.
+///
+/// This function removes the newest entry and then adds the line in the same
+/// way as [`record`]. Since the removed entry may already have been appended
+/// to the history file, the file is [rewritten](save) to reflect the
+/// replacement.
+pub async fn replace_last<S>(env: &mut Env<S>, line: &str)
+where
+    S: Close + Open + WriteAll,
+{
+    env.history.pop();
+    let line = line.strip_suffix('\n').unwrap_or(line);
+    if !line.trim().is_empty() {
+        env.history.add(line.to_owned());
+        env.history.trim(size_limit(&env.variables));
+    }
+    save(env).await;
+}
+
+/// Writes the whole history to the history file.
+///
+/// This function replaces the content of the file named by the [`HISTFILE`]
+/// variable with the entries of the history, one per line. If the variable is
+/// not set, this function does nothing. Errors in writing to the file are
+/// ignored.
+pub async fn save<S>(env: &mut Env<S>)
+where
+    S: Close + Open + WriteAll,
+{
+    let mut data = String::new();
+    for line in env.history.iter() {
+        data.push_str(line);
+        data.push('\n');
+    }
+    write_file(env, OpenFlag::Truncate, &data).await;
+}
+
+/// Writes data to the history file, if any.
+///
+/// The file is opened with `flag` in addition to the flags for creating the
+/// file. Errors are ignored.
+async fn write_file<S>(env: &mut Env<S>, flag: OpenFlag, data: &str)
+where
+    S: Close + Open + WriteAll,
+{
+    let Some(path) = history_file(&env.variables) else {
+        return;
+    };
+    let flags = flag | OpenFlag::Create | OpenFlag::CloseOnExec;
+    let mode = Mode::USER_READ | Mode::USER_WRITE;
+    if let Ok(fd) = env
+        .system
+        .open(&path, OfdAccess::WriteOnly, flags, mode)
+        .await
+    {
+        _ = env.system.write_all(fd, data.as_bytes()).await;
+        _ = env.system.close(fd);
+    }
+}
+
+/// Loads the history from the history file.
+///
+/// This function reads the file named by the [`HISTFILE`] variable and adds
+/// each line in the file to the history. The history is then trimmed to the
+/// [size limit](size_limit). If the variable is not set or the file cannot
+/// be read, the history is left unchanged.
+pub async fn load<S>(env: &mut Env<S>)
+where
+    S: Close + Open + ReadAll,
+{
+    let Some(path) = history_file(&env.variables) else {
+        return;
+    };
+    let flags = OpenFlag::CloseOnExec.into();
+    let Ok(fd) = env
+        .system
+        .open(&path, OfdAccess::ReadOnly, flags, Mode::empty())
+        .await
+    else {
+        return;
+    };
+    let result = env.system.read_all(fd).await;
+    _ = env.system.close(fd);
+    let Ok(content) = result else {
+        return;
+    };
+
+    let content = String::from_utf8_lossy(&content);
+    for line in content.lines() {
+        if !line.trim().is_empty() {
+            env.history.add(line.to_owned());
+        }
+    }
+    env.history.trim(size_limit(&env.variables));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::system::Concurrent;
+    use crate::system::r#virtual::{FileBody, Inode, VirtualSystem};
+    use crate::variable::Scope;
+    use assert_matches::assert_matches;
+    use futures_util::FutureExt as _;
+    use std::cell::RefCell;
+    use std::rc::Rc;
+
+    #[test]
+    fn trim_removes_oldest_entries() {
+        let mut history = History::new();
+        history.add("1".to_string());
+        history.add("2".to_string());
+        history.add("3".to_string());
+
+        history.trim(5);
+        assert_eq!(history.iter().collect::<Vec<_>>(), ["1", "2", "3"]);
+
+        history.trim(2);
+        assert_eq!(history.iter().collect::<Vec<_>>(), ["2", "3"]);
+
+        history.trim(0);
+        assert!(history.is_empty());
+    }
+
+    #[test]
+    fn entries_keep_numbers_after_trim() {
+        let mut history = History::new();
+        history.add("a".to_string());
+        history.add("b".to_string());
+        history.add("c".to_string());
+        assert_eq!(history.first_number(), 1);
+        assert_eq!(history.next_number(), 4);
+        assert_eq!(history.get(2), Some("b"));
+
+        history.trim(2);
+        assert_eq!(history.first_number(), 2);
+        assert_eq!(history.get(1), None);
+        assert_eq!(history.get(2), Some("b"));
+        assert_eq!(history.get(3), Some("c"));
+        assert_eq!(history.get(4), None);
+
+        history.add("d".to_string());
+        let entries = history.iter_numbered().collect::<Vec<_>>();
+        assert_eq!(entries, [(2, "b"), (3, "c"), (4, "d")]);
+    }
+
+    #[test]
+    fn range_of_entries() {
+        let mut history = History::new();
+        for line in ["a", "b", "c", "d", "e"] {
+            history.add(line.to_string());
+        }
+        history.trim(4);
+
+        let entries = history.range(3..=4).collect::<Vec<_>>();
+        assert_eq!(entries, [(3, "c"), (4, "d")]);
+        let entries = history.range(0..=3).collect::<Vec<_>>();
+        assert_eq!(entries, [(2, "b"), (3, "c")]);
+        let entries = history.range(4..=10).collect::<Vec<_>>();
+        assert_eq!(entries, [(4, "d"), (5, "e")]);
+        assert_eq!(history.range(7..=9).count(), 0);
+    }
+
+    #[test]
+    fn size_limit_from_histsize() {
+        let mut variables = VariableSet::new();
+        assert_eq!(size_limit(&variables), DEFAULT_SIZE);
+
+        let histsize = &mut variables.get_or_new(HISTSIZE, Scope::Global);
+        histsize.assign("42", None).unwrap();
+        assert_eq!(size_limit(&variables), 42);
+
+        let histsize = &mut variables.get_or_new(HISTSIZE, Scope::Global);
+        histsize.assign("-1", None).unwrap();
+        assert_eq!(size_limit(&variables), DEFAULT_SIZE);
+    }
+
+    #[test]
+    fn load_reads_histfile_and_trims() {
+        let system = VirtualSystem::new();
+        let file = Rc::new(RefCell::new(Inode::new("a\nb\n\nc\nd\n")));
+        system
+            .state
+            .borrow_mut()
+            .file_system
+            .save("/history", file)
+            .unwrap();
+        let mut env = Env::with_system(Rc::new(Concurrent::new(system)));
+        env.variables
+            .get_or_new(HISTFILE, Scope::Global)
+            .assign("/history", None)
+            .unwrap();
+        env.variables
+            .get_or_new(HISTSIZE, Scope::Global)
+            .assign("3", None)
+            .unwrap();
+
+        load(&mut env).now_or_never().unwrap();
+
+        assert_eq!(env.history.iter().collect::<Vec<_>>(), ["b", "c", "d"]);
+    }
+
+    #[test]
+    fn record_rewrites_histfile_when_history_is_trimmed() {
+        let system = VirtualSystem::new();
+        let state = Rc::clone(&system.state);
+        let file = Rc::new(RefCell::new(Inode::new("a\nb\n")));
+        state
+            .borrow_mut()
+            .file_system
+            .save("/history", file)
+            .unwrap();
+        let mut env = Env::with_system(Rc::new(Concurrent::new(system)));
+        env.variables
+            .get_or_new(HISTFILE, Scope::Global)
+            .assign("/history", None)
+            .unwrap();
+        env.variables
+            .get_or_new(HISTSIZE, Scope::Global)
+            .assign("3", None)
+            .unwrap();
+        load(&mut env).now_or_never().unwrap();
+
+        record(&mut env, "c\n").now_or_never().unwrap();
+        let file = state.borrow().file_system.get("/history").unwrap();
+        assert_matches!(&file.borrow().body, FileBody::Regular { content, .. } => {
+            assert_eq!(std::str::from_utf8(content), Ok("a\nb\nc\n"));
+        });
+
+        record(&mut env, "d\n").now_or_never().unwrap();
+        record(&mut env, "e\n").now_or_never().unwrap();
+        let file = state.borrow().file_system.get("/history").unwrap();
+        assert_matches!(&file.borrow().body, FileBody::Regular { content, .. } => {
+            assert_eq!(std::str::from_utf8(content), Ok("c\nd\ne\n"));
+        });
+        assert_eq!(env.history.iter().collect::<Vec<_>>(), ["c", "d", "e"]);
+    }
+
+    #[test]
+    fn replace_last_rewrites_histfile() {
+        let system = VirtualSystem::new();
+        let state = Rc::clone(&system.state);
+        let mut env = Env::with_system(Rc::new(Concurrent::new(system)));
+        env.variables
+            .get_or_new(HISTFILE, Scope::Global)
+            .assign("/history", None)
+            .unwrap();
+        record(&mut env, "a\n").now_or_never().unwrap();
+        record(&mut env, "b\n").now_or_never().unwrap();
+
+        replace_last(&mut env, "c\n").now_or_never().unwrap();
+
+        assert_eq!(env.history.iter().collect::<Vec<_>>(), ["a", "c"]);
+        let file = state.borrow().file_system.get("/history").unwrap();
+        assert_matches!(&file.borrow().body, FileBody::Regular { content, .. } => {
+            assert_eq!(std::str::from_utf8(content), Ok("a\nc\n"));
+        });
+    }
+
+    #[test]
+    fn load_without_histfile() {
+        let system = VirtualSystem::new();
+        let mut env = Env::with_system(Rc::new(Concurrent::new(system)));
+
+        load(&mut env).now_or_never().unwrap();
+
+        assert!(env.history.is_empty());
+    }
+}
@@ -32,6 +32,7 @@
 //! - [`Echo`]: Echoes each line read to stderr (for the `verbose` option)
 //! - [`IgnoreEof`]: Retries on EOF when the `ignore-eof` option is on (simple
 //!   interactive use)
+//! - [`Recorder`]: Records each line in the [command history](crate::history)
 //! - [`Reporter`]: Reports job status changes before each prompt
 //! - [`EofGuard`]: Combines suspended-jobs protection with `ignore-eof` retry;
 //!   prefer over [`IgnoreEof`] for the interactive read loop
@@ -40,7 +41,8 @@
 //!
 //! [`EofGuard`] reads its behavior configuration from
 //! [`SuspendedJobsGuardConfig`] and [`IgnoreEofConfig`] stored in
-//! [`Env::any`](crate::Env::any):
+//! [`Env::any`](crate::Env::any). The interactive shell also looks up a
+//! [`LineEditor`] there:
 //!
 //! - [`SuspendedJobsGuardConfig`]: enables the suspended-jobs protection in
 //!   [`EofGuard`] (and any other component that opts in). If absent, the
@@ -48,6 +50,9 @@
 //! - [`IgnoreEofConfig`]: enables the `ignore-eof` retry behavior in
 //!   [`EofGuard`]. If absent, EOF is not retried even when the `ignore-eof`
 //!   option is on.
+//! - [`LineEditor`]: provides the input object that reads commands from a
+//!   terminal in the interactive shell. If absent, the shell reads commands
+//!   with [`FdReader2`], relying on the terminal for basic line editing.
 
 use std::ops::DerefMut;
 use std::pin::Pin;
@@ -147,6 +152,14 @@ impl<T: Input> InputObject for T {
     }
 }
 
+/// Allows a boxed [`InputObject`] to be wrapped in decorators that take an
+/// [`Input`].
+impl Input for dyn InputObject + '_ {
+    async fn next_line(&mut self, context: &Context) -> Result {
+        InputObject::next_line(self, context).await
+    }
+}
+
 mod memory;
 pub use memory::Memory;
 
@@ -159,8 +172,14 @@ pub use echo::Echo;
 mod ignore_eof;
 pub use ignore_eof::IgnoreEof;
 
+mod recorder;
+pub use recorder::Recorder;
+
 mod reporter;
 pub use reporter::Reporter;
 
 mod eof_guard;
 pub use eof_guard::{EofGuard, IgnoreEofConfig, SuspendedJobsGuardConfig};
+
+mod line_editor;
+pub use line_editor::LineEditor;
@@ -0,0 +1,215 @@
+// This file is part of yash, an extended POSIX shell.
+// Copyright (C) 2026 WATANABE Yuki
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+//! Defines the [`LineEditor`] hook.
+
+use super::{FdReader2, InputObject};
+use crate::Env;
+use crate::io::Fd;
+use crate::system::{Isatty, Read};
+use std::cell::RefCell;
+
+/// Hook for plugging a line editor into the interactive shell
+///
+/// When present in [`Env::any`](crate::Env::any), the interactive shell calls
+/// the function in this struct to create the input object that reads commands
+/// from a terminal. The function receives the file descriptor to read from and
+/// the shell environment, and returns an [`InputObject`] that implements the
+/// editor. This allows embedders to provide line editing with a library of
+/// their choice.
+///
+/// If absent, the shell reads commands with [`FdReader2`], which leaves line
+/// editing to the terminal's canonical ("cooked") mode. In that mode, the
+/// terminal driver handles basic editing such as erasing characters with the
+/// backspace key, and the shell receives the line when the user presses the
+/// enter key.
+///
+/// The input object is decorated by the shell to print prompts, report job
+/// status changes, and so on, so the editor only needs to read a line. To keep
+/// the shell responsive to signals while waiting for user input, the editor
+/// should perform I/O through the system in the environment (typically
+/// `Rc<Concurrent<_>>`) rather than blocking the thread.
+///
+/// Store this hook in the environment with
+/// `env.any.insert(Box::new(LineEditor::<S>(new_editor)))`.
+pub struct LineEditor<S>(
+    #[allow(clippy::type_complexity, reason = "we can't make this simpler")]
+    pub  for<'a, 'e> fn(Fd, &'a RefCell<&'e mut Env<S>>) -> Box<dyn InputObject + 'a>,
+);
+
+// Not derived automatically because S may not implement Clone, Copy or Debug.
+impl<S> Clone for LineEditor<S> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<S> Copy for LineEditor<S> {}
+
+impl<S> std::fmt::Debug for LineEditor<S> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_tuple("LineEditor").field(&self.0).finish()
+    }
+}
+
+impl<S> LineEditor<S>
+where
+    S: Clone + Isatty + Read + 'static,
+{
+    /// Creates an input object for reading commands from the given FD.
+    ///
+    /// If the FD is a terminal and `env.any` contains a `LineEditor`, this
+    /// function returns the input object created by the editor. Otherwise, it
+    /// returns a [`FdReader2`] that reads from the FD.
+    pub fn new_input<'a, 'e>(
+        fd: Fd,
+        env: &'a RefCell<&'e mut Env<S>>,
+    ) -> Box<dyn InputObject + 'a> {
+        let env_ref = env.borrow();
+        let system = env_ref.system.clone();
+        if system.isatty(fd)
+            && let Some(&LineEditor(new_editor)) = env_ref.any.get::<Self>()
+        {
+            drop(env_ref);
+            return new_editor(fd, env);
+        }
+        Box::new(FdReader2::new(fd, system))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::{Context, Input, Result};
+    use super::*;
+    use crate::system::Mode;
+    use crate::system::r#virtual::{FdBody, FileBody, Inode, OpenFileDescription, VirtualSystem};
+    use enumset::EnumSet;
+    use futures_util::FutureExt as _;
+    use std::rc::Rc;
+
+    fn set_stdin(system: &VirtualSystem, body: FileBody) {
+        system
+            .current_process_mut()
+            .set_fd(
+                Fd::STDIN,
+                FdBody {
+                    open_file_description: Rc::new(RefCell::new(OpenFileDescription::new(
+                        Rc::new(RefCell::new(Inode {
+                            body,
+                            permissions: Mode::empty(),
+                        })),
+                        /* offset = */ 0,
+                        /* is_readable = */ true,
+                        /* is_writable = */ true,
+                        /* is_appending = */ false,
+                        /* is_nonblocking = */ false,
+                    ))),
+                    flags: EnumSet::empty(),
+                },
+            )
+            .unwrap();
+    }
+
+    /// Toy line editor that handles the backspace character
+    struct BackspaceEditor {
+        fd: Fd,
+        system: VirtualSystem,
+    }
+
+    impl Input for BackspaceEditor {
+        async fn next_line(&mut self, _context: &Context) -> Result {
+            let mut line = String::new();
+            loop {
+                let mut byte = [0];
+                match self.system.read(self.fd, &mut byte).await? {
+                    0 => break,
+                    _ if byte[0] == 0x7F => _ = line.pop(),
+                    _ => {
+                        line.push(byte[0].into());
+                        if byte[0] == b'\n' {
+                            break;
+                        }
+                    }
+                }
+            }
+            Ok(line)
+        }
+    }
+
+    fn new_backspace_editor<'a>(
+        fd: Fd,
+        env: &'a RefCell<&mut Env<VirtualSystem>>,
+    ) -> Box<dyn InputObject + 'a> {
+        let system = env.borrow().system.clone();
+        Box::new(BackspaceEditor { fd, system })
+    }
+
+    #[test]
+    fn default_input_reads_line_from_terminal() {
+        let system = VirtualSystem::new();
+        let content = b"echo ok\n".to_vec();
+        set_stdin(&system, FileBody::Terminal { content });
+        let mut env = Env::with_system(system);
+        let env = RefCell::new(&mut env);
+
+        let mut input = LineEditor::new_input(Fd::STDIN, &env);
+        let line = Input::next_line(&mut input, &Context::default())
+            .now_or_never()
+            .unwrap();
+        assert_eq!(line.unwrap(), "echo ok\n");
+    }
+
+    #[test]
+    fn line_editor_reads_line_from_terminal() {
+        let system = VirtualSystem::new();
+        let content = b"ecx\x7Fho ok\n".to_vec();
+        set_stdin(&system, FileBody::Terminal { content });
+        let mut env = Env::with_system(system);
+        env.any
+            .insert(Box::new(LineEditor::<VirtualSystem>(new_backspace_editor)));
+        let env = RefCell::new(&mut env);
+
+        let mut input = LineEditor::new_input(Fd::STDIN, &env);
+        let line = Input::next_line(&mut input, &Context::default())
+            .now_or_never()
+            .unwrap();
+        assert_eq!(line.unwrap(), "echo ok\n");
+    }
+
+    #[test]
+    fn line_editor_is_not_used_for_non_terminal() {
+        let system = VirtualSystem::new();
+        let content = b"ecx\x7Fho ok\n".to_vec();
+        let is_native_executable = false;
+        set_stdin(
+            &system,
+            FileBody::Regular {
+                content,
+                is_native_executable,
+            },
+        );
+        let mut env = Env::with_system(system);
+        env.any
+            .insert(Box::new(LineEditor::<VirtualSystem>(new_backspace_editor)));
+        let env = RefCell::new(&mut env);
+
+        let mut input = LineEditor::new_input(Fd::STDIN, &env);
+        let line = Input::next_line(&mut input, &Context::default())
+            .now_or_never()
+            .unwrap();
+        assert_eq!(line.unwrap(), "ecx\x7Fho ok\n");
+    }
+}
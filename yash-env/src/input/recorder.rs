@@ -0,0 +1,155 @@
+// This file is part of yash, an extended POSIX shell.
+// Copyright (C) 2026 WATANABE Yuki
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+//! `Recorder` definition
+
+use super::{Context, Input, Result};
+use crate::Env;
+use crate::history::record;
+use crate::system::concurrency::WriteAll;
+use crate::system::{Close, Open};
+use std::cell::RefCell;
+
+/// `Input` decorator that records the input in the command history.
+///
+/// This decorator passes each line read from the inner input to
+/// [`history::record`](crate::history::record) before returning it to the
+/// caller, so that the line is added to [`Env::history`] and the history file.
+#[derive(Debug)]
+#[doc(alias = "History")]
+#[must_use = "Recorder does nothing unless used by a parser"]
+pub struct Recorder<'a, 'b, S, T> {
+    inner: T,
+    env: &'a RefCell<&'b mut Env<S>>,
+}
+
+impl<'a, 'b, S, T> Recorder<'a, 'b, S, T> {
+    /// Creates a new `Recorder` decorator.
+    ///
+    /// The first argument is the inner `Input` that performs the actual input
+    /// operation. The second argument is the shell environment that contains
+    /// the history and the system interface to write to the history file. It
+    /// is wrapped in a `RefCell` so that it can be shared with other
+    /// decorators and the parser.
+    pub fn new(inner: T, env: &'a RefCell<&'b mut Env<S>>) -> Self {
+        Self { inner, env }
+    }
+}
+
+// Not derived automatically because S may not implement Clone.
+impl<S, T: Clone> Clone for Recorder<'_, '_, S, T> {
+    fn clone(&self) -> Self {
+        Self {
+            inner: self.inner.clone(),
+            env: self.env,
+        }
+    }
+}
+
+impl<S, T> Input for Recorder<'_, '_, S, T>
+where
+    S: Close + Open + WriteAll,
+    T: Input,
+{
+    #[allow(
+        clippy::await_holding_refcell_ref,
+        reason = "other decorators, the parser, or the executor do not run concurrently with this method"
+    )]
+    async fn next_line(&mut self, context: &Context) -> Result {
+        let line = self.inner.next_line(context).await?;
+        record(&mut self.env.borrow_mut(), &line).await;
+        Ok(line)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::Memory;
+    use super::*;
+    use crate::system::Concurrent;
+    use crate::system::r#virtual::{FileBody, VirtualSystem};
+    use crate::variable::{HISTFILE, HISTSIZE, Scope};
+    use assert_matches::assert_matches;
+    use futures_util::FutureExt as _;
+    use std::rc::Rc;
+
+    fn read_all_lines<T: Input>(input: &mut T) {
+        loop {
+            let line = input
+                .next_line(&Context::default())
+                .now_or_never()
+                .unwrap()
+                .unwrap();
+            if line.is_empty() {
+                break;
+            }
+        }
+    }
+
+    #[test]
+    fn submitted_lines_are_recorded() {
+        let system = VirtualSystem::new();
+        let mut env = Env::with_system(Rc::new(Concurrent::new(system)));
+        let ref_env = RefCell::new(&mut env);
+        let memory = Memory::new("echo 1\n\n  \necho 2\nfoo");
+        let mut recorder = Recorder::new(memory, &ref_env);
+
+        read_all_lines(&mut recorder);
+
+        let history = env.history.iter().collect::<Vec<_>>();
+        assert_eq!(history, ["echo 1", "echo 2", "foo"]);
+    }
+
+    #[test]
+    fn history_is_trimmed_to_histsize() {
+        let system = VirtualSystem::new();
+        let mut env = Env::with_system(Rc::new(Concurrent::new(system)));
+        env.variables
+            .get_or_new(HISTSIZE, Scope::Global)
+            .assign("2", None)
+            .unwrap();
+        let ref_env = RefCell::new(&mut env);
+        let memory = Memory::new("a\nb\nc\nd\n");
+        let mut recorder = Recorder::new(memory, &ref_env);
+
+        read_all_lines(&mut recorder);
+
+        let history = env.history.iter().collect::<Vec<_>>();
+        assert_eq!(history, ["c", "d"]);
+    }
+
+    #[test]
+    fn lines_are_appended_to_histfile() {
+        let system = VirtualSystem::new();
+        let state = Rc::clone(&system.state);
+        let mut env = Env::with_system(Rc::new(Concurrent::new(system)));
+        env.variables
+            .get_or_new(HISTFILE, Scope::Global)
+            .assign("/history", None)
+            .unwrap();
+        let ref_env = RefCell::new(&mut env);
+        let memory = Memory::new("echo 1\necho 2\n");
+        let mut recorder = Recorder::new(memory, &ref_env);
+
+        read_all_lines(&mut recorder);
+
+        let file = state.borrow().file_system.get("/history").unwrap();
+        let file = file.borrow();
+        assert_matches!(&file.body, FileBody::Regular { content, .. } => {
+            assert_eq!(std::str::from_utf8(content), Ok("echo 1\necho 2\n"));
+        });
+    }
+}
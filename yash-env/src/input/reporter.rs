@@ -19,8 +19,8 @@ use crate::Env;
 use crate::io::Fd;
 use crate::job::fmt::Accumulator;
 use crate::option::{Interactive, Monitor, Off};
-use crate::system::Signals;
 use crate::system::concurrency::WriteAll;
+use crate::system::{Signals, Wait};
 use std::cell::RefCell;
 
 /// `Input` decorator that reports job status changes before reading a line
@@ -28,6 +28,11 @@ use std::cell::RefCell;
 /// This decorator prints the status of jobs that have changed since the last
 /// report. The status is printed to the standard error before the input is read.
 /// This is done only if the [`Interactive`] and [`Monitor`] options are enabled.
+///
+/// Before printing the report, the decorator
+/// [applies pending job status updates](Env::update_all_subshell_statuses) so
+/// that background jobs that have finished since the last command are reported
+/// before the next prompt.
 #[derive(Debug)]
 pub struct Reporter<'a, 'b, S, T> {
     inner: T,
@@ -57,7 +62,7 @@ impl<S, T: Clone> Clone for Reporter<'_, '_, S, T> {
     }
 }
 
-impl<S: Signals + WriteAll, T: Input> Input for Reporter<'_, '_, S, T> {
+impl<S: Signals + Wait + WriteAll, T: Input> Input for Reporter<'_, '_, S, T> {
     #[allow(
         clippy::await_holding_refcell_ref,
         reason = "other decorators, the parser, or the executor do not run concurrently with this method"
@@ -68,11 +73,13 @@ impl<S: Signals + WriteAll, T: Input> Input for Reporter<'_, '_, S, T> {
     }
 }
 
-async fn report<S: Signals + WriteAll>(env: &mut Env<S>) {
+async fn report<S: Signals + Wait + WriteAll>(env: &mut Env<S>) {
     if env.options.get(Interactive) == Off || env.options.get(Monitor) == Off {
         return;
     }
 
+    env.update_all_subshell_statuses();
+
     let mut accumulator = Accumulator::new();
     accumulator.current_job_index = env.jobs.current_job();
     accumulator.previous_job_index = env.jobs.previous_job();
@@ -100,9 +107,11 @@ mod tests {
     use crate::VirtualSystem;
     use crate::job::{Job, Pid, ProcessState};
     use crate::option::On;
+    use crate::semantics::ExitStatus;
+    use crate::subshell::{Config, JobControl};
     use crate::system::Concurrent;
-    use crate::system::r#virtual::SystemState;
-    use crate::test_helper::assert_stderr;
+    use crate::system::r#virtual::{SIGCHLD, SystemState};
+    use crate::test_helper::{assert_stderr, in_virtual_system};
     use futures_util::FutureExt as _;
     use std::rc::Rc;
 
@@ -203,6 +212,53 @@ mod tests {
         });
     }
 
+    #[test]
+    fn finished_background_job_is_reported_before_reading_input() {
+        in_virtual_system(|mut env, state| async move {
+            env.options.set(Interactive, On);
+            env.options.set(Monitor, On);
+            env.traps
+                .enable_internal_disposition_for_sigchld(&env.system)
+                .await
+                .unwrap();
+
+            let config = Config {
+                job_control: Some(JobControl::Background),
+                ..Config::new()
+            };
+            let (pid, _) = config
+                .start(&mut env, async |env, _job_control| {
+                    env.exit_status = ExitStatus(0)
+                })
+                .await
+                .unwrap();
+            let index = env.jobs.insert({
+                let mut job = Job::new(pid);
+                job.job_controlled = true;
+                job.state_changed = false;
+                job.name = "echo hello".to_string();
+                job
+            });
+
+            // Let the job finish without applying its status to the job list.
+            env.wait_for_signal(SIGCHLD).await;
+            assert_eq!(env.jobs[index].state, ProcessState::Running);
+
+            let ref_env = RefCell::new(&mut env);
+            let memory = Memory::new("echo hello\n");
+            let mut reporter = Reporter::new(memory, &ref_env);
+            reporter.next_line(&Context::default()).await.unwrap();
+
+            assert_stderr(&state, |stderr| {
+                assert!(stderr.starts_with("[1]"), "stderr: {stderr:?}");
+                assert!(stderr.contains("Done"), "stderr: {stderr:?}");
+                assert!(stderr.contains("echo hello"), "stderr: {stderr:?}");
+            });
+            assert_eq!(env.jobs[index].state, ProcessState::exited(0));
+            assert!(!env.jobs[index].state_changed);
+        })
+    }
+
     #[test]
     fn reporter_clears_state_changed_flag() {
         let mut env = Env::new_virtual();
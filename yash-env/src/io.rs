@@ -25,6 +25,7 @@ use annotate_snippets::Renderer;
 use std::borrow::Cow;
 #[cfg(unix)]
 pub(crate) use std::os::unix::io::RawFd;
+use std::rc::Rc;
 
 #[cfg(not(unix))]
 pub(crate) type RawFd = i32;
@@ -103,6 +104,10 @@ where
 ///
 /// To print the returned string to the standard error, you can use
 /// [`WriteAll::print_error`].
+///
+/// If a [`MessageCatalog`] is stored in [`Env::any`] and provides a
+/// translation for the report's [`message_id`](Report::message_id), the
+/// translated message is used as the title of the report.
 #[must_use]
 pub fn report_to_string<S: Isatty>(env: &Env<S>, report: &Report<'_>) -> String {
     let renderer = if env.should_print_error_in_color() {
@@ -110,14 +115,100 @@ pub fn report_to_string<S: Isatty>(env: &Env<S>, report: &Report<'_>) -> String
     } else {
         Renderer::plain()
     };
+
+    let translated;
+    let report = match translate_title(env, report) {
+        Some(title) => {
+            translated = Report {
+                title: title.into(),
+                ..report.clone()
+            };
+            &translated
+        }
+        None => report,
+    };
+
     format!("{}\n", renderer.render(&[report.into()]))
 }
 
+/// Translator of report titles
+///
+/// An instance of this type can be stored in [`Env::any`] to localize
+/// diagnostic messages. When [`report_to_string`] renders a report that has a
+/// [`message_id`](Report::message_id), it calls the wrapped function with the
+/// identifier. If the function returns `Some` message, the message replaces
+/// the title of the report. Otherwise, the default English title is used.
+///
+/// ```
+/// # use std::rc::Rc;
+/// # use yash_env::Env;
+/// # use yash_env::io::MessageCatalog;
+/// let mut env = Env::new_virtual();
+/// env.any.insert(Box::new(MessageCatalog(Rc::new(|id: &str| match id {
+///     "syntax.unclosed-paren" => Some("la parenthèse n'est pas fermée".to_string()),
+///     _ => None,
+/// }))));
+/// ```
+#[derive(Clone)]
+pub struct MessageCatalog(pub Rc<TranslateFn>);
+
+/// Function that returns the translation of a message identifier
+type TranslateFn = dyn Fn(&str) -> Option<String>;
+
+impl std::fmt::Debug for MessageCatalog {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_tuple("MessageCatalog").finish_non_exhaustive()
+    }
+}
+
+/// Looks up the translation of the report's title in the [`MessageCatalog`].
+fn translate_title<S>(env: &Env<S>, report: &Report<'_>) -> Option<String> {
+    let id = report.message_id.as_deref()?;
+    let MessageCatalog(catalog) = env.any.get::<MessageCatalog>()?;
+    catalog(id)
+}
+
+/// Destination of reports printed by [`print_report`]
+///
+/// By default, [`print_report`] prints reports to the standard error. If an
+/// instance of this type is stored in [`Env::any`], reports are passed to the
+/// wrapped function instead. This allows embedders such as IDEs and test
+/// harnesses to capture error and warning messages programmatically.
+///
+/// ```
+/// # use std::cell::RefCell;
+/// # use std::rc::Rc;
+/// # use yash_env::Env;
+/// # use yash_env::io::ReportSink;
+/// # use yash_env::source::pretty::Report;
+/// let mut env = Env::new_virtual();
+/// let titles = Rc::new(RefCell::new(Vec::new()));
+/// let titles_2 = Rc::clone(&titles);
+/// env.any.insert(Box::new(ReportSink(Rc::new(move |report: &Report<'_>| {
+///     titles_2.borrow_mut().push(report.title.to_string());
+/// }))));
+/// ```
+#[derive(Clone)]
+pub struct ReportSink(pub Rc<dyn Fn(&Report<'_>)>);
+
+impl std::fmt::Debug for ReportSink {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_tuple("ReportSink").finish_non_exhaustive()
+    }
+}
+
 /// Convenience function for printing a report.
 ///
 /// This function converts the `report` into a string by using
 /// [`report_to_string`], and prints the result to the standard error.
-pub async fn print_report<S: Isatty + WriteAll>(env: &mut Env<S>, report: &Report<'_>) {
+/// If a [`ReportSink`] is stored in [`Env::any`], the report is passed to it
+/// instead.
+pub async fn print_report<S: Isatty + WriteAll>(env: &Env<S>, report: &Report<'_>) {
+    if let Some(ReportSink(sink)) = env.any.get::<ReportSink>() {
+        sink(report);
+        return;
+    }
+
     let report_str = report_to_string(env, report);
     env.system.print_error(&report_str).await;
 }
@@ -138,3 +229,43 @@ pub async fn print_error<S: Isatty + WriteAll>(
     report.snippets = Snippet::with_primary_span(location, label);
     print_report(env, &report).await;
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn report_with_message_id() -> Report<'static> {
+        let mut report = Report::new();
+        report.r#type = ReportType::Error;
+        report.title = "default title".into();
+        report.message_id = Some("test.message".into());
+        report
+    }
+
+    #[test]
+    fn report_to_string_without_catalog() {
+        let env = Env::new_virtual();
+        let result = report_to_string(&env, &report_with_message_id());
+        assert!(result.contains("default title"), "{result:?}");
+    }
+
+    #[test]
+    fn report_to_string_with_translation() {
+        let mut env = Env::new_virtual();
+        env.any.insert(Box::new(MessageCatalog(Rc::new(|id: &str| {
+            (id == "test.message").then(|| "translated title".to_string())
+        }))));
+        let result = report_to_string(&env, &report_with_message_id());
+        assert!(result.contains("translated title"), "{result:?}");
+        assert!(!result.contains("default title"), "{result:?}");
+    }
+
+    #[test]
+    fn report_to_string_without_translation() {
+        let mut env = Env::new_virtual();
+        env.any
+            .insert(Box::new(MessageCatalog(Rc::new(|_: &str| None))));
+        let result = report_to_string(&env, &report_with_message_id());
+        assert!(result.contains("default title"), "{result:?}");
+    }
+}
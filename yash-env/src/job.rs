@@ -23,8 +23,9 @@
 //! The job list stores jobs in an internal array. The index of a job in the
 //! array never changes once the [job is added](JobList::add) to the job list.
 //! The index of the other jobs does not change when you [remove a
-//! job](JobList::remove). Note that the job list may reuse the index of a
-//! removed job for another job added later.
+//! job](JobList::remove). Like job numbers in other shells, a new job is given
+//! the smallest index that is not used by any other job, so the index of a
+//! removed job may be reused for another job added later.
 //!
 //! When the [wait system call](crate::system::Wait::wait) returns a new state of a
 //! child process, the caller should pass it to [`JobList::update_status`],
@@ -39,12 +40,15 @@
 //!
 //! The [`JobList::set_last_async_pid`] function remembers the process ID of the
 //! last executed asynchronous command, which will be the value of the `$!`
-//! special parameter.
+//! special parameter. The value should be obtained by
+//! [`JobList::expand_last_async_pid`] when the parameter is expanded so that
+//! the job is kept in the list until waited for.
 
 use crate::Env;
+use crate::option::{HupOnExit, Off};
 use crate::semantics::{Divert, ExitStatus};
 use crate::signal;
-use crate::system::Signals;
+use crate::system::{SendSignal, Signals};
 #[cfg(any(doc, test))]
 use crate::trap::Action;
 use slab::Slab;
@@ -285,6 +289,19 @@ pub struct Job {
     /// these jobs to finish.
     pub is_owned: bool,
 
+    /// Whether this job should not be sent `SIGHUP`
+    ///
+    /// This flag is set by the `disown -h` built-in to mark the job as one that
+    /// should not be sent `SIGHUP` when the shell hangs up.
+    pub no_sighup: bool,
+
+    /// Whether the process ID of this job has been expanded as `$!`
+    ///
+    /// This flag is set by [`JobList::expand_last_async_pid`]. The `jobs`
+    /// built-in does not remove a finished job that has this flag set, so that
+    /// the user can still wait for the job to obtain its exit status.
+    pub pid_expanded: bool,
+
     /// String representation of this process
     pub name: String,
 }
@@ -302,6 +319,8 @@ impl Job {
             expected_state: None,
             state_changed: true,
             is_owned: true,
+            no_sighup: false,
+            pid_expanded: false,
             name: String::new(),
         }
     }
@@ -346,6 +365,14 @@ impl JobRefMut<'_> {
     pub fn state_reported(&mut self) {
         self.0.state_changed = false
     }
+
+    /// Sets the `no_sighup` flag of the job.
+    ///
+    /// This method should be called when the user requests that the job not
+    /// be sent `SIGHUP`.
+    pub fn set_no_sighup(&mut self) {
+        self.0.no_sighup = true
+    }
 }
 
 impl Deref for JobRefMut<'_> {
@@ -604,10 +631,28 @@ where
 
 impl<F> FusedIterator for ExtractIf<'_, F> where F: FnMut(usize, JobRefMut) -> bool {}
 
+/// Inserts a job into the slab at the lowest vacant index.
+///
+/// `Slab::insert` reuses the most recently vacated index, which does not match
+/// how shells number jobs. If the lowest vacant index is not the one the slab
+/// would pick, this function rebuilds the slab, which makes it reuse vacant
+/// indices in ascending order.
+fn insert_at_lowest_vacancy(jobs: &mut Slab<Job>, job: Job) -> usize {
+    let index = (0..).find(|&index| !jobs.contains(index)).unwrap();
+    if jobs.vacant_key() == index {
+        jobs.insert(job)
+    } else {
+        let pairs = std::mem::take(jobs).into_iter();
+        *jobs = pairs.chain(std::iter::once((index, job))).collect();
+        index
+    }
+}
+
 impl JobList {
     /// Inserts a job to this job list.
     ///
-    /// This function returns a unique index assigned to the job.
+    /// This function returns a unique index assigned to the job. The index is
+    /// the smallest one that is not used by any other job.
     ///
     /// If there already is a job that has the same process ID as that of the
     /// new job, the existing job is silently removed.
@@ -627,7 +672,7 @@ impl JobList {
         use std::collections::hash_map::Entry::*;
         let index = match self.pids_to_indices.entry(job.pid) {
             Vacant(entry) => {
-                let index = self.jobs.insert(job);
+                let index = insert_at_lowest_vacancy(&mut self.jobs, job);
                 entry.insert(index);
                 index
             }
@@ -934,6 +979,24 @@ impl JobList {
     pub fn set_last_async_pid(&mut self, pid: Pid) {
         self.last_async_pid = pid;
     }
+
+    /// Returns the process ID of the most recently executed asynchronous
+    /// command, marking the job as referenced.
+    ///
+    /// This function returns the same value as
+    /// [`last_async_pid`](Self::last_async_pid). Additionally, if the job
+    /// list contains a job with the process ID, this function sets the job's
+    /// [`pid_expanded`](Job::pid_expanded) flag so that the job is not removed
+    /// by the `jobs` built-in before the user waits for it.
+    ///
+    /// This function should be used when expanding the `$!` special parameter.
+    pub fn expand_last_async_pid(&mut self) -> Pid {
+        let pid = self.last_async_pid;
+        if let Some(index) = self.find_by_pid(pid) {
+            self.jobs[index].pid_expanded = true;
+        }
+        pid
+    }
 }
 
 /// Adds a job if the process is suspended.
@@ -1040,6 +1103,43 @@ where
     Continue(exit_status)
 }
 
+/// Sends `SIGHUP` to jobs before the shell exits.
+///
+/// This function does nothing unless the current environment is
+/// [interactive](Env::is_interactive) and the [`HupOnExit`] option is on.
+/// Otherwise, it sends `SIGHUP` to every alive job in the job list except the
+/// jobs that are not [owned](Job::is_owned) by the current environment and
+/// the jobs whose [`no_sighup`](Job::no_sighup) flag is set. Suspended jobs
+/// are also sent `SIGCONT` so that they can receive `SIGHUP`.
+///
+/// The signals are sent to the process group of job-controlled jobs and to the
+/// process of other jobs. Errors sending the signals are ignored.
+///
+pub async fn hang_up_jobs<S>(env: &mut Env<S>)
+where
+    S: SendSignal + Signals,
+{
+    if !env.is_interactive() || env.options.get(HupOnExit) == Off {
+        return;
+    }
+
+    for (_, job) in &env.jobs {
+        if !job.is_owned || job.no_sighup || !job.state.is_alive() {
+            continue;
+        }
+
+        let target = if job.job_controlled {
+            -job.pid
+        } else {
+            job.pid
+        };
+        env.system.kill(target, Some(S::SIGHUP)).await.ok();
+        if job.state.is_stopped() {
+            env.system.kill(target, Some(S::SIGCONT)).await.ok();
+        }
+    }
+}
+
 pub mod fmt;
 pub mod id;
 mod tcsetpgrp;
@@ -1074,7 +1174,6 @@ mod tests {
 
     #[test]
     fn job_list_add_and_remove() {
-        // This test case depends on how Slab reuses the index of removed items.
         let mut list = JobList::default();
 
         assert_eq!(list.insert(Job::new(Pid(10))), 0);
@@ -1084,12 +1183,14 @@ mod tests {
         assert_eq!(list.remove(0).unwrap().pid, Pid(10));
         assert_eq!(list.remove(1).unwrap().pid, Pid(11));
 
-        // Indices are reused in the reverse order of removals.
-        assert_eq!(list.insert(Job::new(Pid(13))), 1);
-        assert_eq!(list.insert(Job::new(Pid(14))), 0);
+        // The smallest vacant index is reused first.
+        assert_eq!(list.insert(Job::new(Pid(13))), 0);
+        assert_eq!(list.insert(Job::new(Pid(14))), 1);
+        assert_eq!(list.insert(Job::new(Pid(15))), 3);
 
-        assert_eq!(list.remove(0).unwrap().pid, Pid(14));
-        assert_eq!(list.remove(1).unwrap().pid, Pid(13));
+        assert_eq!(list.remove(0).unwrap().pid, Pid(13));
+        assert_eq!(list.remove(1).unwrap().pid, Pid(14));
+        assert_eq!(list.remove(3).unwrap().pid, Pid(15));
         assert_eq!(list.remove(2).unwrap().pid, Pid(12));
 
         // Once the job list is empty, indices start from 0 again.
@@ -1097,6 +1198,25 @@ mod tests {
         assert_eq!(list.insert(Job::new(Pid(14))), 1);
     }
 
+    #[test]
+    fn job_list_reuses_smallest_index_regardless_of_removal_order() {
+        let mut list = JobList::default();
+        for pid in 10..15 {
+            list.insert(Job::new(Pid(pid)));
+        }
+
+        list.remove(1);
+        list.remove(3);
+        list.remove(2);
+
+        assert_eq!(list.insert(Job::new(Pid(20))), 1);
+        assert_eq!(list.insert(Job::new(Pid(21))), 2);
+        assert_eq!(list.insert(Job::new(Pid(22))), 3);
+        assert_eq!(list.insert(Job::new(Pid(23))), 5);
+        assert_eq!(list.find_by_pid(Pid(22)), Some(3));
+        assert_eq!(list[4].pid, Pid(14));
+    }
+
     #[test]
     fn job_list_add_same_pid() {
         let mut list = JobList::default();
@@ -1655,6 +1775,28 @@ mod tests {
         assert_eq!(list.previous_job(), Some(i10));
     }
 
+    #[test]
+    fn expand_last_async_pid_marks_job() {
+        let mut list = JobList::default();
+        let i11 = list.insert(Job::new(Pid(11)));
+        let i12 = list.insert(Job::new(Pid(12)));
+        list.set_last_async_pid(Pid(12));
+
+        assert_eq!(list.expand_last_async_pid(), Pid(12));
+        assert!(!list[i11].pid_expanded);
+        assert!(list[i12].pid_expanded);
+    }
+
+    #[test]
+    fn expand_last_async_pid_without_job() {
+        let mut list = JobList::default();
+        let i11 = list.insert(Job::new(Pid(11)));
+        list.set_last_async_pid(Pid(20));
+
+        assert_eq!(list.expand_last_async_pid(), Pid(20));
+        assert!(!list[i11].pid_expanded);
+    }
+
     #[allow(deprecated, reason = "to test the deprecated function")]
     mod add_job_if_suspended {
         use super::*;
@@ -1886,4 +2028,100 @@ mod tests {
             assert_eq!(result, Continue(ExitStatus::from(SIGINT)));
         }
     }
+
+    mod hang_up_jobs {
+        use super::*;
+        use crate::system::Concurrent;
+        use crate::system::r#virtual::{Process, SIGHUP, VirtualSystem};
+        use futures_util::FutureExt as _;
+        use std::rc::Rc;
+
+        fn env_with_jobs(
+            system: &VirtualSystem,
+            pids: [Pid; 2],
+        ) -> Env<Rc<Concurrent<VirtualSystem>>> {
+            let mut env = Env::with_system(Rc::new(Concurrent::new(system.clone())));
+            env.options.set(Interactive, On);
+            env.options.set(HupOnExit, On);
+            let mut state = system.state.borrow_mut();
+            for pid in pids {
+                let process = Process::with_parent_and_group(system.process_id, pid);
+                state.processes.insert(pid, process);
+                let mut job = Job::new(pid);
+                job.job_controlled = true;
+                env.jobs.insert(job);
+            }
+            env
+        }
+
+        #[test]
+        fn running_job_is_sent_sighup_unless_flagged() {
+            let system = VirtualSystem::new();
+            let pids = [Pid(10), Pid(20)];
+            let mut env = env_with_jobs(&system, pids);
+            let index = env.jobs.find_by_pid(Pid(20)).unwrap();
+            env.jobs.get_mut(index).unwrap().set_no_sighup();
+
+            hang_up_jobs(&mut env).now_or_never().unwrap();
+
+            let state = system.state.borrow();
+            assert_eq!(
+                state.processes[&Pid(10)].state(),
+                ProcessState::Halted(ProcessResult::Signaled {
+                    signal: SIGHUP,
+                    core_dump: false,
+                })
+            );
+            assert_eq!(state.processes[&Pid(20)].state(), ProcessState::Running);
+        }
+
+        #[test]
+        fn disowned_job_is_not_sent_sighup() {
+            let system = VirtualSystem::new();
+            let pids = [Pid(10), Pid(20)];
+            let mut env = env_with_jobs(&system, pids);
+            let index = env.jobs.find_by_pid(Pid(20)).unwrap();
+            env.jobs.remove(index);
+
+            hang_up_jobs(&mut env).now_or_never().unwrap();
+
+            let state = system.state.borrow();
+            assert_eq!(
+                state.processes[&Pid(10)].state(),
+                ProcessState::Halted(ProcessResult::Signaled {
+                    signal: SIGHUP,
+                    core_dump: false,
+                })
+            );
+            assert_eq!(state.processes[&Pid(20)].state(), ProcessState::Running);
+        }
+
+        #[test]
+        fn no_signal_without_huponexit_option() {
+            let system = VirtualSystem::new();
+            let pids = [Pid(10), Pid(20)];
+            let mut env = env_with_jobs(&system, pids);
+            env.options.set(HupOnExit, Off);
+
+            hang_up_jobs(&mut env).now_or_never().unwrap();
+
+            let state = system.state.borrow();
+            assert_eq!(state.processes[&Pid(10)].state(), ProcessState::Running);
+            assert_eq!(state.processes[&Pid(20)].state(), ProcessState::Running);
+        }
+
+        #[test]
+        fn no_signal_in_non_interactive_shell() {
+            let system = VirtualSystem::new();
+            let pids = [Pid(10), Pid(20)];
+            let mut env = env_with_jobs(&system, pids);
+            env.options.set(Interactive, Off);
+
+            hang_up_jobs(&mut env).now_or_never().unwrap();
+
+            let state = system.state.borrow();
+            assert_eq!(state.processes[&Pid(10)].state(), ProcessState::Running);
+            assert_eq!(state.processes[&Pid(20)].state(), ProcessState::Running);
+        }
+    }
 }
@@ -202,8 +202,9 @@ impl JobId<'_> {
 
 #[cfg(test)]
 mod tests {
-    use super::super::Pid;
+    use super::super::{Pid, ProcessState};
     use super::*;
+    use crate::system::r#virtual::SIGTSTP;
 
     #[test]
     fn job_id_display() {
@@ -251,6 +252,31 @@ mod tests {
         assert_eq!(job_id.find(&list), Ok(previous_job_index));
     }
 
+    #[test]
+    fn current_and_previous_job_follow_suspension_and_removal() {
+        let mut list = sample_job_list();
+        let current = |list: &JobList| JobId::CurrentJob.find(list);
+        let previous = |list: &JobList| JobId::PreviousJob.find(list);
+
+        // A suspended job becomes the current job.
+        list.update_status(Pid(12), ProcessState::stopped(SIGTSTP));
+        assert_eq!(current(&list), Ok(2));
+        assert_eq!(previous(&list), Ok(0));
+
+        list.update_status(Pid(11), ProcessState::stopped(SIGTSTP));
+        assert_eq!(current(&list), Ok(1));
+        assert_eq!(previous(&list), Ok(2));
+
+        // The previous job is promoted when the current job is removed.
+        list.remove(1);
+        assert_eq!(current(&list), Ok(2));
+        assert_eq!(previous(&list), Ok(0));
+
+        list.remove(2);
+        assert_eq!(current(&list), Ok(0));
+        assert_eq!(previous(&list), Err(FindError::NotFound));
+    }
+
     #[test]
     fn find_unique_job_by_job_number() {
         let list = sample_job_list();
@@ -334,6 +360,28 @@ mod tests {
         assert_eq!(job_id.find(&list), Err(FindError::Ambiguous));
     }
 
+    #[test]
+    fn parse_and_find_each_form() {
+        let mut list = JobList::default();
+        for (pid, name) in [(10, "echo foo"), (11, "echo bar"), (12, "sleep 10")] {
+            let mut job = Job::new(Pid(pid));
+            job.name = name.to_string();
+            list.insert(job);
+        }
+        let find = |job_id| parse(job_id).unwrap().find(&list);
+
+        assert_eq!(find("%3"), Ok(2));
+        assert_eq!(find("%%"), list.current_job().ok_or(FindError::NotFound));
+        assert_eq!(find("%+"), list.current_job().ok_or(FindError::NotFound));
+        assert_eq!(find("%-"), list.previous_job().ok_or(FindError::NotFound));
+        assert_eq!(find("%sl"), Ok(2));
+        assert_eq!(find("%?bar"), Ok(1));
+        assert_eq!(find("%ec"), Err(FindError::Ambiguous));
+        assert_eq!(find("%?echo"), Err(FindError::Ambiguous));
+        assert_eq!(find("%4"), Err(FindError::NotFound));
+        assert_eq!(find("%cat"), Err(FindError::NotFound));
+    }
+
     #[test]
     fn find_ambiguous_substring() {
         let list = sample_job_list();
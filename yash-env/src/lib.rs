@@ -47,6 +47,7 @@ use self::any::DataSet;
 use self::builtin::Builtin;
 use self::fork::ForkEnvState;
 use self::function::FunctionSet;
+use self::history::History;
 use self::io::Fd;
 use self::job::JobList;
 use self::job::Pid;
@@ -77,6 +78,7 @@ use self::system::OpenFlag;
 use self::system::SignalList;
 use self::system::Signals;
 use self::system::TcSetPgrp;
+use self::system::TtySize;
 use self::system::Wait;
 use self::system::concurrency::Select;
 use self::system::concurrency::WaitForSignals;
@@ -86,6 +88,8 @@ pub use self::system::r#virtual::VirtualSystem;
 use self::trap::Action;
 use self::trap::SignalSystem;
 use self::trap::TrapSet;
+use self::variable::COLUMNS;
+use self::variable::LINES;
 use self::variable::PPID;
 use self::variable::Scope;
 use self::variable::VariableRefMut;
@@ -130,6 +134,9 @@ pub struct Env<S> {
     /// Functions defined in the environment
     pub functions: FunctionSet<S>,
 
+    /// Command lines entered in the interactive shell
+    pub history: History,
+
     /// Jobs managed in the environment
     pub jobs: JobList,
 
@@ -184,6 +191,7 @@ impl<S> Env<S> {
             builtins: Default::default(),
             exit_status: Default::default(),
             functions: Default::default(),
+            history: Default::default(),
             jobs: Default::default(),
             main_pgid: system.getpgrp(),
             main_pid: system.getpid(),
@@ -210,6 +218,7 @@ impl<S> Env<S> {
             builtins: self.builtins.clone(),
             exit_status: self.exit_status,
             functions: self.functions.clone(),
+            history: self.history.clone(),
             jobs: self.jobs.clone(),
             main_pgid: self.main_pgid,
             main_pid: self.main_pid,
@@ -371,6 +380,29 @@ impl<S> Env<S> {
         self.system.isatty(Fd::STDERR)
     }
 
+    /// Updates the `COLUMNS` and `LINES` variables to the window size of the
+    /// terminal.
+    ///
+    /// This function gets the [window size](TtySize::tty_size) of the terminal
+    /// associated with the standard error and assigns the numbers of columns
+    /// and lines to the variables. A variable is left intact if the size is
+    /// unknown or the variable is read-only.
+    pub fn update_tty_size(&mut self)
+    where
+        S: TtySize,
+    {
+        let Ok(size) = self.system.tty_size(Fd::STDERR) else {
+            return;
+        };
+        for (name, value) in [(COLUMNS, size.columns), (LINES, size.lines)] {
+            if value != 0 {
+                self.get_or_create_variable(name, Scope::Global)
+                    .assign(value.to_string(), None)
+                    .ok();
+            }
+        }
+    }
+
     /// Returns a file descriptor to the controlling terminal.
     ///
     /// This function returns `self.tty` if it is `Some` FD. Otherwise, it
@@ -696,6 +728,7 @@ pub mod any;
 pub mod builtin;
 pub mod decl_util;
 pub mod function;
+pub mod history;
 pub mod input;
 pub mod io;
 pub mod job;
@@ -1071,6 +1104,22 @@ mod tests {
         assert_eq!(env.apply_errexit(), Break(Divert::Exit(None)));
     }
 
+    #[test]
+    fn is_interactive_with_interactive_option() {
+        let mut env = Env::new_virtual();
+        assert!(!env.is_interactive());
+        env.options.set(Interactive, On);
+        assert!(env.is_interactive());
+    }
+
+    #[test]
+    fn is_interactive_in_subshell() {
+        let mut env = Env::new_virtual();
+        env.options.set(Interactive, On);
+        let env = env.push_frame(Frame::Subshell);
+        assert!(!env.is_interactive());
+    }
+
     #[test]
     fn errexit_with_zero_exit_status() {
         let mut env = Env::new_virtual();
@@ -110,6 +110,8 @@ pub enum Option {
     /// Performs command search for each command in a function on its
     /// definition.
     HashOnDefinition,
+    /// Makes the interactive shell send `SIGHUP` to its jobs when exiting.
+    HupOnExit,
     /// Prevents the interactive shell from exiting when the user enters an
     /// end-of-file.
     IgnoreEof,
@@ -170,6 +172,7 @@ impl Option {
             Exec => Some(('n', Off)),
             Glob => Some(('f', Off)),
             HashOnDefinition => Some(('h', On)),
+            HupOnExit => None,
             IgnoreEof => None,
             Interactive => Some(('i', On)),
             Log => None,
@@ -201,6 +204,7 @@ impl Option {
             Exec => "exec",
             Glob => "glob",
             HashOnDefinition => "hashondefinition",
+            HupOnExit => "huponexit",
             IgnoreEof => "ignoreeof",
             Interactive => "interactive",
             Log => "log",
@@ -267,6 +271,7 @@ impl FromStr for Option {
             ("exec", Exec),
             ("glob", Glob),
             ("hashondefinition", HashOnDefinition),
+            ("huponexit", HupOnExit),
             ("ignoreeof", IgnoreEof),
             ("interactive", Interactive),
             ("log", Log),
@@ -505,6 +510,15 @@ impl Extend<Option> for OptionSet {
     }
 }
 
+/// Option set saved to be restored when the current function returns
+///
+/// The `local -` command stores an instance of this type in
+/// [`Env::any`](crate::Env::any) to make the shell options local to the
+/// currently executing function. The function executor removes the instance
+/// when the function returns and restores the saved options.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct SavedOptions(pub OptionSet);
+
 #[cfg(test)]
 mod tests {
     use super::*;
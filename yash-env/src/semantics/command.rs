@@ -23,6 +23,7 @@ pub mod search;
 use crate::Env;
 use crate::function::Function;
 use crate::job::{RunBlocking, RunUnblocking, handle_job_status};
+use crate::option::{On, PosixlyCorrect};
 use crate::semantics::{ExitStatus, Field, Result};
 use crate::source::Location;
 use crate::source::pretty::{Report, ReportType, Snippet};
@@ -30,11 +31,12 @@ use crate::subshell::{BlockSignals, Config};
 use crate::system::concurrency::WaitForSignals;
 use crate::system::resource::SetRlimit;
 use crate::system::{
-    Close, Dup, Errno, Exec, Exit, Fork, GetPid, Open, SendSignal, SetPgid, ShellPath, TcSetPgrp,
-    Wait,
+    Close, Dup, Errno, Exec, Exit, Fork, GetPid, IsExecutableFile, Open, SendSignal, SetPgid,
+    ShellPath, TcSetPgrp, Wait,
 };
 use crate::trap::SignalSystem;
 use itertools::Itertools as _;
+use search::search_path;
 use std::convert::Infallible;
 use std::ffi::CString;
 use std::ops::ControlFlow::Continue;
@@ -111,6 +113,84 @@ impl<S> std::fmt::Debug for RunFunction<S> {
     }
 }
 
+/// Configuration for the command-not-found handler
+///
+/// When present in [`Env::any`], the simple command execution invokes the
+/// function named [`function_name`](Self::function_name) instead of reporting
+/// the "not found" error if the command search fails to find a target and the
+/// function is defined. The function receives the command name and arguments
+/// as its positional parameters, and its exit status becomes the exit status
+/// of the simple command.
+///
+/// If absent from `env.any`, the handler is disabled. The handler is also
+/// disabled when the [`PosixlyCorrect`] option is on.
+///
+/// Store this config in the environment with
+/// `env.any.insert(Box::new(config))`.
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+#[non_exhaustive]
+pub struct CommandNotFoundHandlerConfig {
+    /// Name of the function invoked as the handler
+    pub function_name: String,
+}
+
+impl CommandNotFoundHandlerConfig {
+    /// Default name of the handler function, `command_not_found_handle`
+    pub const DEFAULT_FUNCTION_NAME: &str = "command_not_found_handle";
+
+    /// Creates a new `CommandNotFoundHandlerConfig` with the given function
+    /// name.
+    #[must_use]
+    pub fn with_function_name<N: Into<String>>(function_name: N) -> Self {
+        Self {
+            function_name: function_name.into(),
+        }
+    }
+
+    /// Returns the handler function to be invoked in the environment, if any.
+    ///
+    /// This function returns `None` if the config is absent from `env.any`,
+    /// the [`PosixlyCorrect`] option is on, or no function with the configured
+    /// name is defined.
+    #[must_use]
+    pub fn handler<S>(env: &Env<S>) -> Option<Rc<Function<S>>> {
+        if env.options.get(PosixlyCorrect) == On {
+            return None;
+        }
+        let config = env.any.get::<Self>()?;
+        env.functions.get(&config.function_name).cloned()
+    }
+}
+
+/// Uses the [default function name](Self::DEFAULT_FUNCTION_NAME).
+impl Default for CommandNotFoundHandlerConfig {
+    fn default() -> Self {
+        Self::with_function_name(Self::DEFAULT_FUNCTION_NAME)
+    }
+}
+
+/// Marker that disables the execution of external utilities
+///
+/// When present in [`Env::any`], the shell runs in a builtin-only mode suitable
+/// for sandboxing: the [command search](search::search) never finds external
+/// utilities, and [`replace_current_process`] and
+/// [`run_external_utility_in_subshell`] fail with [`Errno::EPERM`] without
+/// executing anything. Functions and built-ins, including substitutive
+/// built-ins, are still available regardless of `$PATH`.
+///
+/// Store this marker in the environment with
+/// `env.any.insert(Box::new(NoExternalUtilities))`.
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
+pub struct NoExternalUtilities;
+
+impl NoExternalUtilities {
+    /// Tests whether the marker is present in the environment.
+    #[must_use]
+    pub fn is_set<S>(env: &Env<S>) -> bool {
+        env.any.get::<Self>().is_some()
+    }
+}
+
 /// Error returned when [replacing the current process](replace_current_process) fails
 #[derive(Clone, Debug, Error)]
 #[error("cannot execute external utility {path:?}: {errno}")]
@@ -135,27 +215,57 @@ pub struct ReplaceCurrentProcessError {
 /// can interpret the script. The path to the shell executable is taken from
 /// [`ShellPath::shell_path`].
 ///
+/// If the `execve` call fails with [`ENOENT`](Errno::ENOENT) and the utility
+/// name (the first field of `args`) does not contain a slash, the `path` is
+/// assumed to be the result of a command search that has become stale because
+/// the file has been removed. In this case, this function searches `$PATH`
+/// again and retries `execve` once with the newly found path, if any.
+///
 /// If the `execve` call succeeds, the future returned by this function never
 /// resolves.
 ///
+/// If [`NoExternalUtilities`] is set in the environment, this function fails
+/// with [`Errno::EPERM`] without calling `execve`. In this case,
+/// `env.exit_status` is not updated, so the caller should set an appropriate
+/// exit status.
+///
 /// This function is for implementing the simple command execution semantics and
 /// the `exec` built-in utility.
-pub async fn replace_current_process<S: Exec + ShellPath + SignalSystem>(
+pub async fn replace_current_process<S: Exec + IsExecutableFile + ShellPath + SignalSystem>(
     env: &mut Env<S>,
-    path: CString,
+    mut path: CString,
     args: Vec<Field>,
 ) -> std::result::Result<Infallible, ReplaceCurrentProcessError> {
+    if NoExternalUtilities::is_set(env) {
+        let errno = Errno::EPERM;
+        return Err(ReplaceCurrentProcessError { path, errno });
+    }
+
     env.traps
         .disable_internal_dispositions(&env.system)
         .await
         .ok();
 
+    let name = args.first().map(|field| field.value.clone());
     let args = to_c_strings(args);
     let envs = env.variables.env_c_strings();
-    let Err(errno) = env
+    let Err(mut errno) = env
         .system
         .execve(path.as_c_str(), args.as_slice(), envs.as_slice())
         .await;
+
+    if errno == Errno::ENOENT
+        && let Some(name) = name.filter(|name| !name.contains('/'))
+        && let Some(new_path) = search_path(env, &name).filter(|new_path| *new_path != path)
+    {
+        path = new_path;
+        let Err(new_errno) = env
+            .system
+            .execve(path.as_c_str(), args.as_slice(), envs.as_slice())
+            .await;
+        errno = new_errno;
+    }
+
     env.exit_status = match errno {
         Errno::ENOEXEC => {
             fall_back_on_sh(&env.system, path.clone(), args, envs).await;
@@ -244,6 +354,10 @@ impl<'a> From<&'a StartSubshellError> for Report<'a> {
 /// replacing the subshell process with the utility fails. Both functions
 /// should print appropriate error messages.
 ///
+/// If [`NoExternalUtilities`] is set in the environment, this function does not
+/// start a subshell but calls `handle_start_subshell_error` with
+/// [`Errno::EPERM`].
+///
 /// This function is for implementing the simple command execution semantics and
 /// the `command` built-in utility. This function internally uses
 /// [`replace_current_process`] to execute the utility in the subshell.
@@ -266,6 +380,7 @@ where
         + Exit
         + Fork
         + GetPid
+        + IsExecutableFile
         + Open
         + RunBlocking
         + RunUnblocking
@@ -281,6 +396,12 @@ where
 {
     let utility = args[0].clone();
 
+    if NoExternalUtilities::is_set(env) {
+        let errno = Errno::EPERM;
+        handle_start_subshell_error(env, StartSubshellError { utility, errno }).await;
+        return Continue(ExitStatus::NOEXEC);
+    }
+
     let job_name = if env.controls_jobs() {
         to_job_name(&args)
     } else {
@@ -308,3 +429,107 @@ fn to_job_name(fields: &[Field]) -> String {
         .format_with(" ", |field, f| f(&format_args!("{}", field.value)))
         .to_string()
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::str::UnixStr;
+    use crate::system::r#virtual::{FileBody, Inode, VirtualSystem};
+    use crate::system::{Concurrent, Mode};
+    use crate::variable::{PATH, Scope};
+    use futures_util::FutureExt as _;
+    use std::cell::RefCell;
+
+    fn save_executable_file(system: &VirtualSystem, path: &str) {
+        let mut content = Inode::default();
+        content.body = FileBody::Regular {
+            content: Vec::new(),
+            is_native_executable: true,
+        };
+        content.permissions.set(Mode::USER_EXEC, true);
+        let content = Rc::new(RefCell::new(content));
+        let mut state = system.state.borrow_mut();
+        state.file_system.save(path, content).unwrap();
+    }
+
+    fn remove_file(system: &VirtualSystem, dir: &str, name: &str) {
+        let state = system.state.borrow();
+        let dir = state.file_system.get(dir).unwrap();
+        let mut dir = dir.borrow_mut();
+        let FileBody::Directory { files } = &mut dir.body else {
+            panic!("not a directory");
+        };
+        files.remove(UnixStr::new(name)).unwrap();
+    }
+
+    #[test]
+    fn replace_current_process_searches_path_again_if_file_is_removed() {
+        let system = VirtualSystem::new();
+        let mut env = Env::with_system(Rc::new(Concurrent::new(system.clone())));
+        env.variables
+            .get_or_new(PATH, Scope::Global)
+            .assign("/bin:/usr/bin", None)
+            .unwrap();
+        save_executable_file(&system, "/bin/foo");
+        let path = search_path(&mut env, "foo").unwrap();
+        assert_eq!(path, c"/bin/foo".to_owned());
+
+        // The file found in the search is removed, but another copy remains.
+        remove_file(&system, "/bin", "foo");
+        save_executable_file(&system, "/usr/bin/foo");
+
+        let args = Field::dummies(["foo", "bar"]);
+        let result = replace_current_process(&mut env, path, args)
+            .now_or_never()
+            .unwrap();
+        // In VirtualSystem, a successful execve returns ENOSYS.
+        let error = result.unwrap_err();
+        assert_eq!(error.path, c"/usr/bin/foo".to_owned());
+        assert_eq!(error.errno, Errno::ENOSYS);
+
+        let process = system.current_process();
+        let arguments = process.last_exec().as_ref().unwrap();
+        assert_eq!(arguments.0, c"/usr/bin/foo".to_owned());
+        assert_eq!(arguments.1, [c"foo".to_owned(), c"bar".to_owned()]);
+    }
+
+    #[test]
+    fn replace_current_process_does_not_search_path_for_name_with_slash() {
+        let system = VirtualSystem::new();
+        let mut env = Env::with_system(Rc::new(Concurrent::new(system.clone())));
+        env.variables
+            .get_or_new(PATH, Scope::Global)
+            .assign("/usr/bin", None)
+            .unwrap();
+        save_executable_file(&system, "/usr/bin/foo");
+
+        let args = Field::dummies(["/bin/foo"]);
+        let result = replace_current_process(&mut env, c"/bin/foo".to_owned(), args)
+            .now_or_never()
+            .unwrap();
+        let error = result.unwrap_err();
+        assert_eq!(error.path, c"/bin/foo".to_owned());
+        assert_eq!(error.errno, Errno::ENOENT);
+        assert_eq!(env.exit_status, ExitStatus::NOT_FOUND);
+        assert_eq!(*system.current_process().last_exec(), None);
+    }
+
+    #[test]
+    fn replace_current_process_fails_without_updating_exit_status_if_externals_disabled() {
+        let system = VirtualSystem::new();
+        let mut env = Env::with_system(Rc::new(Concurrent::new(system.clone())));
+        env.any.insert(Box::new(NoExternalUtilities));
+        save_executable_file(&system, "/bin/foo");
+        env.exit_status = ExitStatus(42);
+
+        let args = Field::dummies(["/bin/foo"]);
+        let result = replace_current_process(&mut env, c"/bin/foo".to_owned(), args)
+            .now_or_never()
+            .unwrap();
+        let error = result.unwrap_err();
+        assert_eq!(error.path, c"/bin/foo".to_owned());
+        assert_eq!(error.errno, Errno::EPERM);
+        assert_eq!(env.exit_status, ExitStatus(42));
+        assert_eq!(*system.current_process().last_exec(), None);
+    }
+}
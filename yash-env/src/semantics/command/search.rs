@@ -37,6 +37,10 @@
 //! as non-existing) when the [`PosixlyCorrect`] option is on, so the search
 //! falls through to external utilities in that case.
 //!
+//! If [external utilities are disabled](PathEnv::external_utilities_disabled),
+//! the search never yields an external utility, and substitutive built-ins are
+//! chosen without checking `PATH`.
+//!
 //! [command search]: https://pubs.opengroup.org/onlinepubs/9799919799/utilities/V3_chap02.html#tag_19_09_01_04
 //! [simple command]: https://pubs.opengroup.org/onlinepubs/9799919799/utilities/V3_chap02.html#tag_19_09_01
 
@@ -46,6 +50,7 @@ use crate::builtin::Type::{Extension, Special, Substitutive};
 use crate::function::Function;
 use crate::option::{On, PosixlyCorrect};
 use crate::path::PathBuf;
+use crate::semantics::command::NoExternalUtilities;
 use crate::system::IsExecutableFile;
 use crate::variable::Expansion;
 use crate::variable::PATH;
@@ -195,6 +200,17 @@ pub trait PathEnv {
     #[must_use]
     fn is_executable_file(&self, path: &CStr) -> bool;
     // TODO Cache the results of external utility search
+
+    /// Whether the execution of external utilities is disabled.
+    ///
+    /// If this function returns true, [`search`] does not search `$PATH` and
+    /// never returns an external utility target.
+    ///
+    /// The default implementation returns `false`.
+    #[must_use]
+    fn external_utilities_disabled(&self) -> bool {
+        false
+    }
 }
 
 impl<S: IsExecutableFile> PathEnv for Env<S> {
@@ -215,6 +231,11 @@ impl<S: IsExecutableFile> PathEnv for Env<S> {
     fn is_executable_file(&self, path: &CStr) -> bool {
         self.system.is_executable_file(path)
     }
+
+    /// Returns true if [`NoExternalUtilities`] is set in the environment.
+    fn external_utilities_disabled(&self) -> bool {
+        NoExternalUtilities::is_set(self)
+    }
 }
 
 impl<S> ClassifyEnv<S> for Env<S> {
@@ -243,14 +264,21 @@ impl<S> ClassifyEnv<S> for Env<S> {
 #[must_use]
 pub fn search<S, E: ClassifyEnv<S> + PathEnv>(env: &mut E, name: &str) -> Option<Target<S>> {
     let mut target = classify(env, name);
+    let externals_disabled = env.external_utilities_disabled();
 
     'fill_path: {
         let path = match &mut target {
             Target::Builtin { builtin, path } if builtin.r#type == Substitutive => {
+                if externals_disabled {
+                    // Use the built-in without the external counterpart.
+                    break 'fill_path;
+                }
                 // Must verify the external counterpart exists.
                 path
             }
 
+            Target::External { .. } if externals_disabled => return None,
+
             Target::External { path } => {
                 if name.contains('/') {
                     // Just access the given path.
@@ -411,6 +439,7 @@ mod tests {
         functions: FunctionSet<()>,
         path: Expansion<'static>,
         executables: HashSet<String>,
+        externals_disabled: bool,
     }
 
     impl PathEnv for DummyEnv {
@@ -424,6 +453,9 @@ mod tests {
                 false
             }
         }
+        fn external_utilities_disabled(&self) -> bool {
+            self.externals_disabled
+        }
     }
 
     impl ClassifyEnv<()> for DummyEnv {
@@ -713,6 +745,24 @@ mod tests {
         assert!(target.is_none(), "target = {target:?}");
     }
 
+    #[test]
+    fn substitutive_builtin_is_found_without_path_if_externals_disabled() {
+        let mut env = DummyEnv::default();
+        let builtin = Builtin::new(Substitutive, |_, _| unreachable!());
+        env.builtins.insert("foo", builtin);
+        env.path = Expansion::from("/bin");
+        env.executables.insert("/bin/foo".to_string());
+        env.externals_disabled = true;
+
+        assert_matches!(
+            search(&mut env, "foo"),
+            Some(Target::Builtin { builtin: result, path }) => {
+                assert_eq!(result.r#type, builtin.r#type);
+                assert_eq!(*path, *c"");
+            }
+        );
+    }
+
     #[test]
     fn substitutive_builtin_is_classified_even_without_external_executable() {
         let mut env = DummyEnv::default();
@@ -765,6 +815,28 @@ mod tests {
         });
     }
 
+    #[test]
+    fn external_utility_is_not_found_if_externals_disabled() {
+        let mut env = DummyEnv::default();
+        env.path = Expansion::from("/bin");
+        env.executables.insert("/bin/foo".to_string());
+        env.executables.insert("bar/baz".to_string());
+        env.externals_disabled = true;
+
+        let target = search(&mut env, "foo");
+        assert!(target.is_none(), "target = {target:?}");
+        let target = search(&mut env, "bar/baz");
+        assert!(target.is_none(), "target = {target:?}");
+    }
+
+    #[test]
+    fn env_external_utilities_disabled_by_marker() {
+        let mut env = Env::new_virtual();
+        assert!(!env.external_utilities_disabled());
+        env.any.insert(Box::new(NoExternalUtilities));
+        assert!(env.external_utilities_disabled());
+    }
+
     #[test]
     fn returns_external_utility_if_name_contains_slash() {
         // In this case, the external utility file does not have to exist.
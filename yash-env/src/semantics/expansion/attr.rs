@@ -29,6 +29,7 @@ use crate::source::Location;
 
 /// Category of syntactic elements from which expansion originates
 #[derive(Copy, Clone, Debug, Eq, PartialEq)]
+#[non_exhaustive]
 pub enum Origin {
     /// The character appeared literally in the original word.
     Literal,
@@ -39,11 +40,32 @@ pub enum Origin {
     /// This kind of character is treated literally in the pathname expansion.
     HardExpansion,
 
-    /// The character originates from a parameter expansion, command
-    /// substitution, or arithmetic expansion.
+    /// The character originates from a parameter expansion or arithmetic
+    /// expansion.
     ///
     /// This kind of character is subject to field splitting where applicable.
     SoftExpansion,
+
+    /// The character originates from the output of a command substitution.
+    ///
+    /// This kind of character is treated the same as [`SoftExpansion`] in
+    /// field splitting and pathname expansion. The distinct origin allows
+    /// later stages to tell command substitution results from other
+    /// expansion results.
+    ///
+    /// [`SoftExpansion`]: Self::SoftExpansion
+    CommandSubst,
+}
+
+impl Origin {
+    /// Tests whether the character is subject to field splitting.
+    ///
+    /// Returns `true` for [`SoftExpansion`](Self::SoftExpansion) and
+    /// [`CommandSubst`](Self::CommandSubst).
+    #[must_use]
+    pub const fn is_soft_expansion(self) -> bool {
+        matches!(self, Self::SoftExpansion | Self::CommandSubst)
+    }
 }
 
 /// Character with attributes describing how it was derived in the expansion
@@ -225,4 +225,31 @@ mod tests {
             ]
         );
     }
+
+    #[test]
+    fn split_with_non_whitespace_ifs() {
+        let field = dummy_attr_field("a::b:");
+        let ifs = Ifs::new(":");
+        let fields: Vec<AttrField> = split(field, &ifs);
+        assert_eq!(
+            fields,
+            [
+                dummy_attr_field("a"),
+                dummy_attr_field(""),
+                dummy_attr_field("b")
+            ]
+        );
+    }
+
+    #[test]
+    fn quoted_chars_are_not_split() {
+        let mut field = dummy_attr_field("a b c:d");
+        field.chars[1].is_quoted = true;
+        field.chars[5].is_quoted = true;
+        let ifs = Ifs::new(" :");
+        let fields: Vec<AttrField> = split(field.clone(), &ifs);
+        assert_eq!(fields.len(), 2, "{fields:?}");
+        assert_eq!(fields[0].chars, field.chars[..3]);
+        assert_eq!(fields[1].chars, field.chars[4..]);
+    }
 }
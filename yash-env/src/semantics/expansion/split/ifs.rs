@@ -16,7 +16,7 @@
 
 //! IFS parser
 
-use crate::semantics::expansion::attr::{AttrChar, Origin};
+use crate::semantics::expansion::attr::AttrChar;
 use std::borrow::Cow;
 
 /// Type of characters that affect field splitting
@@ -193,7 +193,7 @@ impl Ifs<'_> {
     /// Returns the type of the attributed character.
     #[must_use]
     pub fn classify_attr(&self, c: AttrChar) -> Class {
-        if c.is_quoted || c.is_quoting || c.origin != Origin::SoftExpansion {
+        if c.is_quoted || c.is_quoting || !c.origin.is_soft_expansion() {
             Class::NonIfs
         } else {
             self.classify(c.value)
@@ -204,6 +204,7 @@ impl Ifs<'_> {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::semantics::expansion::attr::Origin;
 
     fn assert_non_ifs(ifs: &Ifs, c: char) {
         assert!(!ifs.is_ifs(c), "{c:?} not to be separator");
@@ -333,6 +334,27 @@ mod tests {
         assert_non_ifs(&ifs, 'e');
     }
 
+    #[test]
+    fn classify_attr_by_origin() {
+        let ifs = Ifs::default();
+        let c = |origin| AttrChar {
+            value: ' ',
+            origin,
+            is_quoted: false,
+            is_quoting: false,
+        };
+        assert_eq!(ifs.classify_attr(c(Origin::Literal)), Class::NonIfs);
+        assert_eq!(ifs.classify_attr(c(Origin::HardExpansion)), Class::NonIfs);
+        assert_eq!(
+            ifs.classify_attr(c(Origin::SoftExpansion)),
+            Class::IfsWhitespace
+        );
+        assert_eq!(
+            ifs.classify_attr(c(Origin::CommandSubst)),
+            Class::IfsWhitespace
+        );
+    }
+
     #[test]
     fn eq() {
         assert_eq!(Ifs::empty(), Ifs::empty());
@@ -82,6 +82,10 @@ pub enum SpanRole<'a> {
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub struct Span<'a> {
     /// Range of bytes in the source code
+    ///
+    /// The range may span multiple lines. In that case, the rendered report
+    /// marks the start of the range with a caret on the first line and draws
+    /// a continuation line down to the end of the range, as rustc does.
     pub range: Range<usize>,
     /// Type and label of this span
     pub role: SpanRole<'a>,
@@ -213,6 +217,12 @@ pub struct Report<'a> {
     pub id: Option<Cow<'a, str>>,
     /// Main caption of this report
     pub title: Cow<'a, str>,
+    /// Optional stable identifier of the message template used for the title
+    ///
+    /// Unlike [`id`](Self::id), this identifier is not included in the
+    /// rendered report. It allows a [`MessageCatalog`](crate::io::MessageCatalog)
+    /// to replace the title with a translated message.
+    pub message_id: Option<Cow<'a, str>>,
     /// Source code fragments annotated with additional information
     pub snippets: Vec<Snippet<'a>>,
     /// Additional message without associated source code
@@ -262,6 +272,23 @@ pub fn add_span<'a>(code: &'a super::Code, span: Span<'a>, snippets: &mut Vec<Sn
     snippet_for_code(snippets, code).spans.push(span);
 }
 
+/// Adds a supplementary span annotating the given location.
+///
+/// This is a convenience function that creates a [`Span`] with the
+/// [`Supplementary`](SpanRole::Supplementary) role from the location and
+/// label, and adds it to the vector by calling [`add_span`]. It is typically
+/// used to point at a location related to the main cause of a problem, such
+/// as where an unclosed construct was opened.
+pub fn add_supplementary_span<'a>(
+    location: &'a Location,
+    label: Cow<'a, str>,
+    snippets: &mut Vec<Snippet<'a>>,
+) {
+    let range = location.byte_range();
+    let role = SpanRole::Supplementary { label };
+    add_span(&location.code, Span { range, role }, snippets);
+}
+
 #[test]
 fn test_add_span_with_matching_code() {
     let code = Rc::new(super::Code {
@@ -347,61 +374,57 @@ impl super::Source {
             | Other { .. } => (),
 
             CommandSubst { original } => {
-                let range = original.byte_range();
-                let role = SpanRole::Supplementary {
-                    label: "command substitution appeared here".into(),
-                };
-                add_span(&original.code, Span { range, role }, snippets);
+                add_supplementary_span(
+                    original,
+                    "command substitution appeared here".into(),
+                    snippets,
+                );
             }
 
             Arith { original } => {
-                let range = original.byte_range();
-                let role = SpanRole::Supplementary {
-                    label: "arithmetic expansion appeared here".into(),
-                };
-                add_span(&original.code, Span { range, role }, snippets);
+                add_supplementary_span(
+                    original,
+                    "arithmetic expansion appeared here".into(),
+                    snippets,
+                );
             }
 
             Eval { original } => {
-                let range = original.byte_range();
-                let role = SpanRole::Supplementary {
-                    label: "command passed to the eval built-in here".into(),
-                };
-                add_span(&original.code, Span { range, role }, snippets);
+                add_supplementary_span(
+                    original,
+                    "command passed to the eval built-in here".into(),
+                    snippets,
+                );
             }
 
             DotScript { name, origin } => {
-                let range = origin.byte_range();
-                let role = SpanRole::Supplementary {
-                    label: format!("script `{name}` was sourced here").into(),
-                };
-                add_span(&origin.code, Span { range, role }, snippets);
+                add_supplementary_span(
+                    origin,
+                    format!("script `{name}` was sourced here").into(),
+                    snippets,
+                );
             }
 
             Trap { origin, .. } => {
-                let range = origin.byte_range();
-                let role = SpanRole::Supplementary {
-                    label: "trap was set here".into(),
-                };
-                add_span(&origin.code, Span { range, role }, snippets);
+                add_supplementary_span(origin, "trap was set here".into(), snippets);
             }
 
             Alias { original, alias } => {
                 // Where the alias was substituted
-                let range = original.byte_range();
-                let role = SpanRole::Supplementary {
-                    label: format!("alias `{}` was substituted here", alias.name).into(),
-                };
-                add_span(&original.code, Span { range, role }, snippets);
+                add_supplementary_span(
+                    original,
+                    format!("alias `{}` was substituted here", alias.name).into(),
+                    snippets,
+                );
                 // Recurse into the source of the substituted code
                 original.code.source.extend_with_context(snippets);
 
                 // Where the alias was defined
-                let range = alias.origin.byte_range();
-                let role = SpanRole::Supplementary {
-                    label: format!("alias `{}` was defined here", alias.name).into(),
-                };
-                add_span(&alias.origin.code, Span { range, role }, snippets);
+                add_supplementary_span(
+                    &alias.origin,
+                    format!("alias `{}` was defined here", alias.name).into(),
+                    snippets,
+                );
                 // Recurse into the source of the alias definition
                 alias.origin.code.source.extend_with_context(snippets);
             }
@@ -517,3 +540,35 @@ mod annotate_snippets_support {
         }
     }
 }
+
+#[test]
+fn test_rendering_multi_line_span() {
+    let code = Rc::new(super::Code {
+        value: std::cell::RefCell::new("while true\ndo echo\n".to_string()),
+        start_line_number: std::num::NonZero::new(1).unwrap(),
+        source: Rc::new(super::Source::Stdin),
+    });
+    let span = Span {
+        range: 6..13,
+        role: SpanRole::Primary {
+            label: "the loop".into(),
+        },
+    };
+    let mut report = Report::new();
+    report.r#type = ReportType::Error;
+    report.title = "the `while` loop is missing its `do` clause".into();
+    report.snippets = vec![Snippet::with_code_and_spans(&code, vec![span])];
+
+    let result =
+        annotate_snippets::Renderer::plain().render(&[annotate_snippets::Group::from(&report)]);
+    assert_eq!(
+        result,
+        "error: the `while` loop is missing its `do` clause
+ --> <stdin>:1:7
+  |
+1 |   while true
+  |  _______^
+2 | | do echo
+  | |__^ the loop"
+    );
+}
@@ -32,6 +32,7 @@
 
 use crate::Env;
 use crate::semantics::Field;
+use crate::source::Location;
 use std::ops::Deref;
 use std::ops::DerefMut;
 
@@ -52,6 +53,22 @@ pub struct Builtin {
     pub is_special: bool,
 }
 
+/// Information about the currently executing function
+///
+/// An instance of `Function` wrapped in a [`Frame::Function`] is pushed to the
+/// stack when executing a function.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Function {
+    /// Name of the function
+    ///
+    /// The origin of the field is the location of the command word that
+    /// invoked the function.
+    pub name: Field,
+
+    /// Location of the function definition
+    pub definition: Location,
+}
+
 /// Element of runtime execution context stack
 #[derive(Clone, Debug, Eq, PartialEq)]
 #[non_exhaustive]
@@ -77,7 +94,9 @@ pub enum Frame {
     /// Trap
     Trap(crate::trap::Condition),
 
-    // TODO function
+    /// Function
+    Function(Function),
+
     /// File executed during shell startup
     InitFile,
 }
@@ -88,6 +107,12 @@ impl From<Builtin> for Frame {
     }
 }
 
+impl From<Function> for Frame {
+    fn from(function: Function) -> Self {
+        Frame::Function(function)
+    }
+}
+
 impl From<crate::trap::Condition> for Frame {
     fn from(condition: crate::trap::Condition) -> Self {
         Frame::Trap(condition)
@@ -162,7 +187,7 @@ impl Stack {
     pub fn loop_count(&self, max_count: usize) -> usize {
         fn retains_context(frame: &Frame) -> bool {
             match frame {
-                Frame::Loop | Frame::Condition | Frame::Builtin(_) => true,
+                Frame::Loop | Frame::Condition | Frame::Builtin(_) | Frame::Function(_) => true,
                 Frame::Subshell | Frame::DotScript | Frame::Trap(_) | Frame::InitFile => false,
             }
         }
@@ -184,6 +209,17 @@ impl Stack {
             _ => None,
         })
     }
+
+    /// Returns an iterator over the functions in the stack.
+    ///
+    /// The iterator yields the innermost (most recently called) function
+    /// first.
+    pub fn functions(&self) -> impl Iterator<Item = &Function> {
+        self.inner.iter().rev().filter_map(|frame| match frame {
+            Frame::Function(function) => Some(function),
+            _ => None,
+        })
+    }
 }
 
 /// When the guard is dropped, the stack frame that was pushed when creating the
@@ -412,4 +448,24 @@ mod tests {
         let stack = stack.push(Frame::Builtin(builtin.clone()));
         assert_eq!(stack.current_builtin(), Some(&builtin));
     }
+
+    #[test]
+    fn functions() {
+        let mut stack = Stack::default();
+        assert_eq!(stack.functions().next(), None);
+
+        let outer = Function {
+            name: Field::dummy("outer"),
+            definition: Location::dummy("outer definition"),
+        };
+        let mut stack = stack.push(Frame::Function(outer.clone()));
+        let mut stack = stack.push(Frame::Loop);
+        let inner = Function {
+            name: Field::dummy("inner"),
+            definition: Location::dummy("inner definition"),
+        };
+        let stack = stack.push(Frame::Function(inner.clone()));
+        let functions = stack.functions().collect::<Vec<_>>();
+        assert_eq!(functions, [&inner, &outer]);
+    }
 }
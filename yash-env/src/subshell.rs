@@ -321,7 +321,7 @@ mod tests {
     use crate::source::Location;
     use crate::stack::Frame;
     use crate::system::r#virtual::{Inode, SystemState, VirtualSystem};
-    use crate::system::r#virtual::{SIGCHLD, SIGINT, SIGQUIT, SIGTSTP, SIGTTIN, SIGTTOU};
+    use crate::system::r#virtual::{SIGCHLD, SIGINT, SIGQUIT, SIGSTOP, SIGTSTP, SIGTTIN, SIGTTOU};
     use crate::system::{Concurrent, Disposition};
     use crate::test_helper::in_virtual_system;
     use crate::trap::Action;
@@ -652,7 +652,30 @@ mod tests {
     }
 
     // TODO wait_for_foreground_job_to_be_signaled
-    // TODO wait_for_foreground_job_to_be_stopped
+
+    #[test]
+    fn wait_for_foreground_job_to_be_stopped() {
+        in_virtual_system(|mut env, state| async move {
+            env.options.set(Monitor, On);
+            stub_tty(&state);
+
+            let subshell = Subshell::new(
+                |env: &mut Env<Rc<Concurrent<VirtualSystem>>>, _job_control| {
+                    Box::pin(async {
+                        let pid = env.system.getpid();
+                        env.system.kill(pid, Some(SIGSTOP)).await.unwrap();
+                    })
+                },
+            )
+            .job_control(JobControl::Foreground);
+            let (pid, process_result) = subshell.start_and_wait(&mut env).await.unwrap();
+            assert_eq!(process_result, ProcessResult::Stopped(SIGSTOP));
+            let process_state = state.borrow().processes[&pid].state();
+            assert_eq!(process_state, ProcessState::stopped(SIGSTOP));
+            // The shell should have regained the terminal.
+            assert_eq!(state.borrow().foreground, Some(env.main_pgid));
+        });
+    }
 
     #[test]
     fn sigint_sigquit_not_ignored_by_default() {
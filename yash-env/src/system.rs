@@ -22,6 +22,8 @@
 //!
 //! - [`CaughtSignals`]: Declares the `caught_signals` method for retrieving
 //!   caught signals.
+//! - [`CheckAccess`]: Declares the `is_readable_file` and `is_writable_file`
+//!   methods for checking if a file is readable or writable.
 //! - [`Chdir`]: Declares the `chdir` method for changing the current
 //!   working directory.
 //! - [`Clock`]: Declares the `now` method for getting the current time.
@@ -51,6 +53,8 @@
 //!   associated with a terminal device.
 //! - [`Open`]: Declares the `open` and other methods for opening files.
 //! - [`Pipe`]: Declares the `pipe` method for creating pipes.
+//! - [`Poll`]: Declares the `poll` method for waiting on multiple file
+//!   descriptors and signals without the `FD_SETSIZE` limit.
 //! - [`Read`]: Declares the `read` method for reading from file descriptors.
 //! - [`Seek`]: Declares the `lseek` method for seeking within file
 //!   descriptors.
@@ -70,6 +74,8 @@
 //! - [`TcSetPgrp`]: Declares the `tcsetpgrp` method for setting the
 //!   foreground process group ID of a terminal.
 //! - [`Times`]: Declares the `times` method for getting CPU times.
+//! - [`TtySize`]: Declares the `tty_size` method for getting the window size
+//!   of a terminal.
 //! - [`Umask`]: Declares the `umask` method for setting the file mode
 //!   creation mask.
 //! - [`Wait`]: Declares the `wait` method for waiting for child processes.
@@ -90,6 +96,29 @@
 //! Additionally, [`Concurrent`] is a wrapper that extends the interface with
 //! asynchronous methods for concurrency. (See the [`concurrency`] module.)
 //!
+//! # Custom system implementations
+//!
+//! The shell is not tied to the two implementors above. [`Env`](crate::Env)
+//! is generic over the system type, so you can sandbox the shell completely
+//! by running it on your own system implementation, e.g., one that forwards
+//! operations to a restricted or non-POSIX backend. To do so:
+//!
+//! 1. Define a type that implements the base traits listed above (those
+//!    required by the shell features you use) as well as
+//!    [`RunLoop`](concurrency::RunLoop). Methods that your backend cannot
+//!    support can simply fail with an appropriate [`Errno`] such as `ENOSYS`.
+//! 2. Wrap an instance of the type in [`Concurrent`] and [`Rc`](std::rc::Rc).
+//!    `Rc<Concurrent<_>>` implements the additional traits required for
+//!    asynchronous I/O, signal handling, and subshells on top of the base
+//!    traits.
+//! 3. Construct an environment with
+//!    [`Env::with_system`](crate::Env::with_system).
+//!
+//! The `yash_semantics::Runtime` trait summarizes the traits needed to run
+//! shell scripts. Every access from the shell to the underlying system goes
+//! through the traits implemented by the system type, so the type is the
+//! single point of injection.
+//!
 //! Some methods of these traits return [futures](std::future::Future), not
 //! because the underlying system calls are asynchronous, but to allow
 //! `VirtualSystem` to simulate blocking behavior and run virtual processes
@@ -102,6 +131,7 @@ mod errno;
 mod file_system;
 mod future;
 mod io;
+mod poll;
 mod process;
 #[cfg(unix)]
 pub mod real;
@@ -119,12 +149,13 @@ pub use self::errno::Errno;
 pub use self::errno::RawErrno;
 pub use self::errno::Result;
 pub use self::file_system::{
-    AT_FDCWD, Chdir, Dir, DirEntry, FileType, Fstat, GetCwd, IsExecutableFile, Mode, OfdAccess,
-    Open, OpenFlag, RawMode, Seek, Stat, Umask,
+    AT_FDCWD, Chdir, CheckAccess, Dir, DirEntry, FileType, Fstat, GetCwd, IsExecutableFile, Mode,
+    OfdAccess, Open, OpenFlag, RawMode, Seek, Stat, Umask,
 };
 #[allow(deprecated, reason = "for backward compatible API")]
 pub use self::future::FlexFuture;
 pub use self::io::{Close, Dup, Fcntl, FdFlag, Pipe, Read, Write};
+pub use self::poll::{Poll, PollFd};
 pub use self::process::{Exec, Exit, Fork, GetPid, SetPgid, Wait};
 #[cfg(all(doc, unix))]
 use self::real::RealSystem;
@@ -135,7 +166,7 @@ pub use self::signal::{
     Sigset,
 };
 pub use self::sysconf::{ShellPath, Sysconf};
-pub use self::terminal::{Isatty, TcGetPgrp, TcSetPgrp};
+pub use self::terminal::{Isatty, TcGetPgrp, TcSetPgrp, TtySize, WindowSize};
 pub use self::time::{Clock, CpuTimes, Times};
 pub use self::user::{GetPw, GetUid, Gid, RawGid, RawUid, Uid};
 #[cfg(doc)]
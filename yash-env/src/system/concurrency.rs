@@ -20,7 +20,7 @@
 //! systems that enables concurrent execution of multiple possibly blocking I/O
 //! tasks on a single thread.
 
-use super::{CaughtSignals, Clock, Errno, Fcntl, FdSet, Fork, Read, Result, Sigmask, Write};
+use super::{CaughtSignals, Clock, Errno, Fcntl, Fork, PollFd, Read, Result, Sigmask, Write};
 use crate::io::Fd;
 use crate::job::Pid;
 use crate::waker::{ScheduledWakerQueue, WakerSet};
@@ -39,7 +39,7 @@ use std::time::{Duration, Instant};
 /// This struct is used as a wrapper for systems for enabling concurrent
 /// execution of multiple possibly blocking I/O tasks on a single thread. The
 /// inner system is expected to implement the [`Read`], [`Write`], and
-/// [`super::Select`] traits with synchronous (blocking) behavior. This struct leaves
+/// [`super::Poll`] traits with synchronous (blocking) behavior. This struct leaves
 /// [`Future`]s returned by I/O methods pending until the I/O operation is ready
 /// to avoid blocking the entire process. This allows you to start multiple I/O
 /// tasks and wait for them to complete concurrently on a single thread. This
@@ -136,6 +136,10 @@ struct State<S> {
     /// so they can interrupt `select`. The value is `None` until the signal
     /// mask is first updated by `update_sigmask_and_select_mask`.
     select_mask: Option<S>,
+    /// Resolution within which timeouts are coalesced
+    ///
+    /// See [`Concurrent::set_timer_resolution`].
+    timer_resolution: Duration,
 }
 
 impl<S: Clone> State<S> {
@@ -151,6 +155,8 @@ impl<S: Clone> State<S> {
             // as the parent process, so the child should also have the same
             // mask for `select` as the parent process.
             select_mask: self.select_mask.clone(),
+            // The timer resolution is a configuration rather than a state.
+            timer_resolution: self.timer_resolution,
 
             // The other fields manage tasks and wakers for the current process,
             // so they should be reset for the child process.
@@ -163,6 +169,47 @@ impl<S: Clone> State<S> {
     }
 }
 
+impl<S> State<S> {
+    /// Removes dead wakers for file descriptors and timeouts.
+    ///
+    /// File descriptors whose waiting tasks are all dead are removed from the
+    /// maps so that they are not passed to the `poll` call.
+    fn remove_dead_wakers(&mut self) {
+        fn remove_dead(task_map: &mut HashMap<Fd, WakerSet>) {
+            task_map.retain(|_, wakers| {
+                wakers.remove_dead();
+                !wakers.is_empty()
+            });
+        }
+        remove_dead(&mut self.reads);
+        remove_dead(&mut self.writes);
+        self.timeouts.trim_to_next_wake_time();
+    }
+
+    /// Tests whether there is no task waiting for any event.
+    ///
+    /// This method should be called after
+    /// [`remove_dead_wakers`](Self::remove_dead_wakers) because dead wakers
+    /// are counted as waiting tasks.
+    #[must_use]
+    fn is_idle(&self) -> bool {
+        self.reads.is_empty()
+            && self.writes.is_empty()
+            && self.timeouts.is_empty()
+            && self.signals.strong_count() == 0
+    }
+}
+
+/// Result of [`Concurrent::try_select`]
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+#[must_use]
+pub enum SelectResult {
+    /// The `poll` call has been performed and the ready tasks have been woken.
+    Selected,
+    /// There was no task waiting for any event, so `poll` was not called.
+    NothingToWait,
+}
+
 impl<S: Sigmask> Concurrent<S> {
     /// Creates a new `Concurrent` system that wraps the given inner system.
     #[must_use]
@@ -170,6 +217,29 @@ impl<S: Sigmask> Concurrent<S> {
         let state = Default::default();
         Self { inner, state }
     }
+
+    /// Returns the resolution within which timeouts are coalesced.
+    ///
+    /// See [`set_timer_resolution`](Self::set_timer_resolution).
+    #[must_use]
+    pub fn timer_resolution(&self) -> Duration {
+        self.state.borrow().timer_resolution
+    }
+
+    /// Sets the resolution within which timeouts are coalesced.
+    ///
+    /// By default, the resolution is zero, and [`select`](Select::select)
+    /// waits until the earliest deadline of the pending
+    /// [`sleep`](Sleep::sleep) tasks. With a non-zero resolution, `select`
+    /// instead waits until the latest deadline that is within the resolution
+    /// after the earliest one, so that the tasks whose deadlines fall in the
+    /// window are woken together by a single wakeup. This reduces the number
+    /// of wakeups when there are many concurrent timers, at the cost of
+    /// delaying some tasks by up to the resolution. No task is woken before
+    /// its deadline.
+    pub fn set_timer_resolution(&self, resolution: Duration) {
+        self.state.borrow_mut().timer_resolution = resolution;
+    }
 }
 
 /// Reads from a file descriptor concurrently.
@@ -414,20 +484,21 @@ impl<S: Sigmask> WaitForSignals for Concurrent<S> {
 
 /// Trait for peeking and waiting for pending concurrent events
 ///
-/// This trait is different from the [`super::Select`] trait implemented by the
-/// inner system. The inner system's `select` method is expected to perform a
-/// single `select` system call with the specified file descriptors and timeout,
-/// and return the result of the system call. In contrast, the methods of this
-/// trait are designed to be used in the main loop of the process to handle all
-/// pending tasks and events. The `peek` method performs a `select` system call
-/// with a zero timeout to check for any ready events without blocking, while
-/// the `select` method performs a `select` system call with the appropriate
+/// This trait is different from the [`super::Select`] and [`super::Poll`]
+/// traits implemented by the inner system. The inner system's `poll` method is
+/// expected to perform a single `poll` system call with the specified file
+/// descriptors and timeout, and return the result of the system call. In
+/// contrast, the methods of this trait are designed to be used in the main loop
+/// of the process to handle all pending tasks and events. The `peek` method
+/// performs a `poll` system call with a zero timeout to check for any ready
+/// events without blocking, while the `select` method performs a `poll` system
+/// call with the appropriate
 /// file descriptors and timeout based on the current state of pending tasks,
 /// and returns a future that completes when any event becomes ready.
 pub trait Select {
     /// Peeks for any ready events without blocking.
     ///
-    /// This method performs a `select` system call with the file descriptors
+    /// This method performs a `poll` system call with the file descriptors
     /// and timeout of pending tasks, and wakes the tasks whose events are
     /// ready. This method is similar to [`select`](Self::select), but it
     /// does not block and returns immediately.
@@ -435,7 +506,7 @@ pub trait Select {
 
     /// Waits for any of the pending tasks to become ready.
     ///
-    /// This method performs a `select` system call with the file descriptors
+    /// This method performs a `poll` system call with the file descriptors
     /// and timeout of pending tasks, and wakes the tasks whose events are
     /// ready. This method should be called in the main loop of the process to
     /// ensure that tasks can make progress. In a typical use case, the main
@@ -453,9 +524,18 @@ pub trait Select {
     /// The [`RunLoop`] trait declares an interface for implementing such a main
     /// loop.
     ///
+    /// If the `poll` call fails with `EBADF`, this method probes each file
+    /// descriptor individually to find the ones that are not open, and wakes
+    /// only the tasks waiting for those (or for file descriptors that have
+    /// become ready). Tasks waiting for valid file descriptors remain pending.
+    ///
+    /// If there is no task waiting for any event, the future returned by this
+    /// method never completes unless the process is terminated by a signal.
+    /// Use [`Concurrent::try_select`] to detect this case.
+    ///
     /// The future returned by this method will be pending if and only if the
     /// future returned by the inner system's
-    /// [`select`](super::Select::select) method is pending.
+    /// [`poll`](super::Poll::poll) method is pending.
     fn select(&self) -> impl Future<Output = ()> + use<'_, Self>;
 }
 
@@ -476,7 +556,7 @@ where
 
 impl<S> Select for Concurrent<S>
 where
-    S: CaughtSignals + Clock + super::Select + Sigmask,
+    S: CaughtSignals + Clock + super::Poll + Sigmask,
 {
     fn peek(&self) {
         let select = pin!(self.select_impl(true));
@@ -491,48 +571,108 @@ where
 
 impl<S> Concurrent<S>
 where
-    S: CaughtSignals + Clock + super::Select + Sigmask,
+    S: CaughtSignals + Clock + super::Poll + Sigmask,
 {
+    /// Waits for any of the pending tasks to become ready, unless there is
+    /// nothing to wait for.
+    ///
+    /// This method is similar to [`select`](Select::select), but it first
+    /// checks whether any task is waiting for a file descriptor, a timeout,
+    /// or a signal. Tasks that have been dropped or already woken do not
+    /// count. If no task is waiting, this method returns
+    /// [`SelectResult::NothingToWait`] immediately instead of performing a
+    /// `poll` call that would never return. Otherwise, this method performs
+    /// `select` and returns [`SelectResult::Selected`].
+    pub async fn try_select(&self) -> SelectResult {
+        {
+            let mut state = self.state.borrow_mut();
+            state.remove_dead_wakers();
+            if state.is_idle() {
+                return SelectResult::NothingToWait;
+            }
+        }
+        self.select_impl(false).await;
+        SelectResult::Selected
+    }
+
     #[allow(
         clippy::await_holding_refcell_ref,
         reason = "see the comment in the method body"
     )]
     async fn select_impl(&self, peek: bool) {
         // In this method, we keep the borrow of `state` across the `await` point. This is
-        // intentional because the real `select` call blocks the entire process, so there cannot
-        // be any other task that modifies the state while we are waiting for the `select` call to
+        // intentional because the real `poll` call blocks the entire process, so there cannot
+        // be any other task that modifies the state while we are waiting for the `poll` call to
         // return.
         let mut state = self.state.borrow_mut();
 
-        // Prepare parameters for the `select` call based on the current state
-        let mut readers = S::FdSet::from_fds(state.reads.keys().cloned());
-        let mut writers = S::FdSet::from_fds(state.writes.keys().cloned());
+        // Don't poll FDs that no live task is waiting for
+        state.remove_dead_wakers();
+
+        // Prepare parameters for the `poll` call based on the current state
+        let readers = state.reads.keys().copied().map(PollFd::reader);
+        let writers = state.writes.keys().copied().map(PollFd::writer);
+        let mut fds = readers.chain(writers).collect::<Vec<_>>();
         let timeout = if peek {
             Some(Duration::ZERO)
         } else {
             state
                 .timeouts
-                .next_wake_time()
+                .coalesced_wake_time(state.timer_resolution)
                 .map(|target| target.saturating_duration_since(self.inner.now()))
         };
         let signal_mask = (state.signals.strong_count() > 0)
             .then(|| state.select_mask.as_ref())
             .flatten();
 
-        // Perform the `select` call
-        let result = self
-            .inner
-            .select(&mut readers, &mut writers, timeout, signal_mask)
-            .await;
+        // Perform the `poll` call
+        let result = self.inner.poll(&mut fds, timeout, signal_mask).await;
 
         // Wake eligible tasks
-        if result != Err(Errno::EINTR) {
-            // If `select` succeeded, `readers` and `writers` contain the lists of ready FDs. In
-            // case of error, `select` leaves the input lists unmodified (which is required by
-            // POSIX), but we don't know which FD caused the error, so we conservatively wake all
-            // tasks waiting for any FD.
-            wake_tasks_for_ready_fds(&mut state.reads, &readers);
-            wake_tasks_for_ready_fds(&mut state.writes, &writers);
+        match result {
+            Ok(_) => {
+                for fd in &fds {
+                    if fd.ready_for_reading {
+                        wake_tasks_for_ready_fd(&mut state.reads, fd.fd);
+                    }
+                    if fd.ready_for_writing {
+                        wake_tasks_for_ready_fd(&mut state.writes, fd.fd);
+                    }
+                }
+            }
+            Err(Errno::EINTR) => {}
+            Err(Errno::EBADF) => {
+                // Some FD is not open, but we don't know which. Probe each FD
+                // individually and wake only the tasks waiting for the FDs
+                // that are not open or have become ready in the meantime.
+                for fd in &fds {
+                    let mut probe = [PollFd {
+                        ready_for_reading: false,
+                        ready_for_writing: false,
+                        ..*fd
+                    }];
+                    let failed = self
+                        .inner
+                        .poll(&mut probe, Some(Duration::ZERO), None)
+                        .await
+                        .is_err();
+                    let [probe] = probe;
+                    if fd.reading && (failed || probe.ready_for_reading) {
+                        wake_tasks_for_ready_fd(&mut state.reads, fd.fd);
+                    }
+                    if fd.writing && (failed || probe.ready_for_writing) {
+                        wake_tasks_for_ready_fd(&mut state.writes, fd.fd);
+                    }
+                }
+            }
+            Err(_) => {
+                // We don't know which FD caused the error, so we conservatively
+                // wake all tasks waiting for any FD.
+                for fd in &fds {
+                    wake_tasks_for_ready_fd(&mut state.reads, fd.fd);
+                    wake_tasks_for_ready_fd(&mut state.writes, fd.fd);
+                }
+            }
         }
         if !state.timeouts.is_empty() {
             state.timeouts.wake(self.inner.now());
@@ -552,15 +692,10 @@ where
     }
 }
 
-fn wake_tasks_for_ready_fds<S: FdSet>(task_map: &mut HashMap<Fd, WakerSet>, ready_fds: &S) {
-    task_map.retain(|&fd, wakers| {
-        if ready_fds.contains(fd) {
-            wakers.wake_all();
-            false
-        } else {
-            true
-        }
-    })
+fn wake_tasks_for_ready_fd(task_map: &mut HashMap<Fd, WakerSet>, ready_fd: Fd) {
+    if let Some(mut wakers) = task_map.remove(&ready_fd) {
+        wakers.wake_all();
+    }
 }
 
 /// Guard for temporarily setting a file descriptor to non-blocking mode and
@@ -713,7 +848,8 @@ pub use rw_all::WriteAll;
 #[cfg(test)]
 mod tests {
     use super::super::{
-        Close as _, Disposition, Mode, OfdAccess, Open as _, OpenFlag, Pipe as _, SendSignal as _,
+        Close as _, Disposition, Dup as _, Mode, OfdAccess, Open as _, OpenFlag, Pipe as _,
+        SendSignal as _,
     };
     use super::*;
     use crate::system::r#virtual::{PIPE_SIZE, SIGCHLD, SIGINT, SIGUSR2, VirtualSystem};
@@ -743,6 +879,64 @@ mod tests {
         assert!(!wake_flag.is_woken());
     }
 
+    #[test]
+    fn try_select_with_only_dead_wakers_returns_nothing_to_wait() {
+        let system = Rc::new(Concurrent::new(VirtualSystem::new()));
+        let now = Instant::now();
+        system.inner.state.borrow_mut().now = Some(now);
+        let (_read_fd, write_fd) = system.pipe().unwrap();
+        // Fill the pipe buffer to make the next write pending
+        system
+            .write(write_fd, &[0; PIPE_SIZE])
+            .now_or_never()
+            .unwrap()
+            .unwrap();
+        let (read_fd2, _write_fd2) = system.pipe().unwrap();
+
+        let mut context = Context::from_waker(Waker::noop());
+        {
+            // Register tasks that are dropped without being woken
+            let mut buffer = [0; 4];
+            let mut read = pin!(system.read(read_fd2, &mut buffer));
+            let mut write = pin!(system.write(write_fd, &[1]));
+            let mut sleep = pin!(system.sleep(Duration::from_secs(1)));
+            assert_eq!(read.as_mut().poll(&mut context), Pending);
+            assert_eq!(write.as_mut().poll(&mut context), Pending);
+            assert_eq!(sleep.as_mut().poll(&mut context), Pending);
+        }
+
+        let result = system.try_select().now_or_never();
+        assert_eq!(result, Some(SelectResult::NothingToWait));
+    }
+
+    #[test]
+    fn try_select_with_live_waker_performs_select() {
+        let system = Rc::new(Concurrent::new(VirtualSystem::new()));
+        let (read_fd, write_fd) = system.pipe().unwrap();
+
+        let mut buffer = [0; 4];
+        let mut read = pin!(system.read(read_fd, &mut buffer));
+        let wake_flag = Arc::new(WakeFlag::new());
+        let waker = Waker::from(wake_flag.clone());
+        let mut context = Context::from_waker(&waker);
+        assert_eq!(read.as_mut().poll(&mut context), Pending);
+
+        let mut select = pin!(system.try_select());
+        let mut null_context = Context::from_waker(Waker::noop());
+        assert_eq!(select.as_mut().poll(&mut null_context), Pending);
+
+        system
+            .write(write_fd, &[1])
+            .now_or_never()
+            .unwrap()
+            .unwrap();
+        assert_eq!(
+            select.as_mut().poll(&mut null_context),
+            Ready(SelectResult::Selected),
+        );
+        assert!(wake_flag.is_woken());
+    }
+
     #[test]
     fn regular_file_read_completes_immediately() {
         let system = Rc::new(Concurrent::new(VirtualSystem::new()));
@@ -943,6 +1137,35 @@ mod tests {
         assert!(wake_flag2.is_woken());
     }
 
+    #[test]
+    fn select_wakes_read_task_with_fd_above_fd_setsize() {
+        let system = Rc::new(Concurrent::new(VirtualSystem::new()));
+        let (read_fd, write_fd) = system.pipe().unwrap();
+        // Larger than any common value of FD_SETSIZE
+        let high_fd = system.dup2(read_fd, Fd(100_000)).unwrap();
+
+        let mut buffer = [0; 4];
+        let mut read = pin!(system.read(high_fd, &mut buffer));
+        let wake_flag = Arc::new(WakeFlag::new());
+        let waker = Waker::from(wake_flag.clone());
+        let mut context = Context::from_waker(&waker);
+        assert_eq!(read.as_mut().poll(&mut context), Pending);
+
+        let mut select = pin!(system.select());
+        let mut context2 = Context::from_waker(Waker::noop());
+        assert_eq!(select.as_mut().poll(&mut context2), Pending);
+        assert!(!wake_flag.is_woken());
+
+        system
+            .write(write_fd, &[1, 2, 3, 4])
+            .now_or_never()
+            .unwrap()
+            .unwrap();
+        assert_eq!(select.as_mut().poll(&mut context2), Ready(()));
+        assert!(wake_flag.is_woken());
+        assert_eq!(read.as_mut().poll(&mut context), Ready(Ok(4)));
+    }
+
     #[test]
     fn select_wakes_only_write_tasks_with_ready_fd() {
         let system = Rc::new(Concurrent::new(VirtualSystem::new()));
@@ -1053,6 +1276,62 @@ mod tests {
         assert!(!wake_flag.is_woken());
     }
 
+    #[test]
+    fn select_coalesces_timeouts_within_timer_resolution() {
+        let system = VirtualSystem::new();
+        let state = system.state.clone();
+        let now = Instant::now();
+        state.borrow_mut().now = Some(now);
+        let system = Concurrent::new(system);
+        system.set_timer_resolution(Duration::from_millis(500));
+        assert_eq!(system.timer_resolution(), Duration::from_millis(500));
+
+        let mut sleep1 = pin!(system.sleep(Duration::from_millis(1000)));
+        let mut sleep2 = pin!(system.sleep(Duration::from_millis(1200)));
+        let mut sleep3 = pin!(system.sleep(Duration::from_millis(1800)));
+
+        let flags: [_; 3] = std::array::from_fn(|_| Arc::new(WakeFlag::new()));
+        let wakers = flags.each_ref().map(|flag| Waker::from(flag.clone()));
+        let mut contexts = wakers.each_ref().map(Context::from_waker);
+        assert_eq!(sleep1.as_mut().poll(&mut contexts[0]), Pending);
+        assert_eq!(sleep2.as_mut().poll(&mut contexts[1]), Pending);
+        assert_eq!(sleep3.as_mut().poll(&mut contexts[2]), Pending);
+
+        let mut select = pin!(system.select());
+        let mut select_context = Context::from_waker(Waker::noop());
+        assert_eq!(select.as_mut().poll(&mut select_context), Pending);
+
+        // The first deadline has passed, but the select waits for the second
+        state
+            .borrow_mut()
+            .advance_time(now + Duration::from_millis(1000));
+        assert_eq!(select.as_mut().poll(&mut select_context), Pending);
+        assert!(!flags[0].is_woken());
+
+        // Now the first two sleeps are woken together, but not the third
+        state
+            .borrow_mut()
+            .advance_time(now + Duration::from_millis(1200));
+        assert_eq!(select.as_mut().poll(&mut select_context), Ready(()));
+        assert!(flags[0].is_woken());
+        assert!(flags[1].is_woken());
+        assert!(!flags[2].is_woken());
+
+        // The third sleep is not woken before its deadline
+        let mut select = pin!(system.select());
+        assert_eq!(select.as_mut().poll(&mut select_context), Pending);
+        state
+            .borrow_mut()
+            .advance_time(now + Duration::from_millis(1799));
+        assert_eq!(select.as_mut().poll(&mut select_context), Pending);
+        assert!(!flags[2].is_woken());
+        state
+            .borrow_mut()
+            .advance_time(now + Duration::from_millis(1800));
+        assert_eq!(select.as_mut().poll(&mut select_context), Ready(()));
+        assert!(flags[2].is_woken());
+    }
+
     #[test]
     fn signal_wait_completes_on_signal() {
         let system = Rc::new(Concurrent::new(VirtualSystem::new()));
@@ -1275,7 +1554,7 @@ mod tests {
     }
 
     #[test]
-    fn select_wakes_all_reads_and_writes_on_ebadf() {
+    fn select_wakes_only_tasks_for_closed_fd_on_ebadf() {
         let system = Rc::new(Concurrent::new(VirtualSystem::new()));
         let (read_fd1, _write_fd1) = system.pipe().unwrap();
         let (_read_fd2, write_fd2) = system.pipe().unwrap();
@@ -1318,10 +1597,68 @@ mod tests {
         let mut select_context = Context::from_waker(&select_waker);
         assert_eq!(select.as_mut().poll(&mut select_context), Ready(()));
         assert!(wake_flag1.is_woken());
-        assert!(wake_flag2.is_woken());
+        assert!(!wake_flag2.is_woken());
         assert!(!wake_select.is_woken());
     }
 
+    #[test]
+    fn select_probes_each_fd_on_ebadf() {
+        let system = Rc::new(Concurrent::new(VirtualSystem::new()));
+        let (closed_fd, _closed_write_fd) = system.pipe().unwrap();
+        let (read_fd1, write_fd1) = system.pipe().unwrap();
+        let (read_fd2, _write_fd2) = system.pipe().unwrap();
+        let (read_fd3, write_fd3) = system.pipe().unwrap();
+        let (_read_fd4, write_fd4) = system.pipe().unwrap();
+        // Fill the pipe buffer to make the next write pending
+        system
+            .write(write_fd4, &[0; PIPE_SIZE])
+            .now_or_never()
+            .unwrap()
+            .unwrap();
+
+        let mut buffer0 = [0; 4];
+        let mut buffer1 = [0; 4];
+        let mut buffer2 = [0; 4];
+        let mut buffer3 = [0; 4];
+        let mut read0 = pin!(system.read(closed_fd, &mut buffer0));
+        let mut read1 = pin!(system.read(read_fd1, &mut buffer1));
+        let mut read2 = pin!(system.read(read_fd2, &mut buffer2));
+        let mut read3 = pin!(system.read(read_fd3, &mut buffer3));
+        let mut write4 = pin!(system.write(write_fd4, &[1]));
+
+        let flags: [_; 5] = std::array::from_fn(|_| Arc::new(WakeFlag::new()));
+        let wakers = flags.each_ref().map(|flag| Waker::from(flag.clone()));
+        let mut contexts = wakers.each_ref().map(Context::from_waker);
+        assert_eq!(read0.as_mut().poll(&mut contexts[0]), Pending);
+        assert_eq!(read1.as_mut().poll(&mut contexts[1]), Pending);
+        assert_eq!(read2.as_mut().poll(&mut contexts[2]), Pending);
+        assert_eq!(read3.as_mut().poll(&mut contexts[3]), Pending);
+        assert_eq!(write4.as_mut().poll(&mut contexts[4]), Pending);
+
+        // Make one FD not open and another ready
+        system.close(closed_fd).unwrap();
+        system
+            .write(write_fd3, &[42])
+            .now_or_never()
+            .unwrap()
+            .unwrap();
+
+        system.peek();
+        assert!(flags[0].is_woken());
+        assert!(!flags[1].is_woken());
+        assert!(!flags[2].is_woken());
+        assert!(flags[3].is_woken());
+        assert!(!flags[4].is_woken());
+
+        // The tasks for the valid FDs are still registered and can be woken
+        // when the FDs become ready.
+        system.close(write_fd1).unwrap();
+        system.peek();
+        assert!(flags[1].is_woken());
+        assert!(!flags[2].is_woken());
+        assert!(!flags[4].is_woken());
+    }
+
     #[test]
     fn select_does_not_wake_reads_or_writes_on_eintr() {
         // Prepare a system and a pipe
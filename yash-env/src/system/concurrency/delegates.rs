@@ -19,10 +19,10 @@
 use super::super::c_string::IntoCStrArray;
 use super::super::resource::{LimitPair, Resource};
 use super::super::{
-    Chdir, Clock, Close, CpuTimes, Dir, Dup, Exec, Exit, Fcntl, FdFlag, Fstat, GetCwd, GetPid,
-    GetPw, GetRlimit, GetUid, Gid, IsExecutableFile, Isatty, Mode, OfdAccess, Open, OpenFlag, Pipe,
-    Result, Seek, SendSignal, SetPgid, SetRlimit, ShellPath, Sigmask, Signals, Sysconf, TcGetPgrp,
-    TcSetPgrp, Times, Uid, Umask, Wait, signal,
+    Chdir, CheckAccess, Clock, Close, CpuTimes, Dir, Dup, Exec, Exit, Fcntl, FdFlag, Fstat, GetCwd,
+    GetPid, GetPw, GetRlimit, GetUid, Gid, IsExecutableFile, Isatty, Mode, OfdAccess, Open,
+    OpenFlag, Pipe, Result, Seek, SendSignal, SetPgid, SetRlimit, ShellPath, Sigmask, Signals,
+    Sysconf, TcGetPgrp, TcSetPgrp, Times, TtySize, Uid, Umask, Wait, WindowSize, signal,
 };
 use super::Concurrent;
 use crate::io::Fd;
@@ -71,6 +71,20 @@ where
     }
 }
 
+impl<S> CheckAccess for Concurrent<S>
+where
+    S: CheckAccess + Sigmask,
+{
+    #[inline]
+    fn is_readable_file(&self, path: &CStr) -> bool {
+        self.inner.is_readable_file(path)
+    }
+    #[inline]
+    fn is_writable_file(&self, path: &CStr) -> bool {
+        self.inner.is_writable_file(path)
+    }
+}
+
 impl<S> Pipe for Concurrent<S>
 where
     S: Pipe + Sigmask,
@@ -374,6 +388,16 @@ where
     }
 }
 
+impl<S> TtySize for Concurrent<S>
+where
+    S: Sigmask + TtySize,
+{
+    #[inline]
+    fn tty_size(&self, fd: Fd) -> Result<WindowSize> {
+        self.inner.tty_size(fd)
+    }
+}
+
 impl<S> TcGetPgrp for Concurrent<S>
 where
     S: Sigmask + TcGetPgrp,
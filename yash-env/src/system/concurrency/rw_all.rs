@@ -47,6 +47,30 @@ pub trait ReadAll {
             Ok(buffer)
         }
     }
+
+    /// Reads from the file descriptor until EOF is reached or `limit` bytes
+    /// have been read, appending the data to the provided buffer.
+    ///
+    /// This method is similar to [`read_all_to`](Self::read_all_to), but stops
+    /// reading as soon as `limit` bytes have been appended to the buffer. The
+    /// rest of the data, if any, is left unread in the file descriptor.
+    ///
+    /// The default implementation calls `read_all_to` and then truncates the
+    /// appended data, so it does not stop reading early. Implementors should
+    /// override this method to avoid reading more than necessary.
+    fn read_up_to(
+        &self,
+        fd: Fd,
+        buffer: &mut Vec<u8>,
+        limit: usize,
+    ) -> impl Future<Output = Result<(), Errno>> {
+        async move {
+            let start = buffer.len();
+            let result = self.read_all_to(fd, buffer).await;
+            buffer.truncate(start.saturating_add(limit));
+            result
+        }
+    }
 }
 
 impl<S> ReadAll for Rc<S>
@@ -62,6 +86,16 @@ where
     fn read_all(&self, fd: Fd) -> impl Future<Output = Result<Vec<u8>, Errno>> {
         (self as &S).read_all(fd)
     }
+
+    #[inline]
+    fn read_up_to(
+        &self,
+        fd: Fd,
+        buffer: &mut Vec<u8>,
+        limit: usize,
+    ) -> impl Future<Output = Result<(), Errno>> {
+        (self as &S).read_up_to(fd, buffer, limit)
+    }
 }
 
 impl<S> ReadAll for Concurrent<S>
@@ -69,17 +103,32 @@ where
     S: Fcntl + Read + Sigmask,
 {
     async fn read_all_to(&self, fd: Fd, buffer: &mut Vec<u8>) -> Result<(), Errno> {
+        self.read_up_to(fd, buffer, usize::MAX).await
+    }
+
+    async fn read_up_to(&self, fd: Fd, buffer: &mut Vec<u8>, limit: usize) -> Result<(), Errno> {
         let this = TemporaryNonBlockingGuard::new(self, fd);
         let waker = LazyCell::default();
         let mut effective_length = buffer.len();
+        let end = effective_length.saturating_add(limit);
         loop {
+            if effective_length >= end {
+                buffer.truncate(effective_length);
+                return Ok(());
+            }
+
             // The `read` method requires an initialized buffer, so we reserve
             // additional capacity and fill it with zeros.
             let unused = buffer.capacity() - effective_length;
             buffer.reserve(0x400_usize.saturating_sub(unused));
             buffer.extend(repeat_n(0, buffer.capacity() - buffer.len()));
+            let read_end = buffer.len().min(end);
 
-            match this.inner.read(fd, &mut buffer[effective_length..]).await {
+            match this
+                .inner
+                .read(fd, &mut buffer[effective_length..read_end])
+                .await
+            {
                 Ok(0) => {
                     buffer.truncate(effective_length);
                     return Ok(());
@@ -285,4 +334,28 @@ mod tests {
         // (which was set to non-blocking before the write)
         assert_eq!(system.inner.get_and_set_nonblocking(fd, true), Ok(true));
     }
+
+    #[test]
+    fn read_up_to_stops_at_limit() {
+        let system = Rc::new(Concurrent::new(VirtualSystem::new()));
+        let (read_fd, write_fd) = system.pipe().unwrap();
+        system
+            .write_all(write_fd, b"0123456789")
+            .now_or_never()
+            .unwrap()
+            .unwrap();
+
+        let mut buffer = b"abc".to_vec();
+        system
+            .read_up_to(read_fd, &mut buffer, 4)
+            .now_or_never()
+            .unwrap()
+            .unwrap();
+        assert_eq!(buffer, b"abc0123");
+
+        // The rest of the data remains unread.
+        system.close(write_fd).unwrap();
+        let rest = system.read_all(read_fd).now_or_never().unwrap().unwrap();
+        assert_eq!(rest, b"456789");
+    }
 }
@@ -341,6 +341,35 @@ impl<S: IsExecutableFile> IsExecutableFile for Rc<S> {
     }
 }
 
+/// Trait for checking if a file is readable or writable
+///
+/// This trait declares the `is_readable_file` and `is_writable_file` methods,
+/// which check whether the current process has permission to read or write the
+/// file at a path. Like [`IsExecutableFile`], this trait is separate from the
+/// [`Fstat`] trait because the implementation depends on the `faccessat`
+/// system call.
+pub trait CheckAccess {
+    /// Whether there is a file that can be read at the specified path.
+    #[must_use]
+    fn is_readable_file(&self, path: &CStr) -> bool;
+
+    /// Whether there is a file that can be written at the specified path.
+    #[must_use]
+    fn is_writable_file(&self, path: &CStr) -> bool;
+}
+
+/// Delegates the `CheckAccess` trait to the contained instance of `S`
+impl<S: CheckAccess> CheckAccess for Rc<S> {
+    #[inline]
+    fn is_readable_file(&self, path: &CStr) -> bool {
+        (self as &S).is_readable_file(path)
+    }
+    #[inline]
+    fn is_writable_file(&self, path: &CStr) -> bool {
+        (self as &S).is_writable_file(path)
+    }
+}
+
 /// File access mode of open file descriptions
 #[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
 #[non_exhaustive]
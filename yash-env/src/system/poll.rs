@@ -0,0 +1,147 @@
+// This file is part of yash, an extended POSIX shell.
+// Copyright (C) 2026 WATANABE Yuki
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+//! Items related to the `poll` system call
+
+#[cfg(doc)]
+use super::Concurrent;
+use super::Result;
+use super::Sigmask;
+use crate::io::Fd;
+use std::ffi::c_int;
+use std::future::Future;
+use std::rc::Rc;
+use std::time::Duration;
+
+/// File descriptor (FD) to be monitored by [`Poll::poll`]
+///
+/// This is an abstraction over the `pollfd` structure used in the `poll`
+/// system call. The `reading` and `writing` fields specify the events to wait
+/// for, and the `ready_for_reading` and `ready_for_writing` fields are set by
+/// the `poll` method to report the events that occurred.
+///
+/// Unlike an [`FdSet`](super::FdSet), a list of `PollFd`s can contain any
+/// non-negative FD regardless of `FD_SETSIZE`.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct PollFd {
+    /// FD to be monitored
+    pub fd: Fd,
+    /// Whether to wait for the FD to become ready for reading
+    pub reading: bool,
+    /// Whether to wait for the FD to become ready for writing
+    pub writing: bool,
+    /// Whether the FD is ready for reading
+    ///
+    /// This field is set by [`Poll::poll`]. It is never set if `reading` is
+    /// false.
+    pub ready_for_reading: bool,
+    /// Whether the FD is ready for writing
+    ///
+    /// This field is set by [`Poll::poll`]. It is never set if `writing` is
+    /// false.
+    pub ready_for_writing: bool,
+}
+
+impl PollFd {
+    /// Creates a `PollFd` that waits for the FD to become ready for reading.
+    #[must_use]
+    pub const fn reader(fd: Fd) -> Self {
+        PollFd {
+            fd,
+            reading: true,
+            writing: false,
+            ready_for_reading: false,
+            ready_for_writing: false,
+        }
+    }
+
+    /// Creates a `PollFd` that waits for the FD to become ready for writing.
+    #[must_use]
+    pub const fn writer(fd: Fd) -> Self {
+        PollFd {
+            fd,
+            reading: false,
+            writing: true,
+            ready_for_reading: false,
+            ready_for_writing: false,
+        }
+    }
+
+    /// Whether the FD is ready for any of the requested events
+    #[must_use]
+    pub const fn is_ready(&self) -> bool {
+        self.ready_for_reading || self.ready_for_writing
+    }
+}
+
+/// Trait for performing the `poll` operation
+///
+/// This trait provides the `poll` method, which represents the `poll` system
+/// call (or `ppoll` where available). It serves the same purpose as the
+/// [`Select`](super::Select) trait, but it is not limited by `FD_SETSIZE`, so
+/// [`Concurrent`] uses this trait to wait for events.
+pub trait Poll: Sigmask {
+    /// Waits for a next event.
+    ///
+    /// In a typical configuration, this trait is not used directly. Instead,
+    /// it is used by [`Concurrent`] to implement asynchronous I/O, signal
+    /// handling, and timer functions.
+    ///
+    /// This function blocks the calling thread until one of the following
+    /// conditions is met:
+    ///
+    /// - An FD in `fds` becomes ready for the requested event.
+    /// - The specified `timeout` duration has passed.
+    /// - A signal handler catches a signal.
+    ///
+    /// When this function returns an `Ok`, the `ready_for_reading` and
+    /// `ready_for_writing` fields of each element of `fds` are set according
+    /// to the readiness of the FD. An FD that has an error condition or is
+    /// not open is reported as ready for the requested events so that a
+    /// subsequent read or write reports the error. The return value is the
+    /// number of elements that are ready.
+    ///
+    /// An implementation may fail with `EBADF` instead of reporting an FD that
+    /// is not open as ready. In case of an error, the fields of `fds` are
+    /// unspecified.
+    ///
+    /// If `signal_mask` is `Some` list of signals, it is used as the signal
+    /// blocking mask while waiting and restored when the function returns.
+    ///
+    /// The return type is a future so that
+    /// [virtual systems](crate::system::virtual) can simulate the blocking
+    /// behavior of `poll` without blocking the entire process. See
+    /// [`Select::select`](super::Select::select) for details.
+    fn poll<'a>(
+        &self,
+        fds: &'a mut [PollFd],
+        timeout: Option<Duration>,
+        signal_mask: Option<&Self::Sigset>,
+    ) -> impl Future<Output = Result<c_int>> + use<'a, Self>;
+}
+
+/// Delegates the `Poll` trait to the contained instance of `S`
+impl<S: Poll> Poll for Rc<S> {
+    #[inline]
+    fn poll<'a>(
+        &self,
+        fds: &'a mut [PollFd],
+        timeout: Option<Duration>,
+        signal_mask: Option<&S::Sigset>,
+    ) -> impl Future<Output = Result<c_int>> + use<'a, S> {
+        (self as &S).poll(fds, timeout, signal_mask)
+    }
+}
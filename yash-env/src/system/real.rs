@@ -31,6 +31,7 @@ mod signal;
 use super::AT_FDCWD;
 use super::CaughtSignals;
 use super::Chdir;
+use super::CheckAccess;
 use super::Clock;
 use super::Close;
 use super::CpuTimes;
@@ -59,6 +60,8 @@ use super::OfdAccess;
 use super::Open;
 use super::OpenFlag;
 use super::Pipe;
+use super::Poll;
+use super::PollFd;
 use super::Read;
 use super::Result;
 use super::Seek;
@@ -76,9 +79,11 @@ use super::Sysconf;
 use super::TcGetPgrp;
 use super::TcSetPgrp;
 use super::Times;
+use super::TtySize;
 use super::Uid;
 use super::Umask;
 use super::Wait;
+use super::WindowSize;
 use super::Write;
 use super::c_string::{AsCStrArray as _, IntoCStrArray};
 use super::resource::LimitPair;
@@ -262,6 +267,16 @@ impl RealSystem {
     fn has_execute_permission(&self, path: &CStr) -> bool {
         (unsafe { libc::access(path.as_ptr(), libc::X_OK) }) != -1
     }
+
+    // TODO Should use AT_EACCESS on all platforms
+    #[cfg(not(target_os = "redox"))]
+    fn has_permission(&self, path: &CStr, mode: libc::c_int) -> bool {
+        (unsafe { libc::faccessat(libc::AT_FDCWD, path.as_ptr(), mode, libc::AT_EACCESS) }) != -1
+    }
+    #[cfg(target_os = "redox")]
+    fn has_permission(&self, path: &CStr, mode: libc::c_int) -> bool {
+        (unsafe { libc::access(path.as_ptr(), mode) }) != -1
+    }
 }
 
 impl Fstat for RealSystem {
@@ -297,6 +312,16 @@ impl IsExecutableFile for RealSystem {
     }
 }
 
+impl CheckAccess for RealSystem {
+    fn is_readable_file(&self, path: &CStr) -> bool {
+        self.has_permission(path, libc::R_OK)
+    }
+
+    fn is_writable_file(&self, path: &CStr) -> bool {
+        self.has_permission(path, libc::W_OK)
+    }
+}
+
 impl Pipe for RealSystem {
     fn pipe(&self) -> Result<(Fd, Fd)> {
         let mut fds = MaybeUninit::<[c_int; 2]>::uninit();
@@ -859,6 +884,38 @@ impl SendSignal for RealSystem {
     }
 }
 
+/// Performs the `pselect` system call.
+fn pselect(
+    readers: &mut FdSet,
+    writers: &mut FdSet,
+    timeout: Option<Duration>,
+    signal_mask: Option<&Sigset>,
+) -> Result<c_int> {
+    use std::ptr::{null, null_mut};
+
+    let upper_bound = readers.upper_bound().max(writers.upper_bound());
+    let readers_ptr = readers.as_mut_ptr();
+    let writers_ptr = writers.as_mut_ptr();
+    let errors = null_mut();
+
+    let timeout_spec = timeout.map(to_timespec);
+    let timeout_ptr = timeout_spec.as_ref().map_or(null(), |spec| spec.as_ptr());
+
+    let raw_mask_ptr = signal_mask.map_or(null(), |mask| mask.0.as_ptr());
+
+    unsafe {
+        libc::pselect(
+            upper_bound.0,
+            readers_ptr,
+            writers_ptr,
+            errors,
+            timeout_ptr,
+            raw_mask_ptr,
+        )
+    }
+    .errno_if_m1()
+}
+
 impl Select for RealSystem {
     type FdSet = FdSet;
 
@@ -869,40 +926,127 @@ impl Select for RealSystem {
         timeout: Option<Duration>,
         signal_mask: Option<&Sigset>,
     ) -> impl Future<Output = Result<c_int>> + use<'a> {
-        ready({
-            use std::ptr::{null, null_mut};
+        ready(pselect(readers, writers, timeout, signal_mask))
+    }
+}
 
-            let upper_bound = readers.upper_bound().max(writers.upper_bound());
-            let readers_ptr = readers.as_mut_ptr();
-            let writers_ptr = writers.as_mut_ptr();
-            let errors = null_mut();
+/// Implements `poll` with `ppoll`, which waits for events with a temporary
+/// signal mask atomically as `pselect` does.
+#[cfg(target_os = "linux")]
+impl Poll for RealSystem {
+    fn poll<'a>(
+        &self,
+        fds: &'a mut [PollFd],
+        timeout: Option<Duration>,
+        signal_mask: Option<&Sigset>,
+    ) -> impl Future<Output = Result<c_int>> + use<'a> {
+        ready({
+            use std::ptr::null;
+
+            let mut raw_fds = fds
+                .iter()
+                .map(|fd| {
+                    let mut events = 0;
+                    if fd.reading {
+                        events |= libc::POLLIN;
+                    }
+                    if fd.writing {
+                        events |= libc::POLLOUT;
+                    }
+                    libc::pollfd {
+                        fd: fd.fd.0,
+                        events,
+                        revents: 0,
+                    }
+                })
+                .collect::<Vec<_>>();
+            let count = raw_fds.len().try_into().unwrap_or(libc::nfds_t::MAX);
 
             let timeout_spec = timeout.map(to_timespec);
             let timeout_ptr = timeout_spec.as_ref().map_or(null(), |spec| spec.as_ptr());
 
             let raw_mask_ptr = signal_mask.map_or(null(), |mask| mask.0.as_ptr());
 
-            unsafe {
-                libc::pselect(
-                    upper_bound.0,
-                    readers_ptr,
-                    writers_ptr,
-                    errors,
-                    timeout_ptr,
-                    raw_mask_ptr,
-                )
+            let result =
+                unsafe { libc::ppoll(raw_fds.as_mut_ptr(), count, timeout_ptr, raw_mask_ptr) }
+                    .errno_if_m1();
+
+            if result.is_ok() {
+                // Report errors as readiness so that the next read or write
+                // reveals the error.
+                let errors = libc::POLLERR | libc::POLLHUP | libc::POLLNVAL;
+                for (fd, raw_fd) in fds.iter_mut().zip(&raw_fds) {
+                    let error = raw_fd.revents & errors != 0;
+                    fd.ready_for_reading =
+                        fd.reading && (raw_fd.revents & libc::POLLIN != 0 || error);
+                    fd.ready_for_writing =
+                        fd.writing && (raw_fd.revents & libc::POLLOUT != 0 || error);
+                }
             }
-            .errno_if_m1()
+            result
         })
     }
 }
 
+/// Implements `poll` with `pselect` on platforms without `ppoll`.
+///
+/// Since `poll` cannot apply a temporary signal mask atomically, this
+/// implementation falls back to `pselect`, which is subject to the
+/// `FD_SETSIZE` limit. If any FD is out of the range, this function fails
+/// with `EINVAL`.
+#[cfg(not(target_os = "linux"))]
+impl Poll for RealSystem {
+    fn poll<'a>(
+        &self,
+        fds: &'a mut [PollFd],
+        timeout: Option<Duration>,
+        signal_mask: Option<&Sigset>,
+    ) -> impl Future<Output = Result<c_int>> + use<'a> {
+        use crate::system::FdSet as _;
+
+        let mut readers = FdSet::new();
+        let mut writers = FdSet::new();
+        for fd in fds.iter() {
+            if fd.fd.0 < 0 || fd.fd > FdSet::MAX_FD {
+                return ready(Err(Errno::EINVAL));
+            }
+            if fd.reading {
+                readers.insert(fd.fd);
+            }
+            if fd.writing {
+                writers.insert(fd.fd);
+            }
+        }
+
+        let result = pselect(&mut readers, &mut writers, timeout, signal_mask);
+        if result.is_ok() {
+            for fd in fds.iter_mut() {
+                fd.ready_for_reading = fd.reading && readers.contains(fd.fd);
+                fd.ready_for_writing = fd.writing && writers.contains(fd.fd);
+            }
+        }
+        ready(result)
+    }
+}
+
 impl Isatty for RealSystem {
     fn isatty(&self, fd: Fd) -> bool {
         (unsafe { libc::isatty(fd.0) } != 0)
     }
 }
 
+impl TtySize for RealSystem {
+    fn tty_size(&self, fd: Fd) -> Result<WindowSize> {
+        let mut size = MaybeUninit::<libc::winsize>::uninit();
+        unsafe { libc::ioctl(fd.0, libc::TIOCGWINSZ, size.as_mut_ptr()) }.errno_if_m1()?;
+        let size = unsafe { size.assume_init() };
+        Ok(WindowSize {
+            columns: size.ws_col,
+            lines: size.ws_row,
+        })
+    }
+}
+
 impl TcGetPgrp for RealSystem {
     fn tcgetpgrp(&self, fd: Fd) -> Result<Pid> {
         unsafe { libc::tcgetpgrp(fd.0) }.errno_if_m1().map(Pid)
@@ -1221,4 +1365,42 @@ mod tests {
             assert_eq!(result, []);
         }
     }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn real_system_poll_with_fd_above_fd_setsize() {
+        use crate::system::resource::{INFINITY, Limit};
+        use futures_util::FutureExt as _;
+
+        let system = unsafe { RealSystem::new() };
+        let high_fd = Fd(libc::FD_SETSIZE as c_int + 10);
+
+        // Make sure the FD is within the resource limit.
+        let mut limits = system.getrlimit(Resource::NOFILE).unwrap();
+        let required = high_fd.0 as Limit + 1;
+        if limits.soft != INFINITY && limits.soft < required {
+            if limits.hard != INFINITY && limits.hard < required {
+                // The FD cannot be opened in this environment.
+                return;
+            }
+            limits.soft = required;
+            system.setrlimit(Resource::NOFILE, limits).unwrap();
+        }
+
+        let (reader, writer) = system.pipe().unwrap();
+        let high_reader = system.dup2(reader, high_fd).unwrap();
+        system.write(writer, &[0]).now_or_never().unwrap().unwrap();
+        let mut fds = [PollFd::reader(high_reader), PollFd::writer(writer)];
+        let result = system
+            .poll(&mut fds, Some(Duration::ZERO), None)
+            .now_or_never()
+            .unwrap();
+
+        for fd in [reader, writer, high_reader] {
+            system.close(fd).unwrap();
+        }
+        assert_eq!(result, Ok(2));
+        assert!(fds[0].ready_for_reading);
+        assert!(fds[1].ready_for_writing);
+    }
 }
@@ -42,6 +42,35 @@ impl<S: Isatty> Isatty for Rc<S> {
     }
 }
 
+/// Size of a terminal window
+///
+/// A zero value means that the size is unknown.
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
+pub struct WindowSize {
+    /// Number of columns
+    pub columns: u16,
+    /// Number of lines
+    pub lines: u16,
+}
+
+/// Trait for getting the window size of a terminal
+pub trait TtySize {
+    /// Returns the window size of the terminal associated with the file
+    /// descriptor `fd`.
+    ///
+    /// This is a thin wrapper around the `TIOCGWINSZ` request of the `ioctl`
+    /// system function.
+    fn tty_size(&self, fd: Fd) -> Result<WindowSize>;
+}
+
+/// Delegates the `TtySize` trait to the contained instance of `S`
+impl<S: TtySize> TtySize for Rc<S> {
+    #[inline]
+    fn tty_size(&self, fd: Fd) -> Result<WindowSize> {
+        (self as &S).tty_size(fd)
+    }
+}
+
 /// Trait for getting the foreground process group ID of a terminal
 pub trait TcGetPgrp {
     /// Returns the current foreground process group ID.
@@ -75,6 +75,7 @@ use self::sigset::Sigset;
 use super::AT_FDCWD;
 use super::CaughtSignals;
 use super::Chdir;
+use super::CheckAccess;
 use super::Clock;
 use super::Close;
 use super::CpuTimes;
@@ -117,9 +118,11 @@ use super::Sysconf;
 use super::TcGetPgrp;
 use super::TcSetPgrp;
 use super::Times;
+use super::TtySize;
 use super::Uid;
 use super::Umask;
 use super::Wait;
+use super::WindowSize;
 use super::Write;
 use super::c_string::{AsCStrArray, IntoCStrArray};
 use super::resource::INFINITY;
@@ -539,6 +542,28 @@ impl IsExecutableFile for VirtualSystem {
     }
 }
 
+impl CheckAccess for VirtualSystem {
+    /// Tests whether the specified file is readable or not.
+    ///
+    /// The current implementation only checks if the file has any readable
+    /// bit in the permissions. The file owner and group are not considered.
+    fn is_readable_file(&self, path: &CStr) -> bool {
+        let path = Path::new(UnixStr::from_bytes(path.to_bytes()));
+        self.resolve_existing_file(AT_FDCWD, path, /* follow symlinks */ true)
+            .is_ok_and(|inode| inode.borrow().permissions.intersects(Mode::ALL_READ))
+    }
+
+    /// Tests whether the specified file is writable or not.
+    ///
+    /// The current implementation only checks if the file has any writable
+    /// bit in the permissions. The file owner and group are not considered.
+    fn is_writable_file(&self, path: &CStr) -> bool {
+        let path = Path::new(UnixStr::from_bytes(path.to_bytes()));
+        self.resolve_existing_file(AT_FDCWD, path, /* follow symlinks */ true)
+            .is_ok_and(|inode| inode.borrow().permissions.intersects(Mode::ALL_WRITE))
+    }
+}
+
 impl Pipe for VirtualSystem {
     fn pipe(&self) -> Result<(Fd, Fd)> {
         let file = Rc::new(RefCell::new(Inode {
@@ -599,6 +624,10 @@ impl Dup for VirtualSystem {
     fn dup2(&self, from: Fd, to: Fd) -> Result<Fd> {
         let mut process = self.current_process_mut();
         let mut body = process.fds.get(&from).ok_or(Errno::EBADF)?.clone();
+        if from == to {
+            // Like the real dup2, this does not change the FD flags.
+            return Ok(to);
+        }
         body.flags = EnumSet::empty();
         process.set_fd(to, body).map_err(|_| Errno::EBADF)?;
         Ok(to)
@@ -1178,6 +1207,20 @@ impl Isatty for VirtualSystem {
     }
 }
 
+impl TtySize for VirtualSystem {
+    /// Returns the window size of the terminal.
+    ///
+    /// All virtual terminals share the window size stored in
+    /// [`SystemState::window_size`]. This function fails with `ENOTTY` if the
+    /// file descriptor is not associated with a terminal.
+    fn tty_size(&self, fd: Fd) -> Result<WindowSize> {
+        self.with_open_file_description(fd, |ofd| match &ofd.inode().borrow().body {
+            FileBody::Terminal { .. } => Ok(self.state.borrow().window_size),
+            _ => Err(Errno::ENOTTY),
+        })
+    }
+}
+
 impl TcGetPgrp for VirtualSystem {
     /// Returns the current foreground process group ID.
     ///
@@ -1303,6 +1346,10 @@ impl Exec for VirtualSystem {
     /// The `execve` system call cannot be simulated in the userland. This
     /// function returns `ENOSYS` if the file at `path` is a native executable,
     /// `ENOEXEC` if a non-executable file, and `ENOENT` otherwise.
+    ///
+    /// For a native executable, this function records the arguments and the FDs
+    /// that would be inherited by the executed program in the current process.
+    /// See [`Process::last_exec`] and [`Process::last_exec_fds`].
     fn execve<A, E>(
         &self,
         path: &CStr,
@@ -1330,14 +1377,22 @@ impl Exec for VirtualSystem {
                 Ok(file) => file,
                 Err(e) => return ready(Err(e)),
             };
-            // TODO Check file permissions
-            let is_executable = matches!(
-                &file.borrow().body,
-                FileBody::Regular {
-                    is_native_executable: true,
-                    ..
+            // The file owner and group are not considered.
+            let is_executable = {
+                let file = file.borrow();
+                if !matches!(file.body, FileBody::Regular { .. })
+                    || !file.permissions.intersects(Mode::ALL_EXEC)
+                {
+                    return ready(Err(Errno::EACCES));
                 }
-            );
+                matches!(
+                    file.body,
+                    FileBody::Regular {
+                        is_native_executable: true,
+                        ..
+                    }
+                )
+            };
             if is_executable {
                 // Save arguments in the Process
                 let process = state.processes.get_mut(&this.process_id).unwrap();
@@ -1345,6 +1400,12 @@ impl Exec for VirtualSystem {
                 let args = args.into_c_str_array().to_vec();
                 let envs = envs.into_c_str_array().to_vec();
                 process.last_exec = Some((path, args, envs));
+                process.last_exec_fds = process
+                    .fds
+                    .iter()
+                    .filter(|(_, body)| !body.flags.contains(FdFlag::CloseOnExec))
+                    .map(|(&fd, _)| fd)
+                    .collect();
 
                 // TODO: We should abort the currently running task and start the new one.
                 // Just returning `pending()` would break existing tests that rely on
@@ -1534,6 +1595,12 @@ pub struct SystemState {
     /// more _correct_ implementation in the future.
     pub foreground: Option<Pid>,
 
+    /// Window size of the terminals
+    ///
+    /// Note: The current implementation does not distinguish between
+    /// terminals. This size is shared by all terminals in the system.
+    pub window_size: WindowSize,
+
     /// Collection of files existing in the virtual system
     pub file_system: FileSystem,
 
@@ -1755,6 +1822,23 @@ mod tests {
         assert!(system.is_executable_file(c"/some/file"));
     }
 
+    #[test]
+    fn is_readable_and_writable_file() {
+        let system = VirtualSystem::new();
+        let content = Inode {
+            permissions: Mode::USER_READ,
+            ..Inode::default()
+        };
+        let content = Rc::new(RefCell::new(content));
+        let mut state = system.state.borrow_mut();
+        state.file_system.save("/some/file", content).unwrap();
+        drop(state);
+        assert!(system.is_readable_file(c"/some/file"));
+        assert!(!system.is_writable_file(c"/some/file"));
+        assert!(!system.is_readable_file(c"/no/such/file"));
+        assert!(!system.is_writable_file(c"/no/such/file"));
+    }
+
     #[test]
     fn pipe_read_write() {
         let system = VirtualSystem::new();
@@ -2154,6 +2238,72 @@ mod tests {
         assert_eq!(fd6.flags, EnumSet::empty());
     }
 
+    #[test]
+    fn dup2_to_same_fd_keeps_flags() {
+        let system = VirtualSystem::new();
+        let mut process = system.current_process_mut();
+        process.fds.get_mut(&Fd::STDOUT).unwrap().flags = FdFlag::CloseOnExec.into();
+        drop(process);
+
+        let result = system.dup2(Fd::STDOUT, Fd::STDOUT);
+        assert_eq!(result, Ok(Fd::STDOUT));
+
+        let process = system.current_process();
+        let fd1 = process.fds.get(&Fd::STDOUT).unwrap();
+        assert_eq!(fd1.flags, EnumSet::only(FdFlag::CloseOnExec));
+    }
+
+    #[test]
+    fn dup2_closes_previous_target() {
+        let system = VirtualSystem::new();
+        let (reader, writer) = system.pipe().unwrap();
+
+        let result = system.dup2(Fd::STDOUT, writer);
+        assert_eq!(result, Ok(writer));
+
+        // The writing end of the pipe has been closed by dup2.
+        let mut buffer = [0; 4];
+        let result = system.read(reader, &mut buffer).now_or_never().unwrap();
+        assert_eq!(result, Ok(0));
+    }
+
+    #[test]
+    fn dup2_from_closed_fd() {
+        let system = VirtualSystem::new();
+        let result = system.dup2(Fd(10), Fd::STDOUT);
+        assert_eq!(result, Err(Errno::EBADF));
+
+        // The target FD is left intact.
+        let process = system.current_process();
+        assert_ne!(process.fds.get(&Fd::STDOUT), None);
+    }
+
+    #[test]
+    fn dup2_shares_file_offset() {
+        let system = VirtualSystem::new();
+        let fd = system
+            .open(
+                c"file",
+                OfdAccess::ReadWrite,
+                OpenFlag::Create.into(),
+                Mode::ALL_9,
+            )
+            .now_or_never()
+            .unwrap()
+            .unwrap();
+        system.dup2(fd, Fd(7)).unwrap();
+
+        let result = system.write(Fd(7), &[1, 2, 3]).now_or_never().unwrap();
+        assert_eq!(result, Ok(3));
+        assert_eq!(system.lseek(fd, SeekFrom::Current(0)), Ok(3));
+
+        system.lseek(fd, SeekFrom::Start(1)).unwrap();
+        let mut buffer = [0; 4];
+        let result = system.read(Fd(7), &mut buffer).now_or_never().unwrap();
+        assert_eq!(result, Ok(2));
+        assert_eq!(buffer[..2], [2, 3]);
+    }
+
     #[test]
     fn open_non_existing_file_no_creation() {
         let system = VirtualSystem::new();
@@ -2543,6 +2693,28 @@ mod tests {
         assert_eq!(result, Ok(()));
     }
 
+    #[test]
+    fn close_leaves_duplicate_open() {
+        let system = VirtualSystem::new();
+        let (reader, writer) = system.pipe().unwrap();
+        let writer2 = system.dup2(writer, Fd(10)).unwrap();
+
+        system.close(writer).unwrap();
+        let result = system.write(writer2, &[42]).now_or_never().unwrap();
+        assert_eq!(result, Ok(1));
+        let result = system.write(writer, &[42]).now_or_never().unwrap();
+        assert_eq!(result, Err(Errno::EBADF));
+
+        let mut buffer = [0; 4];
+        let result = system.read(reader, &mut buffer).now_or_never().unwrap();
+        assert_eq!(result, Ok(1));
+
+        // Closing the last FD for the writing end closes the pipe.
+        system.close(writer2).unwrap();
+        let result = system.read(reader, &mut buffer).now_or_never().unwrap();
+        assert_eq!(result, Ok(0));
+    }
+
     #[test]
     fn fcntl_getfd_and_setfd() {
         let system = VirtualSystem::new();
@@ -2566,6 +2738,58 @@ mod tests {
         assert_eq!(flags, EnumSet::empty());
     }
 
+    #[test]
+    fn isatty_terminal() {
+        let system = VirtualSystem::new();
+        let content = Rc::new(RefCell::new(Inode {
+            body: FileBody::Terminal { content: vec![] },
+            permissions: Mode::default(),
+        }));
+        let mut state = system.state.borrow_mut();
+        state.file_system.save("/dev/tty", content).unwrap();
+        drop(state);
+
+        let fd = system
+            .open(
+                c"/dev/tty",
+                OfdAccess::ReadWrite,
+                EnumSet::empty(),
+                Mode::empty(),
+            )
+            .now_or_never()
+            .unwrap()
+            .unwrap();
+        assert!(system.isatty(fd));
+    }
+
+    #[test]
+    fn isatty_regular_file() {
+        let system = VirtualSystem::new();
+        let content = Rc::new(RefCell::new(Inode::new([])));
+        let mut state = system.state.borrow_mut();
+        state.file_system.save("/some/file", content).unwrap();
+        drop(state);
+
+        let fd = system
+            .open(
+                c"/some/file",
+                OfdAccess::ReadOnly,
+                EnumSet::empty(),
+                Mode::empty(),
+            )
+            .now_or_never()
+            .unwrap()
+            .unwrap();
+        assert!(!system.isatty(fd));
+        assert!(!system.isatty(Fd::STDIN));
+    }
+
+    #[test]
+    fn isatty_closed_fd() {
+        let system = VirtualSystem::new();
+        assert!(!system.isatty(Fd(100)));
+    }
+
     #[test]
     fn opendir_default_working_directory() {
         // The default working directory is the root directory.
@@ -3306,6 +3530,56 @@ mod tests {
         assert_eq!(arguments.2, envs);
     }
 
+    #[test]
+    fn execve_records_fds_without_cloexec() {
+        let system = VirtualSystem::new();
+        let path = "/some/file";
+        let mut content = Inode::default();
+        content.body = FileBody::Regular {
+            content: Vec::new(),
+            is_native_executable: true,
+        };
+        content.permissions.set(Mode::USER_EXEC, true);
+        let content = Rc::new(RefCell::new(content));
+        let mut state = system.state.borrow_mut();
+        state.file_system.save(path, content).unwrap();
+        drop(state);
+        system.dup(Fd::STDOUT, Fd(5), EnumSet::empty()).unwrap();
+        system
+            .dup(Fd::STDOUT, Fd(10), FdFlag::CloseOnExec.into())
+            .unwrap();
+        system.close(Fd::STDIN).unwrap();
+        let path = CString::new(path).unwrap();
+        system
+            .execve(&path, &[] as &[CString], &[] as &[CString])
+            .now_or_never();
+
+        let process = system.current_process();
+        assert_eq!(process.last_exec_fds(), [Fd::STDOUT, Fd::STDERR, Fd(5)]);
+    }
+
+    #[test]
+    fn execve_returns_eacces_for_file_without_exec_permission() {
+        let system = VirtualSystem::new();
+        let path = "/some/file";
+        let mut content = Inode::default();
+        content.body = FileBody::Regular {
+            content: vec![],
+            is_native_executable: true,
+        };
+        let content = Rc::new(RefCell::new(content));
+        let mut state = system.state.borrow_mut();
+        state.file_system.save(path, content).unwrap();
+        drop(state);
+        let path = CString::new(path).unwrap();
+        let result = system
+            .execve(&path, &[] as &[CString], &[] as &[CString])
+            .now_or_never()
+            .unwrap();
+        assert_eq!(result, Err(Errno::EACCES));
+        assert_eq!(*system.current_process().last_exec(), None);
+    }
+
     #[test]
     fn execve_returns_enoexec_for_non_executable_file() {
         let system = VirtualSystem::new();
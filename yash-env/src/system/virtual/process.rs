@@ -123,6 +123,10 @@ pub struct Process {
 
     /// Copy of arguments passed to [`execve`](crate::System::execve)
     pub(crate) last_exec: Option<(CString, Vec<CString>, Vec<CString>)>,
+
+    /// FDs inherited by the program executed by the last call to
+    /// [`execve`](crate::system::Exec::execve)
+    pub(crate) last_exec_fds: Vec<Fd>,
 }
 
 /// Finds the minimum available FD.
@@ -168,6 +172,7 @@ impl Process {
             signal_wakers: WakerSet::new(),
             resource_limits: HashMap::new(),
             last_exec: None,
+            last_exec_fds: Vec::new(),
         }
     }
 
@@ -573,6 +578,19 @@ impl Process {
     pub fn last_exec(&self) -> &Option<(CString, Vec<CString>, Vec<CString>)> {
         &self.last_exec
     }
+
+    /// Returns the FDs inherited by the program executed by the last call to
+    /// [`execve`](crate::system::Exec::execve) on this process.
+    ///
+    /// The result contains the FDs that were open without the
+    /// [`CloseOnExec`](crate::system::FdFlag::CloseOnExec) flag at the time of
+    /// the call, in ascending order. The result is empty if `execve` has never
+    /// been called.
+    #[inline(always)]
+    #[must_use]
+    pub fn last_exec_fds(&self) -> &[Fd] {
+        &self.last_exec_fds
+    }
 }
 
 /// Result of operations that may deliver a signal to a process.
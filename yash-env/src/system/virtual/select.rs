@@ -14,7 +14,7 @@
 // You should have received a copy of the GNU General Public License
 // along with this program.  If not, see <https://www.gnu.org/licenses/>.
 
-//! Implementation of [`Select`] for [`VirtualSystem`]
+//! Implementation of [`Select`] and [`Poll`](PollTrait) for [`VirtualSystem`]
 
 use super::fd_set::FdSet;
 use super::{
@@ -23,6 +23,7 @@ use super::{
 };
 use crate::job::ProcessState;
 use crate::system::FdSet as _;
+use crate::system::{Poll as PollTrait, PollFd};
 use std::cell::{Cell, LazyCell};
 use std::ffi::c_int;
 use std::future::poll_fn;
@@ -203,6 +204,40 @@ impl Select for VirtualSystem {
     }
 }
 
+impl PollTrait for VirtualSystem {
+    /// Waits for a next event.
+    ///
+    /// The `VirtualSystem` implementation for this method performs
+    /// [`select`](Select::select) with the FDs in `fds`. Since the virtual
+    /// [`FdSet`] has no upper limit of FDs, this method behaves the same as
+    /// `select` except for the representation of the FDs.
+    fn poll<'a>(
+        &self,
+        fds: &'a mut [PollFd],
+        timeout: Option<Duration>,
+        signal_mask: Option<&Sigset>,
+    ) -> impl Future<Output = Result<c_int>> + use<'a> {
+        let this = self.clone();
+        let signal_mask = signal_mask.cloned();
+        async move {
+            let mut readers: FdSet = fds.iter().filter(|fd| fd.reading).map(|fd| fd.fd).collect();
+            let mut writers: FdSet = fds.iter().filter(|fd| fd.writing).map(|fd| fd.fd).collect();
+            this.select(&mut readers, &mut writers, timeout, signal_mask.as_ref())
+                .await?;
+
+            let mut count = 0;
+            for fd in fds {
+                fd.ready_for_reading = fd.reading && readers.contains(fd.fd);
+                fd.ready_for_writing = fd.writing && writers.contains(fd.fd);
+                if fd.is_ready() {
+                    count += 1;
+                }
+            }
+            Ok(count)
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::super::Process;
@@ -211,8 +246,8 @@ mod tests {
     use crate::io::Fd;
     use crate::job::Pid;
     use crate::system::{
-        CaughtSignals as _, Close as _, Disposition, Pipe as _, Read as _, SendSignal as _,
-        Sigaction as _, Sigmask as _, Sigset as _, Write as _,
+        CaughtSignals as _, Close as _, Disposition, Dup as _, Pipe as _, Read as _,
+        SendSignal as _, Sigaction as _, Sigmask as _, Sigset as _, Write as _,
     };
     use crate::test_helper::WakeFlag;
     use futures_util::FutureExt as _;
@@ -833,4 +868,34 @@ mod tests {
         let poll = select.as_mut().poll(&mut context);
         assert_eq!(poll, Poll::Ready(Ok(0)));
     }
+
+    #[test]
+    fn poll_reports_readiness_of_fd_above_fd_setsize() {
+        let system = VirtualSystem::new();
+        let (reader, writer) = system.pipe().unwrap();
+        let (empty_reader, _empty_writer) = system.pipe().unwrap();
+        // Larger than any common value of FD_SETSIZE
+        let high_reader = system.dup2(reader, Fd(100_000)).unwrap();
+        system.write(writer, &[0]).now_or_never().unwrap().unwrap();
+        let mut fds = [
+            PollFd::reader(high_reader),
+            PollFd::reader(empty_reader),
+            PollFd::writer(writer),
+        ];
+
+        let result = system.poll(&mut fds, None, None).now_or_never().unwrap();
+        assert_eq!(result, Ok(2));
+        assert!(fds[0].ready_for_reading);
+        assert!(!fds[1].ready_for_reading);
+        assert!(fds[2].ready_for_writing);
+        assert!(!fds[2].ready_for_reading);
+    }
+
+    #[test]
+    fn poll_fails_with_ebadf_for_closed_fd() {
+        let system = VirtualSystem::new();
+        let mut fds = [PollFd::reader(Fd(100_000))];
+        let result = system.poll(&mut fds, None, None).now_or_never().unwrap();
+        assert_eq!(result, Err(Errno::EBADF));
+    }
 }
@@ -305,7 +305,9 @@ impl TrapSet {
 
         for (&cond, state) in &mut self.traps {
             let option = match cond {
-                Condition::Exit => EnterSubshellOption::ClearInternalDisposition,
+                Condition::Exit | Condition::Debug | Condition::Err => {
+                    EnterSubshellOption::ClearInternalDisposition
+                }
                 Condition::Signal(signal) =>
                 {
                     #[allow(clippy::if_same_then_else, reason = "for readability")]
@@ -401,6 +403,22 @@ impl TrapSet {
             .await
     }
 
+    /// Installs the internal disposition for `SIGWINCH`.
+    ///
+    /// An interactive shell should install the internal disposition for
+    /// `SIGWINCH` by using this function so that it can track the window size
+    /// of the terminal. The disposition allows catching `SIGWINCH`.
+    ///
+    /// This function remembers that the disposition has been installed, so a
+    /// second call to the function will be a no-op.
+    pub async fn enable_internal_disposition_for_sigwinch<S: SignalSystem>(
+        &mut self,
+        system: &S,
+    ) -> Result<(), Errno> {
+        self.set_internal_disposition(S::SIGWINCH, Disposition::Catch, system)
+            .await
+    }
+
     /// Installs the internal dispositions for `SIGINT`, `SIGTERM`, and `SIGQUIT`.
     ///
     /// An interactive shell should install the internal dispositions for these
@@ -478,6 +496,8 @@ impl TrapSet {
     ) -> Result<(), Errno> {
         self.set_internal_disposition(S::SIGCHLD, Disposition::Default, system)
             .await?;
+        self.set_internal_disposition(S::SIGWINCH, Disposition::Default, system)
+            .await?;
         self.disable_internal_dispositions_for_terminators(system)
             .await?;
         self.disable_internal_dispositions_for_stoppers(system)
@@ -685,6 +705,33 @@ mod tests {
         assert_eq!(system.0.borrow()[&SIGUSR2], Disposition::Catch);
     }
 
+    #[test]
+    fn setting_trap_for_pseudo_conditions() {
+        let system = DummySystem::default();
+        let mut trap_set = TrapSet::default();
+        let origin = Location::dummy("origin");
+        let command = Action::Command("echo".into());
+        for cond in [Condition::Debug, Condition::Err] {
+            let result = trap_set
+                .set_action(&system, cond, command.clone(), origin.clone(), false)
+                .now_or_never()
+                .unwrap();
+            assert_eq!(result, Ok(()));
+            assert_eq!(
+                trap_set.get_state(cond),
+                (
+                    Some(&TrapState {
+                        action: command.clone(),
+                        origin: Origin::User(origin.clone()),
+                        pending: false
+                    }),
+                    None
+                )
+            );
+        }
+        assert!(system.0.borrow().is_empty());
+    }
+
     #[test]
     fn setting_trap_for_sigkill() {
         let system = DummySystem::default();
@@ -1562,6 +1609,25 @@ mod tests {
         assert_eq!(system.0.borrow()[&SIGCHLD], Disposition::Catch);
     }
 
+    #[test]
+    fn enabling_and_disabling_internal_disposition_for_sigwinch() {
+        let system = DummySystem::default();
+        let mut trap_set = TrapSet::default();
+        trap_set
+            .enable_internal_disposition_for_sigwinch(&system)
+            .now_or_never()
+            .unwrap()
+            .unwrap();
+        assert_eq!(system.0.borrow()[&SIGWINCH], Disposition::Catch);
+
+        trap_set
+            .disable_internal_dispositions(&system)
+            .now_or_never()
+            .unwrap()
+            .unwrap();
+        assert_eq!(system.0.borrow()[&SIGWINCH], Disposition::Default);
+    }
+
     #[test]
     fn enabling_internal_dispositions_for_terminators() {
         let system = DummySystem::default();
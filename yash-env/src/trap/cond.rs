@@ -31,6 +31,11 @@ pub enum Condition {
     Exit,
     /// When the specified signal is delivered to the shell process
     Signal(signal::Number),
+    /// Before executing each simple command
+    Debug,
+    /// When a command fails under the conditions where the
+    /// [`ErrExit`](crate::option::Option::ErrExit) option would take effect
+    Err,
 }
 
 impl From<signal::Number> for Condition {
@@ -53,10 +58,14 @@ impl From<signal::RawNumber> for Condition {
     }
 }
 
+/// Conversion from `Condition` to raw signal number
+///
+/// [`Condition::Signal`] is converted to the signal number. The other
+/// conditions do not have a corresponding signal and are converted to zero.
 impl From<Condition> for signal::RawNumber {
     fn from(cond: Condition) -> Self {
         match cond {
-            Condition::Exit => 0,
+            Condition::Exit | Condition::Debug | Condition::Err => 0,
             Condition::Signal(number) => number.as_raw(),
         }
     }
@@ -66,12 +75,14 @@ impl Condition {
     /// Converts this `Condition` to a `String`.
     ///
     /// The result is an uppercase string representing the condition such as
-    /// `"EXIT"` and `"TERM"`. Signal names are obtained from
+    /// `"EXIT"`, `"DEBUG"`, and `"TERM"`. Signal names are obtained from
     /// [`Signals::sig2str`].
     #[must_use]
     pub fn to_string<S: Signals>(&self, system: &S) -> Cow<'static, str> {
         match self {
             Self::Exit => Cow::Borrowed("EXIT"),
+            Self::Debug => Cow::Borrowed("DEBUG"),
+            Self::Err => Cow::Borrowed("ERR"),
             Self::Signal(number) => system.sig2str(*number).unwrap_or(Cow::Borrowed("?")),
         }
     }
@@ -81,7 +92,8 @@ impl Condition {
     /// The iterator yields all the conditions supported by the given `Signals`
     /// implementation.
     /// The iteration starts with [`Condition::Exit`], followed by all the
-    /// signals in the ascending order of their signal numbers.
+    /// signals in the ascending order of their signal numbers, and ends with
+    /// [`Condition::Debug`] and [`Condition::Err`].
     // TODO Most part of this function is duplicated from yash_builtin::kill::print::all_signals.
     // Consider refactoring to share the code. Note that all_signals does not
     // deduplicate the signals.
@@ -94,10 +106,12 @@ impl Condition {
         let real_time = system.iter_sigrt().map(Condition::Signal);
         let real_time_count = real_time.size_hint().1.unwrap_or_default();
 
-        let mut conditions = Vec::with_capacity(1 + non_real_time_count + real_time_count);
+        let mut conditions = Vec::with_capacity(3 + non_real_time_count + real_time_count);
         conditions.push(Condition::Exit);
         conditions.extend(non_real_time);
         conditions.extend(real_time);
+        conditions.push(Condition::Debug);
+        conditions.push(Condition::Err);
         conditions.sort();
         // Some names may share the same number, so deduplicate.
         conditions.into_iter().dedup()
@@ -108,6 +122,7 @@ impl Condition {
 mod tests {
     use super::*;
     use crate::system::r#virtual::VirtualSystem;
+    use assert_matches::assert_matches;
 
     #[test]
     fn condition_iter_is_sorted() {
@@ -116,6 +131,17 @@ mod tests {
         assert!(iter.is_sorted());
     }
 
+    #[test]
+    fn condition_iter_ends_with_pseudo_conditions() {
+        let system = VirtualSystem::new();
+        let conditions = Condition::iter(&system).collect::<Vec<_>>();
+        assert_eq!(conditions.first(), Some(&Condition::Exit));
+        assert_matches!(
+            conditions.as_slice(),
+            [.., Condition::Signal(_), Condition::Debug, Condition::Err]
+        );
+    }
+
     #[test]
     fn condition_iter_is_unique() {
         let system = VirtualSystem::new();
@@ -196,7 +196,7 @@ impl GrandState {
             Entry::Vacant(vacant) => {
                 let disposition = match *vacant.key() {
                     Condition::Signal(signal) => system.get_disposition(signal)?,
-                    Condition::Exit => Disposition::Default,
+                    Condition::Exit | Condition::Debug | Condition::Err => Disposition::Default,
                 };
                 let state = GrandState {
                     current_state: TrapState::from_initial_disposition(disposition),
@@ -298,7 +298,9 @@ impl GrandState {
     ) -> Result<(), Errno> {
         let signal = match *entry.key() {
             Condition::Signal(signal) => signal,
-            Condition::Exit => panic!("exit condition cannot have an internal disposition"),
+            Condition::Exit | Condition::Debug | Condition::Err => {
+                panic!("non-signal condition cannot have an internal disposition")
+            }
         };
 
         match entry {
@@ -398,7 +400,9 @@ impl GrandState {
     ) -> Result<(), Errno> {
         let signal = match *vacant.key() {
             Condition::Signal(signal) => signal,
-            Condition::Exit => panic!("exit condition cannot be ignored"),
+            Condition::Exit | Condition::Debug | Condition::Err => {
+                panic!("non-signal condition cannot be ignored")
+            }
         };
         let initial_disposition = system.set_disposition(signal, Disposition::Ignore).await?;
         let origin = match initial_disposition {
@@ -575,6 +575,25 @@ impl VariableSet {
         }
     }
 
+    /// Returns the names of variables that start with the given prefix.
+    ///
+    /// Only variables that have a value are included. The names are sorted in
+    /// ascending order.
+    #[must_use]
+    pub fn names_with_prefix(&self, prefix: &str) -> Vec<&str> {
+        let mut names = self
+            .all_variables
+            .iter()
+            .filter(|(name, vars)| {
+                name.starts_with(prefix)
+                    && vars.last().is_some_and(|var| var.variable.value.is_some())
+            })
+            .map(|(name, _)| name.as_str())
+            .collect::<Vec<_>>();
+        names.sort_unstable();
+        names
+    }
+
     /// Returns environment variables in a new vector of C strings.
     ///
     /// This function returns a vector of C strings that represent the currently
@@ -1339,6 +1358,28 @@ mod tests {
         })
     }
 
+    #[test]
+    fn names_with_prefix() {
+        let mut set = VariableSet::new();
+        for name in ["foo2", "bar", "foo1", "foo", "fo"] {
+            set.get_or_new(name, Scope::Global)
+                .assign("", None)
+                .unwrap();
+        }
+        set.get_or_new("foo3", Scope::Global);
+        set.push_context_impl(Context::default());
+        set.get_or_new("foo4", Scope::Local)
+            .assign("", None)
+            .unwrap();
+
+        assert_eq!(
+            set.names_with_prefix("foo"),
+            ["foo", "foo1", "foo2", "foo4"]
+        );
+        assert_eq!(set.names_with_prefix("b"), ["bar"]);
+        assert_eq!(set.names_with_prefix("x"), [] as [&str; 0]);
+    }
+
     #[test]
     fn env_c_strings() {
         let mut variables = VariableSet::new();
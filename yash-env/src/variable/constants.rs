@@ -16,18 +16,55 @@
 
 //! Variable name and default value constants
 
+/// The name of the `BASH_LINENO` variable
+///
+/// The `BASH_LINENO` variable is an array of the line numbers at which the
+/// functions in [`FUNCNAME`] were called. This is a non-POSIX extension.
+pub const BASH_LINENO: &str = "BASH_LINENO";
+
+/// The name of the `BASH_SOURCE` variable
+///
+/// The `BASH_SOURCE` variable is an array of the names of the sources in which
+/// the functions in [`FUNCNAME`] were defined. This is a non-POSIX extension.
+pub const BASH_SOURCE: &str = "BASH_SOURCE";
+
 /// The name of the `CDPATH` variable
 ///
 /// The `CDPATH` variable is used by the `cd` built-in to search for
 /// directories. Its value is a colon-separated list of directories.
 pub const CDPATH: &str = "CDPATH";
 
+/// The name of the `COLUMNS` variable
+///
+/// The `COLUMNS` variable specifies the number of columns of the terminal.
+/// An interactive shell updates it when the window size of the terminal
+/// changes.
+pub const COLUMNS: &str = "COLUMNS";
+
 /// The name of the `ENV` variable
 ///
 /// The `ENV` variable specifies the file to read for environment
 /// variables when the shell is invoked.
 pub const ENV: &str = "ENV";
 
+/// The name of the `FUNCNAME` variable
+///
+/// The `FUNCNAME` variable is an array of the names of the functions currently
+/// being executed, the innermost first. This is a non-POSIX extension.
+pub const FUNCNAME: &str = "FUNCNAME";
+
+/// The name of the `HISTFILE` variable
+///
+/// The `HISTFILE` variable specifies the file in which the command history
+/// is saved. See the [`history`](crate::history) module.
+pub const HISTFILE: &str = "HISTFILE";
+
+/// The name of the `HISTSIZE` variable
+///
+/// The `HISTSIZE` variable specifies the maximum number of entries in the
+/// command history. See the [`history`](crate::history) module.
+pub const HISTSIZE: &str = "HISTSIZE";
+
 /// The name of the `HOME` variable
 ///
 /// The `HOME` variable stores the path to the user's home directory.
@@ -42,6 +79,13 @@ pub const IFS: &str = "IFS";
 /// The initial value of the `IFS` variable (`" \t\n"`)
 pub const IFS_INITIAL_VALUE: &str = " \t\n";
 
+/// The name of the `LINES` variable
+///
+/// The `LINES` variable specifies the number of lines of the terminal.
+/// An interactive shell updates it when the window size of the terminal
+/// changes.
+pub const LINES: &str = "LINES";
+
 /// The name of the `LINENO` variable
 ///
 /// The `LINENO` variable expands to the line number of the current command.
@@ -21,7 +21,7 @@ use std::cell::Cell;
 use std::collections::{BTreeSet, HashMap};
 use std::rc::Weak;
 use std::task::Waker;
-use std::time::Instant;
+use std::time::{Duration, Instant};
 
 /// Priority queue of scheduled wakers to wake up processes at specific times
 ///
@@ -180,6 +180,36 @@ impl ScheduledWakerQueue {
             .map(|(wake_time, _)| *wake_time)
     }
 
+    /// Returns the next wake time, coalescing wakers within the resolution.
+    ///
+    /// This method is similar to [`next_wake_time`](Self::next_wake_time), but
+    /// it returns the latest wake time among the live wakers scheduled within
+    /// `resolution` after the earliest one. Waiting until the returned time
+    /// and then calling [`wake`](Self::wake) wakes all those wakers at once
+    /// instead of one after another. Since the returned time is never earlier
+    /// than the wake time of any of the coalesced wakers, no waker is woken
+    /// before its scheduled time.
+    ///
+    /// If `resolution` is zero, this method returns the same value as
+    /// `next_wake_time`.
+    pub fn coalesced_wake_time(&self, resolution: Duration) -> Option<Instant> {
+        let mut wake_times = self
+            .wakers_by_time
+            .iter()
+            .filter(|(_, entry)| entry.is_alive())
+            .map(|(wake_time, _)| *wake_time);
+        let first = wake_times.next()?;
+        let Some(limit) = first.checked_add(resolution) else {
+            return Some(first);
+        };
+        Some(
+            wake_times
+                .take_while(|&wake_time| wake_time <= limit)
+                .last()
+                .unwrap_or(first),
+        )
+    }
+
     /// Trims dead wakers to find the next wake time.
     ///
     /// This method removes dead wakers from the beginning of the priority queue
@@ -255,7 +285,6 @@ mod tests {
     use crate::test_helper::WakeFlag;
     use std::rc::Rc;
     use std::sync::Arc;
-    use std::time::Duration;
 
     fn dummy_waker() -> Rc<Cell<Option<Waker>>> {
         Rc::new(Cell::new(Some(Waker::noop().clone())))
@@ -385,6 +414,50 @@ mod tests {
         assert_eq!(queue.next_wake_time(), Some(now + Duration::from_secs(5)));
     }
 
+    #[test]
+    fn coalesced_wake_time_returns_none_if_empty() {
+        let queue = ScheduledWakerQueue::new();
+        assert_eq!(queue.coalesced_wake_time(Duration::from_secs(1)), None);
+    }
+
+    #[test]
+    fn coalesced_wake_time_returns_latest_time_within_resolution() {
+        let mut queue = ScheduledWakerQueue::new();
+        let now = Instant::now();
+        let waker_1 = dummy_waker();
+        let waker_2 = dummy_waker();
+        let waker_3 = dummy_waker();
+        let waker_4 = dummy_waker();
+        queue.push(now + Duration::from_millis(1000), Rc::downgrade(&waker_1));
+        queue.push(now + Duration::from_millis(1300), Rc::downgrade(&waker_2));
+        queue.push(now + Duration::from_millis(1500), Rc::downgrade(&waker_3));
+        queue.push(now + Duration::from_millis(1501), Rc::downgrade(&waker_4));
+
+        let result = queue.coalesced_wake_time(Duration::from_millis(500));
+        assert_eq!(result, Some(now + Duration::from_millis(1500)));
+        let result = queue.coalesced_wake_time(Duration::from_millis(100));
+        assert_eq!(result, Some(now + Duration::from_millis(1000)));
+        let result = queue.coalesced_wake_time(Duration::ZERO);
+        assert_eq!(result, queue.next_wake_time());
+    }
+
+    #[test]
+    fn coalesced_wake_time_ignores_dead_wakers() {
+        let mut queue = ScheduledWakerQueue::new();
+        let now = Instant::now();
+        let waker_1 = dummy_waker();
+        let waker_2 = dummy_waker();
+        let waker_3 = dummy_waker();
+        queue.push(now, Rc::downgrade(&waker_1));
+        queue.push(now + Duration::from_secs(1), Rc::downgrade(&waker_2));
+        queue.push(now + Duration::from_secs(2), Rc::downgrade(&waker_3));
+        drop(waker_1);
+        waker_3.take();
+
+        let result = queue.coalesced_wake_time(Duration::from_secs(5));
+        assert_eq!(result, Some(now + Duration::from_secs(1)));
+    }
+
     #[test]
     fn trim_to_next_wake_time_removes_leading_dead_wakers() {
         let mut queue = ScheduledWakerQueue::new();
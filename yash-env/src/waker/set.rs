@@ -152,6 +152,16 @@ impl WakerSet {
         entry.is_alive() && self.wakers.insert(entry)
     }
 
+    /// Removes dead wakers from the set.
+    ///
+    /// After this method returns, [`len`](Self::len) is the number of live
+    /// wakers in the set, and [`is_empty`](Self::is_empty) tells whether there
+    /// is any live waker.
+    #[inline]
+    pub fn remove_dead(&mut self) {
+        self.wakers.retain(WakerEntry::is_alive)
+    }
+
     /// Wakes all wakers in the set and clears the set.
     ///
     /// If a waker has been consumed or its strong reference has been dropped,
@@ -233,6 +243,24 @@ mod tests {
         assert_eq!(Rc::weak_count(&waker_2), 0);
     }
 
+    #[test]
+    fn removing_dead_wakers() {
+        let mut set = WakerSet::new();
+        let waker_1 = Rc::new(Cell::new(Some(Waker::noop().clone())));
+        let waker_2 = Rc::new(Cell::new(Some(Waker::noop().clone())));
+        let waker_3 = Rc::new(Cell::new(Some(Waker::noop().clone())));
+        assert!(set.insert(Rc::downgrade(&waker_1)));
+        assert!(set.insert(Rc::downgrade(&waker_2)));
+        assert!(set.insert(Rc::downgrade(&waker_3)));
+        waker_1.take(); // Consume waker_1 to make it dead
+        drop(waker_3);
+
+        set.remove_dead();
+        assert_eq!(set.len(), 1);
+        assert_eq!(Rc::weak_count(&waker_1), 0);
+        assert_eq!(Rc::weak_count(&waker_2), 1);
+    }
+
     #[test]
     fn dead_wakers_are_removed_before_insertion_if_full() {
         let mut set = WakerSet::new();
@@ -113,8 +113,13 @@ mod tests {
     use super::*;
     use crate::tests::{env_with_expand_text, env_with_expand_text_and_system};
     use futures_util::FutureExt as _;
+    use std::future::ready;
+    use std::ops::ControlFlow::Continue;
     use std::rc::Rc;
+    use yash_env::builtin::Builtin;
+    use yash_env::builtin::Type::Mandatory;
     use yash_env::input::Memory;
+    use yash_env::semantics::ExitStatus;
     use yash_env::system::Concurrent;
     use yash_env::system::r#virtual::SystemState;
     use yash_env::system::r#virtual::VirtualSystem;
@@ -122,6 +127,8 @@ mod tests {
     use yash_env::variable::PS1;
     use yash_env::variable::PS1_INITIAL_VALUE_NON_ROOT;
     use yash_env::variable::Value;
+    use yash_semantics::interactive_read_eval_loop;
+    use yash_syntax::parser::lex::Lexer;
 
     fn define_variable<S, N: Into<String>, V: Into<Value>>(env: &mut Env<S>, name: N, value: V) {
         env.variables
@@ -222,4 +229,34 @@ mod tests {
             .ok();
         assert_stderr(&state, |stderr| assert_eq!(stderr, "foo $ "));
     }
+
+    #[test]
+    fn prompting_for_commands_in_interactive_read_eval_loop() {
+        let system = VirtualSystem::new();
+        let state = Rc::clone(&system.state);
+        let mut env = env_with_expand_text_and_system(Rc::new(Concurrent::new(system)));
+        define_variable(&mut env, PS1, "$? $ ");
+        define_variable(&mut env, PS2, "> ");
+        env.builtins.insert(
+            "false",
+            Builtin::new(Mandatory, |_env, _args| {
+                Box::pin(ready(yash_env::builtin::Result::new(ExitStatus::FAILURE)))
+            }),
+        );
+        let ref_env = RefCell::new(&mut env);
+        let prompter = Prompter::new(Memory::new("false\nx='a\nb'\n"), &ref_env);
+        let mut lexer = Lexer::new(Box::new(prompter));
+
+        let result = interactive_read_eval_loop(&ref_env, &mut lexer)
+            .now_or_never()
+            .unwrap();
+        drop(lexer);
+        assert_eq!(result, Continue(()));
+        assert_eq!(env.exit_status, ExitStatus::SUCCESS);
+        assert_eq!(env.variables.get_scalar("x"), Some("a\nb"));
+        // The main prompt shows the exit status of the previous command, and
+        // the continuation prompt is shown for the second line of the
+        // assignment.
+        assert_stderr(&state, |stderr| assert_eq!(stderr, "0 $ 1 $ > 0 $ "));
+    }
 }
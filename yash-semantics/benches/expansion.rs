@@ -0,0 +1,191 @@
+// This file is part of yash, an extended POSIX shell.
+// Copyright (C) 2026 WATANABE Yuki
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+//! Benchmarks for word expansion
+//!
+//! Run the benchmarks with `cargo bench -p yash-semantics`. To run only some
+//! of them, pass a substring of the benchmark names after `--`, as in
+//! `cargo bench -p yash-semantics -- join`.
+//!
+//! This is a minimal self-contained harness that does not depend on external
+//! benchmarking crates. Each benchmark is warmed up and then run repeatedly
+//! for a fixed duration, and the mean time per iteration is printed.
+
+use futures_util::FutureExt as _;
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+use yash_env::Env;
+use yash_env::variable::{IFS, Scope, Value};
+use yash_semantics::expansion::{expand_word, expand_words};
+use yash_syntax::syntax::Word;
+
+/// Number of iterations run before measuring
+const WARM_UP_ITERATIONS: u32 = 10;
+
+/// Duration for which each benchmark is measured
+const MEASUREMENT_TIME: Duration = Duration::from_secs(3);
+
+/// Number of elements in large arrays and strings
+const SIZE: usize = 10_000;
+
+/// Runs a benchmark and prints the result.
+fn bench<F: FnMut()>(filter: Option<&str>, name: &str, mut f: F) {
+    if filter.is_some_and(|filter| !name.contains(filter)) {
+        return;
+    }
+
+    for _ in 0..WARM_UP_ITERATIONS {
+        f();
+    }
+
+    let mut iterations = 0u32;
+    let start = Instant::now();
+    let elapsed = loop {
+        f();
+        iterations += 1;
+        let elapsed = start.elapsed();
+        if elapsed >= MEASUREMENT_TIME {
+            break elapsed;
+        }
+    };
+
+    let nanos_per_iteration = elapsed.as_nanos() / u128::from(iterations);
+    println!("{name:<40} {nanos_per_iteration:>12} ns/iter ({iterations} iterations)");
+}
+
+fn parse_word(source: &str) -> Word {
+    source.parse().unwrap()
+}
+
+/// Expands a word with many parameter expansions into a single field.
+fn many_parameters(filter: Option<&str>) {
+    let mut env = Env::new_virtual();
+    let mut source = String::new();
+    for i in 0..100 {
+        let name = format!("v{i}");
+        env.variables
+            .get_or_new(&*name, Scope::Global)
+            .assign(format!("value{i}"), None)
+            .unwrap();
+        source.push_str(&format!("${{{name}}}"));
+    }
+    let word = parse_word(&source);
+
+    bench(filter, "many_parameters", || {
+        let result = expand_word(&mut env, &word).now_or_never().unwrap();
+        black_box(result.unwrap());
+    });
+}
+
+/// Joins a large array with `"${a[*]}"`.
+fn large_array_join(filter: Option<&str>) {
+    let mut env = Env::new_virtual();
+    let values = (0..SIZE).map(|i| i.to_string());
+    env.variables
+        .get_or_new("a", Scope::Global)
+        .assign(Value::array(values), None)
+        .unwrap();
+    let word = parse_word(r#""${a[*]}""#);
+
+    bench(filter, "large_array_join", || {
+        let result = expand_word(&mut env, &word).now_or_never().unwrap();
+        black_box(result.unwrap());
+    });
+}
+
+/// Joins many positional parameters with `"$*"` using a non-default `IFS`.
+fn positional_params_ifs_join(filter: Option<&str>) {
+    let mut env = Env::new_virtual();
+    env.variables.positional_params_mut().values = (0..SIZE).map(|i| i.to_string()).collect();
+    env.variables
+        .get_or_new(IFS, Scope::Global)
+        .assign(":", None)
+        .unwrap();
+    let word = parse_word(r#""$*""#);
+
+    bench(filter, "positional_params_ifs_join", || {
+        let result = expand_word(&mut env, &word).now_or_never().unwrap();
+        black_box(result.unwrap());
+    });
+}
+
+/// Splits a long value with a mix of whitespace and non-whitespace `IFS`
+/// characters into many fields.
+fn field_splitting(filter: Option<&str>) {
+    let mut env = Env::new_virtual();
+    let value = (0..SIZE)
+        .map(|i| match i % 3 {
+            0 => format!("{i} "),
+            1 => format!("{i} : "),
+            _ => format!("{i}\t:"),
+        })
+        .collect::<String>();
+    env.variables
+        .get_or_new("v", Scope::Global)
+        .assign(value, None)
+        .unwrap();
+    env.variables
+        .get_or_new(IFS, Scope::Global)
+        .assign(" \t:", None)
+        .unwrap();
+    let words = [parse_word("$v")];
+
+    bench(filter, "field_splitting", || {
+        let result = expand_words(&mut env, &words).now_or_never().unwrap();
+        black_box(result.unwrap());
+    });
+}
+
+/// Expands many literal words, which take the fast path that skips the
+/// attributed-character machinery.
+fn literal_words(filter: Option<&str>) {
+    let mut env = Env::new_virtual();
+    let words = (0..SIZE)
+        .map(|i| parse_word(&format!("word{i}")))
+        .collect::<Vec<_>>();
+
+    bench(filter, "literal_words", || {
+        let result = expand_words(&mut env, &words).now_or_never().unwrap();
+        black_box(result.unwrap());
+    });
+}
+
+/// Expands many quoted words, which yield the same fields as
+/// [`literal_words`] but go through the full expansion steps.
+fn quoted_words(filter: Option<&str>) {
+    let mut env = Env::new_virtual();
+    let words = (0..SIZE)
+        .map(|i| parse_word(&format!("'word{i}'")))
+        .collect::<Vec<_>>();
+
+    bench(filter, "quoted_words", || {
+        let result = expand_words(&mut env, &words).now_or_never().unwrap();
+        black_box(result.unwrap());
+    });
+}
+
+fn main() {
+    // Cargo passes `--bench` to the benchmark binary, so skip options.
+    let filter = std::env::args().skip(1).find(|arg| !arg.starts_with("--"));
+    let filter = filter.as_deref();
+
+    many_parameters(filter);
+    large_array_join(filter);
+    positional_params_ifs_join(filter);
+    field_splitting(filter);
+    literal_words(filter);
+    quoted_words(filter);
+}
@@ -20,6 +20,7 @@ use super::Command;
 use crate::Handle as _;
 use crate::Runtime;
 use crate::redir::RedirGuard;
+use crate::trap::run_err_trap;
 use crate::xtrace::XTrace;
 use crate::xtrace::finish;
 use std::ops::ControlFlow::Continue;
@@ -52,6 +53,7 @@ async fn evaluate_condition<S: Runtime + 'static>(
     Continue(env.exit_status.is_successful())
 }
 
+mod arith_for;
 mod case;
 mod for_loop;
 mod r#if;
@@ -70,6 +72,7 @@ impl<S: Runtime + 'static> Command<S> for syntax::FullCompoundCommand {
             Ok(_) => self.command.execute(&mut env).await,
             Err(error) => {
                 error.handle(&mut env).await?;
+                run_err_trap(&mut env).await?;
                 env.apply_errexit()
             }
         }
@@ -96,6 +99,18 @@ impl<S: Runtime + 'static> Command<S> for syntax::FullCompoundCommand {
 /// field resulting from the expansion is assigned to the variable `name`, and
 /// in turn, `body` is executed.
 ///
+/// # Arithmetic for loop
+///
+/// The arithmetic for loop first evaluates the `init` expression. Then, the
+/// `cond` expression is evaluated, and if the result is non-zero, the `body` is
+/// executed and the `update` expression is evaluated. This is repeated until
+/// `cond` evaluates to zero. An empty `cond` is regarded as always non-zero.
+/// The exit status of the loop is that of the last executed `body`, or zero if
+/// the `body` was never executed.
+///
+/// A `continue` in the `body` skips to the evaluation of `update`, and a
+/// `break` exits the loop without evaluating `update`.
+///
 /// # While loop
 ///
 /// The `condition` is executed first. If its exit status is zero, the `body` is
@@ -136,6 +151,13 @@ impl<S: Runtime + 'static> Command<S> for syntax::CompoundCommand {
             Grouping(list) => list.execute(env).await,
             Subshell { body, location } => subshell::execute(env, body.clone(), location).await,
             For { name, values, body } => for_loop::execute(env, name, values, body).await,
+            ArithmeticFor {
+                init,
+                cond,
+                update,
+                body,
+                location,
+            } => arith_for::execute(env, init, cond, update, body, location).await,
             While { condition, body } => while_loop::execute_while(env, condition, body).await,
             Until { condition, body } => while_loop::execute_until(env, condition, body).await,
             If {
@@ -0,0 +1,254 @@
+// This file is part of yash, an extended POSIX shell.
+// Copyright (C) 2026 WATANABE Yuki
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+//! Execution of the arithmetic for loop
+
+use crate::Handle as _;
+use crate::Runtime;
+use crate::command::Command as _;
+use crate::expansion::expand_text;
+use crate::expansion::initial::evaluate_arith;
+use std::ops::ControlFlow::{Break, Continue};
+use yash_arith::Value;
+use yash_env::Env;
+use yash_env::semantics::Divert;
+use yash_env::semantics::ExitStatus;
+use yash_env::semantics::Result;
+use yash_env::stack::Frame;
+use yash_syntax::source::Location;
+use yash_syntax::syntax::List;
+use yash_syntax::syntax::Text;
+
+/// Expands and evaluates an arithmetic expression of the loop header.
+///
+/// Returns `None` if the expression is empty.
+async fn evaluate<S: Runtime + 'static>(
+    env: &mut Env<S>,
+    text: &Text,
+    location: &Location,
+) -> Result<Option<Value>> {
+    let result = match expand_text(env, text).await {
+        Ok((expression, _)) if expression.trim().is_empty() => Ok(None),
+        Ok((expression, _)) => evaluate_arith(env, &expression, location).map(Some),
+        Err(error) => Err(error),
+    };
+    match result {
+        Ok(value) => Continue(value),
+        Err(error) => {
+            error.handle(env).await?;
+            Continue(None)
+        }
+    }
+}
+
+/// Executes the arithmetic for loop.
+pub async fn execute<S: Runtime + 'static>(
+    env: &mut Env<S>,
+    init: &Text,
+    cond: &Text,
+    update: &Text,
+    body: &List,
+    location: &Location,
+) -> Result {
+    let mut exit_status = ExitStatus::SUCCESS;
+
+    evaluate(env, init, location).await?;
+
+    let env = &mut env.push_frame(Frame::Loop);
+
+    while evaluate(env, cond, location).await? != Some(Value::Integer(0)) {
+        match body.execute(env).await {
+            Continue(()) | Break(Divert::Continue { count: 0 }) => (),
+            Break(Divert::Break { count: 0 }) => {
+                exit_status = env.exit_status;
+                break;
+            }
+            Break(Divert::Break { count }) => return Break(Divert::Break { count: count - 1 }),
+            Break(Divert::Continue { count }) => {
+                return Break(Divert::Continue { count: count - 1 });
+            }
+            other => return other,
+        }
+        exit_status = env.exit_status;
+        evaluate(env, update, location).await?;
+    }
+
+    env.exit_status = exit_status;
+    Continue(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::break_builtin;
+    use crate::tests::continue_builtin;
+    use crate::tests::echo_builtin;
+    use crate::tests::return_builtin;
+    use futures_util::FutureExt as _;
+    use std::cell::RefCell;
+    use std::rc::Rc;
+    use yash_env::VirtualSystem;
+    use yash_env::system::Concurrent;
+    use yash_env::system::r#virtual::SystemState;
+    use yash_env::test_helper::assert_stderr;
+    use yash_env::test_helper::assert_stdout;
+    use yash_env::variable::Value;
+    use yash_syntax::syntax::CompoundCommand;
+
+    fn fixture() -> (Env<Rc<Concurrent<VirtualSystem>>>, Rc<RefCell<SystemState>>) {
+        let system = VirtualSystem::new();
+        let state = Rc::clone(&system.state);
+        let mut env = Env::with_system(Rc::new(Concurrent::new(system)));
+        env.builtins.insert("break", break_builtin());
+        env.builtins.insert("continue", continue_builtin());
+        env.builtins.insert("echo", echo_builtin());
+        env.builtins.insert("return", return_builtin());
+        (env, state)
+    }
+
+    #[test]
+    fn three_round_arithmetic_for_loop() {
+        let (mut env, state) = fixture();
+        let command = "for ((i=0;i<3;i++)); do echo $i; done";
+        let command: CompoundCommand = command.parse().unwrap();
+
+        let result = command.execute(&mut env).now_or_never().unwrap();
+        assert_eq!(result, Continue(()));
+        assert_eq!(env.exit_status, ExitStatus::SUCCESS);
+        assert_stdout(&state, |stdout| assert_eq!(stdout, "0\n1\n2\n"));
+        assert_eq!(
+            env.variables.get("i").unwrap().value,
+            Some(Value::scalar("3"))
+        );
+    }
+
+    #[test]
+    fn zero_round_arithmetic_for_loop() {
+        let (mut env, state) = fixture();
+        env.exit_status = ExitStatus(5);
+        let command = "for ((i=3;i<3;i++)); do echo $i; done";
+        let command: CompoundCommand = command.parse().unwrap();
+
+        let result = command.execute(&mut env).now_or_never().unwrap();
+        assert_eq!(result, Continue(()));
+        assert_eq!(env.exit_status, ExitStatus::SUCCESS);
+        assert_stdout(&state, |stdout| assert_eq!(stdout, ""));
+    }
+
+    #[test]
+    fn exit_status_of_arithmetic_for_loop() {
+        let (mut env, _state) = fixture();
+        let command = "for ((i=0;i<2;i++)) do return -n $((i+7)); done";
+        let command: CompoundCommand = command.parse().unwrap();
+
+        let result = command.execute(&mut env).now_or_never().unwrap();
+        assert_eq!(result, Continue(()));
+        assert_eq!(env.exit_status, ExitStatus(8));
+    }
+
+    #[test]
+    fn empty_condition_is_always_true() {
+        let (mut env, state) = fixture();
+        let command = "for ((;;)) do echo $((i+=1)); case $i in (3) return 9; esac; done";
+        let command: CompoundCommand = command.parse().unwrap();
+
+        let result = command.execute(&mut env).now_or_never().unwrap();
+        assert_eq!(result, Break(Divert::Return(Some(ExitStatus(9)))));
+        assert_stdout(&state, |stdout| assert_eq!(stdout, "1\n2\n3\n"));
+    }
+
+    #[test]
+    fn expressions_are_expanded() {
+        let (mut env, state) = fixture();
+        let command = "for ((i=$1 ; i < ${2} ; i+=$3)) do echo $i; done";
+        let command: CompoundCommand = command.parse().unwrap();
+        env.variables.positional_params_mut().values =
+            vec!["1".to_string(), "6".to_string(), "2".to_string()];
+
+        let result = command.execute(&mut env).now_or_never().unwrap();
+        assert_eq!(result, Continue(()));
+        assert_stdout(&state, |stdout| assert_eq!(stdout, "1\n3\n5\n"));
+    }
+
+    #[test]
+    fn arithmetic_error_in_condition() {
+        let (mut env, state) = fixture();
+        let command = "for ((i=0;i/0;i++)) do echo $i; done";
+        let command: CompoundCommand = command.parse().unwrap();
+
+        let result = command.execute(&mut env).now_or_never().unwrap();
+        assert_eq!(result, Break(Divert::Interrupt(Some(ExitStatus::ERROR))));
+        assert_stdout(&state, |stdout| assert_eq!(stdout, ""));
+        assert_stderr(&state, |stderr| assert_ne!(stderr, ""));
+    }
+
+    #[test]
+    fn break_arithmetic_for_loop() {
+        let (mut env, state) = fixture();
+        let command = "for ((i=0;i<5;i++)) do echo $i; case $i in (2) break; esac; done";
+        let command: CompoundCommand = command.parse().unwrap();
+
+        let result = command.execute(&mut env).now_or_never().unwrap();
+        assert_eq!(result, Continue(()));
+        assert_eq!(env.exit_status, ExitStatus::SUCCESS);
+        assert_stdout(&state, |stdout| assert_eq!(stdout, "0\n1\n2\n"));
+        // The update is not evaluated after break.
+        assert_eq!(
+            env.variables.get("i").unwrap().value,
+            Some(Value::scalar("2"))
+        );
+    }
+
+    #[test]
+    fn continue_arithmetic_for_loop() {
+        let (mut env, state) = fixture();
+        let command = "for ((i=0;i<4;i++)) do case $i in (1|2) continue; esac; echo $i; done";
+        let command: CompoundCommand = command.parse().unwrap();
+
+        let result = command.execute(&mut env).now_or_never().unwrap();
+        assert_eq!(result, Continue(()));
+        assert_stdout(&state, |stdout| assert_eq!(stdout, "0\n3\n"));
+        // The update is evaluated after continue.
+        assert_eq!(
+            env.variables.get("i").unwrap().value,
+            Some(Value::scalar("4"))
+        );
+    }
+
+    #[test]
+    fn break_outer_loop_from_arithmetic_for_loop() {
+        let (mut env, state) = fixture();
+        let command =
+            "for ((i=0;i<3;i++)) do for ((j=0;j<3;j++)) do echo $i$j; break 2; done; done";
+        let command: CompoundCommand = command.parse().unwrap();
+
+        let result = command.execute(&mut env).now_or_never().unwrap();
+        assert_eq!(result, Continue(()));
+        assert_stdout(&state, |stdout| assert_eq!(stdout, "00\n"));
+    }
+
+    #[test]
+    fn continue_outer_loop_from_arithmetic_for_loop() {
+        let (mut env, state) = fixture();
+        let command =
+            "for ((i=0;i<2;i++)) do for ((j=0;j<3;j++)) do echo $i$j; continue 2; done; done";
+        let command: CompoundCommand = command.parse().unwrap();
+
+        let result = command.execute(&mut env).now_or_never().unwrap();
+        assert_eq!(result, Continue(()));
+        assert_stdout(&state, |stdout| assert_eq!(stdout, "00\n10\n"));
+    }
+}
@@ -18,6 +18,7 @@
 
 use crate::Runtime;
 use crate::command::Command as _;
+use crate::trap::run_err_trap;
 use crate::trap::run_exit_trap;
 use std::ops::ControlFlow::Break;
 use std::rc::Rc;
@@ -44,6 +45,7 @@ pub async fn execute<S: Runtime + 'static>(
     match subshell.await {
         Ok((pid, result)) => {
             env.exit_status = handle_job_status(env, pid, result, || body.to_string())?;
+            run_err_trap(env).await?;
             env.apply_errexit()
         }
         Err(errno) => {
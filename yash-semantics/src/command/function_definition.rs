@@ -21,6 +21,7 @@ use crate::Runtime;
 use crate::command::Command;
 use crate::expansion::Field;
 use crate::expansion::expand_word;
+use crate::trap::run_err_trap;
 use std::ops::ControlFlow::Continue;
 use std::rc::Rc;
 use yash_env::Env;
@@ -66,6 +67,7 @@ impl<S: Runtime + 'static> FunctionBody<S> for BodyImpl {
 impl<S: Runtime + 'static> Command<S> for syntax::FunctionDefinition {
     async fn execute(&self, env: &mut Env<S>) -> Result {
         define_function(env, self).await?;
+        run_err_trap(env).await?;
         env.apply_errexit()
     }
 }
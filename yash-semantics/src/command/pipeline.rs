@@ -18,6 +18,7 @@
 
 use super::Command;
 use crate::Runtime;
+use crate::trap::run_err_trap;
 use crate::trap::run_exit_trap;
 use enumset::EnumSet;
 use itertools::Itertools as _;
@@ -35,7 +36,7 @@ use yash_env::stack::Frame;
 use yash_env::subshell::Config;
 use yash_env::subshell::JobControl;
 use yash_env::system::concurrency::WriteAll;
-use yash_env::system::{Close, Dup, Errno, Isatty, Pipe};
+use yash_env::system::{Close, Dup, Errno, Fcntl, FdFlag, Isatty, Pipe};
 use yash_syntax::syntax;
 
 /// Executes the pipeline.
@@ -114,6 +115,7 @@ async fn execute_commands_in_pipeline<S: Runtime + 'static>(
             } else {
                 execute_multi_command_pipeline(env, commands).await?
             }
+            run_err_trap(env).await?;
             env.apply_errexit()
         }
     }
@@ -195,7 +197,7 @@ async fn execute_multi_command_pipeline<S: Runtime + 'static>(
 
 async fn shift_or_fail<S>(env: &mut Env<S>, pipes: &mut PipeSet, has_next: bool) -> Result
 where
-    S: Close + Isatty + Pipe + WriteAll,
+    S: Close + Fcntl + Isatty + Pipe + WriteAll,
 {
     match pipes.shift(env, has_next) {
         Ok(()) => Continue(()),
@@ -266,8 +268,9 @@ impl PipeSet {
     /// Updates the pipe set for the next command.
     ///
     /// Closes FDs that are no longer necessary and opens a new pipe if there is
-    /// a next command.
-    fn shift<S: Pipe + Close>(
+    /// a next command. The new pipe FDs have the `CloseOnExec` flag so that
+    /// they do not leak into external utilities.
+    fn shift<S: Close + Fcntl + Pipe>(
         &mut self,
         env: &mut Env<S>,
         has_next: bool,
@@ -285,7 +288,10 @@ impl PipeSet {
 
         self.next = None;
         if has_next {
-            self.next = Some(env.system.pipe()?);
+            let (reader, writer) = env.system.pipe()?;
+            self.next = Some((reader, writer));
+            env.system.fcntl_setfd(reader, FdFlag::CloseOnExec.into())?;
+            env.system.fcntl_setfd(writer, FdFlag::CloseOnExec.into())?;
         }
 
         Ok(())
@@ -293,7 +299,10 @@ impl PipeSet {
 
     /// Moves the pipe FDs to stdin/stdout and closes the FDs that are no longer
     /// necessary.
-    fn move_to_stdin_stdout<S: Dup + Close>(
+    ///
+    /// The `CloseOnExec` flag is cleared for stdin/stdout even if the pipe FD
+    /// is already at stdin/stdout.
+    fn move_to_stdin_stdout<S: Close + Dup + Fcntl>(
         mut self,
         env: &mut Env<S>,
     ) -> std::result::Result<(), Errno> {
@@ -310,13 +319,17 @@ impl PipeSet {
                 }
                 env.system.dup2(writer, Fd::STDOUT)?;
                 env.system.close(writer)?;
+            } else {
+                env.system.fcntl_setfd(Fd::STDOUT, EnumSet::empty())?;
             }
         }
-        if let Some(reader) = self.read_previous
-            && reader != Fd::STDIN
-        {
-            env.system.dup2(reader, Fd::STDIN)?;
-            env.system.close(reader)?;
+        if let Some(reader) = self.read_previous {
+            if reader != Fd::STDIN {
+                env.system.dup2(reader, Fd::STDIN)?;
+                env.system.close(reader)?;
+            } else {
+                env.system.fcntl_setfd(Fd::STDIN, EnumSet::empty())?;
+            }
         }
         Ok(())
     }
@@ -330,6 +343,7 @@ mod tests {
     use crate::tests::suspend_builtin;
     use futures_util::FutureExt as _;
     use std::assert_matches;
+    use std::cell::RefCell;
     use std::pin::Pin;
     use std::rc::Rc;
     use yash_env::VirtualSystem;
@@ -341,7 +355,9 @@ mod tests {
     use yash_env::semantics::Field;
     use yash_env::system::Concurrent;
     use yash_env::system::GetPid as _;
+    use yash_env::system::Mode;
     use yash_env::system::r#virtual::FileBody;
+    use yash_env::system::r#virtual::Inode;
     use yash_env::system::r#virtual::SIGSTOP;
     use yash_env::test_helper::assert_stdout;
     use yash_env::test_helper::in_virtual_system;
@@ -675,6 +691,37 @@ mod tests {
         })
     }
 
+    #[test]
+    fn pipe_fds_are_not_inherited_by_external_utility() {
+        in_virtual_system(|mut env, state| async move {
+            env.builtins.insert("cat", cat_builtin());
+            let mut content = Inode::default();
+            content.body = FileBody::Regular {
+                content: Vec::new(),
+                is_native_executable: true,
+            };
+            content.permissions.set(Mode::USER_EXEC, true);
+            let content = Rc::new(RefCell::new(content));
+            state
+                .borrow_mut()
+                .file_system
+                .save("/some/file", content)
+                .unwrap();
+
+            let pipeline: syntax::Pipeline = "/some/file 3>foo | cat".parse().unwrap();
+            let result = pipeline.execute(&mut env).await;
+            assert_eq!(result, Continue(()));
+
+            // The pipe FDs have been moved to stdout and the user-redirected
+            // FD 3 is inherited, but no other FDs are.
+            let state = state.borrow();
+            let process = state.processes.values();
+            let process = process.filter(|p| p.last_exec().is_some()).exactly_one();
+            let process = process.unwrap();
+            assert_eq!(process.last_exec_fds(), [Fd(0), Fd(1), Fd(2), Fd(3)]);
+        })
+    }
+
     #[test]
     fn pipe_set_shift_to_first_command() {
         let system = VirtualSystem::new();
@@ -689,8 +736,14 @@ mod tests {
         assert_eq!(pipes.next, Some((Fd(3), Fd(4))));
         let state = state.borrow();
         let process = &state.processes[&process_id];
-        assert_eq!(process.fds().get(&Fd(3)).unwrap().flags, EnumSet::empty());
-        assert_eq!(process.fds().get(&Fd(4)).unwrap().flags, EnumSet::empty());
+        assert_eq!(
+            process.fds().get(&Fd(3)).unwrap().flags,
+            FdFlag::CloseOnExec
+        );
+        assert_eq!(
+            process.fds().get(&Fd(4)).unwrap().flags,
+            FdFlag::CloseOnExec
+        );
     }
 
     #[test]
@@ -708,9 +761,18 @@ mod tests {
         assert_eq!(pipes.next, Some((Fd(4), Fd(5))));
         let state = state.borrow();
         let process = &state.processes[&process_id];
-        assert_eq!(process.fds().get(&Fd(3)).unwrap().flags, EnumSet::empty());
-        assert_eq!(process.fds().get(&Fd(4)).unwrap().flags, EnumSet::empty());
-        assert_eq!(process.fds().get(&Fd(5)).unwrap().flags, EnumSet::empty());
+        assert_eq!(
+            process.fds().get(&Fd(3)).unwrap().flags,
+            FdFlag::CloseOnExec
+        );
+        assert_eq!(
+            process.fds().get(&Fd(4)).unwrap().flags,
+            FdFlag::CloseOnExec
+        );
+        assert_eq!(
+            process.fds().get(&Fd(5)).unwrap().flags,
+            FdFlag::CloseOnExec
+        );
     }
 
     #[test]
@@ -728,7 +790,33 @@ mod tests {
         assert_eq!(pipes.next, None);
         let state = state.borrow();
         let process = &state.processes[&process_id];
-        assert_eq!(process.fds().get(&Fd(3)).unwrap().flags, EnumSet::empty());
+        assert_eq!(
+            process.fds().get(&Fd(3)).unwrap().flags,
+            FdFlag::CloseOnExec
+        );
+    }
+
+    #[test]
+    fn pipe_set_move_to_stdout_clears_cloexec() {
+        let system = VirtualSystem::new();
+        let process_id = system.process_id;
+        let state = Rc::clone(&system.state);
+        let mut env = Env::with_system(system);
+        env.system.close(Fd::STDIN).unwrap();
+        env.system.close(Fd::STDOUT).unwrap();
+        let mut pipes = PipeSet::new();
+        pipes.shift(&mut env, true).unwrap();
+        assert_eq!(pipes.next, Some((Fd::STDIN, Fd::STDOUT)));
+
+        let result = pipes.move_to_stdin_stdout(&mut env);
+        assert_eq!(result, Ok(()));
+        let state = state.borrow();
+        let process = &state.processes[&process_id];
+        assert_eq!(process.fds().get(&Fd::STDIN), None);
+        assert_eq!(
+            process.fds().get(&Fd::STDOUT).unwrap().flags,
+            EnumSet::empty()
+        );
     }
 
     // TODO test PipeSet::move_to_stdin_stdout
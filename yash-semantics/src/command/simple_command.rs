@@ -26,6 +26,7 @@ use crate::Runtime;
 use crate::command::Command;
 use crate::command::search::classify;
 use crate::expansion::expand_word_with_mode;
+use crate::trap::{run_debug_trap, run_err_trap};
 use crate::xtrace::XTrace;
 use std::ops::ControlFlow::Continue;
 use yash_env::Env;
@@ -35,6 +36,8 @@ use yash_env::semantics::ExitStatus;
 use yash_env::semantics::Field;
 use yash_env::semantics::Result;
 #[cfg(doc)]
+use yash_env::semantics::command::CommandNotFoundHandlerConfig;
+#[cfg(doc)]
 use yash_env::variable::Context;
 use yash_env::variable::Scope;
 use yash_syntax::syntax;
@@ -46,11 +49,13 @@ use yash_syntax::syntax::Word;
 ///
 /// # Outline
 ///
-/// The execution starts with the [expansion](crate::expansion) of the command
-/// words. Next, the [command search](crate::command::search) is performed to
+/// The execution starts with running the DEBUG trap, if any, with
+/// [`run_debug_trap`]. Then, the command words are
+/// [expanded](crate::expansion). Next, the [command search](crate::command::search) is performed to
 /// find an execution [target](crate::command::search::Target) named by the
 /// first [field](Field) of the expansion results. The target type defines how
-/// the target is executed. After the execution, the `ErrExit` option is applied
+/// the target is executed. After the execution, the ERR trap is run with
+/// [`run_err_trap`] if the command failed, and the `ErrExit` option is applied
 /// with [`Env::apply_errexit`].
 ///
 /// # Target types and their semantics
@@ -109,12 +114,22 @@ use yash_syntax::syntax::Word;
 /// executable file so that the restarted shell executes the external utility as
 /// a shell script.
 ///
+/// If `execve` fails with an `ENOENT` error because the file found by the
+/// command search has been removed, `$PATH` is searched again and `execve` is
+/// retried once with the newly found file.
+///
 /// ## Target not found
 ///
 /// If the command search could not find a valid target, the execution proceeds
 /// in the same manner as an external utility except that it does not call
 /// `execve` and performs error handling as if it failed with `ENOENT`.
 ///
+/// However, if a [`CommandNotFoundHandlerConfig`] is stored in `env.any` and
+/// the function it names is defined, the function is executed in the current
+/// shell environment instead of reporting the error. The command name and
+/// arguments are passed to the function as positional parameters, and the exit
+/// status of the function becomes that of the simple command.
+///
 /// # Redirections
 ///
 /// Redirections are performed in the order of appearance. The file descriptors
@@ -163,6 +178,8 @@ use yash_syntax::syntax::Word;
 /// detail semantics may differ in other shell implementations.
 impl<S: Runtime + 'static> Command<S> for syntax::SimpleCommand {
     async fn execute(&self, env: &mut Env<S>) -> Result {
+        run_debug_trap(env).await?;
+
         let (fields, exit_status) = match expand_words(env, &self.words).await {
             Ok(result) => result,
             Err(error) => return error.handle(env).await,
@@ -186,6 +203,7 @@ impl<S: Runtime + 'static> Command<S> for syntax::SimpleCommand {
             execute_absent_target(env, &self.assigns, &self.redirs, exit_status).await
         }?;
 
+        run_err_trap(env).await?;
         env.apply_errexit()
     }
 }
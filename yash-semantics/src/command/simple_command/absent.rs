@@ -43,7 +43,7 @@ pub async fn execute_absent_target<S: Runtime + 'static>(
 ) -> Result {
     // Perform redirections in a subshell
     let redir_exit_status = if let Some(redir) = redirs.first() {
-        let first_redir_location = redir.body.operand().location.clone();
+        let first_redir_location = redir.body.location().clone();
         let redirs_2 = Rc::clone(redirs);
         let subshell = Config::foreground().start_and_wait(env, async move |env, _job_control| {
             let env = &mut RedirGuard::new(env);
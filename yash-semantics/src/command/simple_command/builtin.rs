@@ -19,6 +19,7 @@
 use super::perform_assignments;
 use crate::Handle as _;
 use crate::Runtime;
+use crate::command::search::PathEnv as _;
 use crate::command::search::search_path;
 use crate::redir::RedirGuard;
 use crate::xtrace::XTrace;
@@ -79,7 +80,10 @@ pub async fn execute_builtin<S: Runtime + 'static>(
         print(env, xtrace).await;
 
         let name = fields.remove(0);
-        if builtin.r#type == Substitutive && search_path(env, &name.value).is_none() {
+        if builtin.r#type == Substitutive
+            && !env.external_utilities_disabled()
+            && search_path(env, &name.value).is_none()
+        {
             print_error(
                 env,
                 format!("cannot execute built-in utility {:?}", name.value).into(),
@@ -172,6 +176,7 @@ mod tests {
     use yash_env::system::Mode;
     use yash_env::system::SendSignal as _;
     use yash_env::system::Signals as _;
+    use yash_env::system::concurrency::WriteAll as _;
     use yash_env::system::r#virtual::FileBody;
     use yash_env::system::r#virtual::Inode;
     use yash_env::system::r#virtual::SIGINT;
@@ -228,6 +233,31 @@ mod tests {
         });
     }
 
+    #[test]
+    fn simple_command_applies_stderr_redirection_to_builtin() {
+        let system = VirtualSystem::new();
+        let state = Rc::clone(&system.state);
+        let mut env = Env::with_system(Rc::new(Concurrent::new(system)));
+        env.builtins.insert(
+            "warn",
+            Builtin::new(Mandatory, |env, _args| {
+                Box::pin(async move {
+                    env.system.print_error("warning\n").await;
+                    yash_env::builtin::Result::default()
+                })
+            }),
+        );
+        let command: syntax::SimpleCommand = "warn 2>/tmp/file".parse().unwrap();
+        _ = command.execute(&mut env).now_or_never().unwrap();
+
+        let file = state.borrow().file_system.get("/tmp/file").unwrap();
+        let file = file.borrow();
+        assert_matches!(&file.body, FileBody::Regular { content, .. } => {
+            assert_eq!(from_utf8(content), Ok("warning\n"));
+        });
+        assert_stderr(&state, |stderr| assert_eq!(stderr, ""));
+    }
+
     #[test]
     fn simple_command_by_default_reverts_redirections_to_builtin() {
         let system = VirtualSystem::new();
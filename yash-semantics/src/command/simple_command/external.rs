@@ -16,9 +16,11 @@
 
 //! Simple command semantics for external utilities
 
+use super::execute_function_body;
 use super::perform_assignments;
 use crate::Handle as _;
 use crate::Runtime;
+use crate::command::search::PathEnv as _;
 use crate::command::search::search_path;
 use crate::redir::RedirGuard;
 use crate::xtrace::XTrace;
@@ -34,6 +36,7 @@ use yash_env::job::RunUnblocking;
 use yash_env::semantics::ExitStatus;
 use yash_env::semantics::Field;
 use yash_env::semantics::Result;
+use yash_env::semantics::command::CommandNotFoundHandlerConfig;
 use yash_env::semantics::command::ReplaceCurrentProcessError;
 use yash_env::semantics::command::run_external_utility_in_subshell;
 use yash_env::subshell::BlockSignals;
@@ -41,8 +44,8 @@ use yash_env::system::concurrency::WaitForSignals;
 use yash_env::system::concurrency::WriteAll;
 use yash_env::system::resource::SetRlimit;
 use yash_env::system::{
-    Close, Dup, Exec, Exit, Fork, GetPid, Isatty, Open, SendSignal, SetPgid, ShellPath, TcSetPgrp,
-    Wait,
+    Close, Dup, Exec, Exit, Fork, GetPid, IsExecutableFile, Isatty, Open, SendSignal, SetPgid,
+    ShellPath, TcSetPgrp, Wait,
 };
 use yash_env::trap::SignalSystem;
 use yash_env::variable::Context;
@@ -69,7 +72,9 @@ pub async fn execute_external_utility<S: Runtime + 'static>(
     print(&mut env, xtrace).await;
 
     let name = &fields[0];
-    let path = if name.value.contains('/') {
+    let path = if env.external_utilities_disabled() {
+        None
+    } else if name.value.contains('/') {
         CString::new(&*name.value).ok()
     } else {
         search_path(&mut *env, &name.value)
@@ -78,6 +83,22 @@ pub async fn execute_external_utility<S: Runtime + 'static>(
     if let Some(path) = path {
         env.exit_status =
             start_external_utility_in_subshell_and_wait(&mut env, path, fields).await?;
+    } else if let Some(handler) = CommandNotFoundHandlerConfig::handler(&env) {
+        let handler_name = Field {
+            value: handler.name.clone(),
+            origin: name.origin.clone(),
+        };
+        let args = std::iter::once(handler_name).chain(fields).collect();
+        execute_function_body(&mut env, handler, args, None).await?;
+    } else if env.external_utilities_disabled() {
+        print_error(
+            &mut env,
+            format!("cannot execute external utility {:?}", name.value).into(),
+            "external utilities are disabled".into(),
+            &name.origin,
+        )
+        .await;
+        env.exit_status = ExitStatus::NOEXEC;
     } else {
         print_error(
             &mut env,
@@ -117,6 +138,7 @@ where
         + Exit
         + Fork
         + GetPid
+        + IsExecutableFile
         + Isatty
         + Open
         + RunBlocking
@@ -175,20 +197,31 @@ pub fn to_c_strings(s: Vec<Field>) -> Vec<CString> {
 mod tests {
     use super::*;
     use crate::command::Command as _;
+    use crate::command::function_definition::BodyImpl;
+    use crate::tests::echo_builtin;
+    use crate::tests::return_builtin;
     use futures_util::FutureExt as _;
     use std::assert_matches;
     use std::cell::RefCell;
     use std::ops::ControlFlow::Continue;
     use std::rc::Rc;
+    use yash_env::VirtualSystem;
+    use yash_env::function::Function;
+    use yash_env::function::FunctionBodyObject;
     use yash_env::option::State::On;
+    use yash_env::semantics::command::NoExternalUtilities;
+    use yash_env::system::Concurrent;
+    use yash_env::system::Errno;
     use yash_env::system::Mode;
     use yash_env::system::r#virtual::FileBody;
     use yash_env::system::r#virtual::Inode;
     use yash_env::test_helper::assert_stderr;
+    use yash_env::test_helper::assert_stdout;
     use yash_env::test_helper::in_virtual_system;
     use yash_env::test_helper::stub_tty;
     use yash_env::variable::Scope;
     use yash_env::variable::Value;
+    use yash_syntax::source::Location;
     use yash_syntax::syntax;
 
     #[test]
@@ -313,6 +346,49 @@ mod tests {
         });
     }
 
+    #[test]
+    fn simple_command_returns_126_for_file_without_exec_permission() {
+        in_virtual_system(|mut env, state| async move {
+            let content = Rc::new(RefCell::new(Inode::default()));
+            state
+                .borrow_mut()
+                .file_system
+                .save("/some/file", content)
+                .unwrap();
+
+            let command: syntax::SimpleCommand = "/some/file".parse().unwrap();
+            let result = command.execute(&mut env).await;
+            assert_eq!(result, Continue(()));
+            assert_eq!(env.exit_status, ExitStatus::NOEXEC);
+            assert_stderr(&state, |stderr| {
+                assert!(
+                    stderr.contains("cannot execute external utility \"/some/file\""),
+                    "stderr = {stderr:?}"
+                );
+                assert!(
+                    stderr.contains(&Errno::EACCES.to_string()),
+                    "stderr = {stderr:?}"
+                );
+            });
+        });
+    }
+
+    #[test]
+    fn simple_command_returns_127_with_message_for_missing_command() {
+        let system = VirtualSystem::new();
+        let mut env = Env::with_system(Rc::new(Concurrent::new(system.clone())));
+        let command: syntax::SimpleCommand = "no_such_command".parse().unwrap();
+        let result = command.execute(&mut env).now_or_never().unwrap();
+        assert_eq!(result, Continue(()));
+        assert_eq!(env.exit_status, ExitStatus::NOT_FOUND);
+        assert_stderr(&system.state, |stderr| {
+            assert!(
+                stderr.contains("utility \"no_such_command\" not found"),
+                "stderr = {stderr:?}"
+            );
+        });
+    }
+
     #[test]
     fn simple_command_returns_126_on_fork_failure() {
         let mut env = Env::new_virtual();
@@ -331,6 +407,159 @@ mod tests {
         assert_eq!(env.exit_status, ExitStatus::NOT_FOUND);
     }
 
+    fn function_body_impl(src: &str) -> Rc<dyn FunctionBodyObject<Rc<Concurrent<VirtualSystem>>>> {
+        Rc::new(BodyImpl(src.parse().unwrap()))
+    }
+
+    #[test]
+    fn command_not_found_handler_is_invoked_with_command_and_arguments() {
+        let system = VirtualSystem::new();
+        let mut env = Env::with_system(Rc::new(Concurrent::new(system.clone())));
+        env.builtins.insert("echo", echo_builtin());
+        env.builtins.insert("return", return_builtin());
+        env.any
+            .insert(Box::new(CommandNotFoundHandlerConfig::default()));
+        let body = "{ echo handled \"$@\"; return -n 42; }";
+        let function = Function::new(
+            "command_not_found_handle",
+            function_body_impl(body),
+            Location::dummy("dummy"),
+        );
+        env.functions.define(function).unwrap();
+
+        let command: syntax::SimpleCommand = "no_such_command foo bar".parse().unwrap();
+        let result = command.execute(&mut env).now_or_never().unwrap();
+        assert_eq!(result, Continue(()));
+        assert_eq!(env.exit_status, ExitStatus(42));
+        assert_stdout(&system.state, |stdout| {
+            assert_eq!(stdout, "handled no_such_command foo bar\n")
+        });
+        assert_stderr(&system.state, |stderr| assert_eq!(stderr, ""));
+    }
+
+    #[test]
+    fn command_not_found_handler_is_invoked_if_externals_disabled() {
+        let mut env = Env::new_virtual();
+        env.builtins.insert("return", return_builtin());
+        env.any.insert(Box::new(NoExternalUtilities));
+        env.any
+            .insert(Box::new(CommandNotFoundHandlerConfig::default()));
+        let body = "{ return -n 42; }";
+        let function = Function::new(
+            "command_not_found_handle",
+            function_body_impl(body),
+            Location::dummy("dummy"),
+        );
+        env.functions.define(function).unwrap();
+
+        let command: syntax::SimpleCommand = "no_such_command".parse().unwrap();
+        let result = command.execute(&mut env).now_or_never().unwrap();
+        assert_eq!(result, Continue(()));
+        assert_eq!(env.exit_status, ExitStatus(42));
+    }
+
+    #[test]
+    fn command_not_found_handler_with_custom_name() {
+        let mut env = Env::new_virtual();
+        env.builtins.insert("return", return_builtin());
+        env.any
+            .insert(Box::new(CommandNotFoundHandlerConfig::with_function_name(
+                "my_handler",
+            )));
+        let body = "{ return -n 7; }";
+        let function = Function::new(
+            "my_handler",
+            function_body_impl(body),
+            Location::dummy("dummy"),
+        );
+        env.functions.define(function).unwrap();
+
+        let command: syntax::SimpleCommand = "no_such_command".parse().unwrap();
+        let result = command.execute(&mut env).now_or_never().unwrap();
+        assert_eq!(result, Continue(()));
+        assert_eq!(env.exit_status, ExitStatus(7));
+    }
+
+    #[test]
+    fn command_not_found_handler_is_not_invoked_without_config() {
+        let mut env = Env::new_virtual();
+        env.builtins.insert("return", return_builtin());
+        let body = "{ return -n 42; }";
+        let function = Function::new(
+            "command_not_found_handle",
+            function_body_impl(body),
+            Location::dummy("dummy"),
+        );
+        env.functions.define(function).unwrap();
+
+        let command: syntax::SimpleCommand = "no_such_command".parse().unwrap();
+        let result = command.execute(&mut env).now_or_never().unwrap();
+        assert_eq!(result, Continue(()));
+        assert_eq!(env.exit_status, ExitStatus::NOT_FOUND);
+    }
+
+    #[test]
+    fn command_not_found_handler_is_not_invoked_in_posixly_correct_mode() {
+        let mut env = Env::new_virtual();
+        env.builtins.insert("return", return_builtin());
+        env.options.set(yash_env::option::PosixlyCorrect, On);
+        env.any
+            .insert(Box::new(CommandNotFoundHandlerConfig::default()));
+        let body = "{ return -n 42; }";
+        let function = Function::new(
+            "command_not_found_handle",
+            function_body_impl(body),
+            Location::dummy("dummy"),
+        );
+        env.functions.define(function).unwrap();
+
+        let command: syntax::SimpleCommand = "no_such_command".parse().unwrap();
+        let result = command.execute(&mut env).now_or_never().unwrap();
+        assert_eq!(result, Continue(()));
+        assert_eq!(env.exit_status, ExitStatus::NOT_FOUND);
+    }
+
+    #[test]
+    fn external_utility_is_rejected_if_externals_disabled() {
+        in_virtual_system(|mut env, state| async move {
+            let mut content = Inode::default();
+            content.body = FileBody::Regular {
+                content: Vec::new(),
+                is_native_executable: true,
+            };
+            content.permissions.set(Mode::USER_EXEC, true);
+            let content = Rc::new(RefCell::new(content));
+            state
+                .borrow_mut()
+                .file_system
+                .save("/bin/foo", content)
+                .unwrap();
+            env.variables
+                .get_or_new("PATH", Scope::Global)
+                .assign("/bin", None)
+                .unwrap();
+            env.builtins.insert("echo", echo_builtin());
+            env.any.insert(Box::new(NoExternalUtilities));
+
+            let command: syntax::SimpleCommand = "foo".parse().unwrap();
+            let result = command.execute(&mut env).await;
+            assert_eq!(result, Continue(()));
+            assert_eq!(env.exit_status, ExitStatus::NOEXEC);
+            assert_stderr(&state, |stderr| {
+                assert!(stderr.contains("disabled"), "{stderr}")
+            });
+
+            let command: syntax::SimpleCommand = "echo ok".parse().unwrap();
+            let result = command.execute(&mut env).await;
+            assert_eq!(result, Continue(()));
+            assert_eq!(env.exit_status, ExitStatus::SUCCESS);
+            assert_stdout(&state, |stdout| assert_eq!(stdout, "ok\n"));
+
+            let state = state.borrow();
+            assert_eq!(state.processes.len(), 1, "no process should be forked");
+        });
+    }
+
     #[test]
     fn simple_command_assigns_variables_in_volatile_context_for_external_utility() {
         in_virtual_system(|mut env, _state| async move {
@@ -28,11 +28,20 @@ use std::pin::Pin;
 use std::rc::Rc;
 use yash_env::Env;
 use yash_env::function::Function;
+use yash_env::option::Option::PosixlyCorrect;
+use yash_env::option::SavedOptions;
+use yash_env::option::State::On;
 use yash_env::semantics::Divert;
 use yash_env::semantics::Field;
 use yash_env::semantics::Result;
+use yash_env::source::Source;
+use yash_env::stack::Frame;
+use yash_env::stack::Function as FrameFunction;
 use yash_env::variable::Context;
 use yash_env::variable::PositionalParams;
+use yash_env::variable::Scope;
+use yash_env::variable::Value;
+use yash_env::variable::{BASH_LINENO, BASH_SOURCE, FUNCNAME};
 use yash_syntax::syntax::Assign;
 use yash_syntax::syntax::Redir;
 
@@ -66,6 +75,15 @@ type EnvPrepHook<S> = fn(&mut Env<S>) -> Pin<Box<dyn Future<Output = ()> + '_>>;
 /// passed as positional parameters to the function except for the first field
 /// which is the name of the function.
 ///
+/// A [`Frame::Function`] is pushed to the stack while the function is
+/// executed. Unless the [`PosixlyCorrect`] option is on, the `FUNCNAME`,
+/// `BASH_SOURCE`, and `BASH_LINENO` variables are defined locally to describe
+/// the function call chain, the innermost call first.
+///
+/// If the function body saves the shell options with `local -`, that is, it
+/// stores [`SavedOptions`] in [`Env::any`], the saved options are restored
+/// when the function returns.
+///
 /// `env_prep_hook` is called after the new variable context is pushed to the
 /// environment. This is useful for assigning custom local variables before the
 /// function body is executed.
@@ -75,14 +93,28 @@ pub async fn execute_function_body<S>(
     fields: Vec<Field>,
     env_prep_hook: Option<EnvPrepHook<S>>,
 ) -> Result {
+    let frame = FrameFunction {
+        name: fields[0].clone(),
+        definition: function.origin.clone(),
+    };
     let positional_params = PositionalParams::from_fields(fields);
+
+    let mut env = env.push_frame(Frame::Function(frame));
     let mut env = env.push_context(Context::Regular { positional_params });
+    define_call_stack_variables(&mut env);
     if let Some(hook) = env_prep_hook {
         hook(&mut env).await;
     }
 
-    // TODO Update control flow stack
+    let outer_saved_options = env.any.remove::<SavedOptions>();
     let result = function.body.execute(&mut env).await;
+    if let Some(saved_options) = env.any.remove::<SavedOptions>() {
+        env.options = saved_options.0;
+    }
+    if let Some(outer_saved_options) = outer_saved_options {
+        env.any.insert(outer_saved_options);
+    }
+
     if let Break(Divert::Return(exit_status)) = result {
         if let Some(exit_status) = exit_status {
             env.exit_status = exit_status;
@@ -93,6 +125,63 @@ pub async fn execute_function_body<S>(
     }
 }
 
+/// Returns the `FUNCNAME`, `BASH_SOURCE`, and `BASH_LINENO` values for a
+/// function frame.
+fn call_stack_entries(function: &FrameFunction) -> [String; 3] {
+    let name = function.name.value.clone();
+    let source = function.definition.code.source.label().to_owned();
+    let mut location = &function.name.origin;
+    while let Source::Alias { original, .. } = &*location.code.source {
+        location = original;
+    }
+    let line_number = location.code.line_number(location.range.start).to_string();
+    [name, source, line_number]
+}
+
+/// Defines the variables describing the function call chain.
+///
+/// This function defines the `FUNCNAME`, `BASH_SOURCE`, and `BASH_LINENO`
+/// variables as local variables in the current context. Each variable is the
+/// caller's array with the element for the innermost function frame
+/// prepended. The caller's values are restored when the context is popped.
+/// Nothing is done if the [`PosixlyCorrect`] option is on.
+fn define_call_stack_variables<S>(env: &mut Env<S>) {
+    if env.options.get(PosixlyCorrect) == On {
+        return;
+    }
+
+    let mut functions = env.stack.functions();
+    let Some(function) = functions.next() else {
+        return;
+    };
+    let entries = call_stack_entries(function);
+    let caller_count = functions.count();
+
+    for (index, (name, entry)) in [FUNCNAME, BASH_SOURCE, BASH_LINENO]
+        .into_iter()
+        .zip(entries)
+        .enumerate()
+    {
+        // The caller's array is reused unless it has been tampered with.
+        let caller_values = env.variables.get(name).and_then(|v| v.value.as_ref());
+        let values = match caller_values {
+            Some(Value::Array(values)) if values.len() == caller_count => {
+                let mut values = values.clone();
+                values.insert(0, entry);
+                values
+            }
+            _ => env
+                .stack
+                .functions()
+                .map(|function| call_stack_entries(function)[index].clone())
+                .collect(),
+        };
+
+        let mut variable = env.variables.get_or_new(name, Scope::Local);
+        variable.assign(Value::Array(values), None).ok();
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -106,8 +195,11 @@ mod tests {
     use std::rc::Rc;
     use std::str::from_utf8;
     use yash_env::VirtualSystem;
+    use yash_env::builtin::Builtin;
+    use yash_env::builtin::Type::Mandatory;
     use yash_env::function::FunctionBodyObject;
-    use yash_env::option::State::On;
+    use yash_env::option::Option::AllExport;
+    use yash_env::option::State::{Off, On};
     use yash_env::semantics::ExitStatus;
     use yash_env::system::Concurrent;
     use yash_env::system::r#virtual::FileBody;
@@ -295,6 +387,120 @@ mod tests {
         });
     }
 
+    #[test]
+    fn function_call_defines_call_stack_variables() {
+        let system = VirtualSystem::new();
+        let state = Rc::clone(&system.state);
+        let mut env = Env::with_system(Rc::new(Concurrent::new(system)));
+        env.builtins.insert("echo", echo_builtin());
+        let outer = Function::new(
+            "outer",
+            function_body_impl("{ inner; }"),
+            Location::dummy("dummy"),
+        );
+        env.functions.define(outer).unwrap();
+        let inner = Function::new(
+            "inner",
+            function_body_impl("{ echo $FUNCNAME; echo $BASH_SOURCE; echo $BASH_LINENO; }"),
+            Location::dummy("dummy"),
+        );
+        env.functions.define(inner).unwrap();
+        let command: SimpleCommand = "outer".parse().unwrap();
+
+        let result = command.execute(&mut env).now_or_never().unwrap();
+        assert_eq!(result, Continue(()));
+        assert_stdout(&state, |stdout| {
+            assert_eq!(stdout, "inner outer\n<?> <?>\n1 1\n")
+        });
+        assert_eq!(env.variables.get(FUNCNAME), None);
+        assert!(env.stack.is_empty(), "{:?}", env.stack);
+    }
+
+    #[test]
+    fn call_stack_variables_do_not_affect_global_variables() {
+        let system = VirtualSystem::new();
+        let state = Rc::clone(&system.state);
+        let mut env = Env::with_system(Rc::new(Concurrent::new(system)));
+        env.builtins.insert("echo", echo_builtin());
+        env.variables
+            .get_or_new(FUNCNAME, Scope::Global)
+            .assign("user", None)
+            .unwrap();
+        let outer = Function::new(
+            "outer",
+            function_body_impl("{ inner; echo $FUNCNAME; }"),
+            Location::dummy("dummy"),
+        );
+        env.functions.define(outer).unwrap();
+        let inner = Function::new(
+            "inner",
+            function_body_impl("{ echo $FUNCNAME; }"),
+            Location::dummy("dummy"),
+        );
+        env.functions.define(inner).unwrap();
+        let command: SimpleCommand = "outer".parse().unwrap();
+
+        let result = command.execute(&mut env).now_or_never().unwrap();
+        assert_eq!(result, Continue(()));
+        assert_stdout(&state, |stdout| assert_eq!(stdout, "inner outer\nouter\n"));
+        assert_eq!(
+            env.variables.get(FUNCNAME).unwrap().value,
+            Some(Value::scalar("user")),
+        );
+    }
+
+    #[test]
+    fn function_call_does_not_define_call_stack_variables_if_posixly_correct() {
+        let system = VirtualSystem::new();
+        let state = Rc::clone(&system.state);
+        let mut env = Env::with_system(Rc::new(Concurrent::new(system)));
+        env.builtins.insert("echo", echo_builtin());
+        env.options.set(PosixlyCorrect, On);
+        let function = Function::new(
+            "foo",
+            function_body_impl("{ echo $FUNCNAME; }"),
+            Location::dummy("dummy"),
+        );
+        env.functions.define(function).unwrap();
+        let command: SimpleCommand = "foo".parse().unwrap();
+
+        let result = command.execute(&mut env).now_or_never().unwrap();
+        assert_eq!(result, Continue(()));
+        assert_stdout(&state, |stdout| assert_eq!(stdout, "\n"));
+    }
+
+    #[test]
+    fn saved_options_are_restored_on_function_return() {
+        let mut env = Env::new_virtual();
+        env.builtins.insert("echo", echo_builtin());
+        env.builtins.insert(
+            "local_options",
+            Builtin::new(Mandatory, |env, _args| {
+                env.any.insert(Box::new(SavedOptions(env.options)));
+                env.options.set(AllExport, On);
+                Box::pin(std::future::ready(Default::default()))
+            }),
+        );
+        let outer = Function::new(
+            "outer",
+            function_body_impl("{ local_options; inner; }"),
+            Location::dummy("outer"),
+        );
+        env.functions.define(outer).unwrap();
+        let inner = Function::new(
+            "inner",
+            function_body_impl("{ echo; }"),
+            Location::dummy("inner"),
+        );
+        env.functions.define(inner).unwrap();
+        let command: SimpleCommand = "outer".parse().unwrap();
+
+        let result = command.execute(&mut env).now_or_never().unwrap();
+        assert_eq!(result, Continue(()));
+        assert_eq!(env.options.get(AllExport), Off);
+        assert_eq!(env.any.get::<SavedOptions>(), None);
+    }
+
     #[test]
     fn xtrace_for_function() {
         let system = VirtualSystem::new();
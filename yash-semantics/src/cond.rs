@@ -0,0 +1,540 @@
+// This file is part of yash, an extended POSIX shell.
+// Copyright (C) 2026 WATANABE Yuki
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+//! Evaluation of conditional expressions
+//!
+//! This module defines [`Condition`], the abstract syntax tree of a conditional
+//! expression, and [`CondEval`], which evaluates a condition in the shell
+//! environment. The `test` built-in and the `[[ ... ]]` command parse their own
+//! syntaxes into a `Condition` and share the evaluator. They differ only in
+//! how the `=`, `==`, and `!=` operators compare strings: the `test` built-in
+//! compares them literally, while the `[[ ... ]]` command treats the right-hand
+//! side as a pattern. See [`CondEval::pattern_matching`].
+//!
+//! The operands of a condition are strings that have already been expanded.
+
+use std::ffi::CString;
+use std::str::FromStr;
+use thiserror::Error;
+use yash_env::Env;
+use yash_env::io::Fd;
+use yash_env::system::AT_FDCWD;
+use yash_env::system::CheckAccess;
+use yash_env::system::Fstat;
+use yash_env::system::IsExecutableFile;
+use yash_env::system::Isatty;
+use yash_env::system::Mode;
+use yash_env::system::Stat as _;
+use yash_fnmatch::Config;
+use yash_fnmatch::Pattern;
+use yash_fnmatch::with_escape;
+
+/// Unary operator in a conditional expression
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+#[non_exhaustive]
+pub enum UnaryOperator {
+    /// `-b`: The file is a block special file.
+    BlockSpecial,
+    /// `-c`: The file is a character special file.
+    CharacterSpecial,
+    /// `-d`: The file is a directory.
+    Directory,
+    /// `-e`: The file exists.
+    Exists,
+    /// `-f`: The file is a regular file.
+    Regular,
+    /// `-g`: The file has the set-group-ID bit.
+    SetGroupId,
+    /// `-h` or `-L`: The file is a symbolic link.
+    Symlink,
+    /// `-k`: The file has the sticky bit.
+    Sticky,
+    /// `-n`: The string is not empty.
+    NonEmptyString,
+    /// `-p`: The file is a FIFO.
+    Fifo,
+    /// `-r`: The file is readable.
+    Readable,
+    /// `-s`: The file is not empty.
+    NonEmptyFile,
+    /// `-S`: The file is a socket.
+    Socket,
+    /// `-t`: The file descriptor is associated with a terminal.
+    Terminal,
+    /// `-u`: The file has the set-user-ID bit.
+    SetUserId,
+    /// `-v`: The variable is set.
+    VariableSet,
+    /// `-w`: The file is writable.
+    Writable,
+    /// `-x`: The file is an executable regular file.
+    Executable,
+    /// `-z`: The string is empty.
+    EmptyString,
+}
+
+/// Binary operator in a conditional expression
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+#[non_exhaustive]
+pub enum BinaryOperator {
+    /// `=` or `==`: The strings are equal.
+    StringEqual,
+    /// `!=`: The strings are not equal.
+    StringNotEqual,
+    /// `<`: The left-hand side sorts before the right-hand side.
+    StringLess,
+    /// `>`: The left-hand side sorts after the right-hand side.
+    StringGreater,
+    /// `-eq`: The integers are equal.
+    IntEqual,
+    /// `-ne`: The integers are not equal.
+    IntNotEqual,
+    /// `-lt`: The left-hand side is less than the right-hand side.
+    IntLess,
+    /// `-le`: The left-hand side is less than or equal to the right-hand side.
+    IntLessEqual,
+    /// `-gt`: The left-hand side is greater than the right-hand side.
+    IntGreater,
+    /// `-ge`: The left-hand side is greater than or equal to the right-hand
+    /// side.
+    IntGreaterEqual,
+    /// `-ef`: The files are the same file.
+    SameFile,
+}
+
+/// Error value for an unknown operator
+///
+/// This error is used by the [`FromStr`] implementations for
+/// [`UnaryOperator`] and [`BinaryOperator`] to indicate that the input string
+/// is not an operator.
+#[derive(Clone, Debug, Eq, Error, Hash, PartialEq)]
+#[error("unknown operator")]
+pub struct UnknownOperatorError;
+
+/// Parses a unary operator such as `-e`.
+impl FromStr for UnaryOperator {
+    type Err = UnknownOperatorError;
+
+    fn from_str(s: &str) -> Result<Self, UnknownOperatorError> {
+        use UnaryOperator::*;
+        match s {
+            "-b" => Ok(BlockSpecial),
+            "-c" => Ok(CharacterSpecial),
+            "-d" => Ok(Directory),
+            "-e" => Ok(Exists),
+            "-f" => Ok(Regular),
+            "-g" => Ok(SetGroupId),
+            "-h" | "-L" => Ok(Symlink),
+            "-k" => Ok(Sticky),
+            "-n" => Ok(NonEmptyString),
+            "-p" => Ok(Fifo),
+            "-r" => Ok(Readable),
+            "-s" => Ok(NonEmptyFile),
+            "-S" => Ok(Socket),
+            "-t" => Ok(Terminal),
+            "-u" => Ok(SetUserId),
+            "-v" => Ok(VariableSet),
+            "-w" => Ok(Writable),
+            "-x" => Ok(Executable),
+            "-z" => Ok(EmptyString),
+            _ => Err(UnknownOperatorError),
+        }
+    }
+}
+
+/// Parses a binary operator such as `-eq`.
+impl FromStr for BinaryOperator {
+    type Err = UnknownOperatorError;
+
+    fn from_str(s: &str) -> Result<Self, UnknownOperatorError> {
+        use BinaryOperator::*;
+        match s {
+            "=" | "==" => Ok(StringEqual),
+            "!=" => Ok(StringNotEqual),
+            "<" => Ok(StringLess),
+            ">" => Ok(StringGreater),
+            "-eq" => Ok(IntEqual),
+            "-ne" => Ok(IntNotEqual),
+            "-lt" => Ok(IntLess),
+            "-le" => Ok(IntLessEqual),
+            "-gt" => Ok(IntGreater),
+            "-ge" => Ok(IntGreaterEqual),
+            "-ef" => Ok(SameFile),
+            _ => Err(UnknownOperatorError),
+        }
+    }
+}
+
+/// Conditional expression
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub enum Condition {
+    /// Single string, which is true if not empty
+    String(String),
+    /// Unary operator applied to an operand
+    Unary(UnaryOperator, String),
+    /// Binary operator applied to two operands
+    Binary(String, BinaryOperator, String),
+    /// Negation (`!`)
+    Not(Box<Condition>),
+    /// Conjunction (`-a` or `&&`)
+    And(Box<Condition>, Box<Condition>),
+    /// Disjunction (`-o` or `||`)
+    Or(Box<Condition>, Box<Condition>),
+}
+
+/// Error that may occur in evaluating a condition
+#[derive(Clone, Debug, Eq, Error, Hash, PartialEq)]
+#[non_exhaustive]
+pub enum Error {
+    /// An operand of an integer comparison is not a valid integer.
+    #[error("{0:?} is not a valid integer")]
+    InvalidInteger(String),
+}
+
+/// Evaluator of conditional expressions
+///
+/// See the [module documentation](self) for details.
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
+#[non_exhaustive]
+pub struct CondEval {
+    /// Whether the right-hand side of `=`, `==`, and `!=` is a pattern
+    ///
+    /// If `false`, the operands are compared literally. If `true`, the
+    /// right-hand side is parsed as a pattern in which backslashes escape the
+    /// following character, so the caller should backslash-escape quoted
+    /// characters. A pattern that fails to parse is compared literally.
+    pub pattern_matching: bool,
+}
+
+impl CondEval {
+    /// Creates an evaluator that compares strings literally.
+    ///
+    /// This is the behavior of the `test` built-in.
+    #[must_use]
+    pub const fn literal() -> Self {
+        Self {
+            pattern_matching: false,
+        }
+    }
+
+    /// Creates an evaluator that matches strings against patterns.
+    ///
+    /// This is the behavior of the `[[ ... ]]` command.
+    #[must_use]
+    pub const fn pattern() -> Self {
+        Self {
+            pattern_matching: true,
+        }
+    }
+
+    /// Evaluates the condition.
+    ///
+    /// The operands of `-a` and `-o` are evaluated from left to right, and the
+    /// right-hand side is not evaluated if the left-hand side determines the
+    /// result.
+    pub fn eval<S>(&self, env: &Env<S>, condition: &Condition) -> Result<bool, Error>
+    where
+        S: CheckAccess + Fstat + IsExecutableFile + Isatty,
+    {
+        match condition {
+            Condition::String(value) => Ok(!value.is_empty()),
+            Condition::Unary(operator, operand) => Ok(eval_unary(env, *operator, operand)),
+            Condition::Binary(lhs, operator, rhs) => self.eval_binary(env, lhs, *operator, rhs),
+            Condition::Not(condition) => Ok(!self.eval(env, condition)?),
+            Condition::And(lhs, rhs) => Ok(self.eval(env, lhs)? && self.eval(env, rhs)?),
+            Condition::Or(lhs, rhs) => Ok(self.eval(env, lhs)? || self.eval(env, rhs)?),
+        }
+    }
+
+    fn eval_binary<S>(
+        &self,
+        env: &Env<S>,
+        lhs: &str,
+        operator: BinaryOperator,
+        rhs: &str,
+    ) -> Result<bool, Error>
+    where
+        S: Fstat,
+    {
+        use BinaryOperator::*;
+        Ok(match operator {
+            StringEqual => self.string_matches(lhs, rhs),
+            StringNotEqual => !self.string_matches(lhs, rhs),
+            StringLess => lhs < rhs,
+            StringGreater => lhs > rhs,
+            IntEqual => parse_integer(lhs)? == parse_integer(rhs)?,
+            IntNotEqual => parse_integer(lhs)? != parse_integer(rhs)?,
+            IntLess => parse_integer(lhs)? < parse_integer(rhs)?,
+            IntLessEqual => parse_integer(lhs)? <= parse_integer(rhs)?,
+            IntGreater => parse_integer(lhs)? > parse_integer(rhs)?,
+            IntGreaterEqual => parse_integer(lhs)? >= parse_integer(rhs)?,
+            SameFile => match (stat(env, lhs, true), stat(env, rhs, true)) {
+                (Some(lhs), Some(rhs)) => lhs.identity() == rhs.identity(),
+                _ => false,
+            },
+        })
+    }
+
+    fn string_matches(&self, value: &str, pattern: &str) -> bool {
+        if !self.pattern_matching {
+            return value == pattern;
+        }
+        let mut config = Config::default();
+        config.anchor_begin = true;
+        config.anchor_end = true;
+        match Pattern::parse_with_config(with_escape(pattern), config) {
+            Ok(pattern) => pattern.is_match(value),
+            Err(_) => value == pattern,
+        }
+    }
+}
+
+/// Parses an operand of an integer comparison.
+///
+/// Leading and trailing whitespace is ignored.
+fn parse_integer(value: &str) -> Result<i64, Error> {
+    value
+        .trim()
+        .parse()
+        .map_err(|_| Error::InvalidInteger(value.to_owned()))
+}
+
+/// Returns the file status for the path, or `None` if not available.
+fn stat<S: Fstat>(env: &Env<S>, path: &str, follow_symlinks: bool) -> Option<S::Stat> {
+    let path = CString::new(path).ok()?;
+    env.system.fstatat(AT_FDCWD, &path, follow_symlinks).ok()
+}
+
+fn eval_unary<S>(env: &Env<S>, operator: UnaryOperator, operand: &str) -> bool
+where
+    S: CheckAccess + Fstat + IsExecutableFile + Isatty,
+{
+    use UnaryOperator::*;
+    let has_mode = |mode: Mode| stat(env, operand, true).is_some_and(|s| s.mode().contains(mode));
+    match operator {
+        BlockSpecial => stat(env, operand, true).is_some_and(|s| s.is_block_device()),
+        CharacterSpecial => stat(env, operand, true).is_some_and(|s| s.is_character_device()),
+        Directory => stat(env, operand, true).is_some_and(|s| s.is_directory()),
+        Exists => stat(env, operand, true).is_some(),
+        Regular => stat(env, operand, true).is_some_and(|s| s.is_regular_file()),
+        SetGroupId => has_mode(Mode::SET_GROUP_ID),
+        Symlink => stat(env, operand, false).is_some_and(|s| s.is_symlink()),
+        Sticky => has_mode(Mode::STICKY),
+        NonEmptyString => !operand.is_empty(),
+        Fifo => stat(env, operand, true).is_some_and(|s| s.is_fifo()),
+        Readable => CString::new(operand).is_ok_and(|path| env.system.is_readable_file(&path)),
+        NonEmptyFile => stat(env, operand, true).is_some_and(|s| s.size() > 0),
+        Socket => stat(env, operand, true).is_some_and(|s| s.is_socket()),
+        Terminal => operand
+            .trim()
+            .parse()
+            .is_ok_and(|fd| env.system.isatty(Fd(fd))),
+        SetUserId => has_mode(Mode::SET_USER_ID),
+        VariableSet => env
+            .variables
+            .get(operand)
+            .is_some_and(|variable| variable.value.is_some()),
+        Writable => CString::new(operand).is_ok_and(|path| env.system.is_writable_file(&path)),
+        Executable => CString::new(operand).is_ok_and(|path| env.system.is_executable_file(&path)),
+        EmptyString => operand.is_empty(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cell::RefCell;
+    use std::rc::Rc;
+    use yash_env::VirtualSystem;
+    use yash_env::system::r#virtual::Inode;
+    use yash_env::variable::Scope;
+
+    fn unary(operator: &str, operand: &str) -> Condition {
+        Condition::Unary(operator.parse().unwrap(), operand.to_owned())
+    }
+
+    fn binary(lhs: &str, operator: &str, rhs: &str) -> Condition {
+        Condition::Binary(lhs.to_owned(), operator.parse().unwrap(), rhs.to_owned())
+    }
+
+    fn env_with_file(path: &str, content: &str) -> Env<VirtualSystem> {
+        let system = VirtualSystem::new();
+        let file = Rc::new(RefCell::new(Inode::new(content)));
+        system
+            .state
+            .borrow_mut()
+            .file_system
+            .save(path, file)
+            .unwrap();
+        Env::with_system(system)
+    }
+
+    #[test]
+    fn parsing_operators() {
+        assert_eq!("-e".parse(), Ok(UnaryOperator::Exists));
+        assert_eq!("-L".parse(), Ok(UnaryOperator::Symlink));
+        assert_eq!("-q".parse::<UnaryOperator>(), Err(UnknownOperatorError));
+        assert_eq!("==".parse(), Ok(BinaryOperator::StringEqual));
+        assert_eq!("-ge".parse(), Ok(BinaryOperator::IntGreaterEqual));
+        assert_eq!("-e".parse::<BinaryOperator>(), Err(UnknownOperatorError));
+    }
+
+    #[test]
+    fn string_condition() {
+        let env = Env::new_virtual();
+        let eval = CondEval::literal();
+        assert_eq!(eval.eval(&env, &Condition::String("x".into())), Ok(true));
+        assert_eq!(eval.eval(&env, &Condition::String("".into())), Ok(false));
+        assert_eq!(eval.eval(&env, &unary("-n", "x")), Ok(true));
+        assert_eq!(eval.eval(&env, &unary("-z", "x")), Ok(false));
+    }
+
+    #[test]
+    fn file_existence() {
+        let env = env_with_file("/foo/file", "content");
+        let eval = CondEval::literal();
+        assert_eq!(eval.eval(&env, &unary("-e", "/foo/file")), Ok(true));
+        assert_eq!(eval.eval(&env, &unary("-e", "/foo/none")), Ok(false));
+        assert_eq!(eval.eval(&env, &unary("-f", "/foo/file")), Ok(true));
+        assert_eq!(eval.eval(&env, &unary("-f", "/foo")), Ok(false));
+        assert_eq!(eval.eval(&env, &unary("-d", "/foo")), Ok(true));
+        assert_eq!(eval.eval(&env, &unary("-d", "/foo/file")), Ok(false));
+        assert_eq!(eval.eval(&env, &unary("-s", "/foo/file")), Ok(true));
+    }
+
+    #[test]
+    fn file_access() {
+        let env = env_with_file("/foo/file", "");
+        let eval = CondEval::literal();
+        assert_eq!(eval.eval(&env, &unary("-r", "/foo/file")), Ok(true));
+        assert_eq!(eval.eval(&env, &unary("-w", "/foo/file")), Ok(true));
+        assert_eq!(eval.eval(&env, &unary("-r", "/foo/none")), Ok(false));
+        assert_eq!(eval.eval(&env, &unary("-w", "/foo/none")), Ok(false));
+
+        let file = env
+            .system
+            .state
+            .borrow()
+            .file_system
+            .get("/foo/file")
+            .unwrap();
+        file.borrow_mut().permissions = Mode::USER_READ;
+        assert_eq!(eval.eval(&env, &unary("-r", "/foo/file")), Ok(true));
+        assert_eq!(eval.eval(&env, &unary("-w", "/foo/file")), Ok(false));
+    }
+
+    #[test]
+    fn empty_file() {
+        let env = env_with_file("/empty", "");
+        let eval = CondEval::literal();
+        assert_eq!(eval.eval(&env, &unary("-e", "/empty")), Ok(true));
+        assert_eq!(eval.eval(&env, &unary("-s", "/empty")), Ok(false));
+    }
+
+    #[test]
+    fn same_file() {
+        let env = env_with_file("/foo/file", "");
+        let eval = CondEval::literal();
+        let condition = binary("/foo/file", "-ef", "/foo/../foo/file");
+        assert_eq!(eval.eval(&env, &condition), Ok(true));
+        let condition = binary("/foo/file", "-ef", "/foo");
+        assert_eq!(eval.eval(&env, &condition), Ok(false));
+        let condition = binary("/foo/none", "-ef", "/foo/none");
+        assert_eq!(eval.eval(&env, &condition), Ok(false));
+    }
+
+    #[test]
+    fn variable_set() {
+        let mut env = Env::new_virtual();
+        env.variables
+            .get_or_new("foo", Scope::Global)
+            .assign("", None)
+            .unwrap();
+        let eval = CondEval::literal();
+        assert_eq!(eval.eval(&env, &unary("-v", "foo")), Ok(true));
+        assert_eq!(eval.eval(&env, &unary("-v", "bar")), Ok(false));
+    }
+
+    #[test]
+    fn integer_comparison() {
+        let env = Env::new_virtual();
+        let eval = CondEval::literal();
+        assert_eq!(eval.eval(&env, &binary("1", "-eq", " 1 ")), Ok(true));
+        assert_eq!(eval.eval(&env, &binary("1", "-ne", "1")), Ok(false));
+        assert_eq!(eval.eval(&env, &binary("-3", "-lt", "2")), Ok(true));
+        assert_eq!(eval.eval(&env, &binary("2", "-le", "2")), Ok(true));
+        assert_eq!(eval.eval(&env, &binary("10", "-gt", "9")), Ok(true));
+        assert_eq!(eval.eval(&env, &binary("9", "-ge", "10")), Ok(false));
+    }
+
+    #[test]
+    fn invalid_integer() {
+        let env = Env::new_virtual();
+        let eval = CondEval::literal();
+        assert_eq!(
+            eval.eval(&env, &binary("1", "-eq", "x")),
+            Err(Error::InvalidInteger("x".to_owned()))
+        );
+    }
+
+    #[test]
+    fn string_comparison() {
+        let env = Env::new_virtual();
+        let eval = CondEval::literal();
+        assert_eq!(eval.eval(&env, &binary("a", "=", "a")), Ok(true));
+        assert_eq!(eval.eval(&env, &binary("a", "!=", "a")), Ok(false));
+        assert_eq!(eval.eval(&env, &binary("a", "<", "b")), Ok(true));
+        assert_eq!(eval.eval(&env, &binary("a", ">", "b")), Ok(false));
+    }
+
+    #[test]
+    fn literal_vs_pattern_matching() {
+        let env = Env::new_virtual();
+        let condition = binary("foo.rs", "==", "*.rs");
+        assert_eq!(CondEval::literal().eval(&env, &condition), Ok(false));
+        assert_eq!(CondEval::pattern().eval(&env, &condition), Ok(true));
+
+        let condition = binary("*.rs", "==", "\\*.rs");
+        assert_eq!(CondEval::pattern().eval(&env, &condition), Ok(true));
+        let condition = binary("foo.rs", "!=", "\\*.rs");
+        assert_eq!(CondEval::pattern().eval(&env, &condition), Ok(true));
+    }
+
+    #[test]
+    fn logical_operators() {
+        let env = Env::new_virtual();
+        let eval = CondEval::literal();
+        let t = || Box::new(Condition::String("x".into()));
+        let f = || Box::new(Condition::String("".into()));
+        assert_eq!(eval.eval(&env, &Condition::Not(f())), Ok(true));
+        assert_eq!(eval.eval(&env, &Condition::And(t(), f())), Ok(false));
+        assert_eq!(eval.eval(&env, &Condition::Or(f(), t())), Ok(true));
+    }
+
+    #[test]
+    fn short_circuit() {
+        let env = Env::new_virtual();
+        let eval = CondEval::literal();
+        let error = Box::new(binary("x", "-eq", "0"));
+        let f = Box::new(Condition::String("".into()));
+        let t = Box::new(Condition::String("x".into()));
+        assert_eq!(
+            eval.eval(&env, &Condition::And(f, error.clone())),
+            Ok(false)
+        );
+        assert_eq!(eval.eval(&env, &Condition::Or(t, error)), Ok(true));
+    }
+}
@@ -105,6 +105,7 @@ use yash_syntax::source::pretty::Span;
 use yash_syntax::source::pretty::SpanRole;
 use yash_syntax::source::pretty::add_span;
 use yash_syntax::syntax::ExpansionMode;
+use yash_syntax::syntax::MaybeLiteral as _;
 use yash_syntax::syntax::Param;
 use yash_syntax::syntax::Text;
 use yash_syntax::syntax::Word;
@@ -153,6 +154,10 @@ pub enum ErrorCause {
     #[error("unset parameter `{param}`")]
     UnsetParameter { param: Param },
 
+    /// Indirect expansion of a parameter whose value is not a parameter name
+    #[error("invalid indirect parameter name `{name}`")]
+    InvalidIndirection { name: String },
+
     /// Expansion of an empty value with an error switch
     #[error(transparent)]
     VacantExpansion(#[from] VacantError),
@@ -181,6 +186,7 @@ impl ErrorCause {
             ArithError(_) => "error evaluating the arithmetic expansion",
             AssignReadOnly(_) => "error assigning to variable",
             UnsetParameter { .. } => "cannot expand unset parameter",
+            InvalidIndirection { .. } => "cannot expand parameter indirectly",
             VacantExpansion(error) => error.message_or_default(),
             NonassignableParameter(_) => "cannot assign to parameter",
             Interrupted(_) => "word expansion interrupted",
@@ -197,6 +203,7 @@ impl ErrorCause {
             ArithError(e) => e.to_string(),
             AssignReadOnly(e) => e.to_string(),
             UnsetParameter { param } => format!("parameter `{param}` is not set"),
+            InvalidIndirection { name } => format!("`{name}` is not a valid parameter name"),
             VacantExpansion(e) => match e.vacancy {
                 Vacancy::Unset => format!("parameter `{}` is not set", e.param),
                 Vacancy::EmptyScalar => format!("parameter `{}` is an empty string", e.param),
@@ -225,6 +232,7 @@ impl ErrorCause {
                 "the variable was made read-only here",
             )),
             UnsetParameter { .. } => None,
+            InvalidIndirection { .. } => None,
             VacantExpansion(_) => None,
             NonassignableParameter(_) => None,
             Interrupted(_) => None,
@@ -239,6 +247,7 @@ impl ErrorCause {
             CommandSubstError(_)
             | ArithError(_)
             | AssignReadOnly(_)
+            | InvalidIndirection { .. }
             | VacantExpansion(_)
             | NonassignableParameter(_)
             | Interrupted(_) => None,
@@ -287,6 +296,7 @@ impl Error {
             ErrorCause::ArithError(_) => None,
             ErrorCause::AssignReadOnly(e) => e.vacancy,
             ErrorCause::UnsetParameter { .. } => None,
+            ErrorCause::InvalidIndirection { .. } => None,
             ErrorCause::VacantExpansion(_) => None,
             ErrorCause::NonassignableParameter(e) => Some(e.vacancy),
             ErrorCause::Interrupted(_) => None,
@@ -331,10 +341,17 @@ pub type Result<T> = std::result::Result<T, Error>;
 /// stripping.
 /// The second field of the result tuple is the exit status of the last command
 /// substitution performed during the expansion, if any.
+///
+/// If the text consists of literal characters only, it is converted to a
+/// string directly without going through the expansion steps.
 pub async fn expand_text<S: Runtime + 'static>(
     env: &mut yash_env::Env<S>,
     text: &Text,
 ) -> Result<(String, Option<ExitStatus>)> {
+    if let Some(value) = text.to_string_if_literal() {
+        return Ok((value, None));
+    }
+
     let mut env = initial::Env::new(env);
     // It would be technically correct to set `will_split` to false, but it does
     // not affect the final results because we will join the results anyway.
@@ -375,6 +392,9 @@ pub async fn expand_word_attr<S: Runtime + 'static>(
 /// The second field of the result tuple is the exit status of the last command
 /// substitution performed during the expansion, if any.
 ///
+/// If the word consists of unquoted literal characters only, it is converted
+/// to a field directly without going through the expansion steps.
+///
 /// To expand a word to an [`AttrField`] without performing quote removal or
 /// attribute stripping, use [`expand_word_attr`].
 /// To expand a word to multiple fields, use [`expand_word_multiple`].
@@ -383,6 +403,11 @@ pub async fn expand_word<S: Runtime + 'static>(
     env: &mut yash_env::Env<S>,
     word: &Word,
 ) -> Result<(Field, Option<ExitStatus>)> {
+    if let Some(value) = word.to_string_if_literal() {
+        let origin = word.location.clone();
+        return Ok((Field { value, origin }, None));
+    }
+
     let (field, exit_status) = expand_word_attr(env, word).await?;
     let field = field.remove_quotes_and_strip();
     Ok((field, exit_status))
@@ -395,6 +420,11 @@ pub async fn expand_word<S: Runtime + 'static>(
 /// the given collection. The return value is the exit status of the last
 /// command substitution performed during the expansion, if any.
 ///
+/// If the word consists of unquoted literal characters only and contains no
+/// pattern characters (`*`, `?`, and `[`), it results in a single field
+/// without going through the expansion steps since field splitting and
+/// pathname expansion would have no effect on it.
+///
 /// To expand a single word to a single field, use [`expand_word`].
 /// To expand multiple words to fields, use [`expand_words`].
 pub async fn expand_word_multiple<S, R>(
@@ -406,6 +436,15 @@ where
     S: Runtime + 'static,
     R: Extend<Field>,
 {
+    if let Some(value) = word.to_string_if_literal()
+        && !value.is_empty()
+        && !value.contains(['*', '?', '['])
+    {
+        let origin = word.location.clone();
+        results.extend(std::iter::once(Field { value, origin }));
+        return Ok(None);
+    }
+
     let mut env = initial::Env::new(env);
 
     // initial expansion //
@@ -535,6 +574,44 @@ pub async fn expand_value<S: Runtime + 'static>(
     }
 }
 
+/// Evaluates an arithmetic expression.
+///
+/// This function evaluates the `expression` as is, without performing any
+/// expansion on it. Variables referenced in the expression are read from
+/// `env.variables`, and assignments in the expression modify them.
+///
+/// The `location` should be that of the construct containing the expression.
+/// It is used as the origin of the code in the returned error. Library users
+/// that do not have such a construct may use [`Location::dummy`].
+///
+/// If the evaluation fails because of a problem in the expression itself, the
+/// returned error has an [`ErrorCause::ArithError`]. Other causes are
+/// [`ErrorCause::UnsetParameter`] for an unset variable referenced while the
+/// `nounset` option is on, and [`ErrorCause::AssignReadOnly`] for an
+/// assignment to a read-only variable.
+///
+/// ```
+/// # use yash_env::variable::Scope;
+/// # use yash_semantics::expansion::eval_arith;
+/// # use yash_syntax::source::Location;
+/// let mut env = yash_env::Env::new_virtual();
+/// env.variables
+///     .get_or_new("x", Scope::Global)
+///     .assign("6", None)
+///     .unwrap();
+/// let location = Location::dummy("x *= 7");
+/// assert_eq!(eval_arith(&mut env, "x *= 7", &location), Ok(42));
+/// assert_eq!(env.variables.get_scalar("x"), Some("42"));
+/// ```
+pub fn eval_arith<S>(
+    env: &mut yash_env::Env<S>,
+    expression: &str,
+    location: &Location,
+) -> Result<i64> {
+    let yash_arith::Value::Integer(value) = initial::evaluate_arith(env, expression, location)?;
+    Ok(value)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -542,6 +619,9 @@ mod tests {
     use crate::tests::return_builtin;
     use assert_matches::assert_matches;
     use futures_util::FutureExt as _;
+    use std::rc::Rc;
+    use yash_env::VirtualSystem;
+    use yash_env::system::Concurrent;
     use yash_env::test_helper::in_virtual_system;
     use yash_env::variable::Scope;
 
@@ -608,6 +688,22 @@ mod tests {
         );
     }
 
+    #[test]
+    fn expand_word_attr_tags_command_substitution_output() {
+        in_virtual_system(|mut env, _state| async move {
+            env.builtins.insert("echo", echo_builtin());
+            let word = "a$(echo x)".parse().unwrap();
+            let (field, exit_status) = expand_word_attr(&mut env, &word).await.unwrap();
+            assert_eq!(exit_status, Some(ExitStatus::SUCCESS));
+            assert_matches!(field.chars.as_slice(), [a, x] => {
+                assert_eq!(a.value, 'a');
+                assert_eq!(a.origin, Origin::Literal);
+                assert_eq!(x.value, 'x');
+                assert_eq!(x.origin, Origin::CommandSubst);
+            });
+        })
+    }
+
     #[test]
     fn expand_word_multiple_performs_initial_expansion() {
         in_virtual_system(|mut env, _state| async move {
@@ -625,6 +721,37 @@ mod tests {
         })
     }
 
+    #[test]
+    fn expand_word_multiple_splits_unquoted_command_substitution() {
+        in_virtual_system(|mut env, _state| async move {
+            env.builtins.insert("echo", echo_builtin());
+            let word = "$(echo a b)".parse().unwrap();
+            let mut fields = Vec::new();
+            expand_word_multiple(&mut env, &word, &mut fields)
+                .await
+                .unwrap();
+            assert_matches!(fields.as_slice(), [f1, f2] => {
+                assert_eq!(f1.value, "a");
+                assert_eq!(f2.value, "b");
+            });
+        })
+    }
+
+    #[test]
+    fn expand_word_multiple_does_not_split_quoted_command_substitution() {
+        in_virtual_system(|mut env, _state| async move {
+            env.builtins.insert("echo", echo_builtin());
+            let word = r#""$(echo a b '*')""#.parse().unwrap();
+            let mut fields = Vec::new();
+            expand_word_multiple(&mut env, &word, &mut fields)
+                .await
+                .unwrap();
+            assert_matches!(fields.as_slice(), [f] => {
+                assert_eq!(f.value, "a b *");
+            });
+        })
+    }
+
     #[test]
     fn expand_word_multiple_performs_field_splitting_possibly_with_default_ifs() {
         let mut env = yash_env::Env::new_virtual();
@@ -685,6 +812,67 @@ mod tests {
         });
     }
 
+    #[test]
+    fn expand_text_literal() {
+        let mut env = yash_env::Env::new_virtual();
+        let text = "foo/bar".parse().unwrap();
+        let result = expand_text(&mut env, &text).now_or_never().unwrap();
+        assert_eq!(result.unwrap(), ("foo/bar".to_string(), None));
+    }
+
+    #[test]
+    fn expand_word_literal() {
+        let mut env = yash_env::Env::new_virtual();
+        let word: Word = "foo=bar".parse().unwrap();
+        let (field, exit_status) = expand_word(&mut env, &word)
+            .now_or_never()
+            .unwrap()
+            .unwrap();
+        assert_eq!(exit_status, None);
+        assert_eq!(field.value, "foo=bar");
+        assert_eq!(field.origin, word.location);
+    }
+
+    #[test]
+    fn expand_word_multiple_literal_is_not_split() {
+        let mut env = yash_env::Env::new_virtual();
+        env.variables
+            .get_or_new(IFS, Scope::Global)
+            .assign("o:", None)
+            .unwrap();
+        let word: Word = "foo:bar".parse().unwrap();
+        let mut fields = Vec::new();
+        let exit_status = expand_word_multiple(&mut env, &word, &mut fields)
+            .now_or_never()
+            .unwrap()
+            .unwrap();
+        assert_eq!(exit_status, None);
+        assert_matches!(fields.as_slice(), [f] => {
+            assert_eq!(f.value, "foo:bar");
+            assert_eq!(f.origin, word.location);
+        });
+    }
+
+    #[test]
+    fn expand_word_multiple_literal_with_pattern_performs_pathname_expansion() {
+        let system = VirtualSystem::new();
+        let mut state = system.state.borrow_mut();
+        state.file_system.save("/foo1", Default::default()).unwrap();
+        state.file_system.save("/foo2", Default::default()).unwrap();
+        drop(state);
+        let mut env = yash_env::Env::with_system(Rc::new(Concurrent::new(system)));
+        let word = "/foo?".parse().unwrap();
+        let mut fields = Vec::new();
+        expand_word_multiple(&mut env, &word, &mut fields)
+            .now_or_never()
+            .unwrap()
+            .unwrap();
+        assert_matches!(fields.as_slice(), [f1, f2] => {
+            assert_eq!(f1.value, "/foo1");
+            assert_eq!(f2.value, "/foo2");
+        });
+    }
+
     #[test]
     fn expand_words_returns_exit_status_of_last_command_substitution() {
         in_virtual_system(|mut env, _state| async move {
@@ -738,4 +926,40 @@ mod tests {
         assert_eq!(content, ["".to_string(), r"2\".to_string()]);
         assert_eq!(exit_status, None);
     }
+
+    #[test]
+    fn eval_arith_with_variables() {
+        let mut env = yash_env::Env::new_virtual();
+        for (name, value) in [("a", "3"), ("b", "16"), ("c", "-2")] {
+            env.variables
+                .get_or_new(name, Scope::Global)
+                .assign(value, None)
+                .unwrap();
+        }
+        let expression = "d = (a + b) * c - (a > 2 ? b % 5 : 100) + (1 << 4)";
+        let location = Location::dummy(expression);
+
+        let result = eval_arith(&mut env, expression, &location);
+        assert_eq!(result, Ok(-23));
+        assert_eq!(env.variables.get_scalar("a"), Some("3"));
+        assert_eq!(env.variables.get_scalar("d"), Some("-23"));
+    }
+
+    #[test]
+    fn eval_arith_division_by_zero() {
+        let mut env = yash_env::Env::new_virtual();
+        env.variables
+            .get_or_new("zero", Scope::Global)
+            .assign("0", None)
+            .unwrap();
+        let location = Location::dummy("my location");
+
+        let error = eval_arith(&mut env, "1 / zero", &location).unwrap_err();
+        assert_eq!(
+            error.cause,
+            ErrorCause::ArithError(ArithError::DivisionByZero)
+        );
+        assert_eq!(*error.location.code.value.borrow(), "1 / zero");
+        assert_eq!(error.location.range, 2..3);
+    }
 }
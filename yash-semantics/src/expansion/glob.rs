@@ -475,6 +475,28 @@ mod tests {
         assert_eq!(i.next(), None);
     }
 
+    #[test]
+    fn single_component_pattern_matches_are_sorted() {
+        let mut env = env_with_dummy_files(["c.txt", "a.txt", "b.log", "b.txt"]);
+        let f = dummy_attr_field("*.txt");
+        let mut i = glob(&mut env, f);
+        assert_eq!(i.next().unwrap().unwrap().value, "a.txt");
+        assert_eq!(i.next().unwrap().unwrap().value, "b.txt");
+        assert_eq!(i.next().unwrap().unwrap().value, "c.txt");
+        assert_eq!(i.next(), None);
+    }
+
+    #[test]
+    fn bracket_expression_pattern() {
+        let mut env = env_with_dummy_files(["file1.txt", "file2.txt", "file3.txt", "filex.txt"]);
+        let f = dummy_attr_field("file[12x].txt");
+        let mut i = glob(&mut env, f);
+        assert_eq!(i.next().unwrap().unwrap().value, "file1.txt");
+        assert_eq!(i.next().unwrap().unwrap().value, "file2.txt");
+        assert_eq!(i.next().unwrap().unwrap().value, "filex.txt");
+        assert_eq!(i.next(), None);
+    }
+
     #[test]
     fn no_pattern_matches_dot_or_dot_dot() {
         let mut env = env_with_dummy_files([".foo"]);
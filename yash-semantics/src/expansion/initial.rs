@@ -79,6 +79,8 @@ mod tilde;
 mod word;
 
 pub use arith::ArithError;
+pub(crate) use arith::evaluate as evaluate_arith;
+pub use command_subst::CommandSubstLimit;
 pub use param::NonassignableError;
 pub use param::Vacancy;
 pub use param::VacantError;
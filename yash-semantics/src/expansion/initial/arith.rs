@@ -27,8 +27,10 @@ use crate::expansion::AssignReadOnlyError;
 use crate::expansion::expand_text;
 use std::ops::Range;
 use std::rc::Rc;
-use yash_arith::eval;
-use yash_env::option::Option::Unset;
+use yash_arith::Config;
+use yash_arith::Value;
+use yash_arith::eval_with_config;
+use yash_env::option::Option::{Portable, Unset};
 use yash_env::option::State::{Off, On};
 use yash_env::variable::Scope::Global;
 use yash_syntax::source::Code;
@@ -82,26 +84,30 @@ pub enum ArithError {
     #[error("invalid use of operator")]
     InvalidOperator,
 
+    /// Expression nested too deeply
+    #[error("expression too complex")]
+    TooComplex,
+
+    /// Operator that is not portable
+    #[error("non-portable operator")]
+    NonPortableOperator,
+
     /// A variable value that is not a valid number
     #[error("invalid variable value: {0:?}")]
     InvalidVariableValue(String),
 
-    /// Result out of bounds
-    #[error("overflow")]
-    Overflow,
-
     /// Division by zero
     #[error("division by zero")]
     DivisionByZero,
 
-    /// Left bit-shifting of a negative value
-    #[error("left-shifting a negative integer")]
-    LeftShiftingNegative,
-
     /// Bit-shifting with a negative right-hand-side operand
     #[error("negative shift width")]
     ReverseShifting,
 
+    /// Exponentiation with a negative right-hand-side operand
+    #[error("negative exponent")]
+    NegativeExponent,
+
     /// Assignment with a left-hand-side operand not being a variable
     #[error("assignment to a non-variable")]
     AssignmentToValue,
@@ -120,11 +126,12 @@ impl ArithError {
             | MissingOperator
             | ColonWithoutQuestion
             | InvalidOperator
+            | TooComplex
+            | NonPortableOperator
             | InvalidVariableValue(_)
-            | Overflow
             | DivisionByZero
-            | LeftShiftingNegative
             | ReverseShifting
+            | NegativeExponent
             | AssignmentToValue => None,
             UnclosedParenthesis { opening_location } => {
                 Some((opening_location, "the opening parenthesis was here"))
@@ -182,17 +189,18 @@ fn convert_error_cause(
                 ErrorCause::ArithError(ColonWithoutQuestion)
             }
             yash_arith::SyntaxError::InvalidOperator => ErrorCause::ArithError(InvalidOperator),
+            yash_arith::SyntaxError::TooComplex => ErrorCause::ArithError(TooComplex),
+            yash_arith::SyntaxError::NonPortableOperator => {
+                ErrorCause::ArithError(NonPortableOperator)
+            }
         },
         yash_arith::ErrorCause::EvalError(e) => match e {
             yash_arith::EvalError::InvalidVariableValue(value) => {
                 ErrorCause::ArithError(InvalidVariableValue(value))
             }
-            yash_arith::EvalError::Overflow => ErrorCause::ArithError(Overflow),
             yash_arith::EvalError::DivisionByZero => ErrorCause::ArithError(DivisionByZero),
-            yash_arith::EvalError::LeftShiftingNegative => {
-                ErrorCause::ArithError(LeftShiftingNegative)
-            }
             yash_arith::EvalError::ReverseShifting => ErrorCause::ArithError(ReverseShifting),
+            yash_arith::EvalError::NegativeExponent => ErrorCause::ArithError(NegativeExponent),
             yash_arith::EvalError::AssignmentToValue => ErrorCause::ArithError(AssignmentToValue),
             yash_arith::EvalError::GetVariableError(UnsetVariable { param }) => {
                 ErrorCause::UnsetParameter { param }
@@ -253,6 +261,48 @@ impl<S> yash_arith::Env for VarEnv<'_, S> {
     }
 }
 
+/// Evaluates an expanded arithmetic expression.
+///
+/// The `expression` is the result of expanding the content of an arithmetic
+/// expansion or other construct containing an arithmetic expression. The
+/// `location` is that of the construct and is used to report errors.
+pub(crate) fn evaluate<S>(
+    env: &mut yash_env::Env<S>,
+    expression: &str,
+    location: &Location,
+) -> Result<Value, Error> {
+    let mut config = Config::default();
+    config.portable = env.options.get(Portable).into();
+    let result = eval_with_config(
+        expression,
+        &mut VarEnv {
+            env,
+            expression,
+            expansion_location: location,
+        },
+        config,
+    );
+
+    result.map_err(|error| {
+        let code = Rc::new(Code {
+            value: expression.to_owned().into(),
+            start_line_number: 1.try_into().unwrap(),
+            source: Source::Arith {
+                original: location.clone(),
+            }
+            .into(),
+        });
+        let cause = convert_error_cause(error.cause, &code);
+        Error {
+            cause,
+            location: Location {
+                code,
+                range: error.location,
+            },
+        }
+    })
+}
+
 pub async fn expand<S: Runtime + 'static>(
     text: &Text,
     location: &Location,
@@ -263,48 +313,17 @@ pub async fn expand<S: Runtime + 'static>(
         env.last_command_subst_exit_status = exit_status;
     }
 
-    let result = eval(
-        &expression,
-        &mut VarEnv {
-            env: env.inner,
-            expression: &expression,
-            expansion_location: location,
-        },
-    );
-
-    match result {
-        Ok(value) => {
-            let value = value.to_string();
-            let chars = value
-                .chars()
-                .map(|c| AttrChar {
-                    value: c,
-                    origin: Origin::SoftExpansion,
-                    is_quoted: false,
-                    is_quoting: false,
-                })
-                .collect();
-            Ok(Phrase::Field(chars))
-        }
-        Err(error) => {
-            let code = Rc::new(Code {
-                value: expression.into(),
-                start_line_number: 1.try_into().unwrap(),
-                source: Source::Arith {
-                    original: location.clone(),
-                }
-                .into(),
-            });
-            let cause = convert_error_cause(error.cause, &code);
-            Err(Error {
-                cause,
-                location: Location {
-                    code,
-                    range: error.location,
-                },
-            })
-        }
-    }
+    let value = evaluate(env.inner, &expression, location)?.to_string();
+    let chars = value
+        .chars()
+        .map(|c| AttrChar {
+            value: c,
+            origin: Origin::SoftExpansion,
+            is_quoted: false,
+            is_quoting: false,
+        })
+        .collect();
+    Ok(Phrase::Field(chars))
 }
 
 #[cfg(test)]
@@ -473,4 +492,20 @@ mod tests {
         );
         assert_eq!(e.location.range, 0..2);
     }
+
+    #[test]
+    fn power_operator_is_rejected_in_portable_mode() {
+        let text = "2**3".parse().unwrap();
+        let location = Location::dummy("my location");
+        let mut env = yash_env::Env::new_virtual();
+        env.options.set(Portable, On);
+        let mut env = Env::new(&mut env);
+        let result = expand(&text, &location, &mut env).now_or_never().unwrap();
+        let e = result.unwrap_err();
+        assert_eq!(
+            e.cause,
+            ErrorCause::ArithError(ArithError::NonPortableOperator)
+        );
+        assert_eq!(e.location.range, 1..3);
+    }
 }
@@ -26,6 +26,7 @@ use crate::Runtime;
 use crate::expansion::ErrorCause;
 use crate::read_eval_loop;
 use crate::trap::run_exit_trap;
+use enumset::EnumSet;
 use std::cell::RefCell;
 use yash_env::io::Fd;
 use yash_env::job::Pid;
@@ -35,12 +36,47 @@ use yash_env::subshell::Config;
 use yash_env::subshell::JobControl;
 use yash_env::system::concurrency::ReadAll;
 use yash_env::system::concurrency::WaitForSignals;
-use yash_env::system::{Close, Errno, Wait};
+use yash_env::system::{Close, Errno, Fcntl, FdFlag, Pipe, Wait};
 use yash_env::trap::SignalSystem;
 use yash_syntax::parser::lex::Lexer;
 use yash_syntax::source::Location;
 use yash_syntax::source::Source;
 
+/// Limit on the size of the output captured by command substitution
+///
+/// When present in [`Env::any`](yash_env::Env::any), command substitution
+/// stops reading the output of the substituted command once
+/// [`max_bytes`](Self::max_bytes) bytes have been read. The pipe is then
+/// closed, so the command fails with `EPIPE` (or is killed by `SIGPIPE`) when
+/// it writes further output. The substitution expands to the truncated output,
+/// from which trailing newlines are removed as usual.
+///
+/// If absent, the whole output is captured.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct CommandSubstLimit {
+    /// Maximum number of bytes read from the substituted command
+    pub max_bytes: usize,
+}
+
+/// Opens a pipe with the `CloseOnExec` flag set for both ends.
+///
+/// The flag prevents the pipe from leaking into external utilities started
+/// while the pipe is open.
+fn open_pipe<S: Close + Fcntl + Pipe>(system: &S) -> Result<(Fd, Fd), Errno> {
+    let (reader, writer) = system.pipe()?;
+    let result = system
+        .fcntl_setfd(reader, FdFlag::CloseOnExec.into())
+        .and_then(|()| system.fcntl_setfd(writer, FdFlag::CloseOnExec.into()));
+    match result {
+        Ok(()) => Ok((reader, writer)),
+        Err(errno) => {
+            system.close(reader).ok();
+            system.close(writer).ok();
+            Err(errno)
+        }
+    }
+}
+
 /// Performs command substitution
 pub async fn expand<C, S>(
     command: C,
@@ -54,7 +90,7 @@ where
     let original = location.clone();
 
     // Open a pipe to read the output from the command
-    let (reader, writer) = match env.inner.system.pipe() {
+    let (reader, writer) = match open_pipe(&env.inner.system) {
         Ok(pipes) => pipes,
         Err(errno) => {
             return Err(Error {
@@ -98,6 +134,8 @@ where
             return error.handle(env).await;
         }
         env.system.close(writer).ok();
+    } else {
+        env.system.fcntl_setfd(writer, EnumSet::empty()).ok();
     }
 
     // Run the command
@@ -137,8 +175,18 @@ where
     // Read the output from the subshell
     // (In case of an error, we will use whatever we have read so far and ignore the error,
     // just like bash does.)
+    let limit = env
+        .inner
+        .any
+        .get::<CommandSubstLimit>()
+        .map_or(usize::MAX, |limit| limit.max_bytes);
     let mut result = Vec::new();
-    env.inner.system.read_all_to(reader, &mut result).await.ok();
+    env.inner
+        .system
+        .read_up_to(reader, &mut result, limit)
+        .await
+        .ok();
+    // Closing the reader lets the subshell fail to write any remaining output.
     env.inner.system.close(reader).ok();
 
     // Wait for the subshell to terminate (ignoring intermediate stopped states)
@@ -183,7 +231,7 @@ where
         .chars()
         .map(|value| AttrChar {
             value,
-            origin: Origin::SoftExpansion,
+            origin: Origin::CommandSubst,
             is_quoted: false,
             is_quoting: false,
         })
@@ -230,7 +278,7 @@ mod tests {
 
             let o = AttrChar {
                 value: 'o',
-                origin: Origin::SoftExpansion,
+                origin: Origin::CommandSubst,
                 is_quoted: false,
                 is_quoting: false,
             };
@@ -251,7 +299,84 @@ mod tests {
                 .chars()
                 .map(|value| AttrChar {
                     value,
-                    origin: Origin::SoftExpansion,
+                    origin: Origin::CommandSubst,
+                    is_quoted: false,
+                    is_quoting: false,
+                })
+                .collect();
+            assert_eq!(result, Ok(Phrase::Field(chars)));
+        })
+    }
+
+    #[test]
+    fn substitution_in_word() {
+        in_virtual_system(|mut env, _state| async move {
+            env.builtins.insert("echo", echo_builtin());
+            let word = "$(echo hi)".parse().unwrap();
+            let (field, exit_status) = crate::expansion::expand_word(&mut env, &word)
+                .await
+                .unwrap();
+            assert_eq!(field.value, "hi");
+            assert_eq!(exit_status, Some(ExitStatus::SUCCESS));
+        })
+    }
+
+    #[test]
+    fn all_trailing_newlines_are_removed() {
+        in_virtual_system(|mut env, _state| async move {
+            env.builtins.insert("echo", echo_builtin());
+            let word = "[$(echo a; echo)]".parse().unwrap();
+            let (field, _) = crate::expansion::expand_word(&mut env, &word)
+                .await
+                .unwrap();
+            assert_eq!(field.value, "[a]");
+        })
+    }
+
+    #[test]
+    fn interior_newline_separates_fields_if_unquoted() {
+        in_virtual_system(|mut env, _state| async move {
+            env.builtins.insert("echo", echo_builtin());
+            let word = "$(echo a; echo b)".parse().unwrap();
+            let mut fields = Vec::new();
+            crate::expansion::expand_word_multiple(&mut env, &word, &mut fields)
+                .await
+                .unwrap();
+            let values = fields.iter().map(|f| f.value.as_str()).collect::<Vec<_>>();
+            assert_eq!(values, ["a", "b"]);
+        })
+    }
+
+    #[test]
+    fn interior_newline_is_kept_if_quoted() {
+        in_virtual_system(|mut env, _state| async move {
+            env.builtins.insert("echo", echo_builtin());
+            let word = r#""$(echo a; echo b)""#.parse().unwrap();
+            let mut fields = Vec::new();
+            crate::expansion::expand_word_multiple(&mut env, &word, &mut fields)
+                .await
+                .unwrap();
+            let values = fields.iter().map(|f| f.value.as_str()).collect::<Vec<_>>();
+            assert_eq!(values, ["a\nb"]);
+        })
+    }
+
+    #[test]
+    fn output_is_truncated_to_limit() {
+        in_virtual_system(|mut env, _state| async move {
+            env.builtins.insert("echo", echo_builtin());
+            env.any
+                .insert(Box::new(CommandSubstLimit { max_bytes: 10 }));
+            // This loop would run forever if the output were not capped.
+            let command = "while echo yes; do echo yes; done".to_string();
+            let location = Location::dummy("");
+            let mut env = Env::new(&mut env);
+            let result = expand(command, location, &mut env).await;
+            let chars = "yes\nyes\nye"
+                .chars()
+                .map(|value| AttrChar {
+                    value,
+                    origin: Origin::CommandSubst,
                     is_quoted: false,
                     is_quoting: false,
                 })
@@ -23,20 +23,27 @@ use super::super::Origin;
 use super::super::phrase::Phrase;
 use super::Env;
 use super::Expand;
+use super::evaluate_arith;
 use crate::Runtime;
+use crate::expansion::expand_text;
+use std::borrow::Cow;
 use yash_env::option::Option::Unset;
 use yash_env::option::State::Off;
 use yash_env::variable::Value;
 use yash_syntax::source::Location;
 use yash_syntax::syntax::BracedParam;
+use yash_syntax::syntax::ListSuffix;
 use yash_syntax::syntax::Modifier;
 use yash_syntax::syntax::Param;
 use yash_syntax::syntax::ParamType;
 use yash_syntax::syntax::SpecialParam;
+use yash_syntax::syntax::Subscript;
+use yash_syntax::syntax::Text;
 
 /// Reference to a parameter expansion
 pub struct ParamRef<'a> {
     pub param: &'a Param,
+    pub subscript: Option<&'a Subscript>,
     pub modifier: &'a Modifier,
     pub location: &'a Location,
 }
@@ -45,6 +52,7 @@ impl<'a> From<&'a BracedParam> for ParamRef<'a> {
     fn from(bp: &'a BracedParam) -> Self {
         ParamRef {
             param: &bp.param,
+            subscript: bp.subscript.as_ref(),
             modifier: &bp.modifier,
             location: &bp.location,
         }
@@ -52,6 +60,7 @@ impl<'a> From<&'a BracedParam> for ParamRef<'a> {
 }
 
 // TODO Consider exporting these modules
+mod indirect;
 mod resolve;
 mod switch;
 mod trim;
@@ -63,19 +72,51 @@ pub use switch::VacantError;
 impl<S: Runtime + 'static> Expand<S> for ParamRef<'_> {
     /// Performs parameter expansion.
     async fn expand(&self, env: &mut Env<'_, S>) -> Result<Phrase, Error> {
-        // TODO Expand and parse Index
-
         // Lookup //
         let resolve = resolve::resolve(env.inner, self.param, self.location);
+        let mut value = resolve.into_owned();
+        let mut param = Cow::Borrowed(self.param);
 
-        // TODO Apply Index
+        // Subscript //
+        if let Some(Subscript::Expression(index)) = self.subscript {
+            let index = evaluate_index(env, index, self.location).await?;
+            value = value.and_then(|value| select_element(value, index));
+        }
 
-        let mut value = resolve.into_owned();
+        // Indirection //
+        match self.modifier {
+            Modifier::Indirect => {
+                if let Some(target) = indirect::target(value.take(), self.location)? {
+                    value = resolve::resolve(env.inner, &target, self.location).into_owned();
+                    param = Cow::Owned(target);
+                }
+            }
+            Modifier::VariableNames(_) => {
+                let names = env.inner.variables.names_with_prefix(&self.param.id);
+                value = Some(Value::array(names));
+            }
+            Modifier::Indices(_) => {
+                let count = match &value {
+                    None => 0,
+                    Some(Value::Scalar(_)) => 1,
+                    Some(Value::Array(values)) => values.len(),
+                };
+                value = Some(Value::array((0..count).map(|i| i.to_string())));
+            }
+            _ => (),
+        }
 
         // Switch //
         if let Modifier::Switch(switch) = self.modifier {
-            if let Some(result) =
-                switch::apply(env, switch, self.param, value.as_ref(), self.location).await
+            if let Some(result) = switch::apply(
+                env,
+                switch,
+                &param,
+                self.subscript,
+                value.as_ref(),
+                self.location,
+            )
+            .await
             {
                 return result;
             }
@@ -84,7 +125,7 @@ impl<S: Runtime + 'static> Expand<S> for ParamRef<'_> {
             if value.is_none() && env.inner.options.get(Unset) == Off {
                 return Err(Error {
                     cause: ErrorCause::UnsetParameter {
-                        param: self.param.clone(),
+                        param: param.into_owned(),
                     },
                     location: self.location.clone(),
                 });
@@ -95,7 +136,11 @@ impl<S: Runtime + 'static> Expand<S> for ParamRef<'_> {
 
         // Other modifiers //
         match self.modifier {
-            Modifier::None | Modifier::Switch(_) => (),
+            Modifier::None
+            | Modifier::Switch(_)
+            | Modifier::Indirect
+            | Modifier::VariableNames(_)
+            | Modifier::Indices(_) => (),
 
             Modifier::Length => {
                 // TODO Reject ${#*} and ${#@} in POSIX mode
@@ -113,14 +158,52 @@ impl<S: Runtime + 'static> Expand<S> for ParamRef<'_> {
             }
         }
 
+        let is_asterisk = match (self.modifier, self.subscript) {
+            (Modifier::VariableNames(suffix) | Modifier::Indices(suffix), _)
+            | (_, Some(Subscript::All(suffix))) => *suffix == ListSuffix::Asterisk,
+            _ => param.r#type == ParamType::Special(SpecialParam::Asterisk),
+        };
         let mut phrase = into_phrase(value);
-        if !env.will_split && self.param.r#type == ParamType::Special(SpecialParam::Asterisk) {
+        if !env.will_split && is_asterisk {
             phrase = Phrase::Field(phrase.ifs_join(&env.inner.variables));
         }
         Ok(phrase)
     }
 }
 
+/// Expands and evaluates the index in a subscript.
+async fn evaluate_index<S: Runtime + 'static>(
+    env: &mut Env<'_, S>,
+    index: &Text,
+    location: &Location,
+) -> Result<i64, Error> {
+    let (expression, exit_status) = expand_text(env.inner, index).await?;
+    if exit_status.is_some() {
+        env.last_command_subst_exit_status = exit_status;
+    }
+    let yash_arith::Value::Integer(index) = evaluate_arith(env.inner, &expression, location)?;
+    Ok(index)
+}
+
+/// Selects the element at the specified index from a value.
+///
+/// A scalar value is regarded as an array containing the scalar only.
+/// A negative index counts from the end of the array.
+/// The result is `None` if the index is out of range.
+fn select_element(value: Value, index: i64) -> Option<Value> {
+    let mut values = match value {
+        Value::Scalar(value) => vec![value],
+        Value::Array(values) => values,
+    };
+    let len = values.len() as i64;
+    let index = if index < 0 { index + len } else { index };
+    if (0..len).contains(&index) {
+        Some(Value::Scalar(values.swap_remove(index as usize)))
+    } else {
+        None
+    }
+}
+
 /// Modifies a string to its length.
 fn to_length(v: &mut String) {
     *v = v.chars().count().to_string()
@@ -173,6 +256,7 @@ pub mod tests {
     pub fn braced_param<P: Into<Param>>(param: P) -> BracedParam {
         BracedParam {
             param: param.into(),
+            subscript: None,
             modifier: Modifier::None,
             location: Location::dummy(""),
         }
@@ -249,6 +333,36 @@ pub mod tests {
         );
     }
 
+    #[test]
+    fn length_counts_unicode_scalar_values() {
+        let mut env = yash_env::Env::new_virtual();
+        env.variables
+            .get_or_new("x", Scope::Global)
+            .assign("caf\u{E9}", None)
+            .unwrap();
+        let mut env = Env::new(&mut env);
+        let mut param = braced_variable("x");
+        param.modifier = Modifier::Length;
+        let param = ParamRef::from(&param);
+
+        let phrase = param.expand(&mut env).now_or_never().unwrap().unwrap();
+        assert_eq!(phrase, Phrase::Field(to_field("4")));
+    }
+
+    #[test]
+    fn length_of_positional_parameters() {
+        let mut env = env_with_positional_params_and_ifs();
+        env.variables.positional_params_mut().values = vec!["yellow".into(), "red".into()];
+        let mut param = braced_param(SpecialParam::At);
+        param.modifier = Modifier::Length;
+        let param = ParamRef::from(&param);
+        let mut env = Env::new(&mut env);
+
+        // The modifier applies to each positional parameter.
+        let phrase = param.expand(&mut env).now_or_never().unwrap().unwrap();
+        assert_eq!(phrase, Phrase::Full(vec![to_field("6"), to_field("3")]));
+    }
+
     #[test]
     fn alter_empty() {
         use yash_syntax::syntax::{Switch, SwitchAction, SwitchCondition};
@@ -271,6 +385,76 @@ pub mod tests {
         assert_eq!(phrase, Phrase::Field(to_field("bar")));
     }
 
+    #[test]
+    fn switches_with_unset_empty_and_set_values() {
+        use SwitchAction::{Alter, Assign, Default, Error};
+        use SwitchCondition::{Unset, UnsetOrEmpty};
+
+        // (action, condition, value of foo, expected result or None for error)
+        let cases = [
+            (Default, Unset, None, Some("w")),
+            (Default, Unset, Some(""), Some("")),
+            (Default, Unset, Some("v"), Some("v")),
+            (Default, UnsetOrEmpty, None, Some("w")),
+            (Default, UnsetOrEmpty, Some(""), Some("w")),
+            (Default, UnsetOrEmpty, Some("v"), Some("v")),
+            (Assign, Unset, None, Some("w")),
+            (Assign, Unset, Some(""), Some("")),
+            (Assign, Unset, Some("v"), Some("v")),
+            (Assign, UnsetOrEmpty, None, Some("w")),
+            (Assign, UnsetOrEmpty, Some(""), Some("w")),
+            (Assign, UnsetOrEmpty, Some("v"), Some("v")),
+            (Error, Unset, None, None),
+            (Error, Unset, Some(""), Some("")),
+            (Error, Unset, Some("v"), Some("v")),
+            (Error, UnsetOrEmpty, None, None),
+            (Error, UnsetOrEmpty, Some(""), None),
+            (Error, UnsetOrEmpty, Some("v"), Some("v")),
+            (Alter, Unset, None, Some("")),
+            (Alter, Unset, Some(""), Some("w")),
+            (Alter, Unset, Some("v"), Some("w")),
+            (Alter, UnsetOrEmpty, None, Some("")),
+            (Alter, UnsetOrEmpty, Some(""), Some("")),
+            (Alter, UnsetOrEmpty, Some("v"), Some("w")),
+        ];
+
+        for (action, condition, value, expected) in cases {
+            let mut env = yash_env::Env::new_virtual();
+            if let Some(value) = value {
+                env.variables
+                    .get_or_new("foo", Scope::Global)
+                    .assign(value, None)
+                    .unwrap();
+            }
+            let mut param = braced_variable("foo");
+            param.modifier = Modifier::Switch(Switch {
+                action,
+                condition,
+                word: "w".parse().unwrap(),
+            });
+            let param = ParamRef::from(&param);
+            let mut env = Env::new(&mut env);
+
+            let result = param.expand(&mut env).now_or_never().unwrap();
+            let case = (action, condition, value);
+            match expected {
+                Some(expected) => {
+                    let phrase = result.unwrap();
+                    assert_eq!(phrase, Phrase::Field(to_field(expected)), "{case:?}");
+                }
+                None => assert!(result.is_err(), "{case:?}: {result:?}"),
+            }
+
+            // Only a successful assign switch modifies the variable.
+            let foo = env.inner.variables.get("foo").and_then(|v| v.value.clone());
+            let expected_value = match (action, expected) {
+                (Assign, Some(expected)) => Some(Value::scalar(expected)),
+                _ => value.map(Value::scalar),
+            };
+            assert_eq!(foo, expected_value, "{case:?}");
+        }
+    }
+
     #[test]
     fn trim_some_value() {
         use yash_syntax::syntax::{Trim, TrimLength, TrimSide};
@@ -352,6 +536,303 @@ pub mod tests {
         assert_eq!(phrase, Phrase::one_empty_field());
     }
 
+    #[test]
+    fn nounset_option_is_ignored_with_default_value() {
+        let mut env = yash_env::Env::new_virtual();
+        env.options.set(Unset, Off);
+        let mut env = Env::new(&mut env);
+        let mut param = braced_variable("foo");
+        param.modifier = Modifier::Switch(Switch {
+            action: SwitchAction::Default,
+            condition: SwitchCondition::UnsetOrEmpty,
+            word: "default".parse().unwrap(),
+        });
+        let param = ParamRef::from(&param);
+
+        let phrase = param.expand(&mut env).now_or_never().unwrap().unwrap();
+        assert_eq!(phrase, Phrase::Field(to_field("default")));
+    }
+
+    #[test]
+    fn nounset_option_with_set_variable() {
+        let mut env = yash_env::Env::new_virtual();
+        env.options.set(Unset, Off);
+        env.variables
+            .get_or_new("foo", Scope::Global)
+            .assign("", None)
+            .unwrap();
+        let mut env = Env::new(&mut env);
+        let param = braced_variable("foo");
+        let param = ParamRef::from(&param);
+
+        let phrase = param.expand(&mut env).now_or_never().unwrap().unwrap();
+        assert_eq!(phrase, Phrase::one_empty_field());
+    }
+
+    #[test]
+    fn nounset_error_message() {
+        let mut env = yash_env::Env::new_virtual();
+        env.options.set(Unset, Off);
+        let mut env = Env::new(&mut env);
+        let param = braced_variable("foo");
+        let param = ParamRef::from(&param);
+
+        let e = param.expand(&mut env).now_or_never().unwrap().unwrap_err();
+        assert_eq!(e.cause.message(), "cannot expand unset parameter");
+        assert_eq!(e.cause.label(), "parameter `foo` is not set");
+    }
+
+    #[test]
+    fn indirect_expansion() {
+        let mut env = yash_env::Env::new_virtual();
+        env.variables
+            .get_or_new("ref", Scope::Global)
+            .assign("foo", None)
+            .unwrap();
+        env.variables
+            .get_or_new("foo", Scope::Global)
+            .assign("bar", None)
+            .unwrap();
+        let mut env = Env::new(&mut env);
+        let mut param = braced_variable("ref");
+        param.modifier = Modifier::Indirect;
+        let param = ParamRef::from(&param);
+
+        let phrase = param.expand(&mut env).now_or_never().unwrap().unwrap();
+        assert_eq!(phrase, Phrase::Field(to_field("bar")));
+    }
+
+    #[test]
+    fn indirect_expansion_of_special_parameter() {
+        let mut env = env_with_positional_params_and_ifs();
+        env.variables
+            .get_or_new("ref", Scope::Global)
+            .assign("*", None)
+            .unwrap();
+        let mut env = Env::new(&mut env);
+        env.will_split = false;
+        let mut param = braced_variable("ref");
+        param.modifier = Modifier::Indirect;
+        let param = ParamRef::from(&param);
+
+        let phrase = param.expand(&mut env).now_or_never().unwrap().unwrap();
+        assert_eq!(phrase, Phrase::Field(to_field("a&c")));
+    }
+
+    #[test]
+    fn nounset_option_with_unset_indirect_target() {
+        let mut env = yash_env::Env::new_virtual();
+        env.options.set(Unset, Off);
+        env.variables
+            .get_or_new("ref", Scope::Global)
+            .assign("foo", None)
+            .unwrap();
+        let mut env = Env::new(&mut env);
+        let mut param = braced_variable("ref");
+        param.modifier = Modifier::Indirect;
+        let param = ParamRef::from(&param);
+
+        let e = param.expand(&mut env).now_or_never().unwrap().unwrap_err();
+        let param = Param::variable("foo");
+        assert_eq!(e.cause, ErrorCause::UnsetParameter { param });
+        assert_eq!(e.location, Location::dummy(""));
+    }
+
+    #[test]
+    fn unset_indirect_reference() {
+        let mut env = yash_env::Env::new_virtual();
+        let mut env = Env::new(&mut env);
+        let mut param = braced_variable("ref");
+        param.modifier = Modifier::Indirect;
+        let param = ParamRef::from(&param);
+
+        let phrase = param.expand(&mut env).now_or_never().unwrap().unwrap();
+        assert_eq!(phrase, Phrase::one_empty_field());
+    }
+
+    fn env_with_variables_foo_and_bar() -> yash_env::Env<Rc<Concurrent<VirtualSystem>>> {
+        let mut env = env_with_positional_params_and_ifs();
+        for name in ["foo_2", "bar", "foo_1", "foo"] {
+            env.variables
+                .get_or_new(name, Scope::Global)
+                .assign("", None)
+                .unwrap();
+        }
+        env
+    }
+
+    #[test]
+    fn variable_names_at() {
+        let mut env = env_with_variables_foo_and_bar();
+        let mut env = Env::new(&mut env);
+        env.will_split = false;
+        let mut param = braced_variable("foo");
+        param.modifier = Modifier::VariableNames(ListSuffix::At);
+        let param = ParamRef::from(&param);
+
+        let phrase = param.expand(&mut env).now_or_never().unwrap().unwrap();
+        let fields = vec![to_field("foo"), to_field("foo_1"), to_field("foo_2")];
+        assert_eq!(phrase, Phrase::Full(fields));
+    }
+
+    #[test]
+    fn variable_names_asterisk() {
+        let mut env = env_with_variables_foo_and_bar();
+        let mut env = Env::new(&mut env);
+        env.will_split = false;
+        let mut param = braced_variable("foo_");
+        param.modifier = Modifier::VariableNames(ListSuffix::Asterisk);
+        let param = ParamRef::from(&param);
+
+        let phrase = param.expand(&mut env).now_or_never().unwrap().unwrap();
+        assert_eq!(phrase, Phrase::Field(to_field("foo_1&foo_2")));
+    }
+
+    #[test]
+    fn no_variable_names() {
+        let mut env = env_with_variables_foo_and_bar();
+        env.options.set(Unset, Off);
+        let mut env = Env::new(&mut env);
+        let mut param = braced_variable("x");
+        param.modifier = Modifier::VariableNames(ListSuffix::At);
+        let param = ParamRef::from(&param);
+
+        let phrase = param.expand(&mut env).now_or_never().unwrap().unwrap();
+        assert_eq!(phrase, Phrase::Full(vec![]));
+    }
+
+    #[test]
+    fn indices_of_array() {
+        let mut env = env_with_positional_params_and_ifs();
+        env.variables
+            .get_or_new("a", Scope::Global)
+            .assign(Value::array(["x", "y", "z"]), None)
+            .unwrap();
+        let mut env = Env::new(&mut env);
+        env.will_split = false;
+        let mut param = braced_variable("a");
+        param.modifier = Modifier::Indices(ListSuffix::At);
+        let param = ParamRef::from(&param);
+
+        let phrase = param.expand(&mut env).now_or_never().unwrap().unwrap();
+        let fields = vec![to_field("0"), to_field("1"), to_field("2")];
+        assert_eq!(phrase, Phrase::Full(fields));
+    }
+
+    #[test]
+    fn indices_of_unset_variable() {
+        let mut env = env_with_positional_params_and_ifs();
+        env.options.set(Unset, Off);
+        let mut env = Env::new(&mut env);
+        let mut param = braced_variable("a");
+        param.modifier = Modifier::Indices(ListSuffix::Asterisk);
+        let param = ParamRef::from(&param);
+
+        let phrase = param.expand(&mut env).now_or_never().unwrap().unwrap();
+        assert_eq!(phrase, Phrase::Full(vec![]));
+    }
+
+    fn env_with_array_and_ifs() -> yash_env::Env<Rc<Concurrent<VirtualSystem>>> {
+        let mut env = env_with_positional_params_and_ifs();
+        env.variables
+            .get_or_new("a", Scope::Global)
+            .assign(Value::array(["x", "y", "z"]), None)
+            .unwrap();
+        env
+    }
+
+    #[test]
+    fn numeric_subscript() {
+        let mut env = env_with_array_and_ifs();
+        env.variables
+            .get_or_new("i", Scope::Global)
+            .assign("1", None)
+            .unwrap();
+        let mut env = Env::new(&mut env);
+        let mut param = braced_variable("a");
+        param.subscript = Some(Subscript::Expression("$i+1".parse().unwrap()));
+        let param = ParamRef::from(&param);
+
+        let phrase = param.expand(&mut env).now_or_never().unwrap().unwrap();
+        assert_eq!(phrase, Phrase::Field(to_field("z")));
+    }
+
+    #[test]
+    fn negative_subscript_counts_from_end() {
+        let mut env = env_with_array_and_ifs();
+        let mut env = Env::new(&mut env);
+        let mut param = braced_variable("a");
+        param.subscript = Some(Subscript::Expression("-1".parse().unwrap()));
+        let param = ParamRef::from(&param);
+
+        let phrase = param.expand(&mut env).now_or_never().unwrap().unwrap();
+        assert_eq!(phrase, Phrase::Field(to_field("z")));
+    }
+
+    #[test]
+    fn out_of_range_subscript() {
+        let mut env = env_with_array_and_ifs();
+        let mut env = Env::new(&mut env);
+        for index in ["3", "-4"] {
+            let mut param = braced_variable("a");
+            param.subscript = Some(Subscript::Expression(index.parse().unwrap()));
+            let param = ParamRef::from(&param);
+
+            let phrase = param.expand(&mut env).now_or_never().unwrap().unwrap();
+            assert_eq!(phrase, Phrase::one_empty_field(), "index={index:?}");
+        }
+    }
+
+    #[test]
+    fn subscript_of_scalar() {
+        let mut env = yash_env::Env::new_virtual();
+        env.variables
+            .get_or_new("foo", Scope::Global)
+            .assign("bar", None)
+            .unwrap();
+        let mut env = Env::new(&mut env);
+        let mut param = braced_variable("foo");
+        param.subscript = Some(Subscript::Expression("0".parse().unwrap()));
+        let param = ParamRef::from(&param);
+
+        let phrase = param.expand(&mut env).now_or_never().unwrap().unwrap();
+        assert_eq!(phrase, Phrase::Field(to_field("bar")));
+    }
+
+    #[test]
+    fn at_subscript_in_splitting_and_non_splitting_contexts() {
+        let mut env = env_with_array_and_ifs();
+        let mut env = Env::new(&mut env);
+        let mut param = braced_variable("a");
+        param.subscript = Some(Subscript::All(ListSuffix::At));
+        let param = ParamRef::from(&param);
+        let fields = vec![to_field("x"), to_field("y"), to_field("z")];
+
+        let phrase = param.expand(&mut env).now_or_never().unwrap().unwrap();
+        assert_eq!(phrase, Phrase::Full(fields.clone()));
+
+        env.will_split = false;
+        let phrase = param.expand(&mut env).now_or_never().unwrap().unwrap();
+        assert_eq!(phrase, Phrase::Full(fields));
+    }
+
+    #[test]
+    fn asterisk_subscript_in_splitting_and_non_splitting_contexts() {
+        let mut env = env_with_array_and_ifs();
+        let mut env = Env::new(&mut env);
+        let mut param = braced_variable("a");
+        param.subscript = Some(Subscript::All(ListSuffix::Asterisk));
+        let param = ParamRef::from(&param);
+
+        let phrase = param.expand(&mut env).now_or_never().unwrap().unwrap();
+        let fields = vec![to_field("x"), to_field("y"), to_field("z")];
+        assert_eq!(phrase, Phrase::Full(fields));
+
+        env.will_split = false;
+        let phrase = param.expand(&mut env).now_or_never().unwrap().unwrap();
+        assert_eq!(phrase, Phrase::Field(to_field("x&y&z")));
+    }
+
     #[test]
     fn expand_at_no_join_in_non_splitting_context() {
         let mut env = env_with_positional_params_and_ifs();
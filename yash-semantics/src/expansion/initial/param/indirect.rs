@@ -0,0 +1,115 @@
+// This file is part of yash, an extended POSIX shell.
+// Copyright (C) 2026 WATANABE Yuki
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+//! Parameter expansion indirection semantics
+
+use super::Error;
+use super::ErrorCause;
+use yash_env::variable::Value;
+use yash_syntax::parser::lex::is_name;
+use yash_syntax::source::Location;
+use yash_syntax::syntax::Param;
+use yash_syntax::syntax::ParamType;
+use yash_syntax::syntax::SpecialParam;
+
+/// Parses a string as a parameter name.
+fn parse_param(name: &str) -> Option<Param> {
+    if let Ok(special) = name.parse::<SpecialParam>() {
+        return Some(special.into());
+    }
+    if !name.is_empty() && name.chars().all(|c| c.is_ascii_digit()) {
+        let index = name.parse().unwrap_or(usize::MAX);
+        let id = name.to_owned();
+        let r#type = ParamType::Positional(index);
+        return Some(Param { id, r#type });
+    }
+    is_name(name).then(|| Param::variable(name))
+}
+
+/// Returns the parameter named by the value of an indirect expansion.
+///
+/// If the value is an array, its first element is used as the name. The
+/// result is `None` if the value is unset or an empty array. It is an error if
+/// the name is not a valid parameter name.
+pub fn target(value: Option<Value>, location: &Location) -> Result<Option<Param>, Error> {
+    let name = match value {
+        None => return Ok(None),
+        Some(Value::Scalar(name)) => name,
+        Some(Value::Array(names)) => match names.into_iter().next() {
+            None => return Ok(None),
+            Some(name) => name,
+        },
+    };
+
+    match parse_param(&name) {
+        Some(param) => Ok(Some(param)),
+        None => Err(Error {
+            cause: ErrorCause::InvalidIndirection { name },
+            location: location.clone(),
+        }),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use assert_matches::assert_matches;
+
+    #[test]
+    fn target_of_unset_value() {
+        let location = Location::dummy("");
+        assert_eq!(target(None, &location), Ok(None));
+        assert_eq!(target(Some(Value::Array(vec![])), &location), Ok(None));
+    }
+
+    #[test]
+    fn target_variable() {
+        let location = Location::dummy("");
+        let result = target(Some(Value::scalar("foo")), &location);
+        assert_eq!(result, Ok(Some(Param::variable("foo"))));
+
+        let result = target(Some(Value::array(["bar", "baz"])), &location);
+        assert_eq!(result, Ok(Some(Param::variable("bar"))));
+    }
+
+    #[test]
+    fn target_special_and_positional() {
+        let location = Location::dummy("");
+        let result = target(Some(Value::scalar("@")), &location);
+        assert_eq!(result, Ok(Some(Param::from(SpecialParam::At))));
+
+        let result = target(Some(Value::scalar("0")), &location);
+        assert_eq!(result, Ok(Some(Param::from(SpecialParam::Zero))));
+
+        let result = target(Some(Value::scalar("12")), &location);
+        assert_eq!(result, Ok(Some(Param::from(12))));
+    }
+
+    #[test]
+    fn invalid_target() {
+        let location = Location::dummy("loc");
+        let error = target(Some(Value::scalar("a b")), &location).unwrap_err();
+        assert_matches!(error.cause, ErrorCause::InvalidIndirection { name } => {
+            assert_eq!(name, "a b");
+        });
+        assert_eq!(error.location, location);
+
+        let error = target(Some(Value::scalar("")), &location).unwrap_err();
+        assert_matches!(error.cause, ErrorCause::InvalidIndirection { name } => {
+            assert_eq!(name, "");
+        });
+    }
+}
@@ -25,7 +25,11 @@ use yash_syntax::syntax::ParamType::*;
 use yash_syntax::syntax::SpecialParam::*;
 
 /// Resolves a parameter name to its value.
-pub fn resolve<'a, S>(env: &'a Env<S>, param: &Param, location: &Location) -> Expansion<'a> {
+///
+/// The environment is taken mutably because expanding `$!` marks the
+/// corresponding job as referenced by
+/// [`JobList::expand_last_async_pid`](yash_env::job::JobList::expand_last_async_pid).
+pub fn resolve<'a, S>(env: &'a mut Env<S>, param: &Param, location: &Location) -> Expansion<'a> {
     fn variable<'a, S>(env: &'a Env<S>, name: &str, location: &Location) -> Expansion<'a> {
         env.variables
             .get(name)
@@ -60,7 +64,7 @@ pub fn resolve<'a, S>(env: &'a Env<S>, param: &Param, location: &Location) -> Ex
         Special(Question) => env.exit_status.to_string().into(),
         Special(Hyphen) => options(env),
         Special(Dollar) => env.main_pid.to_string().into(),
-        Special(Exclamation) => non_zero_pid_or_unset(env.jobs.last_async_pid()),
+        Special(Exclamation) => non_zero_pid_or_unset(env.jobs.expand_last_async_pid()),
         Special(Zero) => env.arg0.as_str().into(),
         Positional(0) => Expansion::Unset,
         Positional(index) => positional(env).get(index - 1).into(),
@@ -70,6 +74,7 @@ pub fn resolve<'a, S>(env: &'a Env<S>, param: &Param, location: &Location) -> Ex
 #[cfg(test)]
 mod tests {
     use super::*;
+    use yash_env::job::Job;
     use yash_env::job::Pid;
     use yash_env::variable::PATH;
     use yash_env::variable::Scope;
@@ -79,18 +84,18 @@ mod tests {
 
     #[test]
     fn variable_unset() {
-        let env = Env::new_virtual();
+        let mut env = Env::new_virtual();
         let loc = Location::dummy("");
         assert_eq!(
-            resolve(&env, &Param::variable("foo"), &loc),
+            resolve(&mut env, &Param::variable("foo"), &loc),
             Expansion::Unset
         );
         assert_eq!(
-            resolve(&env, &Param::variable("bar"), &loc),
+            resolve(&mut env, &Param::variable("bar"), &loc),
             Expansion::Unset
         );
         assert_eq!(
-            resolve(&env, &Param::variable("baz"), &loc),
+            resolve(&mut env, &Param::variable("baz"), &loc),
             Expansion::Unset
         );
     }
@@ -107,9 +112,9 @@ mod tests {
         path.make_read_only(Location::dummy("read-only"));
         let loc = Location::dummy("");
 
-        let result = resolve(&env, &Param::variable("x"), &loc);
+        let result = resolve(&mut env, &Param::variable("x"), &loc);
         assert_eq!(result, Expansion::Scalar("foo".into()));
-        let result = resolve(&env, &Param::variable(PATH), &loc);
+        let result = resolve(&mut env, &Param::variable(PATH), &loc);
         assert_eq!(result, Expansion::Scalar("/bin:/usr/bin".into()));
     }
 
@@ -129,9 +134,9 @@ mod tests {
         path.make_read_only(Location::dummy("read-only"));
         let loc = Location::dummy("");
 
-        let result = resolve(&env, &Param::variable("x"), &loc);
+        let result = resolve(&mut env, &Param::variable("x"), &loc);
         assert_eq!(result, Expansion::Array([].as_slice().into()));
-        let result = resolve(&env, &Param::variable(PATH), &loc);
+        let result = resolve(&mut env, &Param::variable(PATH), &loc);
         assert_eq!(result, Expansion::Array(values.as_slice().into()));
     }
 
@@ -139,7 +144,7 @@ mod tests {
     fn special_positional_at() {
         let mut env = Env::new_virtual();
         let loc = Location::dummy("");
-        let result = resolve(&env, &Param::from(SpecialParam::At), &loc);
+        let result = resolve(&mut env, &Param::from(SpecialParam::At), &loc);
         assert_eq!(result, Expansion::Array([].as_slice().into()));
 
         let params = vec!["a".to_string(), "foo bar".to_string(), "9".to_string()];
@@ -147,7 +152,7 @@ mod tests {
             .positional_params_mut()
             .values
             .clone_from(&params);
-        let result = resolve(&env, &Param::from(SpecialParam::At), &loc);
+        let result = resolve(&mut env, &Param::from(SpecialParam::At), &loc);
         assert_eq!(result, Expansion::Array(params.into()));
     }
 
@@ -155,7 +160,7 @@ mod tests {
     fn special_positional_asterisk() {
         let mut env = Env::new_virtual();
         let loc = Location::dummy("");
-        let result = resolve(&env, &Param::from(SpecialParam::Asterisk), &loc);
+        let result = resolve(&mut env, &Param::from(SpecialParam::Asterisk), &loc);
         assert_eq!(result, Expansion::Array([].as_slice().into()));
 
         let params = vec!["a".to_string(), "foo bar".to_string(), "9".to_string()];
@@ -163,7 +168,7 @@ mod tests {
             .positional_params_mut()
             .values
             .clone_from(&params);
-        let result = resolve(&env, &Param::from(SpecialParam::Asterisk), &loc);
+        let result = resolve(&mut env, &Param::from(SpecialParam::Asterisk), &loc);
         assert_eq!(result, Expansion::Array(params.into()));
     }
 
@@ -171,12 +176,12 @@ mod tests {
     fn special_length() {
         let mut env = Env::new_virtual();
         let loc = Location::dummy("");
-        let result = resolve(&env, &Param::from(SpecialParam::Number), &loc);
+        let result = resolve(&mut env, &Param::from(SpecialParam::Number), &loc);
         assert_eq!(result, Expansion::Scalar("0".into()));
 
         let params = vec!["a".to_string(), "foo bar".to_string(), "9".to_string()];
         env.variables.positional_params_mut().values = params;
-        let result = resolve(&env, &Param::from(SpecialParam::Number), &loc);
+        let result = resolve(&mut env, &Param::from(SpecialParam::Number), &loc);
         assert_eq!(result, Expansion::Scalar("3".into()));
     }
 
@@ -184,11 +189,11 @@ mod tests {
     fn special_exit_status() {
         let mut env = Env::new_virtual();
         let loc = Location::dummy("");
-        let result = resolve(&env, &Param::from(SpecialParam::Question), &loc);
+        let result = resolve(&mut env, &Param::from(SpecialParam::Question), &loc);
         assert_eq!(result, Expansion::Scalar("0".into()));
 
         env.exit_status.0 = 49;
-        let result = resolve(&env, &Param::from(SpecialParam::Question), &loc);
+        let result = resolve(&mut env, &Param::from(SpecialParam::Question), &loc);
         assert_eq!(result, Expansion::Scalar("49".into()));
     }
 
@@ -196,19 +201,19 @@ mod tests {
     fn special_shell_options() {
         let mut env = Env::new_virtual();
         let loc = Location::dummy("");
-        let result = resolve(&env, &Param::from(SpecialParam::Hyphen), &loc);
+        let result = resolve(&mut env, &Param::from(SpecialParam::Hyphen), &loc);
         assert_eq!(result, Expansion::Scalar("".into()));
 
         use yash_env::option::{Option::*, OptionSet, State};
         env.options = OptionSet::empty();
-        let result = resolve(&env, &Param::from(SpecialParam::Hyphen), &loc);
+        let result = resolve(&mut env, &Param::from(SpecialParam::Hyphen), &loc);
         assert_eq!(result, Expansion::Scalar("Cnfu".into()));
 
         env.options = OptionSet::default();
         env.options.set(AllExport, State::On);
         env.options.set(Verbose, State::On);
         env.options.set(Vi, State::On);
-        let result = resolve(&env, &Param::from(SpecialParam::Hyphen), &loc);
+        let result = resolve(&mut env, &Param::from(SpecialParam::Hyphen), &loc);
         assert_eq!(result, Expansion::Scalar("av".into()));
     }
 
@@ -216,11 +221,11 @@ mod tests {
     fn special_main_pid() {
         let mut env = Env::new_virtual();
         let loc = Location::dummy("");
-        let result = resolve(&env, &Param::from(SpecialParam::Dollar), &loc);
+        let result = resolve(&mut env, &Param::from(SpecialParam::Dollar), &loc);
         assert_eq!(result, Expansion::Scalar("2".into()));
 
         env.main_pid = Pid(12345);
-        let result = resolve(&env, &Param::from(SpecialParam::Dollar), &loc);
+        let result = resolve(&mut env, &Param::from(SpecialParam::Dollar), &loc);
         assert_eq!(result, Expansion::Scalar("12345".into()));
     }
 
@@ -228,34 +233,48 @@ mod tests {
     fn special_last_async_pid() {
         let mut env = Env::new_virtual();
         let loc = Location::dummy("");
-        let result = resolve(&env, &Param::from(SpecialParam::Exclamation), &loc);
+        let result = resolve(&mut env, &Param::from(SpecialParam::Exclamation), &loc);
         assert_eq!(result, Expansion::Unset);
 
         env.jobs.set_last_async_pid(Pid(72));
-        let result = resolve(&env, &Param::from(SpecialParam::Exclamation), &loc);
+        let result = resolve(&mut env, &Param::from(SpecialParam::Exclamation), &loc);
+        assert_eq!(result, Expansion::Scalar("72".into()));
+    }
+
+    #[test]
+    fn special_last_async_pid_marks_job() {
+        let mut env = Env::new_virtual();
+        let index = env.jobs.insert(Job::new(Pid(72)));
+        let other = env.jobs.insert(Job::new(Pid(73)));
+        env.jobs.set_last_async_pid(Pid(72));
+        let loc = Location::dummy("");
+
+        let result = resolve(&mut env, &Param::from(SpecialParam::Exclamation), &loc);
         assert_eq!(result, Expansion::Scalar("72".into()));
+        assert!(env.jobs[index].pid_expanded);
+        assert!(!env.jobs[other].pid_expanded);
     }
 
     #[test]
     fn special_arg0() {
         let mut env = Env::new_virtual();
         let loc = Location::dummy("");
-        let result = resolve(&env, &Param::from(SpecialParam::Zero), &loc);
+        let result = resolve(&mut env, &Param::from(SpecialParam::Zero), &loc);
         assert_eq!(result, Expansion::Scalar("".into()));
 
         env.arg0 = "foo/bar".to_string();
-        let result = resolve(&env, &Param::from(SpecialParam::Zero), &loc);
+        let result = resolve(&mut env, &Param::from(SpecialParam::Zero), &loc);
         assert_eq!(result, Expansion::Scalar("foo/bar".into()));
     }
 
     #[test]
     fn positional_unset() {
-        let env = Env::new_virtual();
+        let mut env = Env::new_virtual();
         let loc = Location::dummy("");
-        assert_eq!(resolve(&env, &Param::from(0), &loc), Expansion::Unset);
-        assert_eq!(resolve(&env, &Param::from(1), &loc), Expansion::Unset);
-        assert_eq!(resolve(&env, &Param::from(2), &loc), Expansion::Unset);
-        assert_eq!(resolve(&env, &Param::from(10), &loc), Expansion::Unset);
+        assert_eq!(resolve(&mut env, &Param::from(0), &loc), Expansion::Unset);
+        assert_eq!(resolve(&mut env, &Param::from(1), &loc), Expansion::Unset);
+        assert_eq!(resolve(&mut env, &Param::from(2), &loc), Expansion::Unset);
+        assert_eq!(resolve(&mut env, &Param::from(10), &loc), Expansion::Unset);
     }
 
     #[test]
@@ -264,13 +283,13 @@ mod tests {
         env.variables.positional_params_mut().values = vec!["a".to_string(), "b".to_string()];
         let loc = Location::dummy("");
 
-        assert_eq!(resolve(&env, &Param::from(0), &loc), Expansion::Unset);
-        assert_eq!(resolve(&env, &Param::from(3), &loc), Expansion::Unset);
-        assert_eq!(resolve(&env, &Param::from(10), &loc), Expansion::Unset);
+        assert_eq!(resolve(&mut env, &Param::from(0), &loc), Expansion::Unset);
+        assert_eq!(resolve(&mut env, &Param::from(3), &loc), Expansion::Unset);
+        assert_eq!(resolve(&mut env, &Param::from(10), &loc), Expansion::Unset);
 
-        let result = resolve(&env, &Param::from(1), &loc);
+        let result = resolve(&mut env, &Param::from(1), &loc);
         assert_eq!(result, Expansion::Scalar("a".into()));
-        let result = resolve(&env, &Param::from(2), &loc);
+        let result = resolve(&mut env, &Param::from(2), &loc);
         assert_eq!(result, Expansion::Scalar("b".into()));
     }
 }
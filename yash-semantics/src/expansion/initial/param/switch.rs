@@ -33,6 +33,7 @@ use yash_env::variable::Value;
 use yash_syntax::source::Location;
 use yash_syntax::syntax::Param;
 use yash_syntax::syntax::ParamType;
+use yash_syntax::syntax::Subscript;
 use yash_syntax::syntax::Switch;
 use yash_syntax::syntax::SwitchAction;
 use yash_syntax::syntax::SwitchCondition;
@@ -138,11 +139,13 @@ pub enum NonassignableErrorCause {
     /// The parameter is not a variable.
     #[error("parameter `{param}` is not an assignable variable")]
     NotVariable { param: Param },
-    // /// The parameter expansion refers to an array but does not index a single
-    // /// element.
-    // #[error("cannot assign to a non-scalar array range")]
-    // TODO ArrayIndex,
-
+    /// The parameter expansion refers to an array but does not index a single
+    /// element.
+    #[error("cannot assign to a non-scalar array range")]
+    ArrayIndex,
+    /// The parameter expansion indexes a single array element.
+    #[error("cannot assign to an array element")]
+    ArrayElement,
     // /// The parameter expansion is nested.
     // #[error("cannot assign to a nested parameter expansion")]
     // TODO Nested,
@@ -181,9 +184,10 @@ impl ValueCondition {
 /// part of a parameter expansion, the substitution produced by the switch
 /// should be regarded as originating from a parameter expansion.
 fn attribute(mut phrase: Phrase) -> Phrase {
-    phrase.for_each_char_mut(|c| match c.origin {
-        Origin::Literal => c.origin = Origin::SoftExpansion,
-        Origin::HardExpansion | Origin::SoftExpansion => (),
+    phrase.for_each_char_mut(|c| {
+        if c.origin == Origin::Literal {
+            c.origin = Origin::SoftExpansion;
+        }
     });
     phrase
 }
@@ -196,14 +200,22 @@ fn attribute(mut phrase: Phrase) -> Phrase {
 async fn assign<S: Runtime + 'static>(
     env: &mut Env<'_, S>,
     param: &Param,
+    subscript: Option<&Subscript>,
     vacancy: Vacancy,
     value: &Word,
     location: Location,
 ) -> Result<Phrase, Error> {
     // TODO Support assignment to an array element
-    if param.r#type != ParamType::Variable {
-        let param = param.clone();
-        let cause = NonassignableErrorCause::NotVariable { param };
+    let cause = match subscript {
+        _ if param.r#type != ParamType::Variable => {
+            let param = param.clone();
+            Some(NonassignableErrorCause::NotVariable { param })
+        }
+        Some(Subscript::All(_)) => Some(NonassignableErrorCause::ArrayIndex),
+        Some(Subscript::Expression(_)) => Some(NonassignableErrorCause::ArrayElement),
+        None => None,
+    };
+    if let Some(cause) = cause {
         let cause = ErrorCause::NonassignableParameter(NonassignableError { cause, vacancy });
         return Err(Error { cause, location });
     }
@@ -272,6 +284,7 @@ pub async fn apply<S: Runtime + 'static>(
     env: &mut Env<'_, S>,
     switch: &Switch,
     param: &Param,
+    subscript: Option<&Subscript>,
     value: Option<&Value>,
     location: &Location,
 ) -> Option<Result<Phrase, Error>> {
@@ -286,7 +299,8 @@ pub async fn apply<S: Runtime + 'static>(
         }
 
         (Assign, Vacant(vacancy)) => {
-            Some(assign(env, param, vacancy, &switch.word, location.clone()).await)
+            let word = &switch.word;
+            Some(assign(env, param, subscript, vacancy, word, location.clone()).await)
         }
 
         (Error, Vacant(vacancy)) => Some(Err(vacant_expansion_error(
@@ -390,7 +404,7 @@ mod tests {
         };
         let param = Param::variable("var");
         let location = Location::dummy("somewhere");
-        let result = apply(&mut env, &switch, &param, None, &location)
+        let result = apply(&mut env, &switch, &param, None, None, &location)
             .now_or_never()
             .unwrap();
         assert_eq!(result, None);
@@ -408,7 +422,7 @@ mod tests {
         let param = Param::variable("var");
         let value = Value::scalar("bar");
         let location = Location::dummy("somewhere");
-        let result = apply(&mut env, &switch, &param, Some(&value), &location)
+        let result = apply(&mut env, &switch, &param, None, Some(&value), &location)
             .now_or_never()
             .unwrap();
         assert_eq!(result, Some(Ok(Phrase::Field(to_field("foo")))));
@@ -425,7 +439,7 @@ mod tests {
         };
         let param = Param::variable("var");
         let location = Location::dummy("somewhere");
-        let result = apply(&mut env, &switch, &param, None, &location)
+        let result = apply(&mut env, &switch, &param, None, None, &location)
             .now_or_never()
             .unwrap();
         assert_eq!(result, Some(Ok(Phrase::Field(to_field("foo")))));
@@ -443,7 +457,7 @@ mod tests {
         let param = Param::variable("var");
         let value = Value::scalar("bar");
         let location = Location::dummy("somewhere");
-        let result = apply(&mut env, &switch, &param, Some(&value), &location)
+        let result = apply(&mut env, &switch, &param, None, Some(&value), &location)
             .now_or_never()
             .unwrap();
         assert_eq!(result, None);
@@ -461,7 +475,7 @@ mod tests {
         let param = Param::variable("var");
         let location = Location::dummy("somewhere");
 
-        let result = apply(&mut env, &switch, &param, None, &location)
+        let result = apply(&mut env, &switch, &param, None, None, &location)
             .now_or_never()
             .unwrap();
         assert_eq!(result, Some(Ok(Phrase::Field(to_field("foo")))));
@@ -473,6 +487,64 @@ mod tests {
         assert_eq!(var.read_only_location, None);
     }
 
+    #[test]
+    fn alter_with_empty_word() {
+        let mut env = yash_env::Env::new_virtual();
+        let mut env = Env::new(&mut env);
+        let switch = Switch {
+            action: Alter,
+            condition: UnsetOrEmpty,
+            word: "".parse().unwrap(),
+        };
+        let param = Param::variable("var");
+        let value = Value::scalar("bar");
+        let location = Location::dummy("somewhere");
+        let result = apply(&mut env, &switch, &param, None, Some(&value), &location)
+            .now_or_never()
+            .unwrap();
+        assert_eq!(result, Some(Ok(Phrase::one_empty_field())));
+    }
+
+    #[test]
+    fn default_with_empty_word() {
+        let mut env = yash_env::Env::new_virtual();
+        let mut env = Env::new(&mut env);
+        let switch = Switch {
+            action: Default,
+            condition: UnsetOrEmpty,
+            word: "".parse().unwrap(),
+        };
+        let param = Param::variable("var");
+        let value = Value::scalar("");
+        let location = Location::dummy("somewhere");
+        let result = apply(&mut env, &switch, &param, None, Some(&value), &location)
+            .now_or_never()
+            .unwrap();
+        assert_eq!(result, Some(Ok(Phrase::one_empty_field())));
+    }
+
+    #[test]
+    fn assign_with_empty_word() {
+        let mut env = yash_env::Env::new_virtual();
+        let mut env = Env::new(&mut env);
+        let switch = Switch {
+            action: Assign,
+            condition: UnsetOrEmpty,
+            word: "".parse().unwrap(),
+        };
+        let param = Param::variable("var");
+        let location = Location::dummy("somewhere");
+
+        let result = apply(&mut env, &switch, &param, None, None, &location)
+            .now_or_never()
+            .unwrap();
+        assert_eq!(result, Some(Ok(Phrase::one_empty_field())));
+
+        let var = env.inner.variables.get("var").unwrap();
+        assert_eq!(var.value, Some(Value::scalar("")));
+        assert_eq!(var.last_assigned_location, Some(location));
+    }
+
     #[test]
     fn assign_array_word() {
         let mut env = yash_env::Env::new_virtual();
@@ -491,7 +563,7 @@ mod tests {
         let param = Param::variable("var");
         let location = Location::dummy("somewhere");
 
-        let result = apply(&mut env, &switch, &param, None, &location)
+        let result = apply(&mut env, &switch, &param, None, None, &location)
             .now_or_never()
             .unwrap();
 
@@ -538,7 +610,7 @@ mod tests {
         let param = Param::variable("var");
         let value = Value::scalar("bar");
         let location = Location::dummy("somewhere");
-        let result = apply(&mut env, &switch, &param, Some(&value), &location)
+        let result = apply(&mut env, &switch, &param, None, Some(&value), &location)
             .now_or_never()
             .unwrap();
         assert_eq!(result, None);
@@ -561,7 +633,7 @@ mod tests {
         let value = save_var.value.as_ref();
         let location = Location::dummy("somewhere");
 
-        let result = apply(&mut env, &switch, &param, value, &location)
+        let result = apply(&mut env, &switch, &param, None, value, &location)
             .now_or_never()
             .unwrap();
         assert_matches!(result, Some(Err(error)) => {
@@ -589,7 +661,7 @@ mod tests {
         let value = Value::scalar("");
         let location = Location::dummy("somewhere");
 
-        let result = apply(&mut env, &switch, &param, Some(&value), &location)
+        let result = apply(&mut env, &switch, &param, None, Some(&value), &location)
             .now_or_never()
             .unwrap();
         let error = result.unwrap().unwrap_err();
@@ -603,6 +675,34 @@ mod tests {
         assert_eq!(error.location, location);
     }
 
+    #[test]
+    fn assign_to_array_element() {
+        let mut env = yash_env::Env::new_virtual();
+        let mut env = Env::new(&mut env);
+        let switch = Switch {
+            action: Assign,
+            condition: Unset,
+            word: "foo".parse().unwrap(),
+        };
+        let param = Param::variable("var");
+        let subscript = Subscript::Expression("0".parse().unwrap());
+        let location = Location::dummy("somewhere");
+
+        let result = apply(&mut env, &switch, &param, Some(&subscript), None, &location)
+            .now_or_never()
+            .unwrap();
+        let error = result.unwrap().unwrap_err();
+        assert_matches!(
+            error.cause,
+            ErrorCause::NonassignableParameter(error) => {
+                assert_eq!(error.cause, NonassignableErrorCause::ArrayElement);
+                assert_eq!(error.vacancy, Vacancy::Unset);
+            }
+        );
+        assert_eq!(error.location, location);
+        assert_eq!(env.inner.variables.get("var"), None);
+    }
+
     #[test]
     fn error_with_vacant_value_and_non_empty_word() {
         let mut env = yash_env::Env::new_virtual();
@@ -614,7 +714,7 @@ mod tests {
         };
         let param = Param::variable("var");
         let location = Location::dummy("somewhere");
-        let result = apply(&mut env, &switch, &param, None, &location)
+        let result = apply(&mut env, &switch, &param, None, None, &location)
             .now_or_never()
             .unwrap();
         let error = result.unwrap().unwrap_err();
@@ -637,7 +737,7 @@ mod tests {
         let param = Param::variable("var");
         let value = Value::scalar("");
         let location = Location::dummy("somewhere");
-        let result = apply(&mut env, &switch, &param, Some(&value), &location)
+        let result = apply(&mut env, &switch, &param, None, Some(&value), &location)
             .now_or_never()
             .unwrap();
         let error = result.unwrap().unwrap_err();
@@ -648,6 +748,28 @@ mod tests {
         });
     }
 
+    #[test]
+    fn error_with_unset_value_and_empty_word() {
+        let mut env = yash_env::Env::new_virtual();
+        let mut env = Env::new(&mut env);
+        let switch = Switch {
+            action: Error,
+            condition: UnsetOrEmpty,
+            word: "".parse().unwrap(),
+        };
+        let param = Param::variable("var");
+        let location = Location::dummy("somewhere");
+        let result = apply(&mut env, &switch, &param, None, None, &location)
+            .now_or_never()
+            .unwrap();
+        let error = result.unwrap().unwrap_err();
+        assert_matches!(error.cause, ErrorCause::VacantExpansion(e) => {
+            assert_eq!(e.message, None);
+            assert_eq!(e.message_or_default(), "parameter expansion with empty value");
+            assert_eq!(e.vacancy, Vacancy::Unset);
+        });
+    }
+
     #[test]
     fn error_with_valueless_array_and_empty_word() {
         let mut env = yash_env::Env::new_virtual();
@@ -660,7 +782,7 @@ mod tests {
         let param = Param::variable("var");
         let value = Value::Array(vec![]);
         let location = Location::dummy("somewhere");
-        let result = apply(&mut env, &switch, &param, Some(&value), &location)
+        let result = apply(&mut env, &switch, &param, None, Some(&value), &location)
             .now_or_never()
             .unwrap();
         let error = result.unwrap().unwrap_err();
@@ -684,7 +806,7 @@ mod tests {
         let param = Param::variable("var");
         let value = Value::scalar("");
         let location = Location::dummy("somewhere");
-        let result = apply(&mut env, &switch, &param, Some(&value), &location)
+        let result = apply(&mut env, &switch, &param, None, Some(&value), &location)
             .now_or_never()
             .unwrap();
         assert_eq!(result, None);
@@ -189,4 +189,79 @@ mod tests {
         assert_eq!(result, Ok(()));
         assert_eq!(value, Value::scalar("123123123"));
     }
+
+    #[test]
+    fn shortest_prefix_up_to_slash() {
+        let mut env = yash_env::Env::new_virtual();
+        let mut env = Env::new(&mut env);
+        let trim = Trim {
+            side: Prefix,
+            length: Shortest,
+            pattern: "*/".parse().unwrap(),
+        };
+        let mut value = Value::scalar("dir/sub/file.c");
+        let result = apply(&mut env, &trim, &mut value).now_or_never().unwrap();
+        assert_eq!(result, Ok(()));
+        assert_eq!(value, Value::scalar("sub/file.c"));
+    }
+
+    #[test]
+    fn longest_prefix_up_to_slash() {
+        let mut env = yash_env::Env::new_virtual();
+        let mut env = Env::new(&mut env);
+        let trim = Trim {
+            side: Prefix,
+            length: Longest,
+            pattern: "*/".parse().unwrap(),
+        };
+        let mut value = Value::scalar("dir/sub/file.c");
+        let result = apply(&mut env, &trim, &mut value).now_or_never().unwrap();
+        assert_eq!(result, Ok(()));
+        assert_eq!(value, Value::scalar("file.c"));
+    }
+
+    #[test]
+    fn shortest_suffix_extension() {
+        let mut env = yash_env::Env::new_virtual();
+        let mut env = Env::new(&mut env);
+        let trim = Trim {
+            side: Suffix,
+            length: Shortest,
+            pattern: ".c".parse().unwrap(),
+        };
+        let mut value = Value::scalar("dir/sub/file.c");
+        let result = apply(&mut env, &trim, &mut value).now_or_never().unwrap();
+        assert_eq!(result, Ok(()));
+        assert_eq!(value, Value::scalar("dir/sub/file"));
+    }
+
+    #[test]
+    fn quoted_pattern_matches_literally() {
+        let mut env = yash_env::Env::new_virtual();
+        let mut env = Env::new(&mut env);
+        let trim = Trim {
+            side: Prefix,
+            length: Shortest,
+            pattern: r#""*""#.parse().unwrap(),
+        };
+        let mut value = Value::scalar("*/file");
+        let result = apply(&mut env, &trim, &mut value).now_or_never().unwrap();
+        assert_eq!(result, Ok(()));
+        assert_eq!(value, Value::scalar("/file"));
+    }
+
+    #[test]
+    fn quoted_pattern_unmatched() {
+        let mut env = yash_env::Env::new_virtual();
+        let mut env = Env::new(&mut env);
+        let trim = Trim {
+            side: Prefix,
+            length: Shortest,
+            pattern: r#""*""#.parse().unwrap(),
+        };
+        let mut value = Value::scalar("dir/file");
+        let result = apply(&mut env, &trim, &mut value).now_or_never().unwrap();
+        assert_eq!(result, Ok(()));
+        assert_eq!(value, Value::scalar("dir/file"));
+    }
 }
@@ -107,6 +107,7 @@ impl<S: Runtime + 'static> Expand<S> for TextUnit {
             RawParam { param, location } => {
                 let param_ref = ParamRef {
                     param,
+                    subscript: None,
                     modifier: &yash_syntax::syntax::Modifier::None,
                     location,
                 };
@@ -249,7 +250,7 @@ mod tests {
 
             let c = AttrChar {
                 value: '.',
-                origin: Origin::SoftExpansion,
+                origin: Origin::CommandSubst,
                 is_quoted: false,
                 is_quoting: false,
             };
@@ -279,7 +280,7 @@ mod tests {
 
             let c = AttrChar {
                 value: '\\',
-                origin: Origin::SoftExpansion,
+                origin: Origin::CommandSubst,
                 is_quoted: false,
                 is_quoting: false,
             };
@@ -185,3 +185,39 @@ mod parser_error_tests {
         assert_eq!(result, Break(Divert::Interrupt(Some(ExitStatus::ERROR))));
     }
 }
+
+#[cfg(test)]
+mod expansion_error_tests {
+    use super::*;
+    use crate::command::Command as _;
+    use crate::tests::echo_builtin;
+    use futures_util::FutureExt as _;
+    use std::cell::RefCell;
+    use std::rc::Rc;
+    use yash_env::VirtualSystem;
+    use yash_env::io::ReportSink;
+    use yash_env::source::pretty::Report;
+    use yash_env::system::Concurrent;
+    use yash_env::test_helper::assert_stderr;
+    use yash_syntax::syntax::SimpleCommand;
+
+    #[test]
+    fn report_sink_captures_expansion_error() {
+        let system = VirtualSystem::new();
+        let state = Rc::clone(&system.state);
+        let mut env = Env::with_system(Rc::new(Concurrent::new(system)));
+        env.builtins.insert("echo", echo_builtin());
+        let titles = Rc::new(RefCell::new(Vec::new()));
+        let titles_2 = Rc::clone(&titles);
+        env.any
+            .insert(Box::new(ReportSink(Rc::new(move |report: &Report<'_>| {
+                titles_2.borrow_mut().push(report.title.to_string())
+            }))));
+        let command: SimpleCommand = "echo ${x?oops}".parse().unwrap();
+
+        let result = command.execute(&mut env).now_or_never().unwrap();
+        assert_eq!(result, Break(Divert::Interrupt(Some(ExitStatus::ERROR))));
+        assert_eq!(*titles.borrow(), ["oops"]);
+        assert_stderr(&state, |stderr| assert_eq!(stderr, ""));
+    }
+}
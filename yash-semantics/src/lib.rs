@@ -24,7 +24,8 @@
 //! [`expansion`].
 //!
 //! The [`read_eval_loop`] reads, parses, and executes commands from an input.
-//! It is a utility for running a shell script.
+//! It is a utility for running a shell script. The [`run_string`] function is a
+//! shorthand for running a script given as a string.
 //!
 //! # Deprecation
 //!
@@ -33,6 +34,7 @@
 
 pub mod assign;
 pub mod command;
+pub mod cond;
 pub mod expansion;
 pub mod job;
 pub mod redir;
@@ -48,6 +50,7 @@ pub use handle::Handle;
 mod runner;
 pub use runner::interactive_read_eval_loop;
 pub use runner::read_eval_loop;
+pub use runner::run_string;
 
 mod runtime;
 pub use runtime::Runtime;
@@ -171,6 +171,10 @@ pub enum ErrorCause {
     /// Here-string redirection is used, which is not yet implemented.
     #[error("here-string redirection is not yet implemented")]
     UnsupportedHereString,
+
+    /// Process redirection is used, which is not yet implemented.
+    #[error("process redirection is not yet implemented")]
+    UnsupportedProcessRedirection,
 }
 
 impl ErrorCause {
@@ -187,7 +191,9 @@ impl ErrorCause {
             MalformedFd(_, _) => "not a valid file descriptor",
             UnreadableFd(_) | UnwritableFd(_) => "cannot copy file descriptor",
             TemporaryFileUnavailable(_) => "cannot prepare here-document",
-            UnsupportedPipeRedirection | UnsupportedHereString => "unsupported redirection",
+            UnsupportedPipeRedirection | UnsupportedHereString | UnsupportedProcessRedirection => {
+                "unsupported redirection"
+            }
         }
     }
 
@@ -208,6 +214,7 @@ impl ErrorCause {
             TemporaryFileUnavailable(errno) => errno.to_string().into(),
             UnsupportedPipeRedirection => "pipe redirection is not yet implemented".into(),
             UnsupportedHereString => "here-string redirection is not yet implemented".into(),
+            UnsupportedProcessRedirection => "process redirection is not yet implemented".into(),
         }
     }
 }
@@ -295,6 +302,9 @@ fn into_c_string_value_and_origin(field: Field) -> Result<(CString, Location), E
 }
 
 /// Opens a file for redirection.
+///
+/// The file is opened with the `CloseOnExec` flag so that it does not leak
+/// into external utilities if it is not moved to the target FD.
 async fn open_file<S: Open>(
     env: &mut Env<S>,
     access: OfdAccess,
@@ -303,6 +313,7 @@ async fn open_file<S: Open>(
 ) -> Result<(FdSpec, Location), Error> {
     let system = &mut env.system;
     let (path, origin) = into_c_string_value_and_origin(path)?;
+    let flags = flags | OpenFlag::CloseOnExec;
     match system.open(&path, access, flags, MODE).await {
         Ok(fd) => Ok((FdSpec::Owned(fd), origin)),
         Err(errno) => Err(Error {
@@ -320,7 +331,8 @@ where
     let system = &mut env.system;
     let (path, origin) = into_c_string_value_and_origin(path)?;
 
-    const FLAGS_EXCL: EnumSet<OpenFlag> = enum_set!(OpenFlag::Create | OpenFlag::Exclusive);
+    const FLAGS_EXCL: EnumSet<OpenFlag> =
+        enum_set!(OpenFlag::Create | OpenFlag::Exclusive | OpenFlag::CloseOnExec);
     match system
         .open(&path, OfdAccess::WriteOnly, FLAGS_EXCL, MODE)
         .await
@@ -337,7 +349,12 @@ where
 
     // Okay, it seems there is an existing file. Try opening it.
     match system
-        .open(&path, OfdAccess::WriteOnly, EnumSet::empty(), MODE)
+        .open(
+            &path,
+            OfdAccess::WriteOnly,
+            OpenFlag::CloseOnExec.into(),
+            MODE,
+        )
         .await
     {
         Ok(fd) => {
@@ -515,7 +532,7 @@ where
     if is_cloexec(env, target_fd) {
         return Err(Error {
             cause: ErrorCause::ReservedFd(target_fd),
-            location: redir.body.operand().location.clone(),
+            location: redir.body.location().clone(),
         });
     }
 
@@ -529,7 +546,7 @@ where
         Err(errno) => {
             return Err(Error {
                 cause: ErrorCause::FdNotOverwritten(target_fd, errno),
-                location: redir.body.operand().location.clone(),
+                location: redir.body.location().clone(),
             });
         }
     };
@@ -554,6 +571,11 @@ where
                 Err(cause) => return Err(Error { cause, location }),
             }
         }
+        RedirBody::Process { location, .. } => {
+            let cause = ErrorCause::UnsupportedProcessRedirection;
+            let location = location.clone();
+            return Err(Error { cause, location });
+        }
     };
 
     if let Some(fd) = fd_spec.as_fd() {
@@ -569,6 +591,14 @@ where
                     });
                 }
             }
+        } else if let Err(errno) = env.system.fcntl_setfd(target_fd, EnumSet::empty()) {
+            // The FD has been opened at the target FD with the CloseOnExec
+            // flag, which must be cleared for the target FD to be inherited.
+            fd_spec.close(&env.system);
+            return Err(Error {
+                cause: ErrorCause::FdNotOverwritten(target_fd, errno),
+                location,
+            });
         }
     } else {
         let _: Result<(), Errno> = env.system.close(target_fd);
@@ -920,7 +950,7 @@ mod tests {
             e.cause,
             ErrorCause::OpenFile(c"no_such_file".to_owned(), Errno::ENOENT)
         );
-        assert_eq!(e.location, redir.body.operand().location);
+        assert_eq!(e.location, *redir.body.location());
     }
 
     #[test]
@@ -1018,7 +1048,7 @@ mod tests {
             .unwrap()
             .unwrap_err();
         assert_eq!(e.cause, ErrorCause::ReservedFd(fd));
-        assert_eq!(e.location, redir.body.operand().location);
+        assert_eq!(e.location, *redir.body.location());
     }
 
     #[test]
@@ -1135,7 +1165,7 @@ mod tests {
             e.cause,
             ErrorCause::FdNotOverwritten(Fd(999999999), Errno::EBADF)
         );
-        assert_eq!(e.location, redir.body.operand().location);
+        assert_eq!(e.location, *redir.body.location());
         let mut buffer = [0; 1];
         let e = env
             .system
@@ -1168,6 +1198,34 @@ mod tests {
         });
     }
 
+    #[test]
+    fn file_opened_at_target_fd_is_not_cloexec() {
+        let (mut env, _state) = env_with_nofile_limit();
+        let mut env = RedirGuard::new(&mut env);
+        let redir = "3> foo".parse().unwrap();
+        env.perform_redir(&redir, None)
+            .now_or_never()
+            .unwrap()
+            .unwrap();
+
+        assert_eq!(env.system.fcntl_getfd(Fd(3)), Ok(EnumSet::empty()));
+    }
+
+    #[test]
+    fn file_moved_to_target_fd_is_not_cloexec() {
+        let (mut env, _state) = env_with_nofile_limit();
+        let mut env = RedirGuard::new(&mut env);
+        let redir = "5> foo".parse().unwrap();
+        env.perform_redir(&redir, None)
+            .now_or_never()
+            .unwrap()
+            .unwrap();
+
+        assert_eq!(env.system.fcntl_getfd(Fd(5)), Ok(EnumSet::empty()));
+        // The FD originally opened for the file has been closed.
+        assert_eq!(env.system.fcntl_getfd(Fd(3)), Err(Errno::EBADF));
+    }
+
     #[test]
     fn file_out_truncates_existing_file() {
         let file = Rc::new(RefCell::new(Inode::new([42, 123, 254])));
@@ -1210,7 +1268,7 @@ mod tests {
             e.cause,
             ErrorCause::OpenFile(c"foo".to_owned(), Errno::EEXIST)
         );
-        assert_eq!(e.location, redir.body.operand().location);
+        assert_eq!(e.location, *redir.body.location());
         let file = file.borrow();
         assert_matches!(&file.body, FileBody::Regular { content, .. } => {
             assert_eq!(content[..], [42, 123, 254]);
@@ -1258,7 +1316,7 @@ mod tests {
             e.cause,
             ErrorCause::FdNotOverwritten(Fd(999999999), Errno::EBADF)
         );
-        assert_eq!(e.location, redir.body.operand().location);
+        assert_eq!(e.location, *redir.body.location());
         let e = env
             .system
             .write(Fd(3), &[0x20])
@@ -1329,7 +1387,7 @@ mod tests {
             e.cause,
             ErrorCause::FdNotOverwritten(Fd(999999999), Errno::EBADF)
         );
-        assert_eq!(e.location, redir.body.operand().location);
+        assert_eq!(e.location, *redir.body.location());
         let e = env
             .system
             .write(Fd(3), &[0x20])
@@ -1403,7 +1461,7 @@ mod tests {
             e.cause,
             ErrorCause::FdNotOverwritten(Fd(999999999), Errno::EBADF)
         );
-        assert_eq!(e.location, redir.body.operand().location);
+        assert_eq!(e.location, *redir.body.location());
         let e = env
             .system
             .write(Fd(3), &[0x20])
@@ -1475,7 +1533,7 @@ mod tests {
             e.cause,
             ErrorCause::FdNotOverwritten(Fd(999999999), Errno::EBADF)
         );
-        assert_eq!(e.location, redir.body.operand().location);
+        assert_eq!(e.location, *redir.body.location());
         let e = env
             .system
             .write(Fd(3), &[0x20])
@@ -1552,7 +1610,7 @@ mod tests {
             .unwrap()
             .unwrap_err();
         assert_eq!(e.cause, ErrorCause::UnreadableFd(Fd(3)));
-        assert_eq!(e.location, redir.body.operand().location);
+        assert_eq!(e.location, *redir.body.location());
     }
 
     #[test]
@@ -1567,7 +1625,7 @@ mod tests {
             .unwrap()
             .unwrap_err();
         assert_eq!(e.cause, ErrorCause::UnreadableFd(Fd(3)));
-        assert_eq!(e.location, redir.body.operand().location);
+        assert_eq!(e.location, *redir.body.location());
     }
 
     #[test]
@@ -1585,7 +1643,7 @@ mod tests {
             .unwrap()
             .unwrap_err();
         assert_eq!(e.cause, ErrorCause::ReservedFd(Fd(0)));
-        assert_eq!(e.location, redir.body.operand().location);
+        assert_eq!(e.location, *redir.body.location());
     }
 
     #[test]
@@ -1603,7 +1661,7 @@ mod tests {
             e.cause,
             ErrorCause::FdNotOverwritten(Fd(999999999), Errno::EBADF)
         );
-        assert_eq!(e.location, redir.body.operand().location);
+        assert_eq!(e.location, *redir.body.location());
         let mut buffer = [0; 1];
         let read_count = env
             .system
@@ -1675,7 +1733,7 @@ mod tests {
             .unwrap()
             .unwrap_err();
         assert_eq!(e.cause, ErrorCause::UnwritableFd(Fd(3)));
-        assert_eq!(e.location, redir.body.operand().location);
+        assert_eq!(e.location, *redir.body.location());
     }
 
     #[test]
@@ -1690,7 +1748,7 @@ mod tests {
             .unwrap()
             .unwrap_err();
         assert_eq!(e.cause, ErrorCause::UnwritableFd(Fd(3)));
-        assert_eq!(e.location, redir.body.operand().location);
+        assert_eq!(e.location, *redir.body.location());
     }
 
     #[test]
@@ -1708,7 +1766,7 @@ mod tests {
             .unwrap()
             .unwrap_err();
         assert_eq!(e.cause, ErrorCause::ReservedFd(Fd(1)));
-        assert_eq!(e.location, redir.body.operand().location);
+        assert_eq!(e.location, *redir.body.location());
     }
 
     #[test]
@@ -1726,7 +1784,7 @@ mod tests {
             e.cause,
             ErrorCause::FdNotOverwritten(Fd(999999999), Errno::EBADF)
         );
-        assert_eq!(e.location, redir.body.operand().location);
+        assert_eq!(e.location, *redir.body.location());
         let write_count = env
             .system
             .write(Fd(1), &[0x20])
@@ -1748,7 +1806,7 @@ mod tests {
             .unwrap_err();
 
         assert_eq!(e.cause, ErrorCause::UnsupportedPipeRedirection);
-        assert_eq!(e.location, redir.body.operand().location);
+        assert_eq!(e.location, *redir.body.location());
     }
 
     #[test]
@@ -1763,6 +1821,20 @@ mod tests {
             .unwrap_err();
 
         assert_eq!(e.cause, ErrorCause::UnsupportedHereString);
-        assert_eq!(e.location, redir.body.operand().location);
+        assert_eq!(e.location, *redir.body.location());
+    }
+    #[test]
+    fn process_redirection_not_yet_implemented() {
+        let mut env = env_with_nofile_limit().0;
+        let mut env = RedirGuard::new(&mut env);
+        let redir = "<(echo)".parse().unwrap();
+        let e = env
+            .perform_redir(&redir, None)
+            .now_or_never()
+            .unwrap()
+            .unwrap_err();
+
+        assert_eq!(e.cause, ErrorCause::UnsupportedProcessRedirection);
+        assert_eq!(e.location, *redir.body.location());
     }
 }
@@ -21,12 +21,13 @@ use yash_env::Env;
 use yash_env::io::Fd;
 use yash_env::path::Path;
 use yash_env::system::concurrency::WriteAll;
-use yash_env::system::{Close, Errno, Open, Seek};
+use yash_env::system::{Close, Errno, Fcntl, FdFlag, Open, Seek};
 
 async fn fill_content<S>(env: &mut Env<S>, fd: Fd, content: &str) -> Result<(), Errno>
 where
-    S: Seek + WriteAll,
+    S: Fcntl + Seek + WriteAll,
 {
+    env.system.fcntl_setfd(fd, FdFlag::CloseOnExec.into())?;
     env.system.write_all(fd, content.as_bytes()).await?;
     env.system.lseek(fd, std::io::SeekFrom::Start(0))?;
     Ok(())
@@ -36,10 +37,10 @@ where
 ///
 /// This function writes the here-document content to an anonymous temporary
 /// file and returns a file descriptor to the file you can read the content
-/// from.
+/// from. The file descriptor has the `CloseOnExec` flag set.
 pub(super) async fn open_fd<S>(env: &mut Env<S>, content: String) -> Result<Fd, ErrorCause>
 where
-    S: Close + Open + Seek + WriteAll,
+    S: Close + Fcntl + Open + Seek + WriteAll,
 {
     // TODO Use a pipe for short content
     let fd = match env.system.open_tmpfile(Path::new("/tmp")) {
@@ -79,4 +80,15 @@ mod tests {
             .unwrap();
         assert_eq!(std::str::from_utf8(&buffer[..count]), Ok(text));
     }
+    #[test]
+    fn open_fd_sets_cloexec() {
+        let mut env = Env::new_virtual();
+        let fd = open_fd(&mut env, "foo\n".to_owned())
+            .now_or_never()
+            .unwrap()
+            .unwrap();
+
+        let flags = env.system.fcntl_getfd(fd).unwrap();
+        assert_eq!(flags, FdFlag::CloseOnExec);
+    }
 }
@@ -21,11 +21,14 @@ use crate::trap::run_traps_for_caught_signals;
 use crate::{Handle as _, Runtime};
 use std::cell::RefCell;
 use std::ops::ControlFlow::{Break, Continue};
+use std::rc::Rc;
 use yash_env::Env;
-use yash_env::parser::Mode;
+use yash_env::input::Memory;
+use yash_env::parser::{Config, Mode};
 use yash_env::semantics::Divert;
 use yash_env::semantics::ExitStatus;
 use yash_env::semantics::Result;
+use yash_env::source::Source;
 use yash_syntax::parser::lex::Lexer;
 use yash_syntax::parser::{ErrorCause, Parser};
 use yash_syntax::syntax::List;
@@ -45,7 +48,8 @@ use yash_syntax::syntax::List;
 ///
 /// If the input source code contains no commands, the exit status is set to
 /// zero. Otherwise, the exit status reflects the result of the last executed
-/// command.
+/// command. If the parser fails, the exit status is set to that of the
+/// resulting [`Interrupt`](Divert::Interrupt).
 ///
 /// [Pending traps are run](run_traps_for_caught_signals) and [subshell statuses
 /// are updated](Env::update_all_subshell_statuses) between parsing input and
@@ -135,6 +139,51 @@ pub async fn interactive_read_eval_loop<S: Runtime + 'static>(
     read_eval_loop_impl(env, lexer, /* is_interactive */ true).await
 }
 
+/// Parses and executes a script given as a string.
+///
+/// This is a convenience function for embedding the shell. It runs the
+/// [`read_eval_loop`] on the whole `code` and returns the resulting exit
+/// status, which is also left in `env.exit_status`.
+///
+/// If the script runs the `exit` built-in or otherwise results in a
+/// [`Divert`], the execution stops there and the exit status is taken from the
+/// divert if it has one. The EXIT trap is not run, so the caller can continue
+/// using the environment. A syntax error in the script is reported to the
+/// standard error, and the exit status is [`ExitStatus::ERROR`].
+///
+/// The code is parsed with [`Source::CommandString`] as its source.
+///
+/// This function returns a future that embedders can poll with their own
+/// executor. The future is not `Send` because the environment is not, so it
+/// must be polled on the thread that owns the environment. When the system is
+/// [`Concurrent`], the future may wait for I/O or child processes, so it should
+/// be run inside the system's run loop (e.g., [`Concurrent::run_real`]) for
+/// such operations to make progress.
+///
+/// [`Concurrent`]: yash_env::system::Concurrent
+/// [`Concurrent::run_real`]: yash_env::system::Concurrent::run_real
+///
+/// # Example
+///
+/// ```
+/// # futures_executor::block_on(async {
+/// # use yash_env::Env;
+/// # use yash_semantics::ExitStatus;
+/// # use yash_semantics::run_string;
+/// let mut env = Env::new_virtual();
+/// let exit_status = run_string(&mut env, "case foo in (bar) ;; esac").await;
+/// assert_eq!(exit_status, ExitStatus::SUCCESS);
+/// # })
+/// ```
+pub async fn run_string<S: Runtime + 'static>(env: &mut Env<S>, code: &str) -> ExitStatus {
+    let mut config = Config::with_input(Box::new(Memory::new(code)));
+    config.source = Some(Rc::new(Source::CommandString));
+    let mut lexer = Lexer::from(config);
+    let result = read_eval_loop(&RefCell::new(&mut *env), &mut lexer).await;
+    env.apply_result(result);
+    env.exit_status
+}
+
 #[allow(
     clippy::await_holding_refcell_ref,
     reason = "the parser does not run concurrently with the executor"
@@ -180,6 +229,9 @@ async fn read_eval_loop_impl<S: Runtime + 'static>(
             // Parser error
             Err(error) => {
                 let result = error.handle(env).await;
+                if let Break(Divert::Interrupt(Some(exit_status))) = result {
+                    env.exit_status = exit_status;
+                }
                 let error_recoverable = matches!(error.cause, ErrorCause::Syntax(_));
                 (result, error_recoverable)
             }
@@ -203,7 +255,7 @@ async fn read_eval_loop_impl<S: Runtime + 'static>(
     }
 }
 
-async fn run_command<S: Runtime + 'static>(env: &mut Env<S>, command: &List) -> Result {
+pub(crate) async fn run_command<S: Runtime + 'static>(env: &mut Env<S>, command: &List) -> Result {
     run_traps_for_caught_signals(env).await?;
     env.update_all_subshell_statuses();
     command.execute(env).await
@@ -213,19 +265,28 @@ async fn run_command<S: Runtime + 'static>(env: &mut Env<S>, command: &List) ->
 mod tests {
     use super::*;
     use crate::tests::echo_builtin;
+    use crate::tests::exit_builtin;
     use crate::tests::return_builtin;
     use futures_util::FutureExt as _;
-    use std::rc::Rc;
+    use futures_util::poll;
+    use std::pin::pin;
+    use std::task::Poll;
+    use yash_env::builtin::Builtin;
+    use yash_env::builtin::Type::Mandatory;
     use yash_env::input::Echo;
     use yash_env::input::Memory;
-    use yash_env::option::Option::Verbose;
+    use yash_env::option::Option::{Interactive, Portable, Verbose};
     use yash_env::option::State::On;
     use yash_env::system::Concurrent;
+    use yash_env::system::SendSignal as _;
+    use yash_env::system::Signals as _;
     use yash_env::system::r#virtual::SIGUSR1;
     use yash_env::system::r#virtual::VirtualSystem;
     use yash_env::test_helper::assert_stderr;
     use yash_env::test_helper::assert_stdout;
+    use yash_env::test_helper::in_virtual_system;
     use yash_env::trap::Action;
+    use yash_env::variable::Scope;
     use yash_syntax::input::Context;
     use yash_syntax::source::Location;
 
@@ -329,6 +390,37 @@ mod tests {
         assert_stdout(&state, |stdout| assert_eq!(stdout, "2\n"));
     }
 
+    #[test]
+    fn sigint_aborts_command_line_in_interactive_loop() {
+        in_virtual_system(|mut env, state| async move {
+            let system = VirtualSystem {
+                process_id: env.main_pid,
+                state: Rc::clone(&state),
+            };
+            env.options.set(Interactive, On);
+            env.traps
+                .enable_internal_dispositions_for_terminators(&env.system)
+                .await
+                .unwrap();
+            env.builtins.insert("echo", echo_builtin());
+            env.builtins.insert(
+                "wait_forever",
+                Builtin::new(Mandatory, |_env, _args| Box::pin(std::future::pending())),
+            );
+            let mut lexer = Lexer::with_code("wait_forever; echo skipped\necho survived\n");
+            let ref_env = RefCell::new(&mut env);
+
+            let mut loop_fut = pin!(interactive_read_eval_loop(&ref_env, &mut lexer));
+            assert_eq!(poll!(loop_fut.as_mut()), Poll::Pending);
+
+            // The rest of the command line is abandoned, but the loop goes on.
+            system.raise(VirtualSystem::SIGINT).await.unwrap();
+            let result = loop_fut.await;
+            assert_eq!(result, Continue(()));
+            assert_stdout(&state, |stdout| assert_eq!(stdout, "survived\n"));
+        })
+    }
+
     #[test]
     fn command_other_divert_interactive() {
         // If the command execution results in a divert other than an interrupt in
@@ -373,6 +465,7 @@ mod tests {
         let ref_env = RefCell::new(&mut env);
         let result = read_eval_loop(&ref_env, &mut lexer).now_or_never().unwrap();
         assert_eq!(result, Break(Divert::Interrupt(Some(ExitStatus::ERROR))));
+        assert_eq!(env.exit_status, ExitStatus::ERROR);
         assert_stderr(&state, |stderr| assert_ne!(stderr, ""));
     }
 
@@ -408,6 +501,38 @@ mod tests {
         assert_stdout(&state, |stdout| assert_eq!(stdout, "2\n"));
     }
 
+    #[test]
+    fn option_changed_by_command_affects_parsing_of_next_command_line() {
+        let system = VirtualSystem::new();
+        let state = Rc::clone(&system.state);
+        let mut env = Env::with_system(Rc::new(Concurrent::new(system)));
+        env.builtins.insert("echo", echo_builtin());
+        env.builtins.insert(
+            "enable_portable",
+            Builtin::new(Mandatory, |env, _args| {
+                env.options.set(Portable, On);
+                Box::pin(std::future::ready(Default::default()))
+            }),
+        );
+        env.variables
+            .get_or_new("v", Scope::Global)
+            .assign("x", None)
+            .unwrap();
+        env.variables
+            .get_or_new("x", Scope::Global)
+            .assign("ok", None)
+            .unwrap();
+        // The first line is parsed before the option is enabled, so the
+        // non-portable `${!v}` is accepted. The second line is rejected.
+        let mut lexer = Lexer::with_code("enable_portable; echo ${!v}\necho ${!v}\n");
+        let ref_env = RefCell::new(&mut env);
+
+        let result = read_eval_loop(&ref_env, &mut lexer).now_or_never().unwrap();
+        assert_eq!(result, Break(Divert::Interrupt(Some(ExitStatus::ERROR))));
+        assert_stdout(&state, |stdout| assert_eq!(stdout, "ok\n"));
+        assert_stderr(&state, |stderr| assert_ne!(stderr, ""));
+    }
+
     #[test]
     fn input_error_aborts_loop() {
         struct BrokenInput;
@@ -462,4 +587,59 @@ mod tests {
         assert_eq!(env.exit_status, ExitStatus::SUCCESS);
         assert_stdout(&state, |stdout| assert_eq!(stdout, "USR1\n0\n"));
     }
+
+    #[test]
+    fn run_string_executes_whole_script() {
+        in_virtual_system(|mut env, state| async move {
+            env.builtins.insert("echo", echo_builtin());
+            env.builtins.insert("return", return_builtin());
+            let code = "echo 1; return -n 3\necho $?\nfor i in a b; do echo $i; done\n\
+                        return -n 5";
+
+            let exit_status = run_string(&mut env, code).await;
+            assert_eq!(exit_status, ExitStatus(5));
+            assert_eq!(env.exit_status, ExitStatus(5));
+            assert_stdout(&state, |stdout| assert_eq!(stdout, "1\n3\na\nb\n"));
+        })
+    }
+
+    #[test]
+    fn run_string_stops_on_exit() {
+        in_virtual_system(|mut env, state| async move {
+            env.builtins.insert("echo", echo_builtin());
+            env.builtins.insert("exit", exit_builtin());
+
+            let exit_status = run_string(&mut env, "echo 1; exit 4; echo 2").await;
+            assert_eq!(exit_status, ExitStatus(4));
+            assert_stdout(&state, |stdout| assert_eq!(stdout, "1\n"));
+        })
+    }
+
+    #[test]
+    fn run_string_reports_syntax_error() {
+        in_virtual_system(|mut env, state| async move {
+            env.builtins.insert("echo", echo_builtin());
+
+            let exit_status = run_string(&mut env, "echo 1\nfi\necho 2").await;
+            assert_eq!(exit_status, ExitStatus::ERROR);
+            assert_stdout(&state, |stdout| assert_eq!(stdout, "1\n"));
+            assert_stderr(&state, |stderr| {
+                assert!(stderr.contains("<command_string>:2:1"), "{stderr}")
+            });
+        })
+    }
+
+    #[test]
+    fn run_string_with_external_executor() {
+        let system = VirtualSystem::new();
+        let state = Rc::clone(&system.state);
+        let mut env = Env::with_system(Rc::new(Concurrent::new(system)));
+        env.builtins.insert("echo", echo_builtin());
+        env.builtins.insert("return", return_builtin());
+
+        let future = run_string(&mut env, "echo foo\necho bar; return -n 6");
+        let exit_status = futures_executor::block_on(future);
+        assert_eq!(exit_status, ExitStatus(6));
+        assert_stdout(&state, |stdout| assert_eq!(stdout, "foo\nbar\n"));
+    }
 }
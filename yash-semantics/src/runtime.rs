@@ -23,7 +23,7 @@ use yash_env::system::concurrency::{ReadAll, Select, WaitForSignals, WriteAll};
 use yash_env::system::resource::SetRlimit;
 use yash_env::system::{
     Clock, Close, Dup, Exec, Exit, Fcntl, Fork, Fstat, GetPid, GetPw, IsExecutableFile, Isatty,
-    Open, Pipe, Read, Seek, SendSignal, SetPgid, ShellPath, TcSetPgrp, Wait,
+    Open, Pipe, Read, Seek, SendSignal, SetPgid, ShellPath, TcSetPgrp, TtySize, Wait,
 };
 use yash_env::trap::SignalSystem;
 
@@ -65,6 +65,7 @@ pub trait Runtime:
     + ShellPath
     + SignalSystem
     + TcSetPgrp
+    + TtySize
     + Wait
     + WaitForSignals
     + WriteAll
@@ -103,6 +104,7 @@ impl<S> Runtime for S where
         + ShellPath
         + SignalSystem
         + TcSetPgrp
+        + TtySize
         + Wait
         + WaitForSignals
         + WriteAll
@@ -43,11 +43,20 @@
 //! The EXIT trap is executed when the shell exits normally, by running the exit
 //! built-in or reaching the end of the script. The [`run_exit_trap`] function,
 //! which should be called before exiting, runs the trap.
+//!
+//! The DEBUG trap is executed before each simple command. The
+//! [`run_debug_trap`] function runs the trap and is called by the simple
+//! command executor.
+//!
+//! The ERR trap is executed when a command fails under the conditions where
+//! the `errexit` option would take effect. The [`run_err_trap`] function runs
+//! the trap and is called just before the option is applied.
 
 use crate::Runtime;
 use crate::read_eval_loop;
+use crate::runner::run_command;
 use std::cell::RefCell;
-use std::ops::ControlFlow::Break;
+use std::ops::ControlFlow::{Break, Continue};
 use std::rc::Rc;
 use yash_env::Env;
 use yash_env::semantics::Divert;
@@ -59,12 +68,18 @@ use yash_env::trap::TrapSet;
 use yash_syntax::parser::lex::Lexer;
 use yash_syntax::source::Location;
 use yash_syntax::source::Source;
+use yash_syntax::syntax::List;
 
 /// Helper function for running a trap action.
 ///
 /// This function pushes a temporary frame `Frame::Trap(cond)` to the
 /// environment stack and runs the trap action by parsing the `code` with the
-/// given `origin`. The exit status of the trap action does not affect the exit
+/// given `origin`. If the `code` consists of a single command line, the parsed
+/// command is cached for subsequent runs of the same action. Otherwise, the
+/// `code` is parsed and executed line by line so that aliases and options
+/// changed by a command line take effect on the following lines.
+///
+/// The exit status of the trap action does not affect the exit
 /// status of the current environment except when the trap action is interrupted
 /// with `Result::Break(Divert::Interrupt(_))`. In that case, the exit status of
 /// the trap action is left as is in the environment.
@@ -81,14 +96,19 @@ async fn run_trap<S: Runtime + 'static>(
     code: Rc<str>,
     origin: Location,
 ) -> Result {
-    let condition = cond.to_string(&env.system).into_owned();
-    let mut lexer = Lexer::from_memory(&code, Source::Trap { condition, origin });
     let mut env = env.push_frame(Frame::Trap(cond));
 
     let previous_exit_status = env.exit_status;
 
+    let cached = cache::parse_trap(&mut env, cond, Rc::clone(&code), origin.clone()).await;
     // Boxing needed for recursion
-    let mut result = Box::pin(read_eval_loop(&RefCell::new(&mut env), &mut lexer)).await;
+    let mut result = if let Some(commands) = cached {
+        Box::pin(run_commands(&mut env, &commands)).await
+    } else {
+        let condition = cond.to_string(&env.system).into_owned();
+        let mut lexer = Lexer::from_memory(&code, Source::Trap { condition, origin });
+        Box::pin(read_eval_loop(&RefCell::new(&mut env), &mut lexer)).await
+    };
 
     if let Break(Divert::Interrupt(ref mut exit_status)) = result {
         if let Some(exit_status) = exit_status {
@@ -103,9 +123,24 @@ async fn run_trap<S: Runtime + 'static>(
     result
 }
 
+async fn run_commands<S: Runtime + 'static>(env: &mut Env<S>, commands: &[List]) -> Result {
+    for command in commands {
+        run_command(env, command).await?;
+    }
+    Continue(())
+}
+
+mod cache;
+
 mod signal;
 pub use signal::run_trap_if_caught;
 pub use signal::run_traps_for_caught_signals;
 
 mod exit;
 pub use exit::run_exit_trap;
+
+mod debug;
+pub use debug::run_debug_trap;
+
+mod err;
+pub use err::run_err_trap;
@@ -0,0 +1,280 @@
+// This file is part of yash, an extended POSIX shell.
+// Copyright (C) 2026 WATANABE Yuki
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+//! Caching parsed trap actions
+
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::fmt::Debug;
+use std::rc::Rc;
+use yash_env::Env;
+use yash_env::alias::Alias;
+use yash_env::parser::Mode;
+use yash_env::system::Signals;
+use yash_env::trap::Condition;
+use yash_syntax::parser::Parser;
+use yash_syntax::parser::lex::Lexer;
+use yash_syntax::source::{Location, Source};
+use yash_syntax::syntax::List;
+
+/// Result of parsing a trap action
+#[derive(Clone, Debug)]
+struct Entry {
+    /// Command string of the trap action
+    code: Rc<str>,
+    /// Parsing mode used in parsing
+    mode: Mode,
+    /// Aliases looked up in parsing
+    aliases: Vec<(String, Option<Rc<Alias>>)>,
+    /// Declaration utilities looked up in parsing
+    decl_utils: Vec<(String, Option<bool>)>,
+    /// Commands parsed from the command string
+    ///
+    /// This is `None` if the command string contains more than one command
+    /// line, in which case the commands must be parsed and executed line by
+    /// line.
+    commands: Option<Rc<[List]>>,
+}
+
+impl Entry {
+    /// Tests whether parsing `code` in `env` would yield the same commands.
+    fn is_valid_for<S: Debug>(&self, env: &Env<S>, code: &Rc<str>) -> bool {
+        use yash_env::alias::Glossary as _;
+        use yash_env::decl_util::Glossary as _;
+
+        Rc::ptr_eq(&self.code, code)
+            && self.mode == Mode::from(&env.options)
+            && self
+                .aliases
+                .iter()
+                .all(|(name, alias)| match (alias, env.look_up(name)) {
+                    (None, None) => true,
+                    (Some(old), Some(new)) => Rc::ptr_eq(old, &new),
+                    _ => false,
+                })
+            && self
+                .decl_utils
+                .iter()
+                .all(|(name, result)| env.is_declaration_utility(name) == *result)
+    }
+}
+
+/// Cache of parsed trap actions
+///
+/// This cache is stored in [`Env::any`]. Each entry maps a condition to the
+/// commands parsed from the trap action. The entry is reused as long as the
+/// trap action is the same string instance and the aliases and declaration
+/// utilities the parser looked up are unchanged, so a trap that fires
+/// repeatedly is parsed only once. Setting the trap again creates a new string
+/// instance, which invalidates the entry.
+///
+/// Only trap actions that consist of a single command line are cached. A
+/// command line may define aliases or change shell options that affect how
+/// the following command lines are parsed, so an action containing more than
+/// one command line has to be parsed line by line as it is executed. For such
+/// an action, the entry only records that it cannot be cached.
+#[derive(Clone, Debug, Default)]
+pub(super) struct ParsedTraps(HashMap<Condition, Entry>);
+
+/// Glossary that records lookups the parser makes
+///
+/// Lookups are delegated to the environment and recorded so that the cache
+/// entry can be invalidated when their results change.
+#[derive(Debug)]
+struct RecordingGlossary<'a, S> {
+    env: &'a Env<S>,
+    aliases: RefCell<Vec<(String, Option<Rc<Alias>>)>>,
+    decl_utils: RefCell<Vec<(String, Option<bool>)>>,
+}
+
+impl<S: Debug> yash_env::alias::Glossary for RecordingGlossary<'_, S> {
+    fn look_up(&self, name: &str) -> Option<Rc<Alias>> {
+        let alias = self.env.look_up(name);
+        let record = (name.to_owned(), alias.clone());
+        self.aliases.borrow_mut().push(record);
+        alias
+    }
+
+    /// Always returns `false` so that all lookups are recorded, even if the
+    /// environment has no aliases yet.
+    fn is_empty(&self) -> bool {
+        false
+    }
+}
+
+impl<S: Debug> yash_env::decl_util::Glossary for RecordingGlossary<'_, S> {
+    fn is_declaration_utility(&self, name: &str) -> Option<bool> {
+        let result = self.env.is_declaration_utility(name);
+        self.decl_utils.borrow_mut().push((name.to_owned(), result));
+        result
+    }
+}
+
+/// Parses a trap action, or returns the cached result.
+///
+/// The action is parsed with [`Source::Trap`] so that `$LINENO` in the action
+/// is attributed to the trap.
+///
+/// This function returns `None` if the action cannot be run from the cached
+/// result, that is, if the action contains more than one command line or a
+/// syntax error. In that case, the caller should parse and execute the action
+/// line by line, which also reports the syntax error, if any, after executing
+/// the command lines before it. A syntax error is not cached, so it is
+/// reported each time the trap action is run.
+pub(super) async fn parse_trap<S: Debug + Signals>(
+    env: &mut Env<S>,
+    cond: Condition,
+    code: Rc<str>,
+    origin: Location,
+) -> Option<Rc<[List]>> {
+    if let Some(ParsedTraps(cache)) = env.any.get::<ParsedTraps>()
+        && let Some(entry) = cache.get(&cond)
+        && entry.is_valid_for(env, &code)
+    {
+        return entry.commands.clone();
+    }
+
+    let condition = cond.to_string(&env.system).into_owned();
+    let mut lexer = Lexer::from_memory(&code, Source::Trap { condition, origin });
+    let mode = Mode::from(&env.options);
+    lexer.set_mode(mode);
+    let glossary = RecordingGlossary {
+        env,
+        aliases: RefCell::default(),
+        decl_utils: RefCell::default(),
+    };
+    let mut parser = Parser::config()
+        .aliases(&glossary)
+        .declaration_utilities(&glossary)
+        .input(&mut lexer);
+    let commands = match parser.command_line().await.ok()? {
+        None => Some(Rc::from([])),
+        Some(first) => match parser.command_line().await.ok()? {
+            None => Some(Rc::from([first])),
+            Some(_) => None,
+        },
+    };
+    drop(parser);
+
+    let entry = Entry {
+        code: Rc::clone(&code),
+        mode,
+        aliases: glossary.aliases.into_inner(),
+        decl_utils: glossary.decl_utils.into_inner(),
+        commands: commands.clone(),
+    };
+    let ParsedTraps(cache) = env.any.get_or_insert_with(Box::<ParsedTraps>::default);
+    cache.insert(cond, entry);
+    commands
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use futures_util::FutureExt as _;
+    use yash_env::alias::HashEntry;
+    use yash_env::system::r#virtual::SIGINT;
+    use yash_syntax::syntax::Command;
+
+    fn try_parse<S: Debug + Signals>(env: &mut Env<S>, code: &Rc<str>) -> Option<Rc<[List]>> {
+        let cond = Condition::Signal(SIGINT);
+        let origin = Location::dummy("");
+        parse_trap(env, cond, Rc::clone(code), origin)
+            .now_or_never()
+            .unwrap()
+    }
+
+    fn parse<S: Debug + Signals>(env: &mut Env<S>, code: &Rc<str>) -> Rc<[List]> {
+        try_parse(env, code).unwrap()
+    }
+
+    #[test]
+    fn trap_action_is_parsed_with_trap_source() {
+        let mut env = Env::new_virtual();
+        let commands = parse(&mut env, &"echo trapped".into());
+
+        let command = &commands[0].0[0].and_or.first.commands[0];
+        let Command::Simple(command) = &**command else {
+            panic!("unexpected command: {command:?}");
+        };
+        let source = &command.words[0].0.location.code.source;
+        let Source::Trap { condition, .. } = &**source else {
+            panic!("unexpected source: {source:?}");
+        };
+        assert_eq!(condition, "INT");
+    }
+
+    #[test]
+    fn same_trap_action_is_parsed_once() {
+        let mut env = Env::new_virtual();
+        let code = "echo trapped".into();
+        let first = parse(&mut env, &code);
+        let second = parse(&mut env, &code);
+        assert!(Rc::ptr_eq(&first, &second));
+    }
+
+    #[test]
+    fn new_trap_action_is_parsed_again() {
+        let mut env = Env::new_virtual();
+        let first = parse(&mut env, &"echo trapped".into());
+        let second = parse(&mut env, &"echo trapped".into());
+        assert!(!Rc::ptr_eq(&first, &second));
+    }
+
+    #[test]
+    fn trap_action_is_parsed_again_after_alias_change() {
+        let mut env = Env::new_virtual();
+        let code = "X".into();
+        let first = parse(&mut env, &code);
+
+        env.aliases.insert(HashEntry::new(
+            "X".to_string(),
+            "echo 1".to_string(),
+            false,
+            Location::dummy(""),
+        ));
+        let second = parse(&mut env, &code);
+        assert!(!Rc::ptr_eq(&first, &second));
+        assert_eq!(second[0].to_string(), "echo 1");
+
+        let third = parse(&mut env, &code);
+        assert!(Rc::ptr_eq(&second, &third));
+    }
+
+    #[test]
+    fn empty_trap_action_is_parsed_to_no_commands() {
+        let mut env = Env::new_virtual();
+        let commands = parse(&mut env, &"".into());
+        assert!(commands.is_empty());
+    }
+
+    #[test]
+    fn trap_action_with_multiple_command_lines_is_not_cached() {
+        let mut env = Env::new_virtual();
+        let result = try_parse(&mut env, &"echo 1\necho 2".into());
+        assert!(result.is_none(), "{result:?}");
+    }
+
+    #[test]
+    fn trap_action_with_syntax_error_is_not_cached() {
+        let mut env = Env::new_virtual();
+        let result = try_parse(&mut env, &"echo (".into());
+        assert!(result.is_none(), "{result:?}");
+
+        let result = try_parse(&mut env, &"echo 1\necho (".into());
+        assert!(result.is_none(), "{result:?}");
+    }
+}
@@ -0,0 +1,130 @@
+// This file is part of yash, an extended POSIX shell.
+// Copyright (C) 2026 WATANABE Yuki
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+//! Running the DEBUG trap
+
+use super::run_trap;
+use crate::Runtime;
+use std::ops::ControlFlow::Continue;
+use std::rc::Rc;
+use yash_env::Env;
+use yash_env::semantics::Result;
+use yash_env::stack::Frame;
+use yash_env::trap::Action;
+use yash_env::trap::Condition;
+use yash_env::trap::Origin;
+
+fn in_debug_trap<S>(env: &Env<S>) -> bool {
+    env.stack
+        .iter()
+        .rev()
+        .take_while(|frame| **frame != Frame::Subshell)
+        .any(|frame| *frame == Frame::Trap(Condition::Debug))
+}
+
+/// Executes the DEBUG trap.
+///
+/// This function should be called before executing each simple command. If
+/// the DEBUG trap is not set in the environment, this function does nothing.
+/// The trap is not executed recursively, that is, this function does nothing
+/// if called while the DEBUG trap is already running.
+///
+/// The exit status of the trap is ignored as in other traps, so that the trap
+/// action sees and preserves the exit status of the previous command.
+/// `Result::Break(divert)` from the trap action is returned to the caller.
+pub async fn run_debug_trap<S: Runtime + 'static>(env: &mut Env<S>) -> Result {
+    let Some(state) = env.traps.get_state(Condition::Debug).0 else {
+        return Continue(());
+    };
+    let Action::Command(command) = &state.action else {
+        return Continue(());
+    };
+    if in_debug_trap(env) {
+        return Continue(());
+    }
+
+    let command = Rc::clone(command);
+    let origin = match &state.origin {
+        Origin::Inherited | Origin::Subshell => panic!("user-defined trap must have origin"),
+        Origin::User(location) => location.clone(),
+    };
+    run_trap(env, Condition::Debug, command, origin).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::command::Command as _;
+    use crate::tests::{echo_builtin, return_builtin};
+    use futures_util::FutureExt as _;
+    use yash_env::semantics::ExitStatus;
+    use yash_env::system::Concurrent;
+    use yash_env::system::r#virtual::VirtualSystem;
+    use yash_env::test_helper::assert_stdout;
+    use yash_syntax::source::Location;
+    use yash_syntax::syntax::List;
+
+    fn set_debug_trap<S: Runtime + 'static>(env: &mut Env<S>, command: &str) {
+        env.traps
+            .set_action(
+                &env.system,
+                Condition::Debug,
+                Action::Command(command.into()),
+                Location::dummy(""),
+                false,
+            )
+            .now_or_never()
+            .unwrap()
+            .unwrap();
+    }
+
+    #[test]
+    fn does_nothing_if_debug_trap_is_not_set() {
+        let mut env = Env::new_virtual();
+        let result = run_debug_trap(&mut env).now_or_never().unwrap();
+        assert_eq!(result, Continue(()));
+    }
+
+    #[test]
+    fn debug_trap_runs_before_each_simple_command() {
+        let system = VirtualSystem::new();
+        let state = Rc::clone(&system.state);
+        let mut env = Env::with_system(Rc::new(Concurrent::new(system)));
+        env.builtins.insert("echo", echo_builtin());
+        set_debug_trap(&mut env, "echo debug");
+        let list: List = "echo 1; echo 2".parse().unwrap();
+
+        let result = list.execute(&mut env).now_or_never().unwrap();
+        assert_eq!(result, Continue(()));
+        assert_stdout(&state, |stdout| assert_eq!(stdout, "debug\n1\ndebug\n2\n"));
+    }
+
+    #[test]
+    fn exit_status_is_preserved_across_debug_trap() {
+        let system = VirtualSystem::new();
+        let state = Rc::clone(&system.state);
+        let mut env = Env::with_system(Rc::new(Concurrent::new(system)));
+        env.builtins.insert("echo", echo_builtin());
+        env.builtins.insert("return", return_builtin());
+        set_debug_trap(&mut env, "echo $?; return -n 42");
+        env.exit_status = ExitStatus(5);
+
+        let result = run_debug_trap(&mut env).now_or_never().unwrap();
+        assert_eq!(result, Continue(()));
+        assert_eq!(env.exit_status, ExitStatus(5));
+        assert_stdout(&state, |stdout| assert_eq!(stdout, "5\n"));
+    }
+}
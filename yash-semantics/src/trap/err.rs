@@ -0,0 +1,157 @@
+// This file is part of yash, an extended POSIX shell.
+// Copyright (C) 2026 WATANABE Yuki
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+//! Running the ERR trap
+
+use super::run_trap;
+use crate::Runtime;
+use std::ops::ControlFlow::Continue;
+use std::rc::Rc;
+use yash_env::Env;
+use yash_env::semantics::Result;
+use yash_env::stack::Frame;
+use yash_env::trap::Action;
+use yash_env::trap::Condition;
+use yash_env::trap::Origin;
+
+fn in_err_trap<S>(env: &Env<S>) -> bool {
+    env.stack
+        .iter()
+        .rev()
+        .take_while(|frame| **frame != Frame::Subshell)
+        .any(|frame| *frame == Frame::Trap(Condition::Err))
+}
+
+/// Executes the ERR trap if the last command failed.
+///
+/// This function runs the ERR trap if all of the following are true:
+///
+/// - The ERR trap is set in the environment.
+/// - `env.exit_status` is non-zero.
+/// - The current stack has no [`Condition`](Frame::Condition) frame, that is,
+///   the [`ErrExit`](yash_env::option::Option::ErrExit) option would be
+///   applicable if it were on.
+/// - The ERR trap is not already running.
+///
+/// This function should be called where the `ErrExit` option is
+/// [applied](Env::apply_errexit), before applying it, so that the trap runs
+/// before the shell exits.
+///
+/// The exit status of the trap is ignored as in other traps.
+/// `Result::Break(divert)` from the trap action is returned to the caller.
+pub async fn run_err_trap<S: Runtime + 'static>(env: &mut Env<S>) -> Result {
+    if env.exit_status.is_successful() || env.stack.contains(&Frame::Condition) {
+        return Continue(());
+    }
+    let Some(state) = env.traps.get_state(Condition::Err).0 else {
+        return Continue(());
+    };
+    let Action::Command(command) = &state.action else {
+        return Continue(());
+    };
+    if in_err_trap(env) {
+        return Continue(());
+    }
+
+    let command = Rc::clone(command);
+    let origin = match &state.origin {
+        Origin::Inherited | Origin::Subshell => panic!("user-defined trap must have origin"),
+        Origin::User(location) => location.clone(),
+    };
+    run_trap(env, Condition::Err, command, origin).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::command::Command as _;
+    use crate::tests::{echo_builtin, return_builtin};
+    use futures_util::FutureExt as _;
+    use std::ops::ControlFlow::Break;
+    use yash_env::option::Option::ErrExit;
+    use yash_env::option::State::On;
+    use yash_env::semantics::{Divert, ExitStatus};
+    use yash_env::system::Concurrent;
+    use yash_env::system::r#virtual::VirtualSystem;
+    use yash_env::test_helper::assert_stdout;
+    use yash_syntax::source::Location;
+    use yash_syntax::syntax::List;
+
+    fn fixture() -> (
+        Env<Rc<Concurrent<VirtualSystem>>>,
+        Rc<std::cell::RefCell<yash_env::system::r#virtual::SystemState>>,
+    ) {
+        let system = VirtualSystem::new();
+        let state = Rc::clone(&system.state);
+        let mut env = Env::with_system(Rc::new(Concurrent::new(system)));
+        env.builtins.insert("echo", echo_builtin());
+        env.builtins.insert("return", return_builtin());
+        env.traps
+            .set_action(
+                &env.system,
+                Condition::Err,
+                Action::Command("echo err $?".into()),
+                Location::dummy(""),
+                false,
+            )
+            .now_or_never()
+            .unwrap()
+            .unwrap();
+        (env, state)
+    }
+
+    #[test]
+    fn err_trap_runs_on_failing_command() {
+        let (mut env, state) = fixture();
+        let list: List = "return -n 3; echo ok".parse().unwrap();
+
+        let result = list.execute(&mut env).now_or_never().unwrap();
+        assert_eq!(result, Continue(()));
+        assert_stdout(&state, |stdout| assert_eq!(stdout, "err 3\nok\n"));
+    }
+
+    #[test]
+    fn err_trap_does_not_run_on_successful_command() {
+        let (mut env, state) = fixture();
+        let list: List = "return -n 0".parse().unwrap();
+
+        let result = list.execute(&mut env).now_or_never().unwrap();
+        assert_eq!(result, Continue(()));
+        assert_stdout(&state, |stdout| assert_eq!(stdout, ""));
+    }
+
+    #[test]
+    fn err_trap_does_not_run_in_condition_context() {
+        let (mut env, state) = fixture();
+        let list: List = "return -n 1 || return -n 0; ! return -n 0".parse().unwrap();
+
+        let result = list.execute(&mut env).now_or_never().unwrap();
+        assert_eq!(result, Continue(()));
+        assert_stdout(&state, |stdout| assert_eq!(stdout, ""));
+    }
+
+    #[test]
+    fn err_trap_runs_before_errexit() {
+        let (mut env, state) = fixture();
+        env.options.set(ErrExit, On);
+        let list: List = "return -n 4; echo not reached".parse().unwrap();
+
+        let result = list.execute(&mut env).now_or_never().unwrap();
+        assert_eq!(result, Break(Divert::Exit(None)));
+        assert_eq!(env.exit_status, ExitStatus(4));
+        assert_stdout(&state, |stdout| assert_eq!(stdout, "err 4\n"));
+    }
+}
@@ -84,6 +84,10 @@ fn in_trap<S>(env: &Env<S>) -> bool {
 /// are reentrant. As an exception, this function does run traps in a subshell
 /// executed in a trap.
 ///
+/// If a SIGWINCH signal has been caught, this function [updates the `COLUMNS`
+/// and `LINES` variables](Env::update_tty_size) before running the trap action
+/// for the signal, if any.
+///
 /// If a SIGINT signal has been caught and the trap action for SIGINT is
 /// [`Action::Default`], then this function returns
 /// `Result::Break(Divert::Interrupt(Some(ExitStatus::from(SIGINT))))`
@@ -111,15 +115,23 @@ pub async fn run_traps_for_caught_signals<S: Runtime + 'static>(env: &mut Env<S>
     }
 
     while let Some((signal, state)) = env.traps.take_caught_signal() {
-        let Action::Command(command) = &state.action else {
-            continue;
-        };
-        let code = Rc::clone(command);
-        let origin = match &state.origin {
-            Origin::Inherited | Origin::Subshell => panic!("user-defined trap must have origin"),
-            Origin::User(location) => location.clone(),
+        let trap = match (&state.action, &state.origin) {
+            (Action::Command(command), Origin::User(location)) => {
+                Some((Rc::clone(command), location.clone()))
+            }
+            (Action::Command(_), Origin::Inherited | Origin::Subshell) => {
+                panic!("user-defined trap must have origin")
+            }
+            _ => None,
         };
-        run_trap(env, signal.into(), code, origin).await?;
+
+        if signal == S::SIGWINCH {
+            env.update_tty_size();
+        }
+
+        if let Some((code, origin)) = trap {
+            run_trap(env, signal.into(), code, origin).await?;
+        }
     }
 
     Continue(())
@@ -128,8 +140,10 @@ pub async fn run_traps_for_caught_signals<S: Runtime + 'static>(env: &mut Env<S>
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::command::Command as _;
     use crate::tests::echo_builtin;
     use crate::tests::exit_builtin;
+    use crate::tests::return_builtin;
     use futures_util::FutureExt as _;
     use std::assert_matches;
     use std::ops::ControlFlow::Break;
@@ -142,11 +156,15 @@ mod tests {
     use yash_env::semantics::Field;
     use yash_env::signal;
     use yash_env::system::Concurrent;
+    use yash_env::system::WindowSize;
+    use yash_env::system::r#virtual::FileBody;
     use yash_env::system::r#virtual::VirtualSystem;
-    use yash_env::system::r#virtual::{SIGINT, SIGTERM, SIGUSR1, SIGUSR2};
-    use yash_env::test_helper::assert_stdout;
+    use yash_env::system::r#virtual::{SIGINT, SIGTERM, SIGUSR1, SIGUSR2, SIGWINCH};
+    use yash_env::test_helper::{assert_stderr, assert_stdout};
     use yash_env::trap::Action;
+    use yash_env::variable::{COLUMNS, LINES};
     use yash_syntax::source::Location;
+    use yash_syntax::syntax::List;
 
     fn env_with_echo() -> (Env<Rc<Concurrent<VirtualSystem>>>, VirtualSystem) {
         let system = VirtualSystem::default();
@@ -282,6 +300,48 @@ mod tests {
             .unwrap();
     }
 
+    #[test]
+    fn updating_tty_size_on_sigwinch() {
+        let (mut env, system) = env_with_echo();
+        {
+            let mut state = system.state.borrow_mut();
+            let stderr = state.file_system.get("/dev/stderr").unwrap();
+            stderr.borrow_mut().body = FileBody::Terminal { content: vec![] };
+            state.window_size = WindowSize {
+                columns: 80,
+                lines: 24,
+            };
+        }
+        env.traps
+            .enable_internal_disposition_for_sigwinch(&env.system)
+            .now_or_never()
+            .unwrap()
+            .unwrap();
+        raise_signal(&system, SIGWINCH);
+        let result = run_traps_for_caught_signals(&mut env)
+            .now_or_never()
+            .unwrap();
+        assert_eq!(result, Continue(()));
+        let columns = env.variables.get(COLUMNS).unwrap();
+        assert_eq!(columns.value, Some("80".into()));
+        let lines = env.variables.get(LINES).unwrap();
+        assert_eq!(lines.value, Some("24".into()));
+
+        system.state.borrow_mut().window_size = WindowSize {
+            columns: 132,
+            lines: 43,
+        };
+        raise_signal(&system, SIGWINCH);
+        let result = run_traps_for_caught_signals(&mut env)
+            .now_or_never()
+            .unwrap();
+        assert_eq!(result, Continue(()));
+        let columns = env.variables.get(COLUMNS).unwrap();
+        assert_eq!(columns.value, Some("132".into()));
+        let lines = env.variables.get(LINES).unwrap();
+        assert_eq!(lines.value, Some("43".into()));
+    }
+
     #[test]
     fn exit_status_is_restored_after_running_trap() {
         let (mut env, system) = env_with_sigint_trap();
@@ -320,6 +380,84 @@ mod tests {
         });
     }
 
+    #[test]
+    fn syntax_error_in_trap_is_reported_when_signal_is_caught() {
+        let (mut env, system) = env_with_echo();
+        env.traps
+            .set_action(
+                &env.system,
+                SIGUSR1,
+                Action::Command("echo (".into()),
+                Location::dummy("trap 'echo (' USR1"),
+                false,
+            )
+            .now_or_never()
+            .unwrap()
+            .unwrap();
+        assert_stderr(&system.state, |stderr| assert_eq!(stderr, ""));
+
+        raise_signal(&system, SIGUSR1);
+        let result = run_traps_for_caught_signals(&mut env)
+            .now_or_never()
+            .unwrap();
+        assert_matches!(result, Break(Divert::Interrupt(Some(_))));
+        assert_stderr(&system.state, |stderr| {
+            assert!(stderr.contains("trap was set here"), "{stderr:?}")
+        });
+    }
+
+    #[test]
+    fn syntax_error_in_trap_does_not_prevent_preceding_lines() {
+        let (mut env, system) = env_with_echo();
+        env.traps
+            .set_action(
+                &env.system,
+                SIGUSR1,
+                Action::Command("echo first\necho (".into()),
+                Location::dummy(""),
+                false,
+            )
+            .now_or_never()
+            .unwrap()
+            .unwrap();
+
+        raise_signal(&system, SIGUSR1);
+        let result = run_traps_for_caught_signals(&mut env)
+            .now_or_never()
+            .unwrap();
+        assert_eq!(result, Break(Divert::Interrupt(Some(ExitStatus::ERROR))));
+        assert_stdout(&system.state, |stdout| assert_eq!(stdout, "first\n"));
+        assert_stderr(&system.state, |stderr| assert_ne!(stderr, ""));
+    }
+
+    #[test]
+    fn trap_action_is_reparsed_after_trap_is_reset() {
+        let (mut env, system) = env_with_sigint_trap();
+        raise_signal(&system, SIGINT);
+        let _ = run_traps_for_caught_signals(&mut env)
+            .now_or_never()
+            .unwrap();
+        env.traps
+            .set_action(
+                &env.system,
+                SIGINT,
+                Action::Command("echo again".into()),
+                Location::dummy(""),
+                false,
+            )
+            .now_or_never()
+            .unwrap()
+            .unwrap();
+
+        raise_signal(&system, SIGINT);
+        let _ = run_traps_for_caught_signals(&mut env)
+            .now_or_never()
+            .unwrap();
+        assert_stdout(&system.state, |stdout| {
+            assert_eq!(stdout, "trapped\nagain\n")
+        });
+    }
+
     #[test]
     fn exit_from_trap_with_specified_exit_status() {
         let (mut env, system) = env_with_sigint_trap();
@@ -368,6 +506,32 @@ mod tests {
         assert_eq!(env.exit_status, ExitStatus(42));
     }
 
+    #[test]
+    fn return_from_trap_without_exit_status_in_function() {
+        let (mut env, system) = env_with_echo();
+        env.builtins.insert("return", return_builtin());
+        env.traps
+            .set_action(
+                &env.system,
+                SIGUSR1,
+                Action::Command("echo trapped; return".into()),
+                Location::dummy(""),
+                false,
+            )
+            .now_or_never()
+            .unwrap()
+            .unwrap();
+        let definition: List = "foo() { return -n 7; echo not reached; }".parse().unwrap();
+        let _ = definition.execute(&mut env).now_or_never().unwrap();
+        raise_signal(&system, SIGUSR1);
+
+        let command: List = "foo".parse().unwrap();
+        let result = command.execute(&mut env).now_or_never().unwrap();
+        assert_eq!(result, Continue(()));
+        assert_eq!(env.exit_status, ExitStatus(7));
+        assert_stdout(&system.state, |stdout| assert_eq!(stdout, "trapped\n"));
+    }
+
     #[test]
     fn no_trap_actions_performed_if_interrupted_by_sigint() {
         let (mut env, system) = env_with_echo();
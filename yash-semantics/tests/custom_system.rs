@@ -0,0 +1,528 @@
+// This file is part of yash, an extended POSIX shell.
+// Copyright (C) 2026 WATANABE Yuki
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+//! Test of running a script on a custom system implementation
+//!
+//! This test defines `RecordingSystem`, a system that records every system call
+//! made by the shell and forwards it to a [`VirtualSystem`], except that it
+//! refuses to create child processes or execute external utilities. This
+//! demonstrates how an embedder can inject its own system implementation into
+//! [`Env`] to sandbox the shell.
+
+use enumset::EnumSet;
+use futures_util::poll;
+use std::cell::{Cell, RefCell};
+use std::convert::Infallible;
+use std::ffi::{CStr, CString, c_int};
+use std::io::SeekFrom;
+use std::ops::RangeInclusive;
+use std::pin::{Pin, pin};
+use std::rc::Rc;
+use std::time::{Duration, Instant};
+use yash_env::Env;
+use yash_env::VirtualSystem;
+use yash_env::builtin::{Builtin, Type};
+use yash_env::io::Fd;
+use yash_env::job::{Pid, ProcessState};
+use yash_env::path::{Path, PathBuf};
+use yash_env::semantics::{ExitStatus, Field};
+use yash_env::signal::Number;
+use yash_env::system::c_string::IntoCStrArray;
+use yash_env::system::concurrency::{RunLoop, Select as _, WriteAll as _};
+use yash_env::system::resource::{LimitPair, Resource, SetRlimit};
+use yash_env::system::r#virtual::{FileBody, Inode};
+use yash_env::system::{
+    CaughtSignals, Clock, Close, Concurrent, Dir, Disposition, Dup, Errno, Exec, Exit, Fcntl,
+    FdFlag, Fork, Fstat, GetPid, GetPw, GetSigaction, IsExecutableFile, Isatty, Mode, OfdAccess,
+    Open, OpenFlag, Pipe, Poll, PollFd, Read, Result, Seek, Select, SendSignal, SetPgid, ShellPath,
+    Sigaction, Sigmask, SigmaskOp, Signals, TcSetPgrp, TtySize, Wait, WindowSize, Write,
+};
+use yash_semantics::run_string;
+
+/// System that records the names of the system calls made
+#[derive(Clone, Debug)]
+struct RecordingSystem {
+    inner: VirtualSystem,
+    calls: Rc<RefCell<Vec<&'static str>>>,
+}
+
+impl RecordingSystem {
+    fn record(&self, name: &'static str) {
+        self.calls.borrow_mut().push(name);
+    }
+}
+
+impl Signals for RecordingSystem {
+    const SIGABRT: Number = VirtualSystem::SIGABRT;
+    const SIGALRM: Number = VirtualSystem::SIGALRM;
+    const SIGBUS: Number = VirtualSystem::SIGBUS;
+    const SIGCHLD: Number = VirtualSystem::SIGCHLD;
+    const SIGCLD: Option<Number> = VirtualSystem::SIGCLD;
+    const SIGCONT: Number = VirtualSystem::SIGCONT;
+    const SIGEMT: Option<Number> = VirtualSystem::SIGEMT;
+    const SIGFPE: Number = VirtualSystem::SIGFPE;
+    const SIGHUP: Number = VirtualSystem::SIGHUP;
+    const SIGILL: Number = VirtualSystem::SIGILL;
+    const SIGINFO: Option<Number> = VirtualSystem::SIGINFO;
+    const SIGINT: Number = VirtualSystem::SIGINT;
+    const SIGIO: Option<Number> = VirtualSystem::SIGIO;
+    const SIGIOT: Number = VirtualSystem::SIGIOT;
+    const SIGKILL: Number = VirtualSystem::SIGKILL;
+    const SIGLOST: Option<Number> = VirtualSystem::SIGLOST;
+    const SIGPIPE: Number = VirtualSystem::SIGPIPE;
+    const SIGPOLL: Option<Number> = VirtualSystem::SIGPOLL;
+    const SIGPROF: Number = VirtualSystem::SIGPROF;
+    const SIGPWR: Option<Number> = VirtualSystem::SIGPWR;
+    const SIGQUIT: Number = VirtualSystem::SIGQUIT;
+    const SIGSEGV: Number = VirtualSystem::SIGSEGV;
+    const SIGSTKFLT: Option<Number> = VirtualSystem::SIGSTKFLT;
+    const SIGSTOP: Number = VirtualSystem::SIGSTOP;
+    const SIGSYS: Number = VirtualSystem::SIGSYS;
+    const SIGTERM: Number = VirtualSystem::SIGTERM;
+    const SIGTHR: Option<Number> = VirtualSystem::SIGTHR;
+    const SIGTRAP: Number = VirtualSystem::SIGTRAP;
+    const SIGTSTP: Number = VirtualSystem::SIGTSTP;
+    const SIGTTIN: Number = VirtualSystem::SIGTTIN;
+    const SIGTTOU: Number = VirtualSystem::SIGTTOU;
+    const SIGURG: Number = VirtualSystem::SIGURG;
+    const SIGUSR1: Number = VirtualSystem::SIGUSR1;
+    const SIGUSR2: Number = VirtualSystem::SIGUSR2;
+    const SIGVTALRM: Number = VirtualSystem::SIGVTALRM;
+    const SIGWINCH: Number = VirtualSystem::SIGWINCH;
+    const SIGXCPU: Number = VirtualSystem::SIGXCPU;
+    const SIGXFSZ: Number = VirtualSystem::SIGXFSZ;
+
+    fn sigrt_range(&self) -> Option<RangeInclusive<Number>> {
+        self.inner.sigrt_range()
+    }
+}
+
+impl Sigmask for RecordingSystem {
+    type Sigset = <VirtualSystem as Sigmask>::Sigset;
+
+    fn sigmask(
+        &self,
+        op: Option<(SigmaskOp, &Self::Sigset)>,
+        old_mask: Option<&mut Self::Sigset>,
+    ) -> impl Future<Output = Result<()>> + use<> {
+        self.record("sigmask");
+        self.inner.sigmask(op, old_mask)
+    }
+}
+
+impl GetSigaction for RecordingSystem {
+    fn get_sigaction(&self, signal: Number) -> Result<Disposition> {
+        self.record("get_sigaction");
+        self.inner.get_sigaction(signal)
+    }
+}
+
+impl Sigaction for RecordingSystem {
+    fn sigaction(&self, signal: Number, action: Disposition) -> Result<Disposition> {
+        self.record("sigaction");
+        self.inner.sigaction(signal, action)
+    }
+}
+
+impl CaughtSignals for RecordingSystem {
+    fn caught_signals(&self) -> Vec<Number> {
+        self.record("caught_signals");
+        self.inner.caught_signals()
+    }
+}
+
+impl SendSignal for RecordingSystem {
+    fn kill(&self, pid: Pid, signal: Option<Number>) -> impl Future<Output = Result<()>> + use<> {
+        self.record("kill");
+        self.inner.kill(pid, signal)
+    }
+
+    fn raise(&self, signal: Number) -> impl Future<Output = Result<()>> + use<> {
+        self.record("raise");
+        self.inner.raise(signal)
+    }
+}
+
+impl Select for RecordingSystem {
+    type FdSet = <VirtualSystem as Select>::FdSet;
+
+    fn select<'a>(
+        &self,
+        readers: &'a mut Self::FdSet,
+        writers: &'a mut Self::FdSet,
+        timeout: Option<Duration>,
+        signal_mask: Option<&Self::Sigset>,
+    ) -> impl Future<Output = Result<c_int>> + use<'a> {
+        self.record("select");
+        self.inner.select(readers, writers, timeout, signal_mask)
+    }
+}
+
+impl Poll for RecordingSystem {
+    fn poll<'a>(
+        &self,
+        fds: &'a mut [PollFd],
+        timeout: Option<Duration>,
+        signal_mask: Option<&Self::Sigset>,
+    ) -> impl Future<Output = Result<c_int>> + use<'a> {
+        self.record("poll");
+        self.inner.poll(fds, timeout, signal_mask)
+    }
+}
+
+impl Clock for RecordingSystem {
+    fn now(&self) -> Instant {
+        self.record("now");
+        self.inner.now()
+    }
+}
+
+impl Fstat for RecordingSystem {
+    type Stat = <VirtualSystem as Fstat>::Stat;
+
+    fn fstat(&self, fd: Fd) -> Result<Self::Stat> {
+        self.record("fstat");
+        self.inner.fstat(fd)
+    }
+
+    fn fstatat(&self, dir_fd: Fd, path: &CStr, follow_symlinks: bool) -> Result<Self::Stat> {
+        self.record("fstatat");
+        self.inner.fstatat(dir_fd, path, follow_symlinks)
+    }
+}
+
+impl IsExecutableFile for RecordingSystem {
+    fn is_executable_file(&self, path: &CStr) -> bool {
+        self.record("is_executable_file");
+        self.inner.is_executable_file(path)
+    }
+}
+
+impl Pipe for RecordingSystem {
+    fn pipe(&self) -> Result<(Fd, Fd)> {
+        self.record("pipe");
+        self.inner.pipe()
+    }
+}
+
+impl Dup for RecordingSystem {
+    fn dup(&self, from: Fd, to_min: Fd, flags: EnumSet<FdFlag>) -> Result<Fd> {
+        self.record("dup");
+        self.inner.dup(from, to_min, flags)
+    }
+
+    fn dup2(&self, from: Fd, to: Fd) -> Result<Fd> {
+        self.record("dup2");
+        self.inner.dup2(from, to)
+    }
+}
+
+impl Open for RecordingSystem {
+    fn open(
+        &self,
+        path: &CStr,
+        access: OfdAccess,
+        flags: EnumSet<OpenFlag>,
+        mode: Mode,
+    ) -> impl Future<Output = Result<Fd>> + use<> {
+        self.record("open");
+        self.inner.open(path, access, flags, mode)
+    }
+
+    fn open_tmpfile(&self, parent_dir: &Path) -> Result<Fd> {
+        self.record("open_tmpfile");
+        self.inner.open_tmpfile(parent_dir)
+    }
+
+    fn fdopendir(&self, fd: Fd) -> Result<impl Dir + use<>> {
+        self.record("fdopendir");
+        self.inner.fdopendir(fd)
+    }
+
+    fn opendir(&self, path: &CStr) -> Result<impl Dir + use<>> {
+        self.record("opendir");
+        self.inner.opendir(path)
+    }
+}
+
+impl Close for RecordingSystem {
+    fn close(&self, fd: Fd) -> Result<()> {
+        self.record("close");
+        self.inner.close(fd)
+    }
+}
+
+impl Fcntl for RecordingSystem {
+    fn ofd_access(&self, fd: Fd) -> Result<OfdAccess> {
+        self.record("ofd_access");
+        self.inner.ofd_access(fd)
+    }
+
+    fn get_and_set_nonblocking(&self, fd: Fd, nonblocking: bool) -> Result<bool> {
+        self.record("get_and_set_nonblocking");
+        self.inner.get_and_set_nonblocking(fd, nonblocking)
+    }
+
+    fn fcntl_getfd(&self, fd: Fd) -> Result<EnumSet<FdFlag>> {
+        self.record("fcntl_getfd");
+        self.inner.fcntl_getfd(fd)
+    }
+
+    fn fcntl_setfd(&self, fd: Fd, flags: EnumSet<FdFlag>) -> Result<()> {
+        self.record("fcntl_setfd");
+        self.inner.fcntl_setfd(fd, flags)
+    }
+}
+
+impl Read for RecordingSystem {
+    fn read<'a>(
+        &self,
+        fd: Fd,
+        buffer: &'a mut [u8],
+    ) -> impl Future<Output = Result<usize>> + use<'a> {
+        self.record("read");
+        self.inner.read(fd, buffer)
+    }
+}
+
+impl Write for RecordingSystem {
+    fn write<'a>(&self, fd: Fd, buffer: &'a [u8]) -> impl Future<Output = Result<usize>> + use<'a> {
+        self.record("write");
+        self.inner.write(fd, buffer)
+    }
+}
+
+impl Seek for RecordingSystem {
+    fn lseek(&self, fd: Fd, position: SeekFrom) -> Result<u64> {
+        self.record("lseek");
+        self.inner.lseek(fd, position)
+    }
+}
+
+impl GetPid for RecordingSystem {
+    fn getpid(&self) -> Pid {
+        self.record("getpid");
+        self.inner.getpid()
+    }
+
+    fn getppid(&self) -> Pid {
+        self.record("getppid");
+        self.inner.getppid()
+    }
+
+    fn getpgrp(&self) -> Pid {
+        self.record("getpgrp");
+        self.inner.getpgrp()
+    }
+
+    fn getsid(&self, pid: Pid) -> Result<Pid> {
+        self.record("getsid");
+        self.inner.getsid(pid)
+    }
+}
+
+impl SetPgid for RecordingSystem {
+    fn setpgid(&self, pid: Pid, pgid: Pid) -> Result<()> {
+        self.record("setpgid");
+        self.inner.setpgid(pid, pgid)
+    }
+}
+
+impl Isatty for RecordingSystem {
+    fn isatty(&self, fd: Fd) -> bool {
+        self.record("isatty");
+        self.inner.isatty(fd)
+    }
+}
+
+impl TtySize for RecordingSystem {
+    fn tty_size(&self, fd: Fd) -> Result<WindowSize> {
+        self.record("tty_size");
+        self.inner.tty_size(fd)
+    }
+}
+
+impl TcSetPgrp for RecordingSystem {
+    fn tcsetpgrp(&self, fd: Fd, pgid: Pid) -> impl Future<Output = Result<()>> + use<> {
+        self.record("tcsetpgrp");
+        self.inner.tcsetpgrp(fd, pgid)
+    }
+}
+
+/// This implementation refuses to create child processes.
+impl Fork for RecordingSystem {
+    fn run_in_child_process<D, F>(&self, shared_data: D, _child_task: F) -> (Result<Pid>, D)
+    where
+        D: Clone + 'static,
+        F: AsyncFnOnce(Self, D) + 'static,
+    {
+        self.record("fork");
+        (Err(Errno::ENOSYS), shared_data)
+    }
+}
+
+impl Wait for RecordingSystem {
+    fn wait(&self, target: Pid) -> Result<Option<(Pid, ProcessState)>> {
+        self.record("wait");
+        self.inner.wait(target)
+    }
+}
+
+/// This implementation refuses to execute external utilities.
+impl Exec for RecordingSystem {
+    fn execve<A, E>(
+        &self,
+        _path: &CStr,
+        _args: A,
+        _envs: E,
+    ) -> impl Future<Output = Result<Infallible>> + use<A, E>
+    where
+        A: IntoCStrArray,
+        E: IntoCStrArray,
+    {
+        self.record("execve");
+        std::future::ready(Err(Errno::ENOSYS))
+    }
+}
+
+impl Exit for RecordingSystem {
+    fn exit(&self, exit_status: ExitStatus) -> impl Future<Output = Infallible> + use<> {
+        self.record("exit");
+        self.inner.exit(exit_status)
+    }
+}
+
+impl GetPw for RecordingSystem {
+    fn getpwnam_dir(&self, name: &CStr) -> Result<Option<PathBuf>> {
+        self.record("getpwnam_dir");
+        self.inner.getpwnam_dir(name)
+    }
+}
+
+impl ShellPath for RecordingSystem {
+    fn shell_path(&self) -> CString {
+        self.record("shell_path");
+        self.inner.shell_path()
+    }
+}
+
+impl SetRlimit for RecordingSystem {
+    fn setrlimit(&self, resource: Resource, limits: LimitPair) -> Result<()> {
+        self.record("setrlimit");
+        self.inner.setrlimit(resource, limits)
+    }
+}
+
+impl RunLoop for RecordingSystem {
+    async fn run_loop<F>(concurrent: &Concurrent<Self>, task: F)
+    where
+        F: Future<Output = ()>,
+    {
+        let mut task = pin!(task);
+        while poll!(&mut task).is_pending() {
+            concurrent.select().await;
+        }
+    }
+}
+
+fn echo_builtin_main(
+    env: &mut Env<Rc<Concurrent<RecordingSystem>>>,
+    args: Vec<Field>,
+) -> Pin<Box<dyn Future<Output = yash_env::builtin::Result> + '_>> {
+    Box::pin(async move {
+        let line = format!(
+            "{}\n",
+            args.iter()
+                .map(|f| &f.value[..])
+                .collect::<Vec<_>>()
+                .join(" ")
+        );
+        let result = env.system.write_all(Fd::STDOUT, line.as_bytes()).await;
+        let exit_status = if result.is_ok() {
+            ExitStatus::SUCCESS
+        } else {
+            ExitStatus::FAILURE
+        };
+        exit_status.into()
+    })
+}
+
+#[test]
+fn script_runs_on_custom_system() {
+    let virtual_system = VirtualSystem::new();
+    let state = Rc::clone(&virtual_system.state);
+
+    let calls = Rc::new(RefCell::new(Vec::new()));
+    let system = RecordingSystem {
+        inner: virtual_system,
+        calls: Rc::clone(&calls),
+    };
+    let concurrent = Rc::new(Concurrent::new(system));
+    let mut env = Env::with_system(Rc::clone(&concurrent));
+    env.builtins
+        .insert("echo", Builtin::new(Type::Mandatory, echo_builtin_main));
+
+    let exit_status = Cell::new(None);
+    let script = "greeting=hello\necho \"$greeting\" world >greeting.txt\necho done";
+    let task = async { exit_status.set(Some(run_string(&mut env, script).await)) };
+    futures_executor::block_on(RecordingSystem::run_loop(&concurrent, task));
+
+    assert_eq!(exit_status.get(), Some(ExitStatus::SUCCESS));
+    let calls = calls.borrow();
+    let open = calls.iter().position(|&call| call == "open").unwrap();
+    let write = calls.iter().position(|&call| call == "write").unwrap();
+    assert!(open < write, "{calls:?}");
+    assert!(calls[open..write].contains(&"dup2"), "{calls:?}");
+    assert!(calls[write..].contains(&"close"), "{calls:?}");
+    assert!(!calls.contains(&"fork"), "{calls:?}");
+
+    let state = state.borrow();
+    let file = state.file_system.get("/greeting.txt").unwrap();
+    assert_eq!(file.borrow().body, FileBody::new(*b"hello world\n"));
+    let stdout = state.file_system.get("/dev/stdout").unwrap();
+    assert_eq!(stdout.borrow().body, FileBody::new(*b"done\n"));
+}
+
+#[test]
+fn external_utility_is_not_run_on_custom_system() {
+    let virtual_system = VirtualSystem::new();
+    let mut content = Inode::default();
+    content.body = FileBody::Regular {
+        content: Vec::new(),
+        is_native_executable: true,
+    };
+    content.permissions.set(Mode::USER_EXEC, true);
+    let content = Rc::new(RefCell::new(content));
+    let mut state = virtual_system.state.borrow_mut();
+    state.file_system.save("/bin/ls", content).unwrap();
+    drop(state);
+
+    let calls = Rc::new(RefCell::new(Vec::new()));
+    let system = RecordingSystem {
+        inner: virtual_system,
+        calls: Rc::clone(&calls),
+    };
+    let concurrent = Rc::new(Concurrent::new(system));
+    let mut env = Env::with_system(Rc::clone(&concurrent));
+
+    let exit_status = Cell::new(None);
+    let task = async { exit_status.set(Some(run_string(&mut env, "/bin/ls").await)) };
+    futures_executor::block_on(RecordingSystem::run_loop(&concurrent, task));
+
+    assert_eq!(exit_status.get(), Some(ExitStatus::NOEXEC));
+    assert!(calls.borrow().contains(&"fork"), "{:?}", calls.borrow());
+    assert!(!calls.borrow().contains(&"execve"), "{:?}", calls.borrow());
+}
@@ -621,6 +621,22 @@ mod tests {
         assert_eq!(next.id, EndOfInput);
     }
 
+    #[test]
+    fn parser_case_command_round_trip() {
+        for code in [
+            "case $x in esac",
+            "case $x in (a | b) cmd;; esac",
+            "case $x in (esac) a;; (b | esac) ;; esac",
+            "case $x in (a) b;& (c) d;| (*) e;; esac",
+        ] {
+            let command: CompoundCommand = code.parse().unwrap();
+            let displayed = command.to_string();
+            assert_eq!(displayed, code);
+            let reparsed: CompoundCommand = displayed.parse().unwrap();
+            assert_eq!(reparsed.to_string(), displayed);
+        }
+    }
+
     #[test]
     fn parser_case_command_many_items_without_final_double_semicolon() {
         let mut lexer = Lexer::with_code("case x in\n\na) ;; (b|c):&:; ;;\n d)echo\nesac");
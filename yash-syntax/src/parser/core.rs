@@ -27,8 +27,10 @@ use super::lex::Token;
 use super::lex::TokenId::*;
 use crate::alias::Glossary;
 use crate::parser::lex::is_blank;
+use crate::source::Location;
 use crate::syntax::HereDoc;
 use crate::syntax::MaybeLiteral as _;
+use crate::syntax::Text;
 use crate::syntax::Word;
 use std::rc::Rc;
 
@@ -387,6 +389,23 @@ impl<'a, 'b> Parser<'a, 'b> {
         Ok(c.is_some_and(is_blank))
     }
 
+    /// Parses the header of an arithmetic for loop.
+    ///
+    /// This function must be called after the `for` reserved word has been
+    /// taken and before the next token is peeked. Blanks preceding the header
+    /// are skipped. See
+    /// [`Lexer::arithmetic_for_header`](crate::parser::lex::Lexer::arithmetic_for_header)
+    /// for the return value.
+    ///
+    /// # Panics
+    ///
+    /// If the next token has been peeked.
+    pub(super) async fn arithmetic_for_header(&mut self) -> Result<Option<([Text; 3], Location)>> {
+        assert!(self.token.is_none(), "There should be no pending token");
+        self.lexer.skip_blanks().await?;
+        self.lexer.arithmetic_for_header().await
+    }
+
     /// Remembers the given partial here-document for later parsing of its content.
     ///
     /// The remembered here-document's content will be parsed when
@@ -19,7 +19,7 @@
 use super::lex::Operator;
 use crate::source::Location;
 use crate::source::pretty::{
-    Footnote, FootnoteType, Report, ReportType, Snippet, Span, SpanRole, add_span,
+    Footnote, FootnoteType, Report, ReportType, Snippet, add_supplementary_span,
 };
 use crate::syntax::AndOr;
 use crate::syntax::RedirOp;
@@ -74,8 +74,15 @@ pub enum SyntaxError {
     MissingHereDocDelimiter,
     /// A here-document operator is missing its corresponding content.
     MissingHereDocContent,
-    /// A here-document content is missing its delimiter.
-    UnclosedHereDocContent { redir_op_location: Location },
+    /// The input ended before the delimiter line of a here-document content.
+    ///
+    /// The `delimiter` is the unquoted delimiter that was expected, and the
+    /// `opening_location` is the location of the delimiter word that follows
+    /// the here-document operator.
+    UnterminatedHereDoc {
+        delimiter: String,
+        opening_location: Location,
+    },
     /// An array assignment started with `=(` but lacks a closing `)`.
     UnclosedArrayValue { opening_location: Location },
     /// A `}` appears without a matching `{`.
@@ -90,6 +97,8 @@ pub enum SyntaxError {
     UnclosedSubshell { opening_location: Location },
     /// A subshell contains no commands.
     EmptySubshell,
+    /// A process redirection (`<(...)` or `>(...)`) is not closed.
+    UnclosedProcessRedirection { opening_location: Location },
     /// A `do` appears outside a loop.
     UnopenedLoop,
     /// A `done` appears outside a loop.
@@ -106,6 +115,8 @@ pub enum SyntaxError {
     InvalidForValue,
     /// A for loop is missing a do clause.
     MissingForBody { opening_location: Location },
+    /// The header of an arithmetic for loop is not properly delimited.
+    UnclosedArithmeticFor { opening_location: Location },
     /// A while loop is missing a do clause.
     UnclosedWhileClause { opening_location: Location },
     /// A while loop's condition is empty.
@@ -191,8 +202,6 @@ pub enum SyntaxError {
     UnsupportedFunctionDefinitionSyntax,
     /// A `[[ ... ]]` command is used.
     UnsupportedDoubleBracketCommand,
-    /// A process redirection (`>(...)` or `<(...)`) is used.
-    UnsupportedProcessRedirection,
     /// A `((...))` arithmetic command is used at the beginning of a command
     /// while the `portable` option is on.
     ///
@@ -248,6 +257,9 @@ pub enum SyntaxError {
     /// POSIX leaves the result unspecified if more than two hexadecimal digits
     /// follow `\x`, so such an escape is not portable.
     TooLongHexEscape,
+    /// A `!` prefix is used in a parameter expansion (as in `${!foo}`) while
+    /// the `portable` option is on.
+    NonPortableParamPrefix,
 }
 
 impl SyntaxError {
@@ -277,7 +289,7 @@ impl SyntaxError {
             MissingRedirOperand => "the redirection operator is missing its operand",
             MissingHereDocDelimiter => "the here-document operator is missing its delimiter",
             MissingHereDocContent => "content of the here-document is missing",
-            UnclosedHereDocContent { .. } => {
+            UnterminatedHereDoc { .. } => {
                 "the delimiter to close the here-document content is missing"
             }
             UnclosedArrayValue { .. } => "the array assignment value is not closed",
@@ -287,12 +299,14 @@ impl SyntaxError {
             EmptyGrouping => "the grouping is missing its content",
             UnclosedSubshell { .. } => "the subshell is not closed",
             EmptySubshell => "the subshell is missing its content",
+            UnclosedProcessRedirection { .. } => "the process redirection is not closed",
             UnclosedDoClause { .. } => "the `do` clause is missing its closing `done`",
             EmptyDoClause => "the `do` clause is missing its content",
             MissingForName => "the variable name is missing in the `for` loop",
             InvalidForName => "the variable name is invalid",
             InvalidForValue => "the operator token is invalid in the word list of the `for` loop",
             MissingForBody { .. } => "the `for` loop is missing its `do` clause",
+            UnclosedArithmeticFor { .. } => "the arithmetic `for` loop header is not closed",
             UnclosedWhileClause { .. } => "the `while` loop is missing its `do` clause",
             EmptyWhileCondition => "the `while` loop is missing its condition",
             UnclosedUntilClause { .. } => "the `until` loop is missing its `do` clause",
@@ -333,9 +347,9 @@ impl SyntaxError {
                 "the Unicode escape is incomplete"
             }
             UnicodeEscapeOutOfRange => "the Unicode escape is out of range",
-            UnsupportedFunctionDefinitionSyntax
-            | UnsupportedDoubleBracketCommand
-            | UnsupportedProcessRedirection => "unsupported syntax",
+            UnsupportedFunctionDefinitionSyntax | UnsupportedDoubleBracketCommand => {
+                "unsupported syntax"
+            }
             UnsupportedArithmeticCommand => "`((` is ambiguous at the start of a command",
             UnsupportedExtendedGlob => "`!(` is ambiguous at the start of a command",
             NonPortableCaseTerminator(_) => "the case terminator is not portable",
@@ -346,6 +360,110 @@ impl SyntaxError {
             MissingSeparatorBeforeReservedWord => "a separator is missing before the reserved word",
             NonPortableEscape => "the escape sequence is not portable",
             TooLongHexEscape => "more than two hexadecimal digits follow `\\x`",
+            NonPortableParamPrefix => "the `!` prefix of the parameter expansion is not portable",
+        }
+    }
+
+    /// Returns a stable identifier of the error message.
+    ///
+    /// The identifier names the message template returned by
+    /// [`message`](Self::message). Variants that share the same message have
+    /// the same identifier. Unlike the message text, the identifier does not
+    /// change between releases, so an embedder can use it as a key to look up
+    /// a translation of the message.
+    #[must_use]
+    pub fn message_id(&self) -> &'static str {
+        use SyntaxError::*;
+        match self {
+            IncompleteEscape => "syntax.incomplete-escape",
+            InvalidEscape => "syntax.invalid-escape",
+            UnclosedParen { .. } => "syntax.unclosed-paren",
+            UnclosedSingleQuote { .. } => "syntax.unclosed-single-quote",
+            UnclosedDoubleQuote { .. } => "syntax.unclosed-double-quote",
+            UnclosedDollarSingleQuote { .. } => "syntax.unclosed-dollar-single-quote",
+            UnclosedParam { .. } => "syntax.unclosed-param",
+            EmptyParam => "syntax.empty-param",
+            InvalidParam => "syntax.invalid-param",
+            InvalidModifier => "syntax.invalid-modifier",
+            MultipleModifier => "syntax.multiple-modifier",
+            UnclosedCommandSubstitution { .. } => "syntax.unclosed-command-substitution",
+            UnclosedBackquote { .. } => "syntax.unclosed-backquote",
+            UnclosedArith { .. } => "syntax.unclosed-arith",
+            InvalidCommandToken => "syntax.invalid-command-token",
+            MissingSeparator => "syntax.missing-separator",
+            FdOutOfRange => "syntax.fd-out-of-range",
+            InvalidIoLocation => "syntax.invalid-io-location",
+            MissingRedirOperand => "syntax.missing-redir-operand",
+            MissingHereDocDelimiter => "syntax.missing-here-doc-delimiter",
+            MissingHereDocContent => "syntax.missing-here-doc-content",
+            UnterminatedHereDoc { .. } => "syntax.unterminated-here-doc",
+            UnclosedArrayValue { .. } => "syntax.unclosed-array-value",
+            UnopenedGrouping | UnopenedSubshell | UnopenedLoop | UnopenedDoClause | UnopenedIf
+            | UnopenedCase | InAsCommandName => "syntax.unmatched-compound-command-delimiter",
+            UnclosedGrouping { .. } => "syntax.unclosed-grouping",
+            EmptyGrouping => "syntax.empty-grouping",
+            UnclosedSubshell { .. } => "syntax.unclosed-subshell",
+            EmptySubshell => "syntax.empty-subshell",
+            UnclosedProcessRedirection { .. } => "syntax.unclosed-process-redirection",
+            UnclosedDoClause { .. } => "syntax.unclosed-do-clause",
+            EmptyDoClause => "syntax.empty-do-clause",
+            MissingForName => "syntax.missing-for-name",
+            InvalidForName => "syntax.invalid-for-name",
+            InvalidForValue => "syntax.invalid-for-value",
+            MissingForBody { .. } => "syntax.missing-for-body",
+            UnclosedArithmeticFor { .. } => "syntax.unclosed-arithmetic-for",
+            UnclosedWhileClause { .. } => "syntax.unclosed-while-clause",
+            EmptyWhileCondition => "syntax.empty-while-condition",
+            UnclosedUntilClause { .. } => "syntax.unclosed-until-clause",
+            EmptyUntilCondition => "syntax.empty-until-condition",
+            IfMissingThen { .. } => "syntax.if-missing-then",
+            EmptyIfCondition => "syntax.empty-if-condition",
+            EmptyIfBody => "syntax.empty-if-body",
+            ElifMissingThen { .. } => "syntax.elif-missing-then",
+            EmptyElifCondition => "syntax.empty-elif-condition",
+            EmptyElifBody => "syntax.empty-elif-body",
+            EmptyElse => "syntax.empty-else",
+            UnclosedIf { .. } => "syntax.unclosed-if",
+            MissingCaseSubject => "syntax.missing-case-subject",
+            InvalidCaseSubject => "syntax.invalid-case-subject",
+            MissingIn { .. } => "syntax.missing-in",
+            UnclosedPatternList => "syntax.unclosed-pattern-list",
+            MissingPattern => "syntax.missing-pattern",
+            InvalidPattern => "syntax.invalid-pattern",
+            #[allow(deprecated, reason = "for backward compatible API")]
+            EsacAsPattern => "syntax.esac-as-pattern",
+            UnclosedCase { .. } => "syntax.unclosed-case",
+            UnmatchedParenthesis => "syntax.unmatched-parenthesis",
+            MissingFunctionBody => "syntax.missing-function-body",
+            InvalidFunctionBody => "syntax.invalid-function-body",
+            MissingPipeline(AndOr::AndThen) => "syntax.missing-pipeline-after-and-then",
+            MissingPipeline(AndOr::OrElse) => "syntax.missing-pipeline-after-or-else",
+            DoubleNegation => "syntax.double-negation",
+            BangAfterBar => "syntax.bang-after-bar",
+            MissingCommandAfterBang => "syntax.missing-command-after-bang",
+            MissingCommandAfterBar => "syntax.missing-command-after-bar",
+            RedundantToken => "syntax.redundant-token",
+            IncompleteControlEscape => "syntax.incomplete-control-escape",
+            IncompleteControlBackslashEscape => "syntax.incomplete-control-backslash-escape",
+            InvalidControlEscape => "syntax.invalid-control-escape",
+            OctalEscapeOutOfRange => "syntax.octal-escape-out-of-range",
+            IncompleteHexEscape => "syntax.incomplete-hex-escape",
+            IncompleteShortUnicodeEscape | IncompleteLongUnicodeEscape => {
+                "syntax.incomplete-unicode-escape"
+            }
+            UnicodeEscapeOutOfRange => "syntax.unicode-escape-out-of-range",
+            UnsupportedFunctionDefinitionSyntax | UnsupportedDoubleBracketCommand => {
+                "syntax.unsupported-syntax"
+            }
+            UnsupportedArithmeticCommand => "syntax.unsupported-arithmetic-command",
+            UnsupportedExtendedGlob => "syntax.unsupported-extended-glob",
+            NonPortableCaseTerminator(_) => "syntax.non-portable-case-terminator",
+            NonPortableRedirOperator(_) => "syntax.non-portable-redir-operator",
+            IoTokenAsRedirOperand => "syntax.io-token-as-redir-operand",
+            MissingSeparatorBeforeReservedWord => "syntax.missing-separator-before-reserved-word",
+            NonPortableEscape => "syntax.non-portable-escape",
+            TooLongHexEscape => "syntax.too-long-hex-escape",
+            NonPortableParamPrefix => "syntax.non-portable-param-prefix",
         }
     }
 
@@ -360,6 +478,7 @@ impl SyntaxError {
             | UnclosedCommandSubstitution { .. }
             | UnclosedArrayValue { .. }
             | UnclosedSubshell { .. }
+            | UnclosedProcessRedirection { .. }
             | UnclosedPatternList
             | UnmatchedParenthesis => "expected `)`",
             EmptyGrouping
@@ -393,7 +512,7 @@ impl SyntaxError {
             MissingRedirOperand => "expected a redirection operand",
             MissingHereDocDelimiter => "expected a delimiter word",
             MissingHereDocContent => "content not found",
-            UnclosedHereDocContent { .. } => "missing delimiter",
+            UnterminatedHereDoc { .. } => "missing delimiter",
             UnopenedGrouping => "no grouping command to close",
             UnopenedSubshell => "no subshell to close",
             UnopenedLoop => "not in a loop",
@@ -404,6 +523,7 @@ impl SyntaxError {
             MissingForBody { .. } | UnclosedWhileClause { .. } | UnclosedUntilClause { .. } => {
                 "expected `do ... done`"
             }
+            UnclosedArithmeticFor { .. } => "expected `;` or `))`",
             IfMissingThen { .. } | ElifMissingThen { .. } => "expected `then ... fi`",
             UnopenedIf => "not in an `if` command",
             UnclosedIf { .. } => "expected `fi`",
@@ -427,7 +547,6 @@ impl SyntaxError {
             UnicodeEscapeOutOfRange => "not a valid Unicode scalar value",
             UnsupportedFunctionDefinitionSyntax => "the `function` keyword is not yet supported",
             UnsupportedDoubleBracketCommand => "the `[[ ... ]]` command is not yet supported",
-            UnsupportedProcessRedirection => "process redirection is not yet supported",
             UnsupportedArithmeticCommand => {
                 "other shells read this as an arithmetic command; insert a space for nested subshells"
             }
@@ -452,6 +571,7 @@ impl SyntaxError {
             }
             NonPortableEscape => "not a POSIX escape sequence",
             TooLongHexEscape => "use at most two hexadecimal digits",
+            NonPortableParamPrefix => "not a POSIX parameter expansion",
         }
     }
 
@@ -476,7 +596,8 @@ impl SyntaxError {
             | IoTokenAsRedirOperand
             | MissingSeparatorBeforeReservedWord
             | NonPortableEscape
-            | TooLongHexEscape => &[(
+            | TooLongHexEscape
+            | NonPortableParamPrefix => &[(
                 FootnoteType::Note,
                 "this error is reported because the `portable` shell option is enabled",
             )],
@@ -492,6 +613,7 @@ impl SyntaxError {
         match self {
             UnclosedParen { opening_location }
             | UnclosedSubshell { opening_location }
+            | UnclosedProcessRedirection { opening_location }
             | UnclosedArrayValue { opening_location } => {
                 Some((opening_location, "the opening parenthesis was here"))
             }
@@ -512,9 +634,9 @@ impl SyntaxError {
             UnclosedArith { opening_location } => {
                 Some((opening_location, "the arithmetic expansion started here"))
             }
-            UnclosedHereDocContent { redir_op_location } => {
-                Some((redir_op_location, "the redirection operator was here"))
-            }
+            UnterminatedHereDoc {
+                opening_location, ..
+            } => Some((opening_location, "the here-document started here")),
             UnclosedGrouping { opening_location } => {
                 Some((opening_location, "the opening brace was here"))
             }
@@ -524,6 +646,10 @@ impl SyntaxError {
             MissingForBody { opening_location } => {
                 Some((opening_location, "the `for` loop started here"))
             }
+            UnclosedArithmeticFor { opening_location } => Some((
+                opening_location,
+                "the arithmetic `for` loop header started here",
+            )),
             UnclosedWhileClause { opening_location } => {
                 Some((opening_location, "the `while` loop started here"))
             }
@@ -575,6 +701,20 @@ impl ErrorCause {
         }
     }
 
+    /// Returns a stable identifier of the error message.
+    ///
+    /// For a syntax error, this function returns the result of
+    /// [`SyntaxError::message_id`]. For an I/O error, this function returns
+    /// `None` because the message includes the text of the underlying error.
+    #[must_use]
+    pub fn message_id(&self) -> Option<&'static str> {
+        use ErrorCause::*;
+        match self {
+            Io(_) => None,
+            Syntax(e) => Some(e.message_id()),
+        }
+    }
+
     /// Returns a label for annotating the error location.
     #[must_use]
     pub fn label(&self) -> &'static str {
@@ -628,22 +768,18 @@ impl Error {
     ///
     /// This method constructs a [`Report`] from the error's cause and location.
     /// The result includes information obtained from [`ErrorCause::message`],
-    /// [`ErrorCause::label`], [`ErrorCause::footnotes`], and
-    /// [`ErrorCause::related_location`].
+    /// [`ErrorCause::message_id`], [`ErrorCause::label`],
+    /// [`ErrorCause::footnotes`], and [`ErrorCause::related_location`].
     #[must_use]
     pub fn to_report(&self) -> Report<'_> {
         let mut report = Report::new();
         report.r#type = ReportType::Error;
         report.title = self.cause.message();
+        report.message_id = self.cause.message_id().map(Cow::Borrowed);
         report.snippets = Snippet::with_primary_span(&self.location, self.cause.label().into());
 
         if let Some((location, label)) = self.cause.related_location() {
-            let label = label.into();
-            let span = Span {
-                range: location.byte_range(),
-                role: SpanRole::Supplementary { label },
-            };
-            add_span(&location.code, span, &mut report.snippets);
+            add_supplementary_span(location, label.into(), &mut report.snippets);
         }
 
         report.footnotes.extend(
@@ -673,6 +809,7 @@ mod tests {
     use super::*;
     use crate::source::Code;
     use crate::source::Source;
+    use crate::source::pretty::SpanRole;
     use std::assert_matches;
     use std::num::NonZeroU64;
     use std::rc::Rc;
@@ -714,6 +851,10 @@ mod tests {
             report.title,
             "the here-document operator is missing its delimiter"
         );
+        assert_eq!(
+            report.message_id.as_deref(),
+            Some("syntax.missing-here-doc-delimiter")
+        );
         assert_eq!(report.snippets.len(), 1);
         assert_eq!(*report.snippets[0].code.value.borrow(), "!!!");
         assert_eq!(report.snippets[0].spans.len(), 1);
@@ -725,6 +866,60 @@ mod tests {
         assert_eq!(report.footnotes, []);
     }
 
+    #[test]
+    fn report_for_unclosed_while_annotates_opening_location() {
+        let code = Rc::new(Code {
+            value: "while :\n".to_string().into(),
+            start_line_number: NonZeroU64::new(1).unwrap(),
+            source: Source::Unknown.into(),
+        });
+        let opening_location = Location {
+            code: Rc::clone(&code),
+            range: 0..5,
+        };
+        let error = Error {
+            cause: SyntaxError::UnclosedWhileClause { opening_location }.into(),
+            location: Location { code, range: 7..8 },
+        };
+
+        let report = Report::from(&error);
+
+        assert_eq!(report.snippets.len(), 1);
+        let spans = &report.snippets[0].spans;
+        assert_eq!(spans.len(), 2);
+        assert_eq!(spans[0].range, 7..8);
+        assert_eq!(spans[1].range, 0..5);
+        assert_eq!(
+            spans[1].role,
+            SpanRole::Supplementary {
+                label: "the `while` loop started here".into()
+            }
+        );
+    }
+
+    #[test]
+    fn message_id_for_syntax_error() {
+        assert_eq!(
+            SyntaxError::MissingHereDocDelimiter.message_id(),
+            "syntax.missing-here-doc-delimiter"
+        );
+        assert_eq!(
+            SyntaxError::MissingPipeline(AndOr::OrElse).message_id(),
+            "syntax.missing-pipeline-after-or-else"
+        );
+        // Errors sharing the same message share the same identifier.
+        assert_eq!(
+            SyntaxError::UnopenedLoop.message_id(),
+            SyntaxError::UnopenedIf.message_id()
+        );
+    }
+
+    #[test]
+    fn message_id_for_io_error() {
+        let cause = ErrorCause::from(std::io::Error::other("oops"));
+        assert_eq!(cause.message_id(), None);
+    }
+
     #[test]
     fn footnotes_for_syntax_error() {
         // Errors caused by the `portable` option have a note.
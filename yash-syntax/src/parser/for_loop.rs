@@ -147,6 +147,22 @@ impl Parser<'_, '_> {
         assert_eq!(open.id, Token(Some(For)));
         let opening_location = open.word.location;
 
+        if !self.mode().portable
+            && let Some(([init, cond, update], location)) = self.arithmetic_for_header().await?
+        {
+            if self.peek_token().await?.id == Operator(Semicolon) {
+                self.take_token_raw().await?;
+            }
+            let body = self.for_loop_body(opening_location).await?;
+            return Ok(CompoundCommand::ArithmeticFor {
+                init,
+                cond,
+                update,
+                body,
+                location,
+            });
+        }
+
         let name = self.for_loop_name().await?;
         let (values, opening_location) = self.for_loop_values(opening_location).await?;
         let body = self.for_loop_body(opening_location).await?;
@@ -249,6 +265,26 @@ mod tests {
         assert_eq!(next.id, EndOfInput);
     }
 
+    #[test]
+    fn parser_for_loop_round_trip_with_and_without_in() {
+        for code in ["for x; do :; done", "for x in; do :; done"] {
+            let command: CompoundCommand = code.parse().unwrap();
+            let displayed = command.to_string();
+            let reparsed: CompoundCommand = displayed.parse().unwrap();
+            assert_eq!(reparsed.to_string(), displayed, "{code:?}");
+            assert_matches!(
+                (command, reparsed),
+                (
+                    CompoundCommand::For { values: v1, .. },
+                    CompoundCommand::For { values: v2, .. },
+                ) => assert_eq!(v1, v2, "{code:?}")
+            );
+        }
+        let without_in: CompoundCommand = "for x; do :; done".parse().unwrap();
+        let with_empty_in: CompoundCommand = "for x in; do :; done".parse().unwrap();
+        assert_ne!(without_in.to_string(), with_empty_in.to_string());
+    }
+
     #[test]
     fn parser_for_loop_with_one_value_delimited_by_semicolon_and_newlines() {
         let mut lexer = Lexer::with_code("for foo in bar; \n \n do :; done");
@@ -548,4 +584,93 @@ mod tests {
         assert_eq!(*e.location.code.source, Source::Unknown);
         assert_eq!(e.location.range, 8..9);
     }
+
+    #[test]
+    fn parser_arithmetic_for_loop() {
+        let mut lexer = Lexer::with_code("for ((i=0;i<3;i++)); do echo $i; done");
+        let mut parser = Parser::new(&mut lexer);
+
+        let result = parser.compound_command().now_or_never().unwrap();
+        let compound_command = result.unwrap().unwrap();
+        assert_matches!(compound_command, CompoundCommand::ArithmeticFor { init, cond, update, body, location } => {
+            assert_eq!(init.to_string(), "i=0");
+            assert_eq!(cond.to_string(), "i<3");
+            assert_eq!(update.to_string(), "i++");
+            assert_eq!(body.to_string(), "echo $i");
+            assert_eq!(location.range, 4..19);
+        });
+
+        let next = parser.peek_token().now_or_never().unwrap().unwrap();
+        assert_eq!(next.id, EndOfInput);
+    }
+
+    #[test]
+    fn parser_arithmetic_for_loop_with_empty_expressions() {
+        let mut lexer = Lexer::with_code("for (( ; ;))\ndo :; done");
+        let mut parser = Parser::new(&mut lexer);
+
+        let result = parser.compound_command().now_or_never().unwrap();
+        let compound_command = result.unwrap().unwrap();
+        assert_matches!(compound_command, CompoundCommand::ArithmeticFor { init, cond, update, body, .. } => {
+            assert_eq!(init.to_string(), " ");
+            assert_eq!(cond.to_string(), " ");
+            assert_eq!(update.to_string(), "");
+            assert_eq!(body.to_string(), ":");
+        });
+    }
+
+    #[test]
+    fn parser_arithmetic_for_loop_with_nested_parentheses() {
+        let mut lexer = Lexer::with_code("for ((i=(1);(i)<3;i+=(1))) do :; done");
+        let mut parser = Parser::new(&mut lexer);
+
+        let result = parser.compound_command().now_or_never().unwrap();
+        let compound_command = result.unwrap().unwrap();
+        assert_matches!(compound_command, CompoundCommand::ArithmeticFor { init, cond, update, .. } => {
+            assert_eq!(init.to_string(), "i=(1)");
+            assert_eq!(cond.to_string(), "(i)<3");
+            assert_eq!(update.to_string(), "i+=(1)");
+        });
+    }
+
+    #[test]
+    fn parser_arithmetic_for_loop_missing_semicolon() {
+        let mut lexer = Lexer::with_code("for ((i=0)) do :; done");
+        let mut parser = Parser::new(&mut lexer);
+
+        let result = parser.compound_command().now_or_never().unwrap();
+        let e = result.unwrap_err();
+        assert_matches!(&e.cause,
+            ErrorCause::Syntax(SyntaxError::UnclosedArithmeticFor { opening_location }) => {
+            assert_eq!(opening_location.range, 4..6);
+        });
+        assert_eq!(e.location.range, 9..10);
+    }
+
+    #[test]
+    fn parser_arithmetic_for_loop_unclosed() {
+        let mut lexer = Lexer::with_code("for ((;;)");
+        let mut parser = Parser::new(&mut lexer);
+
+        let result = parser.compound_command().now_or_never().unwrap();
+        let e = result.unwrap_err();
+        assert_matches!(&e.cause,
+            ErrorCause::Syntax(SyntaxError::UnclosedArithmeticFor { opening_location }) => {
+            assert_eq!(opening_location.range, 4..6);
+        });
+        assert_eq!(e.location.range, 9..9);
+    }
+
+    #[test]
+    fn parser_arithmetic_for_loop_in_portable_mode() {
+        let mut mode = yash_env::parser::Mode::default();
+        mode.portable = true;
+        let mut lexer = Lexer::with_code("for ((;;)) do :; done");
+        lexer.set_mode(mode);
+        let mut parser = Parser::new(&mut lexer);
+
+        let result = parser.compound_command().now_or_never().unwrap();
+        let e = result.unwrap_err();
+        assert_eq!(e.cause, ErrorCause::Syntax(SyntaxError::InvalidForName));
+    }
 }
@@ -195,7 +195,7 @@ impl FromStr for Assign {
                 } else if let Some(redir) = c.redirs.first() {
                     Err(Some(Error {
                         cause: ErrorCause::Syntax(SyntaxError::RedundantToken),
-                        location: redir.body.operand().location.clone(),
+                        location: redir.body.location().clone(),
                     }))
                 } else {
                     Ok(last)
@@ -241,6 +241,22 @@ mod tests {
         assert_eq!(next.id, EndOfInput);
     }
 
+    #[test]
+    fn parser_if_command_round_trip() {
+        for code in [
+            "if a; then b; fi",
+            "if a; then b; else c; fi",
+            "if a; then b; elif c; then d; elif e; then f; fi",
+            "if a& then b& elif c& then d& else e& fi",
+        ] {
+            let command: CompoundCommand = code.parse().unwrap();
+            let displayed = command.to_string();
+            assert_eq!(displayed, code);
+            let reparsed: CompoundCommand = displayed.parse().unwrap();
+            assert_eq!(reparsed.to_string(), displayed);
+        }
+    }
+
     #[test]
     fn parser_if_command_without_then_after_if() {
         let mut lexer = Lexer::with_code(" if :; fi");
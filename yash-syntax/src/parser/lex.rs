@@ -36,11 +36,13 @@ mod raw_param;
 mod text;
 mod tilde;
 mod token;
+mod tokenize;
 mod word;
 
 pub use self::braced_param::is_name;
 pub use self::braced_param::is_name_char;
 pub use self::core::*;
+pub use self::heredoc::here_doc_content_from_lines;
 pub use self::keyword::Keyword;
 pub use self::keyword::ParseKeywordError;
 pub use self::op::Operator;
@@ -52,3 +54,8 @@ pub use self::raw_param::is_portable_name_char;
 pub use self::raw_param::is_single_char_name;
 pub use self::raw_param::is_special_parameter_char;
 pub use self::token::is_token_delimiter_char;
+pub use self::tokenize::Retokenized;
+pub use self::tokenize::TokenKind;
+pub use self::tokenize::retokenize;
+pub use self::tokenize::tokenize;
+pub use self::tokenize::tokenize_with_comments;
@@ -20,6 +20,8 @@ use super::core::Lexer;
 use crate::parser::core::Result;
 use crate::parser::error::Error;
 use crate::parser::error::SyntaxError;
+use crate::source::Location;
+use crate::syntax::Text;
 use crate::syntax::TextUnit;
 
 impl Lexer<'_> {
@@ -81,6 +83,55 @@ impl Lexer<'_> {
         let location = self.location_range(start_index..self.index());
         Ok(Some(TextUnit::Arith { content, location }))
     }
+
+    /// Parses the header of an arithmetic for loop.
+    ///
+    /// This function examines the next two characters to see if they are
+    /// `((`. If they are, the header is parsed up to the closing `))`
+    /// (inclusive) and the three expressions are returned along with the
+    /// location of the whole header. Otherwise, no characters are consumed and
+    /// the return value is `Ok(None)`.
+    ///
+    /// The expressions are separated by `;`. Each expression is parsed as a
+    /// text that may contain nested parentheses. An empty expression results in
+    /// an empty text.
+    pub async fn arithmetic_for_header(&mut self) -> Result<Option<([Text; 3], Location)>> {
+        let start_index = self.index();
+
+        // Part 1: Parse `((`
+        if !self.skip_if(|c| c == '(').await? {
+            return Ok(None);
+        }
+        if !self.skip_if(|c| c == '(').await? {
+            self.rewind(start_index);
+            return Ok(None);
+        }
+
+        let opening_location = self.location_range(start_index..self.index());
+
+        // Part 2: Parse the expressions and delimiters
+        let mut texts: [Text; 3] = Default::default();
+        for (i, text) in texts.iter_mut().enumerate() {
+            let is_delimiter = |c| c == ';' || c == ')';
+            let is_escapable = |c| matches!(c, '$' | '`' | '\\');
+            *text = self
+                .text_with_parentheses(is_delimiter, is_escapable)
+                .await?;
+
+            let delimiter: &[char] = if i < 2 { &[';'] } else { &[')', ')'] };
+            for &d in delimiter {
+                if !self.skip_if(|c| c == d).await? {
+                    let opening_location = opening_location.clone();
+                    let cause = SyntaxError::UnclosedArithmeticFor { opening_location }.into();
+                    let location = self.location().await?.clone();
+                    return Err(Error { cause, location });
+                }
+            }
+        }
+
+        let location = self.location_range(start_index..self.index());
+        Ok(Some((texts, location)))
+    }
 }
 
 #[cfg(test)]
@@ -90,6 +141,8 @@ mod tests {
     use crate::source::Source;
     use crate::syntax::Backslashed;
     use crate::syntax::Literal;
+    use crate::syntax::MaybeLiteral as _;
+    use crate::syntax::Unquote as _;
     use assert_matches::assert_matches;
     use futures_util::FutureExt as _;
 
@@ -143,6 +196,22 @@ mod tests {
         assert_eq!(lexer.peek_char().now_or_never().unwrap(), Ok(Some(';')));
     }
 
+    #[test]
+    fn lexer_arithmetic_expansion_round_trip() {
+        let text_unit: TextUnit = "$(( 1 + 2 ))".parse().unwrap();
+        assert_eq!(text_unit.to_string(), "$(( 1 + 2 ))");
+        assert_eq!(text_unit.unquote(), ("$(( 1 + 2 ))".to_string(), false));
+        assert_eq!(text_unit.to_string_if_literal(), None);
+
+        let text_unit: TextUnit = "$(( $x + 1 ))".parse().unwrap();
+        assert_matches!(&text_unit, TextUnit::Arith { content, .. } => {
+            assert_matches!(&content.0[..], [Literal(' '), TextUnit::RawParam { param, .. }, ..] => {
+                assert_eq!(param.id, "x");
+            });
+        });
+        assert_eq!(text_unit.to_string(), "$(( $x + 1 ))");
+    }
+
     #[test]
     fn lexer_arithmetic_expansion_escapes() {
         let mut lexer = Lexer::with_code(r#".$((\\\"\`\$));"#);
@@ -22,11 +22,14 @@ use super::raw_param::is_portable_name_char;
 use crate::parser::core::Result;
 use crate::parser::error::Error;
 use crate::parser::error::SyntaxError;
+use crate::source::Location;
 use crate::syntax::BracedParam;
+use crate::syntax::ListSuffix;
 use crate::syntax::Modifier;
 use crate::syntax::Param;
 use crate::syntax::ParamType;
 use crate::syntax::SpecialParam;
+use crate::syntax::Subscript;
 use std::num::IntErrorKind;
 
 /// Tests if a character can be part of a variable name.
@@ -121,6 +124,118 @@ impl WordLexer<'_, '_> {
         Ok(has_length_prefix)
     }
 
+    /// Consumes an indirection prefix (`!`) if any.
+    ///
+    /// The `!` is regarded as a prefix only if it is followed by a
+    /// [name character](is_name_char). Otherwise, it is left for the special
+    /// parameter `!`, so that POSIX expansions like `${!}` and `${!-foo}` are
+    /// parsed as before.
+    ///
+    /// The prefix is rejected if the lexer's parsing mode has `portable`
+    /// enabled.
+    async fn indirect_prefix(&mut self) -> Result<bool> {
+        let index = self.index();
+        if !self.skip_if(|c| c == '!').await? {
+            return Ok(false);
+        }
+        if !self.peek_char().await?.is_some_and(is_name_char) {
+            self.rewind(index);
+            return Ok(false);
+        }
+        if self.mode().portable {
+            let cause = SyntaxError::NonPortableParamPrefix.into();
+            let location = self.location_range(index..index + 1);
+            return Err(Error { cause, location });
+        }
+        Ok(true)
+    }
+
+    /// Consumes a `*` or `@` suffix that ends a parameter expansion.
+    ///
+    /// The suffix is consumed only if it is followed by the closing brace,
+    /// which is not consumed.
+    async fn list_suffix(&mut self) -> Result<Option<ListSuffix>> {
+        let index = self.index();
+        let suffix = match self.peek_char().await? {
+            Some('*') => ListSuffix::Asterisk,
+            Some('@') => ListSuffix::At,
+            _ => return Ok(None),
+        };
+        self.consume_char();
+        if self.peek_char().await? == Some('}') {
+            Ok(Some(suffix))
+        } else {
+            self.rewind(index);
+            Ok(None)
+        }
+    }
+
+    /// Consumes a `[*]` or `[@]` suffix that ends a parameter expansion.
+    ///
+    /// The suffix is consumed only if it is followed by the closing brace,
+    /// which is not consumed.
+    async fn index_list_suffix(&mut self) -> Result<Option<ListSuffix>> {
+        let index = self.index();
+        if !self.skip_if(|c| c == '[').await? {
+            return Ok(None);
+        }
+        let suffix = match self.peek_char().await? {
+            Some('*') => Some(ListSuffix::Asterisk),
+            Some('@') => Some(ListSuffix::At),
+            _ => None,
+        };
+        if let Some(suffix) = suffix {
+            self.consume_char();
+            if self.skip_if(|c| c == ']').await? && self.peek_char().await? == Some('}') {
+                return Ok(Some(suffix));
+            }
+        }
+        self.rewind(index);
+        Ok(None)
+    }
+
+    /// Consumes an array subscript (`[...]`) if any.
+    ///
+    /// A `[*]` or `[@]` subscript is parsed as [`Subscript::All`]. Otherwise,
+    /// the content up to the next `]` is parsed as a text that is to be
+    /// evaluated as an arithmetic expression.
+    ///
+    /// The `opening_location` parameter should be the location of the `${`
+    /// that starts the parameter expansion. It is used in the error returned
+    /// when the subscript is not closed.
+    async fn subscript(&mut self, opening_location: &Location) -> Result<Option<Subscript>> {
+        if !self.skip_if(|c| c == '[').await? {
+            return Ok(None);
+        }
+
+        let index = self.index();
+        let suffix = match self.peek_char().await? {
+            Some('*') => Some(ListSuffix::Asterisk),
+            Some('@') => Some(ListSuffix::At),
+            _ => None,
+        };
+        if let Some(suffix) = suffix {
+            self.consume_char();
+            if self.skip_if(|c| c == ']').await? {
+                return Ok(Some(Subscript::All(suffix)));
+            }
+            self.rewind(index);
+        }
+
+        let is_delimiter = |c| c == ']';
+        let is_escapable = |c| matches!(c, '$' | '`' | '\\' | ']');
+        // Boxing needed for recursion
+        let text = Box::pin(self.text(is_delimiter, is_escapable)).await?;
+
+        if !self.skip_if(|c| c == ']').await? {
+            let opening_location = opening_location.clone();
+            let cause = SyntaxError::UnclosedParam { opening_location }.into();
+            let location = self.location().await?.clone();
+            return Err(Error { cause, location });
+        }
+        Ok(Some(Subscript::Expression(text)))
+    }
+
     /// Parses a parameter expansion that is enclosed in braces.
     ///
     /// The initial `$` must have been consumed before calling this function.
@@ -139,12 +254,18 @@ impl WordLexer<'_, '_> {
 
         let opening_location = self.location_range(start_index..self.index());
 
-        let has_length_prefix = self.length_prefix().await?;
+        let mut prefix = if self.length_prefix().await? {
+            Some(Modifier::Length)
+        } else if self.indirect_prefix().await? {
+            Some(Modifier::Indirect)
+        } else {
+            None
+        };
 
         let param_start_index = self.index();
 
-        let c = self.peek_char().await?.unwrap();
-        let param = if is_name_char(c) {
+        let c = self.peek_char().await?;
+        let param = if let Some(c) = c.filter(|&c| is_name_char(c)) {
             self.consume_char();
 
             // Parse the remaining characters of the parameter name
@@ -159,18 +280,29 @@ impl WordLexer<'_, '_> {
                 return Err(Error { cause, location });
             };
             Param { id, r#type }
-        } else if let Some(special) = SpecialParam::from_char(c) {
+        } else if let Some(special) = c.and_then(SpecialParam::from_char) {
             self.consume_char();
-            Param {
-                id: c.to_string(),
-                r#type: special.into(),
-            }
+            Param::from(special)
         } else {
             let cause = SyntaxError::EmptyParam.into();
             let location = self.location().await?.clone();
             return Err(Error { cause, location });
         };
 
+        if prefix == Some(Modifier::Indirect) && param.r#type == ParamType::Variable {
+            if let Some(suffix) = self.list_suffix().await? {
+                prefix = Some(Modifier::VariableNames(suffix));
+            } else if let Some(suffix) = self.index_list_suffix().await? {
+                prefix = Some(Modifier::Indices(suffix));
+            }
+        }
+
+        let subscript = match prefix {
+            Some(Modifier::VariableNames(_) | Modifier::Indices(_)) => None,
+            _ if param.r#type == ParamType::Variable => self.subscript(&opening_location).await?,
+            _ => None,
+        };
+
         let suffix_location = self.location().await?.clone();
         let suffix = self.suffix_modifier().await?;
 
@@ -180,18 +312,19 @@ impl WordLexer<'_, '_> {
             return Err(Error { cause, location });
         }
 
-        let modifier = match (has_length_prefix, suffix) {
-            (true, Modifier::None) => Modifier::Length,
-            (true, _) => {
+        let modifier = match (prefix, suffix) {
+            (Some(prefix), Modifier::None) => prefix,
+            (Some(_), _) => {
                 let cause = SyntaxError::MultipleModifier.into();
                 let location = suffix_location;
                 return Err(Error { cause, location });
             }
-            (false, suffix) => suffix,
+            (None, suffix) => suffix,
         };
 
         Ok(Some(BracedParam {
             param,
+            subscript,
             modifier,
             location: self.location_range(start_index..self.index()),
         }))
@@ -357,6 +490,21 @@ mod tests {
         assert_eq!(e.location.range, 2..5);
     }
 
+    #[test]
+    fn lexer_braced_param_name_starting_with_digit() {
+        let mut lexer = Lexer::with_code("${1abc}");
+        let mut lexer = WordLexer {
+            lexer: &mut lexer,
+            context: WordContext::Word,
+        };
+        lexer.peek_char().now_or_never().unwrap().unwrap();
+        lexer.consume_char();
+
+        let e = lexer.braced_param(0).now_or_never().unwrap().unwrap_err();
+        assert_eq!(e.cause, ErrorCause::Syntax(SyntaxError::InvalidParam));
+        assert_eq!(e.location.range, 2..6);
+    }
+
     /// Tests that the parameter expansion `${0}` is parsed as a special
     /// parameter `0`. Compare [`lexer_braced_param_positional_zero`].
     #[test]
@@ -466,6 +614,54 @@ mod tests {
         assert_eq!(e.location.range, 3..4);
     }
 
+    #[test]
+    fn lexer_braced_param_missing_name_at_end_of_input() {
+        let mut lexer = Lexer::with_code("${");
+        let mut lexer = WordLexer {
+            lexer: &mut lexer,
+            context: WordContext::Word,
+        };
+        lexer.peek_char().now_or_never().unwrap().unwrap();
+        lexer.consume_char();
+
+        let e = lexer.braced_param(0).now_or_never().unwrap().unwrap_err();
+        assert_eq!(e.cause, ErrorCause::Syntax(SyntaxError::EmptyParam));
+        assert_eq!(e.location.range, 2..2);
+    }
+
+    #[test]
+    fn lexer_braced_param_length_prefix_at_end_of_input() {
+        let mut lexer = Lexer::with_code("${#");
+        let mut lexer = WordLexer {
+            lexer: &mut lexer,
+            context: WordContext::Word,
+        };
+        lexer.peek_char().now_or_never().unwrap().unwrap();
+        lexer.consume_char();
+
+        let e = lexer.braced_param(0).now_or_never().unwrap().unwrap_err();
+        assert_eq!(e.cause, ErrorCause::Syntax(SyntaxError::EmptyParam));
+        assert_eq!(e.location.range, 3..3);
+    }
+
+    #[test]
+    fn lexer_braced_param_unclosed_at_end_of_input() {
+        let mut lexer = Lexer::with_code("${foo");
+        let mut lexer = WordLexer {
+            lexer: &mut lexer,
+            context: WordContext::Word,
+        };
+        lexer.peek_char().now_or_never().unwrap().unwrap();
+        lexer.consume_char();
+
+        let e = lexer.braced_param(0).now_or_never().unwrap().unwrap_err();
+        assert_matches!(e.cause,
+            ErrorCause::Syntax(SyntaxError::UnclosedParam { opening_location }) => {
+            assert_eq!(opening_location.range, 0..2);
+        });
+        assert_eq!(e.location.range, 5..5);
+    }
+
     #[test]
     fn lexer_braced_param_length_alphanumeric_name() {
         let mut lexer = Lexer::with_code("${#foo_123}<");
@@ -817,6 +1013,280 @@ mod tests {
         assert_eq!(e.location.range, 4..5);
     }
 
+    #[test]
+    fn lexer_braced_param_indirect() {
+        let mut lexer = Lexer::with_code("${!foo}<");
+        let mut lexer = WordLexer {
+            lexer: &mut lexer,
+            context: WordContext::Word,
+        };
+        lexer.peek_char().now_or_never().unwrap().unwrap();
+        lexer.consume_char();
+
+        let result = lexer.braced_param(0).now_or_never().unwrap();
+        let param = result.unwrap().unwrap();
+        assert_eq!(param.param, Param::variable("foo"));
+        assert_eq!(param.modifier, Modifier::Indirect);
+        assert_eq!(param.location.range, 0..7);
+
+        assert_eq!(lexer.peek_char().now_or_never().unwrap(), Ok(Some('<')));
+    }
+
+    #[test]
+    fn lexer_braced_param_exclamation_without_indirection() {
+        let mut lexer = Lexer::with_code("${!}<");
+        let mut lexer = WordLexer {
+            lexer: &mut lexer,
+            context: WordContext::Word,
+        };
+        lexer.peek_char().now_or_never().unwrap().unwrap();
+        lexer.consume_char();
+
+        let result = lexer.braced_param(0).now_or_never().unwrap();
+        let param = result.unwrap().unwrap();
+        assert_eq!(param.param, Param::from(SpecialParam::Exclamation));
+        assert_eq!(param.modifier, Modifier::None);
+
+        let mut lexer = Lexer::with_code("${!-x}<");
+        let mut lexer = WordLexer {
+            lexer: &mut lexer,
+            context: WordContext::Word,
+        };
+        lexer.peek_char().now_or_never().unwrap().unwrap();
+        lexer.consume_char();
+
+        let result = lexer.braced_param(0).now_or_never().unwrap();
+        let param = result.unwrap().unwrap();
+        assert_eq!(param.param, Param::from(SpecialParam::Exclamation));
+        assert_matches!(param.modifier, Modifier::Switch(_));
+    }
+
+    #[test]
+    fn lexer_braced_param_indirect_with_suffix() {
+        let mut lexer = Lexer::with_code("${!x-};");
+        let mut lexer = WordLexer {
+            lexer: &mut lexer,
+            context: WordContext::Word,
+        };
+        lexer.peek_char().now_or_never().unwrap().unwrap();
+        lexer.consume_char();
+
+        let e = lexer.braced_param(0).now_or_never().unwrap().unwrap_err();
+        assert_eq!(e.cause, ErrorCause::Syntax(SyntaxError::MultipleModifier));
+        assert_eq!(e.location.range, 4..5);
+    }
+
+    #[test]
+    fn lexer_braced_param_variable_names() {
+        for (code, suffix) in [
+            ("${!foo*}<", ListSuffix::Asterisk),
+            ("${!foo@}<", ListSuffix::At),
+        ] {
+            let mut lexer = Lexer::with_code(code);
+            let mut lexer = WordLexer {
+                lexer: &mut lexer,
+                context: WordContext::Word,
+            };
+            lexer.peek_char().now_or_never().unwrap().unwrap();
+            lexer.consume_char();
+
+            let result = lexer.braced_param(0).now_or_never().unwrap();
+            let param = result.unwrap().unwrap();
+            assert_eq!(param.param, Param::variable("foo"), "code={code:?}");
+            assert_eq!(
+                param.modifier,
+                Modifier::VariableNames(suffix),
+                "code={code:?}"
+            );
+            assert_eq!(param.location.range, 0..8, "code={code:?}");
+
+            assert_eq!(lexer.peek_char().now_or_never().unwrap(), Ok(Some('<')));
+        }
+    }
+
+    #[test]
+    fn lexer_braced_param_variable_names_not_followed_by_brace() {
+        let mut lexer = Lexer::with_code("${!foo@x}");
+        let mut lexer = WordLexer {
+            lexer: &mut lexer,
+            context: WordContext::Word,
+        };
+        lexer.peek_char().now_or_never().unwrap().unwrap();
+        lexer.consume_char();
+
+        let e = lexer.braced_param(0).now_or_never().unwrap().unwrap_err();
+        assert_matches!(
+            e.cause,
+            ErrorCause::Syntax(SyntaxError::UnclosedParam { .. })
+        );
+        assert_eq!(e.location.range, 6..7);
+    }
+
+    #[test]
+    fn lexer_braced_param_indices() {
+        for (code, suffix) in [
+            ("${!foo[*]}<", ListSuffix::Asterisk),
+            ("${!foo[@]}<", ListSuffix::At),
+        ] {
+            let mut lexer = Lexer::with_code(code);
+            let mut lexer = WordLexer {
+                lexer: &mut lexer,
+                context: WordContext::Word,
+            };
+            lexer.peek_char().now_or_never().unwrap().unwrap();
+            lexer.consume_char();
+
+            let result = lexer.braced_param(0).now_or_never().unwrap();
+            let param = result.unwrap().unwrap();
+            assert_eq!(param.param, Param::variable("foo"), "code={code:?}");
+            assert_eq!(param.modifier, Modifier::Indices(suffix), "code={code:?}");
+            assert_eq!(param.location.range, 0..10, "code={code:?}");
+
+            assert_eq!(lexer.peek_char().now_or_never().unwrap(), Ok(Some('<')));
+        }
+    }
+
+    #[test]
+    fn lexer_braced_param_indices_not_followed_by_brace() {
+        let mut lexer = Lexer::with_code("${!foo[@]x}");
+        let mut lexer = WordLexer {
+            lexer: &mut lexer,
+            context: WordContext::Word,
+        };
+        lexer.peek_char().now_or_never().unwrap().unwrap();
+        lexer.consume_char();
+
+        // The `[@]` is parsed as a subscript, so the `x` is unexpected.
+        let e = lexer.braced_param(0).now_or_never().unwrap().unwrap_err();
+        assert_matches!(
+            e.cause,
+            ErrorCause::Syntax(SyntaxError::UnclosedParam { .. })
+        );
+        assert_eq!(e.location.range, 9..10);
+    }
+
+    #[test]
+    fn lexer_braced_param_subscript_all() {
+        for (code, suffix) in [
+            ("${foo[*]}<", ListSuffix::Asterisk),
+            ("${foo[@]}<", ListSuffix::At),
+        ] {
+            let mut lexer = Lexer::with_code(code);
+            let mut lexer = WordLexer {
+                lexer: &mut lexer,
+                context: WordContext::Word,
+            };
+            lexer.peek_char().now_or_never().unwrap().unwrap();
+            lexer.consume_char();
+
+            let result = lexer.braced_param(0).now_or_never().unwrap();
+            let param = result.unwrap().unwrap();
+            assert_eq!(param.param, Param::variable("foo"), "code={code:?}");
+            assert_eq!(
+                param.subscript,
+                Some(Subscript::All(suffix)),
+                "code={code:?}"
+            );
+            assert_eq!(param.modifier, Modifier::None, "code={code:?}");
+            assert_eq!(param.location.range, 0..9, "code={code:?}");
+
+            assert_eq!(lexer.peek_char().now_or_never().unwrap(), Ok(Some('<')));
+        }
+    }
+
+    #[test]
+    fn lexer_braced_param_subscript_expression() {
+        let mut lexer = Lexer::with_code("${foo[$i-1]}<");
+        let mut lexer = WordLexer {
+            lexer: &mut lexer,
+            context: WordContext::Word,
+        };
+        lexer.peek_char().now_or_never().unwrap().unwrap();
+        lexer.consume_char();
+
+        let result = lexer.braced_param(0).now_or_never().unwrap();
+        let param = result.unwrap().unwrap();
+        assert_eq!(param.param, Param::variable("foo"));
+        assert_matches!(param.subscript, Some(Subscript::Expression(text)) => {
+            assert_eq!(text.to_string(), "$i-1");
+        });
+        assert_eq!(param.modifier, Modifier::None);
+        assert_eq!(param.location.range, 0..12);
+
+        assert_eq!(lexer.peek_char().now_or_never().unwrap(), Ok(Some('<')));
+    }
+
+    #[test]
+    fn lexer_braced_param_subscript_with_modifiers() {
+        let mut lexer = Lexer::with_code("${#foo[1]}${foo[2]:-x}");
+        let mut lexer = WordLexer {
+            lexer: &mut lexer,
+            context: WordContext::Word,
+        };
+        lexer.peek_char().now_or_never().unwrap().unwrap();
+        lexer.consume_char();
+
+        let result = lexer.braced_param(0).now_or_never().unwrap();
+        let param = result.unwrap().unwrap();
+        assert_eq!(param.param, Param::variable("foo"));
+        assert_matches!(param.subscript, Some(Subscript::Expression(text)) => {
+            assert_eq!(text.to_string(), "1");
+        });
+        assert_eq!(param.modifier, Modifier::Length);
+
+        lexer.peek_char().now_or_never().unwrap().unwrap();
+        lexer.consume_char();
+        let result = lexer.braced_param(10).now_or_never().unwrap();
+        let param = result.unwrap().unwrap();
+        assert_eq!(param.param, Param::variable("foo"));
+        assert_matches!(param.subscript, Some(Subscript::Expression(text)) => {
+            assert_eq!(text.to_string(), "2");
+        });
+        assert_matches!(param.modifier, Modifier::Switch(switch) => {
+            assert_eq!(switch.action, SwitchAction::Default);
+            assert_eq!(switch.condition, SwitchCondition::UnsetOrEmpty);
+            assert_eq!(switch.word.to_string(), "x");
+        });
+    }
+
+    #[test]
+    fn lexer_braced_param_unclosed_subscript() {
+        let mut lexer = Lexer::with_code("${foo[1");
+        let mut lexer = WordLexer {
+            lexer: &mut lexer,
+            context: WordContext::Word,
+        };
+        lexer.peek_char().now_or_never().unwrap().unwrap();
+        lexer.consume_char();
+
+        let e = lexer.braced_param(0).now_or_never().unwrap().unwrap_err();
+        assert_matches!(e.cause, ErrorCause::Syntax(SyntaxError::UnclosedParam { opening_location }) => {
+            assert_eq!(opening_location.range, 0..2);
+        });
+        assert_eq!(e.location.range, 7..7);
+    }
+
+    #[test]
+    fn lexer_braced_param_indirect_in_portable_mode() {
+        let mut lexer = Lexer::with_code("${!foo}");
+        let mut mode = yash_env::parser::Mode::default();
+        mode.portable = true;
+        lexer.set_mode(mode);
+        let mut lexer = WordLexer {
+            lexer: &mut lexer,
+            context: WordContext::Word,
+        };
+        lexer.peek_char().now_or_never().unwrap().unwrap();
+        lexer.consume_char();
+
+        let e = lexer.braced_param(0).now_or_never().unwrap().unwrap_err();
+        assert_eq!(
+            e.cause,
+            ErrorCause::Syntax(SyntaxError::NonPortableParamPrefix)
+        );
+        assert_eq!(e.location.range, 2..3);
+    }
+
     #[test]
     fn lexer_braced_param_line_continuations() {
         let mut lexer = Lexer::with_code("${\\\n#\\\n\\\na_\\\n1\\\n\\\n}z");
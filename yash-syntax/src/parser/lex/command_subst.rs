@@ -123,4 +123,50 @@ mod tests {
         assert_eq!(*e.location.code.source, Source::Unknown);
         assert_eq!(e.location.range, 14..14);
     }
+
+    fn command_substitution_content(code: &str) -> (String, usize) {
+        let mut lexer = Lexer::with_code(code);
+        lexer.peek_char().now_or_never().unwrap().unwrap();
+        lexer.consume_char();
+
+        let result = lexer.command_substitution(0).now_or_never().unwrap();
+        let text_unit = result.unwrap().unwrap();
+        let content = assert_matches!(text_unit, TextUnit::CommandSubst { content, .. } => content);
+        (content.to_string(), lexer.index())
+    }
+
+    #[test]
+    fn lexer_command_substitution_with_quoted_parentheses() {
+        let (content, index) = command_substitution_content("$(echo ')' \")(\")X");
+        assert_eq!(content, "echo ')' \")(\"");
+        assert_eq!(index, 16);
+    }
+
+    #[test]
+    fn lexer_command_substitution_with_escaped_parenthesis() {
+        let (content, index) = command_substitution_content(r"$(echo \) \()X");
+        assert_eq!(content, r"echo \) \(");
+        assert_eq!(index, 13);
+    }
+
+    #[test]
+    fn lexer_command_substitution_nested() {
+        let (content, index) = command_substitution_content("$(echo $(echo ')') \"$(echo ')')\")X");
+        assert_eq!(content, "echo $(echo ')') \"$(echo ')')\"");
+        assert_eq!(index, 33);
+    }
+
+    #[test]
+    fn lexer_command_substitution_with_case_pattern_parenthesis() {
+        let (content, index) = command_substitution_content("$(case x in x) :;; esac)X");
+        assert_eq!(content, "case x in x) :;; esac");
+        assert_eq!(index, 24);
+    }
+
+    #[test]
+    fn lexer_command_substitution_with_parenthesis_in_comment() {
+        let (content, index) = command_substitution_content("$(echo # )\n)X");
+        assert_eq!(content, "echo # )\n");
+        assert_eq!(index, 12);
+    }
 }
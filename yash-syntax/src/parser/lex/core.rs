@@ -108,6 +108,46 @@ impl TokenId {
             EndOfInput => true,
         }
     }
+
+    /// Determines if this token can be a reserved word.
+    ///
+    /// This function returns true if the token ID is `Token(Some(_))`. Note
+    /// that it depends on context whether such a token is actually regarded as
+    /// a reserved word.
+    #[must_use]
+    pub const fn is_keyword(self) -> bool {
+        matches!(self, TokenId::Token(Some(_)))
+    }
+
+    /// Determines if this token is an operator.
+    #[must_use]
+    pub const fn is_operator(self) -> bool {
+        matches!(self, TokenId::Operator(_))
+    }
+
+    /// Determines if this token is a control operator.
+    ///
+    /// This function delegates to [`Operator::is_control_operator`] if the
+    /// token ID is an operator. Otherwise, the result is false.
+    #[must_use]
+    pub const fn is_control_operator(self) -> bool {
+        match self {
+            TokenId::Operator(operator) => operator.is_control_operator(),
+            _ => false,
+        }
+    }
+
+    /// Determines if this token is a redirection operator.
+    ///
+    /// This function delegates to [`Operator::is_redirection_operator`] if the
+    /// token ID is an operator. Otherwise, the result is false.
+    #[must_use]
+    pub const fn is_redirection_operator(self) -> bool {
+        match self {
+            TokenId::Operator(operator) => operator.is_redirection_operator(),
+            _ => false,
+        }
+    }
 }
 
 /// Result of lexical analysis produced by the [`Lexer`]
@@ -1013,6 +1053,53 @@ mod tests {
     use assert_matches::assert_matches;
     use futures_util::FutureExt as _;
 
+    #[test]
+    fn token_id_classification() {
+        use Operator::{Bar, GreaterGreater, LessLess, Semicolon};
+
+        let keyword = TokenId::Token(Some(Keyword::If));
+        assert!(keyword.is_keyword());
+        assert!(!keyword.is_operator());
+        assert!(!keyword.is_control_operator());
+        assert!(!keyword.is_redirection_operator());
+
+        let word = TokenId::Token(None);
+        assert!(!word.is_keyword());
+        assert!(!word.is_operator());
+
+        for op in [Bar, Semicolon] {
+            let id = TokenId::Operator(op);
+            assert!(!id.is_keyword(), "{op:?}");
+            assert!(id.is_operator(), "{op:?}");
+            assert!(id.is_control_operator(), "{op:?}");
+            assert!(!id.is_redirection_operator(), "{op:?}");
+        }
+        for op in [GreaterGreater, LessLess] {
+            let id = TokenId::Operator(op);
+            assert!(id.is_operator(), "{op:?}");
+            assert!(!id.is_control_operator(), "{op:?}");
+            assert!(id.is_redirection_operator(), "{op:?}");
+        }
+
+        for id in [TokenId::IoNumber, TokenId::IoLocation, TokenId::EndOfInput] {
+            assert!(!id.is_keyword(), "{id:?}");
+            assert!(!id.is_operator(), "{id:?}");
+            assert!(!id.is_control_operator(), "{id:?}");
+            assert!(!id.is_redirection_operator(), "{id:?}");
+        }
+    }
+
+    #[test]
+    fn keyword_classification() {
+        assert!(Keyword::While.is_compound_command_start());
+        assert!(Keyword::OpenBrace.is_compound_command_start());
+        assert!(!Keyword::While.is_clause_delimiter());
+        assert!(Keyword::Done.is_clause_delimiter());
+        assert!(!Keyword::Done.is_compound_command_start());
+        assert!(!Keyword::Bang.is_compound_command_start());
+        assert!(!Keyword::Bang.is_clause_delimiter());
+    }
+
     #[test]
     fn lexer_mode_defaults_to_permissive() {
         let lexer = Lexer::with_code("");
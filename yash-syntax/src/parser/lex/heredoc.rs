@@ -17,6 +17,8 @@
 //! Here-document content parser
 
 use super::Lexer;
+use crate::input::Context;
+use crate::input::Input;
 use crate::parser::core::Result;
 use crate::parser::error::Error;
 use crate::parser::error::SyntaxError;
@@ -83,8 +85,11 @@ impl Lexer<'_> {
             };
 
             if !self.skip_if(|c| c == NEWLINE).await? {
-                let redir_op_location = here_doc.delimiter.location.clone();
-                let cause = SyntaxError::UnclosedHereDocContent { redir_op_location }.into();
+                let cause = SyntaxError::UnterminatedHereDoc {
+                    delimiter: delimiter_string,
+                    opening_location: here_doc.delimiter.location.clone(),
+                }
+                .into();
                 let location = self.location().await?.clone();
                 return Err(Error { cause, location });
             }
@@ -110,6 +115,80 @@ impl Lexer<'_> {
     }
 }
 
+/// Input that reads lines from an iterator, remembering the consumed lines
+struct Lines<'a, I: Iterator> {
+    lines: I,
+    consumed: &'a mut Vec<I::Item>,
+}
+
+impl<I> Input for Lines<'_, I>
+where
+    I: Iterator,
+    I::Item: AsRef<str>,
+{
+    async fn next_line(&mut self, _context: &Context) -> crate::input::Result {
+        let Some(line) = self.lines.next() else {
+            return Ok(String::new());
+        };
+        let mut result = line.as_ref().to_owned();
+        if !result.ends_with(NEWLINE) {
+            result.push(NEWLINE);
+        }
+        self.consumed.push(line);
+        Ok(result)
+    }
+}
+
+/// Parses the content of a here-document from lines that have been read in
+/// advance.
+///
+/// This function is a variant of [`Lexer::here_doc_content`] for callers that
+/// read the input by themselves. The `lines` are the input lines following
+/// the line containing the here-document operator. Each line may or may not
+/// end with a newline. The lines are consumed up to and including the line
+/// that contains the delimiter. Whether the content is subject to expansions
+/// depends on whether the delimiter in `here_doc` is quoted. If successful,
+/// `here_doc.content` is filled and the consumed lines are returned.
+///
+/// The locations in the parsed content have [`Source::Unknown`] as the source
+/// and count line numbers from 1.
+///
+/// [`Source::Unknown`]: crate::source::Source::Unknown
+///
+/// ```
+/// # use std::cell::OnceCell;
+/// # use yash_syntax::parser::lex::here_doc_content_from_lines;
+/// # use yash_syntax::syntax::HereDoc;
+/// let here_doc = HereDoc {
+///     delimiter: "EOF".parse().unwrap(),
+///     remove_tabs: false,
+///     content: OnceCell::new(),
+/// };
+/// let lines = ["Hello, $USER", "EOF", "echo done"];
+/// let consumed = here_doc_content_from_lines(&here_doc, lines).unwrap();
+/// assert_eq!(consumed, ["Hello, $USER", "EOF"]);
+/// assert_eq!(here_doc.content.get().unwrap().to_string(), "Hello, $USER\n");
+/// ```
+pub fn here_doc_content_from_lines<I>(here_doc: &HereDoc, lines: I) -> Result<Vec<I::Item>>
+where
+    I: IntoIterator,
+    I::Item: AsRef<str>,
+{
+    use futures_util::future::FutureExt as _;
+    let mut consumed = Vec::new();
+    let input = Lines {
+        lines: lines.into_iter(),
+        consumed: &mut consumed,
+    };
+    let mut lexer = Lexer::new(Box::new(input));
+    lexer
+        .here_doc_content(here_doc)
+        .now_or_never()
+        .expect("the lexer should not block when reading from pre-read lines")?;
+    drop(lexer);
+    Ok(consumed)
+}
+
 #[allow(
     clippy::bool_assert_comparison,
     reason = "to make the expected values clearer"
@@ -320,15 +399,63 @@ END
             .unwrap()
             .unwrap_err();
         assert_matches!(e.cause,
-            ErrorCause::Syntax(SyntaxError::UnclosedHereDocContent { redir_op_location }) => {
-            assert_eq!(*redir_op_location.code.value.borrow(), "END");
-            assert_eq!(redir_op_location.code.start_line_number.get(), 1);
-            assert_eq!(*redir_op_location.code.source, Source::Unknown);
-            assert_eq!(redir_op_location.range, 0..3);
+            ErrorCause::Syntax(SyntaxError::UnterminatedHereDoc { delimiter, opening_location }) => {
+            assert_eq!(delimiter, "END");
+            assert_eq!(*opening_location.code.value.borrow(), "END");
+            assert_eq!(opening_location.code.start_line_number.get(), 1);
+            assert_eq!(*opening_location.code.source, Source::Unknown);
+            assert_eq!(opening_location.range, 0..3);
         });
         assert_eq!(*e.location.code.value.borrow(), "");
         assert_eq!(e.location.code.start_line_number.get(), 1);
         assert_eq!(*e.location.code.source, Source::Unknown);
         assert_eq!(e.location.range, 0..0);
     }
+
+    #[test]
+    fn here_doc_content_from_lines_with_expansions() {
+        let heredoc = here_doc_operator("END", false);
+        let lines = ["a$b", "\tc`d`\n", "END", "X"];
+
+        let consumed = here_doc_content_from_lines(&heredoc, lines).unwrap();
+        assert_eq!(consumed, ["a$b", "\tc`d`\n", "END"]);
+        let content = &heredoc.content.get().unwrap().0;
+        assert_eq!(content.len(), 7, "{content:?}");
+        assert_eq!(content[0], Literal('a'));
+        assert_matches!(&content[1], RawParam { param, .. } => {
+            assert_eq!(param.id, "b");
+        });
+        assert_eq!(content[2], Literal('\n'));
+        assert_eq!(content[3], Literal('\t'));
+        assert_eq!(content[4], Literal('c'));
+        assert_matches!(&content[5], Backquote { content, .. } => {
+            assert_eq!(content.len(), 1, "{content:?}");
+        });
+        assert_eq!(content[6], Literal('\n'));
+    }
+
+    #[test]
+    fn here_doc_content_from_lines_with_quoted_delimiter() {
+        let heredoc = here_doc_operator("'END'", true);
+        let lines = vec!["\ta$b".to_string(), "\tEND".to_string()];
+
+        let consumed = here_doc_content_from_lines(&heredoc, lines).unwrap();
+        assert_eq!(consumed, ["\ta$b", "\tEND"]);
+        assert_eq!(
+            heredoc.content.get().unwrap().0,
+            [Literal('a'), Literal('$'), Literal('b'), Literal('\n'),]
+        );
+    }
+
+    #[test]
+    fn here_doc_content_from_lines_missing_delimiter() {
+        let heredoc = here_doc_operator("END", false);
+        let lines = ["foo", "bar"];
+
+        let e = here_doc_content_from_lines(&heredoc, lines).unwrap_err();
+        assert_matches!(
+            e.cause,
+            ErrorCause::Syntax(SyntaxError::UnterminatedHereDoc { .. })
+        );
+    }
 }
@@ -99,6 +99,19 @@ impl Keyword {
             | OpenBrace => false,
         }
     }
+
+    /// Determines if this token can begin a compound command.
+    ///
+    /// This function returns `true` for `OpenBracketBracket`, `Case`, `For`,
+    /// `If`, `Until`, `While`, and `OpenBrace`, and `false` for others.
+    #[must_use]
+    pub const fn is_compound_command_start(self) -> bool {
+        use Keyword::*;
+        match self {
+            OpenBracketBracket | Case | For | If | Until | While | OpenBrace => true,
+            Bang | Do | Done | Elif | Else | Esac | Fi | Function | In | Then | CloseBrace => false,
+        }
+    }
 }
 
 impl fmt::Display for Keyword {
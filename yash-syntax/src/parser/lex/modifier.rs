@@ -269,6 +269,26 @@ mod tests {
         assert_eq!(lexer.peek_char().now_or_never().unwrap(), Ok(Some('}')));
     }
 
+    #[test]
+    fn lexer_suffix_modifier_colon_default_empty() {
+        let mut lexer = Lexer::with_code(":-}");
+        let mut lexer = WordLexer {
+            lexer: &mut lexer,
+            context: WordContext::Word,
+        };
+
+        let result = lexer.suffix_modifier().now_or_never().unwrap().unwrap();
+        assert_matches!(result, Modifier::Switch(switch) => {
+            assert_eq!(switch.action, SwitchAction::Default);
+            assert_eq!(switch.condition, SwitchCondition::UnsetOrEmpty);
+            assert_eq!(switch.word.units, []);
+            assert_eq!(*switch.word.location.code.value.borrow(), ":-}");
+            assert_eq!(switch.word.location.range, 2..2);
+        });
+
+        assert_eq!(lexer.peek_char().now_or_never().unwrap(), Ok(Some('}')));
+    }
+
     #[test]
     fn lexer_suffix_modifier_colon_default_word() {
         let mut lexer = Lexer::with_code(r":-cool}");
@@ -317,6 +337,26 @@ mod tests {
         assert_eq!(lexer.peek_char().now_or_never().unwrap(), Ok(Some('}')));
     }
 
+    #[test]
+    fn lexer_suffix_modifier_assign_empty() {
+        let mut lexer = Lexer::with_code("=}");
+        let mut lexer = WordLexer {
+            lexer: &mut lexer,
+            context: WordContext::Word,
+        };
+
+        let result = lexer.suffix_modifier().now_or_never().unwrap().unwrap();
+        assert_matches!(result, Modifier::Switch(switch) => {
+            assert_eq!(switch.action, SwitchAction::Assign);
+            assert_eq!(switch.condition, SwitchCondition::Unset);
+            assert_eq!(switch.word.units, []);
+            assert_eq!(*switch.word.location.code.value.borrow(), "=}");
+            assert_eq!(switch.word.location.range, 1..1);
+        });
+
+        assert_eq!(lexer.peek_char().now_or_never().unwrap(), Ok(Some('}')));
+    }
+
     #[test]
     fn lexer_suffix_modifier_assign_word() {
         let mut lexer = Lexer::with_code(r"=Yes}");
@@ -364,6 +404,26 @@ mod tests {
         assert_eq!(lexer.peek_char().now_or_never().unwrap(), Ok(Some('}')));
     }
 
+    #[test]
+    fn lexer_suffix_modifier_colon_error_empty() {
+        let mut lexer = Lexer::with_code(":?}");
+        let mut lexer = WordLexer {
+            lexer: &mut lexer,
+            context: WordContext::Word,
+        };
+
+        let result = lexer.suffix_modifier().now_or_never().unwrap().unwrap();
+        assert_matches!(result, Modifier::Switch(switch) => {
+            assert_eq!(switch.action, SwitchAction::Error);
+            assert_eq!(switch.condition, SwitchCondition::UnsetOrEmpty);
+            assert_eq!(switch.word.units, []);
+            assert_eq!(*switch.word.location.code.value.borrow(), ":?}");
+            assert_eq!(switch.word.location.range, 2..2);
+        });
+
+        assert_eq!(lexer.peek_char().now_or_never().unwrap(), Ok(Some('}')));
+    }
+
     #[test]
     fn lexer_suffix_modifier_colon_error_word() {
         let mut lexer = Lexer::with_code(r":?No}");
@@ -142,6 +142,47 @@ impl Operator {
             }
         }
     }
+
+    /// Determines if this token is a control operator.
+    ///
+    /// This function returns `true` for `Newline`, `And` (`&`), `AndAnd`
+    /// (`&&`), `OpenParen` (`(`), `CloseParen` (`)`), `Semicolon` (`;`),
+    /// `SemicolonAnd` (`;&`), `SemicolonSemicolon` (`;;`),
+    /// `SemicolonSemicolonAnd` (`;;&`), `SemicolonBar` (`;|`), `Bar` (`|`),
+    /// and `BarBar` (`||`). The other operators are
+    /// [redirection operators](Self::is_redirection_operator).
+    #[must_use]
+    pub const fn is_control_operator(self) -> bool {
+        !self.is_redirection_operator()
+    }
+
+    /// Determines if this token is a redirection operator.
+    ///
+    /// This function returns `true` for operators that begin with `<` or `>`,
+    /// including the here-document operators (`<<` and `<<-`) and the process
+    /// redirection operators (`<(` and `>(`).
+    #[must_use]
+    pub const fn is_redirection_operator(self) -> bool {
+        use Operator::*;
+        match self {
+            Less | LessAnd | LessOpenParen | LessLess | LessLessDash | LessLessLess
+            | LessGreater | Greater | GreaterAnd | GreaterOpenParen | GreaterGreater
+            | GreaterGreaterBar | GreaterBar => true,
+
+            Newline
+            | And
+            | AndAnd
+            | OpenParen
+            | CloseParen
+            | Semicolon
+            | SemicolonAnd
+            | SemicolonSemicolon
+            | SemicolonSemicolonAnd
+            | SemicolonBar
+            | Bar
+            | BarBar => false,
+        }
+    }
 }
 
 impl fmt::Display for Operator {
@@ -463,6 +504,25 @@ mod tests {
         ensure_sorted(&OPERATORS);
     }
 
+    #[test]
+    fn redirection_operators_begin_with_angle_brackets() {
+        fn all_operators(trie: &Trie, operators: &mut Vec<Operator>) {
+            for edge in trie.0 {
+                operators.extend(edge.value);
+                all_operators(&edge.next, operators);
+            }
+        }
+        let mut operators = Vec::new();
+        all_operators(&OPERATORS, &mut operators);
+        assert!(!operators.is_empty());
+
+        for op in operators {
+            let is_redir = op.as_str().starts_with(['<', '>']);
+            assert_eq!(op.is_redirection_operator(), is_redir, "{op:?}");
+            assert_eq!(op.is_control_operator(), !is_redir, "{op:?}");
+        }
+    }
+
     #[test]
     fn lexer_operator_longest_match() {
         let mut lexer = Lexer::with_code("<<-");
@@ -220,7 +220,10 @@ mod tests {
     use crate::source::Source;
     use crate::syntax::Backquote;
     use crate::syntax::BackquoteUnit;
+    use crate::syntax::BracedParam;
     use crate::syntax::CommandSubst;
+    use crate::syntax::MaybeLiteral as _;
+    use crate::syntax::Modifier;
     use assert_matches::assert_matches;
     use futures_util::FutureExt as _;
 
@@ -487,6 +490,22 @@ mod tests {
         assert_eq!(lexer.peek_char().now_or_never().unwrap(), Ok(None));
     }
 
+    #[test]
+    fn lexer_text_braced_param_between_literals() {
+        let mut lexer = Lexer::with_code("a${foo}b");
+        let text = lexer
+            .text(|_| false, |_| false)
+            .now_or_never()
+            .unwrap()
+            .unwrap();
+        assert_matches!(&text.0[..], [Literal('a'), BracedParam(param), Literal('b')] => {
+            assert_eq!(param.param.id, "foo");
+            assert_eq!(param.modifier, Modifier::None);
+        });
+        assert_eq!(text.to_string(), "a${foo}b");
+        assert_eq!(text.to_string_if_literal(), None);
+    }
+
     #[test]
     fn lexer_text_delimiter() {
         let mut lexer = Lexer::with_code("abc");
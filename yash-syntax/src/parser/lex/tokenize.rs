@@ -0,0 +1,548 @@
+// This file is part of yash, an extended POSIX shell.
+// Copyright (C) 2026 WATANABE Yuki
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+//! Tokenization without parsing
+
+use super::core::Lexer;
+use super::core::TokenId;
+use super::op::Operator::{LessLess, LessLessDash, Newline};
+use crate::parser::core::Result;
+use crate::source::Location;
+use crate::syntax::HereDoc;
+use std::cell::OnceCell;
+use std::ops::Range;
+use std::rc::Rc;
+
+/// Kind of an item returned by [`tokenize_with_comments`]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum TokenKind {
+    /// Token identified by the lexer
+    Token(TokenId),
+    /// Comment
+    ///
+    /// The location of a comment covers the `#` and the rest of the line,
+    /// excluding the terminating newline.
+    Comment,
+}
+
+/// State of tokenization that is carried over from one token to the next
+#[derive(Debug, Default)]
+struct Tokenizer {
+    /// Here-documents whose content is read after the next newline
+    here_docs: Vec<HereDoc>,
+    /// Whether the last token was `<<` (`Some(false)`) or `<<-` (`Some(true)`)
+    remove_tabs: Option<bool>,
+}
+
+impl Tokenizer {
+    /// Reads the next token or comment.
+    ///
+    /// Returns `None` at the end of input. If the token is a newline, the
+    /// contents of pending here-documents are consumed before returning.
+    async fn next(&mut self, lexer: &mut Lexer<'_>) -> Result<Option<(TokenKind, Location)>> {
+        lexer.skip_blanks().await?;
+        let comment_start = lexer.index();
+        lexer.skip_comment().await?;
+        if lexer.index() > comment_start {
+            let location = lexer.location_range(comment_start..lexer.index());
+            return Ok(Some((TokenKind::Comment, location)));
+        }
+
+        let token = lexer.token().await?;
+
+        match token.id {
+            TokenId::EndOfInput => return Ok(None),
+            TokenId::Operator(LessLess) => self.remove_tabs = Some(false),
+            TokenId::Operator(LessLessDash) => self.remove_tabs = Some(true),
+            TokenId::Operator(Newline) => self.remove_tabs = None,
+            TokenId::Token(_) | TokenId::IoNumber | TokenId::IoLocation => {
+                if let Some(remove_tabs) = self.remove_tabs.take() {
+                    self.here_docs.push(HereDoc {
+                        delimiter: token.word.clone(),
+                        remove_tabs,
+                        content: OnceCell::new(),
+                    });
+                }
+            }
+            TokenId::Operator(_) => self.remove_tabs = None,
+        }
+
+        if token.id == TokenId::Operator(Newline) {
+            for here_doc in std::mem::take(&mut self.here_docs) {
+                lexer.here_doc_content(&here_doc).await?;
+            }
+        }
+
+        Ok(Some((TokenKind::Token(token.id), token.word.location)))
+    }
+
+    /// Reads the next token, skipping comments.
+    async fn next_token(&mut self, lexer: &mut Lexer<'_>) -> Result<Option<(TokenId, Location)>> {
+        loop {
+            match self.next(lexer).await? {
+                None => return Ok(None),
+                Some((TokenKind::Token(id), location)) => return Ok(Some((id, location))),
+                Some((TokenKind::Comment, _)) => (),
+            }
+        }
+    }
+}
+
+impl Lexer<'_> {
+    /// Reads all the remaining tokens.
+    ///
+    /// This function repeatedly calls [`token`](Self::token) until the end of
+    /// input, returning the identifier and location of each token. Blanks and
+    /// comments between tokens are skipped. The result does not include the
+    /// final [`EndOfInput`](TokenId::EndOfInput).
+    ///
+    /// A word that follows a `<<` or `<<-` operator is regarded as the
+    /// delimiter of a here-document. The content of the here-document is
+    /// consumed after the next newline token and is not included in the
+    /// result.
+    ///
+    /// Since the tokens are not parsed, a token is not identified as a reserved
+    /// word or an ordinary word depending on the context. See [`TokenId`] for
+    /// details.
+    pub async fn tokenize(&mut self) -> Result<Vec<(TokenId, Location)>> {
+        let mut tokenizer = Tokenizer::default();
+        let mut tokens = Vec::new();
+        while let Some(token) = tokenizer.next_token(self).await? {
+            tokens.push(token);
+        }
+        Ok(tokens)
+    }
+
+    /// Reads all the remaining tokens, including comments.
+    ///
+    /// This function is the same as [`tokenize`](Self::tokenize) except that
+    /// comments are also returned as [`TokenKind::Comment`] items among the
+    /// tokens, which are wrapped in [`TokenKind::Token`].
+    pub async fn tokenize_with_comments(&mut self) -> Result<Vec<(TokenKind, Location)>> {
+        let mut tokenizer = Tokenizer::default();
+        let mut items = Vec::new();
+        while let Some(item) = tokenizer.next(self).await? {
+            items.push(item);
+        }
+        Ok(items)
+    }
+}
+
+/// Tokenizes the given source code.
+///
+/// This is a convenience function that creates a [`Lexer`] for the code and
+/// calls [`Lexer::tokenize`].
+///
+/// ```
+/// # use yash_syntax::parser::lex::{tokenize, Operator, TokenId};
+/// let tokens = tokenize("echo ok; exit").unwrap();
+/// let ids = tokens.iter().map(|(id, _)| *id).collect::<Vec<_>>();
+/// assert_eq!(
+///     ids,
+///     [
+///         TokenId::Token(None),
+///         TokenId::Token(None),
+///         TokenId::Operator(Operator::Semicolon),
+///         TokenId::Token(None),
+///     ]
+/// );
+/// assert_eq!(tokens[1].1.range, 5..7);
+/// ```
+pub fn tokenize(code: &str) -> Result<Vec<(TokenId, Location)>> {
+    use futures_util::future::FutureExt as _;
+    let mut lexer = Lexer::with_code(code);
+    lexer
+        .tokenize()
+        .now_or_never()
+        .expect("the lexer should not block when reading from a string")
+}
+
+/// Tokenizes the given source code, including comments.
+///
+/// This is a convenience function that creates a [`Lexer`] for the code and
+/// calls [`Lexer::tokenize_with_comments`].
+///
+/// ```
+/// # use yash_syntax::parser::lex::{tokenize_with_comments, TokenId, TokenKind};
+/// let items = tokenize_with_comments("echo # greet\n").unwrap();
+/// assert_eq!(items[0].0, TokenKind::Token(TokenId::Token(None)));
+/// assert_eq!(items[1].0, TokenKind::Comment);
+/// assert_eq!(items[1].1.range, 5..12);
+/// ```
+pub fn tokenize_with_comments(code: &str) -> Result<Vec<(TokenKind, Location)>> {
+    use futures_util::future::FutureExt as _;
+    let mut lexer = Lexer::with_code(code);
+    lexer
+        .tokenize_with_comments()
+        .now_or_never()
+        .expect("the lexer should not block when reading from a string")
+}
+
+/// Result of [`retokenize`]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Retokenized {
+    /// Source code after the edit
+    pub code: String,
+    /// Tokens of the whole edited code
+    ///
+    /// The locations of all the tokens refer to the edited code.
+    pub tokens: Vec<(TokenId, Location)>,
+    /// Range of the indices of `tokens` that have been re-lexed
+    ///
+    /// The other tokens have been reused from the previous token stream with
+    /// their locations adjusted.
+    pub relexed: Range<usize>,
+}
+
+/// Returns the positions where tokenization can be restarted.
+///
+/// Each item is a pair of a character index of the code and the number of
+/// tokens that precede the index. An index is a restart point if it is right
+/// after a newline token and there is no here-document content that starts
+/// after the newline. Restarting at such an index does not depend on any
+/// quotation or here-document before it.
+fn restart_points(tokens: &[(TokenId, Location)]) -> Vec<(usize, usize)> {
+    let mut points = vec![(0, 0)];
+    let mut has_here_doc = false;
+    for (i, (id, location)) in tokens.iter().enumerate() {
+        match id {
+            TokenId::Operator(LessLess | LessLessDash) => has_here_doc = true,
+            TokenId::Operator(Newline) => {
+                if !has_here_doc {
+                    points.push((location.range.end, i + 1));
+                }
+                has_here_doc = false;
+            }
+            _ => (),
+        }
+    }
+    points
+}
+
+/// Tokenizes the source code again after an edit.
+///
+/// `code` and `tokens` are the source code before the edit and its tokens,
+/// which must have been obtained by [`tokenize`]. The edit replaces the
+/// characters in `edit` (a range of character indices of `code`) with
+/// `replacement`.
+///
+/// Comments are not supported: like `tokenize`, this function skips comments
+/// and the result does not contain them. To obtain the comments of the edited
+/// code, call [`tokenize_with_comments`] on the [`code`](Retokenized::code)
+/// of the result.
+///
+/// This function re-lexes only the region affected by the edit and reuses the
+/// other tokens. Lexing restarts at the beginning of the line containing the
+/// start of the edit, or an earlier line if the line is inside a
+/// here-document or a token (such as a quotation) spanning multiple lines.
+/// Lexing stops at the first line boundary after the edit where the lexer is
+/// in the same state as it was before the edit, and the rest of the previous
+/// tokens are spliced in.
+///
+/// ```
+/// # use yash_syntax::parser::lex::{retokenize, tokenize};
+/// let code = "echo a\necho b\necho c\n";
+/// let tokens = tokenize(code).unwrap();
+/// let result = retokenize(code, &tokens, 12..13, "b | wc").unwrap();
+/// assert_eq!(result.code, "echo a\necho b | wc\necho c\n");
+/// assert_eq!(result.tokens, tokenize(&result.code).unwrap());
+/// // Only the tokens of the second line have been re-lexed.
+/// assert_eq!(result.relexed, 3..8);
+/// ```
+///
+/// # Panics
+///
+/// If `edit` is out of the bounds of `code`.
+pub fn retokenize(
+    code: &str,
+    tokens: &[(TokenId, Location)],
+    edit: Range<usize>,
+    replacement: &str,
+) -> Result<Retokenized> {
+    use futures_util::future::FutureExt as _;
+
+    let byte_index = |index| {
+        code.char_indices()
+            .map(|(i, _)| i)
+            .chain(std::iter::once(code.len()))
+            .nth(index)
+            .expect("the edit range should be within the code")
+    };
+    let mut new_code = code[..byte_index(edit.start)].to_owned();
+    new_code.push_str(replacement);
+    new_code.push_str(&code[byte_index(edit.end)..]);
+
+    let old_len = edit.len();
+    let new_len = replacement.chars().count();
+    let new_index = |old_index: usize| old_index - old_len + new_len;
+
+    let points = restart_points(tokens);
+    let (restart, kept) = points
+        .iter()
+        .copied()
+        .rfind(|&(index, _)| index <= edit.start)
+        .unwrap_or_default();
+
+    let mut lexer = Lexer::with_code(&new_code);
+    async {
+        let new_code_rc = Rc::clone(&lexer.location().await?.code);
+        let relocate = |id, range| {
+            let code = Rc::clone(&new_code_rc);
+            (id, Location { code, range })
+        };
+        let mut new_tokens = tokens[..kept]
+            .iter()
+            .map(|(id, location)| relocate(*id, location.range.clone()))
+            .collect::<Vec<_>>();
+
+        while lexer.index() < restart {
+            lexer.peek_char().await?;
+            lexer.consume_char();
+        }
+
+        let mut tokenizer = Tokenizer::default();
+        while let Some(token) = tokenizer.next_token(&mut lexer).await? {
+            let is_newline = token.0 == TokenId::Operator(Newline);
+            new_tokens.push(token);
+            if !is_newline || lexer.index() < new_index(edit.end) {
+                continue;
+            }
+
+            let old_index = lexer.index() + old_len - new_len;
+            if let Ok(i) = points.binary_search_by_key(&old_index, |&(index, _)| index) {
+                // The lexer has not read the rest of the code, so the code of
+                // the spliced locations has to be completed here.
+                new_code_rc.value.replace(new_code.clone());
+                let relexed = kept..new_tokens.len();
+                new_tokens.extend(tokens[points[i].1..].iter().map(|(id, location)| {
+                    let range = &location.range;
+                    relocate(*id, new_index(range.start)..new_index(range.end))
+                }));
+                return Ok((new_tokens, relexed));
+            }
+        }
+
+        let relexed = kept..new_tokens.len();
+        Ok((new_tokens, relexed))
+    }
+    .now_or_never()
+    .expect("the lexer should not block when reading from a string")
+    .map(|(tokens, relexed)| Retokenized {
+        code: new_code.clone(),
+        tokens,
+        relexed,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::lex::Keyword;
+    use crate::parser::lex::Operator::{Bar, Greater, Semicolon};
+
+    fn ids_and_ranges(tokens: Vec<(TokenId, Location)>) -> Vec<(TokenId, std::ops::Range<usize>)> {
+        tokens
+            .into_iter()
+            .map(|(id, location)| (id, location.range))
+            .collect()
+    }
+
+    #[test]
+    fn tokenize_empty() {
+        assert_eq!(tokenize("").unwrap(), []);
+        assert_eq!(tokenize("  # comment").unwrap(), []);
+    }
+
+    #[test]
+    fn tokenize_pipeline() {
+        let tokens = tokenize(r#"echo "a b" | wc"#).unwrap();
+        assert_eq!(
+            ids_and_ranges(tokens),
+            [
+                (TokenId::Token(None), 0..4),
+                (TokenId::Token(None), 5..10),
+                (TokenId::Operator(Bar), 11..12),
+                (TokenId::Token(None), 13..15),
+            ]
+        );
+    }
+
+    #[test]
+    fn tokenize_keywords_and_io_numbers() {
+        let tokens = tokenize("if 2>x; then :; fi # done\n").unwrap();
+        assert_eq!(
+            ids_and_ranges(tokens),
+            [
+                (TokenId::Token(Some(Keyword::If)), 0..2),
+                (TokenId::IoNumber, 3..4),
+                (TokenId::Operator(Greater), 4..5),
+                (TokenId::Token(None), 5..6),
+                (TokenId::Operator(Semicolon), 6..7),
+                (TokenId::Token(Some(Keyword::Then)), 8..12),
+                (TokenId::Token(None), 13..14),
+                (TokenId::Operator(Semicolon), 14..15),
+                (TokenId::Token(Some(Keyword::Fi)), 16..18),
+                (TokenId::Operator(Newline), 25..26),
+            ]
+        );
+    }
+
+    #[test]
+    fn tokenize_skips_here_doc_content() {
+        let tokens = tokenize("cat <<END; cat <<-EOF\n\tfoo | bar\nEND\n\tEOF\nwc\n").unwrap();
+        assert_eq!(
+            ids_and_ranges(tokens),
+            [
+                (TokenId::Token(None), 0..3),
+                (TokenId::Operator(LessLess), 4..6),
+                (TokenId::Token(None), 6..9),
+                (TokenId::Operator(Semicolon), 9..10),
+                (TokenId::Token(None), 11..14),
+                (TokenId::Operator(LessLessDash), 15..18),
+                (TokenId::Token(None), 18..21),
+                (TokenId::Operator(Newline), 21..22),
+                (TokenId::Token(None), 42..44),
+                (TokenId::Operator(Newline), 44..45),
+            ]
+        );
+    }
+
+    fn kinds_and_ranges(items: Vec<(TokenKind, Location)>) -> Vec<(TokenKind, Range<usize>)> {
+        items
+            .into_iter()
+            .map(|(kind, location)| (kind, location.range))
+            .collect()
+    }
+
+    #[test]
+    fn tokenize_with_comments_returns_comments() {
+        let items = tokenize_with_comments("# head\necho a # tail\n#\n").unwrap();
+        assert_eq!(
+            kinds_and_ranges(items),
+            [
+                (TokenKind::Comment, 0..6),
+                (TokenKind::Token(TokenId::Operator(Newline)), 6..7),
+                (TokenKind::Token(TokenId::Token(None)), 7..11),
+                (TokenKind::Token(TokenId::Token(None)), 12..13),
+                (TokenKind::Comment, 14..20),
+                (TokenKind::Token(TokenId::Operator(Newline)), 20..21),
+                (TokenKind::Comment, 21..22),
+                (TokenKind::Token(TokenId::Operator(Newline)), 22..23),
+            ]
+        );
+    }
+
+    #[test]
+    fn tokenize_with_comments_keeps_here_doc_state() {
+        let items = tokenize_with_comments("cat <<END # c\n# not a comment\nEND\n").unwrap();
+        assert_eq!(
+            kinds_and_ranges(items),
+            [
+                (TokenKind::Token(TokenId::Token(None)), 0..3),
+                (TokenKind::Token(TokenId::Operator(LessLess)), 4..6),
+                (TokenKind::Token(TokenId::Token(None)), 6..9),
+                (TokenKind::Comment, 10..13),
+                (TokenKind::Token(TokenId::Operator(Newline)), 13..14),
+            ]
+        );
+    }
+
+    #[test]
+    fn tokenize_error() {
+        let error = tokenize("echo 'foo").unwrap_err();
+        assert_eq!(error.location.range, 9..9);
+    }
+
+    /// Retokenizes the code and checks that the result equals the result of
+    /// tokenizing the edited code from scratch.
+    fn retokenize_and_compare(code: &str, edit: Range<usize>, replacement: &str) -> Range<usize> {
+        let tokens = tokenize(code).unwrap();
+        let result = retokenize(code, &tokens, edit, replacement).unwrap();
+        assert_eq!(result.tokens, tokenize(&result.code).unwrap());
+        result.relexed
+    }
+
+    #[test]
+    fn retokenize_edit_within_line() {
+        let code = "echo a\nls -l; cat\nwc\n";
+        let tokens = tokenize(code).unwrap();
+        let result = retokenize(code, &tokens, 11..14, "").unwrap();
+        assert_eq!(result.code, "echo a\nls -cat\nwc\n");
+        assert_eq!(
+            ids_and_ranges(result.tokens[result.relexed.clone()].to_vec()),
+            [
+                (TokenId::Token(None), 7..9),
+                (TokenId::Token(None), 10..14),
+                (TokenId::Operator(Newline), 14..15),
+            ]
+        );
+        assert_eq!(result.relexed, 3..6);
+        assert_eq!(result.tokens, tokenize(&result.code).unwrap());
+    }
+
+    #[test]
+    fn retokenize_edit_adding_line() {
+        let relexed = retokenize_and_compare("echo 1\necho 2\necho 3\n", 13..13, "\n:");
+        assert_eq!(relexed, 3..8);
+    }
+
+    #[test]
+    fn retokenize_edit_at_beginning() {
+        let relexed = retokenize_and_compare("echo 1\necho 2\n", 0..0, "!");
+        assert_eq!(relexed, 0..3);
+    }
+
+    #[test]
+    fn retokenize_edit_with_multibyte_characters() {
+        let relexed = retokenize_and_compare("echo あ\necho い\necho う\n", 12..13, "いい");
+        assert_eq!(relexed, 3..6);
+    }
+
+    #[test]
+    fn retokenize_edit_in_multi_line_quote() {
+        // The second line is in the middle of a quoted word, so lexing
+        // restarts at the first line.
+        let relexed = retokenize_and_compare("echo 'a\nb'\necho c\n", 8..9, "x");
+        assert_eq!(relexed, 0..3);
+    }
+
+    #[test]
+    fn retokenize_edit_in_here_doc_content() {
+        // Lexing restarts before the here-document operator. The line after
+        // the here-document content is also re-lexed since the end of the
+        // content is not known from the tokens.
+        let code = "echo 1\ncat <<END\nfoo\nEND\necho 2\necho 3\n";
+        let relexed = retokenize_and_compare(code, 17..20, "bar");
+        assert_eq!(relexed, 3..10);
+    }
+
+    #[test]
+    fn retokenize_edit_opening_quote() {
+        // The quote opened by the edit is closed in the next line, so the
+        // tokens of both lines are re-lexed.
+        let code = "echo 1\necho 2\necho 3 #\"\necho 4\n";
+        let relexed = retokenize_and_compare(code, 12..12, "\"");
+        assert_eq!(relexed, 3..6);
+    }
+
+    #[test]
+    fn retokenize_error() {
+        let code = "echo 1\necho 2\n";
+        let tokens = tokenize(code).unwrap();
+        let error = retokenize(code, &tokens, 12..12, "'").unwrap_err();
+        assert_eq!(error.location.range, 15..15);
+    }
+}
@@ -143,6 +143,32 @@ impl WordLexer<'_, '_> {
         }
     }
 
+    /// Consumes a run of plain characters that cannot start any word unit
+    /// other than an unquoted literal.
+    ///
+    /// This is a fast path for [`word`](Self::word) that avoids trying every
+    /// kind of word unit for each ordinary character. The consumed characters
+    /// are appended to `units` as `Unquoted(Literal(_))`, so the result is the
+    /// same as that of repeated calls to [`word_unit`](Self::word_unit).
+    async fn literal_run(
+        &mut self,
+        is_delimiter: &dyn Fn(char) -> bool,
+        units: &mut Vec<WordUnit>,
+    ) -> Result<()> {
+        fn is_special(c: char) -> bool {
+            matches!(c, '\\' | '$' | '`' | '\'' | '"')
+        }
+
+        while let Some(c) = self.peek_char().await? {
+            if is_special(c) || is_delimiter(c) {
+                break;
+            }
+            self.consume_char();
+            units.push(Unquoted(TextUnit::Literal(c)));
+        }
+        Ok(())
+    }
+
     /// Parses a word token.
     ///
     /// `is_delimiter` is a function that decides which character is a
@@ -166,8 +192,12 @@ impl WordLexer<'_, '_> {
     async fn word_dyn(&mut self, is_delimiter: &dyn Fn(char) -> bool) -> Result<Word> {
         let start = self.index();
         let mut units = vec![];
-        while let Some(unit) = self.word_unit_dyn(is_delimiter).await? {
-            units.push(unit)
+        loop {
+            self.literal_run(is_delimiter, &mut units).await?;
+            match self.word_unit_dyn(is_delimiter).await? {
+                Some(unit) => units.push(unit),
+                None => break,
+            }
         }
         let location = self.location_range(start..self.index());
         Ok(Word { units, location })
@@ -613,6 +643,28 @@ mod tests {
         assert_eq!(lexer.peek_char().now_or_never().unwrap(), Ok(None));
     }
 
+    #[test]
+    fn lexer_word_long_plain_word() {
+        let code = "abc-_.,:=%@~/0123456789".repeat(100);
+        let input = format!("{code}\\\n{code} ");
+        let mut lexer = Lexer::with_code(&input);
+        let mut lexer = WordLexer {
+            lexer: &mut lexer,
+            context: WordContext::Word,
+        };
+
+        let word = lexer.word(|c| c == ' ').now_or_never().unwrap().unwrap();
+        let expected = code
+            .chars()
+            .chain(code.chars())
+            .map(|c| Unquoted(Literal(c)))
+            .collect::<Vec<_>>();
+        assert_eq!(word.units, expected);
+        assert_eq!(word.location.range, 0..code.len() * 2 + 2);
+
+        assert_eq!(lexer.peek_char().now_or_never().unwrap(), Ok(Some(' ')));
+    }
+
     #[test]
     fn lexer_word_empty() {
         let mut lexer = Lexer::with_code("");
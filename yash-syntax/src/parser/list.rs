@@ -309,6 +309,72 @@ mod tests {
         });
     }
 
+    #[test]
+    fn parser_command_line_multiple_here_docs_in_order() {
+        let mut lexer = Lexer::with_code("cat <<A <<B\nbodyA\nA\nbodyB\nB\n");
+        let mut parser = Parser::new(&mut lexer);
+
+        let result = parser.command_line().now_or_never().unwrap();
+        let List(items) = result.unwrap().unwrap();
+        assert_eq!(items.len(), 1);
+        let AndOrList { first, rest } = &*items[0].and_or;
+        assert!(rest.is_empty(), "expected empty rest: {rest:?}");
+        let Pipeline { commands, negation } = first;
+        assert_eq!(*negation, false);
+        assert_eq!(commands.len(), 1);
+        let cmd = assert_matches!(*commands[0], Command::Simple(ref c) => c);
+        assert_eq!(cmd.words.len(), 1);
+        assert_eq!(cmd.redirs.len(), 2);
+        assert_matches!(cmd.redirs[0].body, RedirBody::HereDoc(ref here_doc) => {
+            assert_eq!(here_doc.delimiter.to_string(), "A");
+            assert_eq!(here_doc.content.get().unwrap().to_string(), "bodyA\n");
+        });
+        assert_matches!(cmd.redirs[1].body, RedirBody::HereDoc(ref here_doc) => {
+            assert_eq!(here_doc.delimiter.to_string(), "B");
+            assert_eq!(here_doc.content.get().unwrap().to_string(), "bodyB\n");
+        });
+    }
+
+    #[test]
+    fn parser_command_line_here_doc_content_reaching_end_of_input() {
+        let mut lexer = Lexer::with_code("cat <<END\nsome content\n");
+        let mut parser = Parser::new(&mut lexer);
+
+        let e = parser.command_line().now_or_never().unwrap().unwrap_err();
+        assert_matches!(
+            &e.cause,
+            ErrorCause::Syntax(SyntaxError::UnterminatedHereDoc { delimiter, opening_location }) => {
+                assert_eq!(delimiter, "END");
+                assert_eq!(*opening_location.code.value.borrow(), "cat <<END\nsome content\n");
+                assert_eq!(opening_location.range, 6..9);
+            }
+        );
+        assert_eq!(*e.location.code.value.borrow(), "cat <<END\nsome content\n");
+        assert_eq!(e.location.range, 23..23);
+    }
+
+    #[test]
+    fn parser_command_line_keywords_as_arguments() {
+        let mut lexer = Lexer::with_code("echo done; echo then\n");
+        let mut parser = Parser::new(&mut lexer);
+
+        let result = parser.command_line().now_or_never().unwrap();
+        let List(items) = result.unwrap().unwrap();
+        assert_eq!(items.len(), 2);
+        for (item, argument) in items.iter().zip(["done", "then"]) {
+            let AndOrList { first, rest } = &*item.and_or;
+            assert!(rest.is_empty(), "expected empty rest: {rest:?}");
+            assert_eq!(first.commands.len(), 1);
+            let cmd = assert_matches!(*first.commands[0], Command::Simple(ref c) => c);
+            let words = cmd
+                .words
+                .iter()
+                .map(|(w, _)| w.to_string())
+                .collect::<Vec<_>>();
+            assert_eq!(words, ["echo", argument]);
+        }
+    }
+
     #[test]
     fn parser_command_line_command_without_newline() {
         let mut lexer = Lexer::with_code("foo");
@@ -20,11 +20,12 @@ use super::core::Parser;
 use super::core::Result;
 use super::error::Error;
 use super::error::SyntaxError;
-use super::lex::Operator::{GreaterOpenParen, LessLess, LessLessDash, LessOpenParen};
+use super::lex::Operator::{CloseParen, LessLess, LessLessDash};
 use super::lex::TokenId::{EndOfInput, IoLocation, IoNumber, Operator, Token};
 use crate::source::Location;
 use crate::syntax::Fd;
 use crate::syntax::HereDoc;
+use crate::syntax::ProcessSubstDirection;
 use crate::syntax::Redir;
 use crate::syntax::RedirBody;
 use crate::syntax::RedirOp;
@@ -90,6 +91,29 @@ impl Parser<'_, '_> {
         Ok(RedirBody::HereDoc(here_doc))
     }
 
+    /// Parses the redirection body for a process redirection.
+    async fn process_redirection_body(
+        &mut self,
+        direction: ProcessSubstDirection,
+    ) -> Result<RedirBody> {
+        let open = self.take_token_raw().await?;
+        let body = self.maybe_compound_list_boxed().await?;
+
+        let close = self.take_token_raw().await?;
+        if close.id != Operator(CloseParen) {
+            let opening_location = open.word.location;
+            let cause = SyntaxError::UnclosedProcessRedirection { opening_location }.into();
+            let location = close.word.location;
+            return Err(Error { cause, location });
+        }
+
+        Ok(RedirBody::Process {
+            direction,
+            body: Rc::new(body),
+            location: open.word.location,
+        })
+    }
+
     /// Parses the redirection body.
     async fn redirection_body(&mut self) -> Result<Option<RedirBody>> {
         let operator = match self.peek_token().await?.id {
@@ -103,12 +127,10 @@ impl Parser<'_, '_> {
         match operator {
             LessLess => Ok(Some(self.here_doc_redirection_body(false).await?)),
             LessLessDash => Ok(Some(self.here_doc_redirection_body(true).await?)),
-            LessOpenParen | GreaterOpenParen => {
-                let cause = SyntaxError::UnsupportedProcessRedirection.into();
-                let location = self.peek_token().await?.word.location.clone();
-                Err(Error { cause, location })
-            }
-            _ => Ok(None),
+            _ => match ProcessSubstDirection::try_from(operator) {
+                Ok(direction) => Ok(Some(self.process_redirection_body(direction).await?)),
+                Err(_) => Ok(None),
+            },
         }
     }
 
@@ -288,36 +310,96 @@ mod tests {
         });
     }
 
+    #[test]
+    fn parser_redirection_normal_operators_with_adjacent_operands() {
+        let cases = [
+            (">out\n", RedirOp::FileOut, "out"),
+            (">>log\n", RedirOp::FileAppend, "log"),
+            ("<in\n", RedirOp::FileIn, "in"),
+            ("<>rw\n", RedirOp::FileInOut, "rw"),
+            (">|clob\n", RedirOp::FileClobber, "clob"),
+            ("<&3\n", RedirOp::FdIn, "3"),
+            (">&-\n", RedirOp::FdOut, "-"),
+            (">>|pipe\n", RedirOp::Pipe, "pipe"),
+            ("<<<string\n", RedirOp::String, "string"),
+        ];
+        for (code, expected_operator, expected_operand) in cases {
+            let mut lexer = Lexer::with_code(code);
+            let mut parser = Parser::new(&mut lexer);
+
+            let result = parser.redirection().now_or_never().unwrap();
+            let redir = result.unwrap().unwrap();
+            assert_eq!(redir.fd, None, "code={code:?}");
+            assert_matches!(redir.body, RedirBody::Normal { operator, operand } => {
+                assert_eq!(operator, expected_operator, "code={code:?}");
+                assert_eq!(operand.to_string(), expected_operand, "code={code:?}");
+            });
+
+            let next = parser.peek_token().now_or_never().unwrap().unwrap();
+            assert_eq!(next.id, Operator(Newline), "code={code:?}");
+        }
+    }
+
     #[test]
     fn parser_redirection_less_paren() {
         let mut lexer = Lexer::with_code("<(foo)\n");
         let mut parser = Parser::new(&mut lexer);
 
-        let e = parser.redirection().now_or_never().unwrap().unwrap_err();
-        assert_eq!(
-            e.cause,
-            ErrorCause::Syntax(SyntaxError::UnsupportedProcessRedirection)
-        );
-        assert_eq!(*e.location.code.value.borrow(), "<(foo)\n");
-        assert_eq!(e.location.code.start_line_number.get(), 1);
-        assert_eq!(*e.location.code.source, Source::Unknown);
-        assert_eq!(e.location.range, 0..2);
+        let result = parser.redirection().now_or_never().unwrap();
+        let redir = result.unwrap().unwrap();
+        assert_eq!(redir.fd, None);
+        assert_matches!(redir.body, RedirBody::Process { direction, body, location } => {
+            assert_eq!(direction, ProcessSubstDirection::In);
+            assert_eq!(body.to_string(), "foo");
+            assert_eq!(location.range, 0..2);
+        });
+
+        let next = parser.peek_token().now_or_never().unwrap().unwrap();
+        assert_eq!(next.id, Operator(Newline));
     }
 
     #[test]
     fn parser_redirection_greater_paren() {
-        let mut lexer = Lexer::with_code(">(foo)\n");
+        let mut lexer = Lexer::with_code("3>(foo; bar)\n");
+        let mut parser = Parser::new(&mut lexer);
+
+        let result = parser.redirection().now_or_never().unwrap();
+        let redir = result.unwrap().unwrap();
+        assert_eq!(redir.fd, Some(Fd(3)));
+        assert_matches!(redir.body, RedirBody::Process { direction, body, location } => {
+            assert_eq!(direction, ProcessSubstDirection::Out);
+            assert_eq!(body.to_string(), "foo; bar");
+            assert_eq!(location.range, 1..3);
+        });
+    }
+
+    #[test]
+    fn parser_redirection_nested_process_redirections() {
+        let mut lexer = Lexer::with_code("<(cat <(foo) >(bar <(baz)))\n");
+        let mut parser = Parser::new(&mut lexer);
+
+        let result = parser.redirection().now_or_never().unwrap();
+        let redir = result.unwrap().unwrap();
+        assert_eq!(redir.to_string(), "<(cat <(foo) >(bar <(baz)))");
+        assert_matches!(redir.body, RedirBody::Process { body, .. } => {
+            assert_eq!(body.to_string(), "cat <(foo) >(bar <(baz))");
+        });
+    }
+
+    #[test]
+    fn parser_redirection_unclosed_process_redirection() {
+        let mut lexer = Lexer::with_code("<(foo\n");
         let mut parser = Parser::new(&mut lexer);
 
         let e = parser.redirection().now_or_never().unwrap().unwrap_err();
-        assert_eq!(
-            e.cause,
-            ErrorCause::Syntax(SyntaxError::UnsupportedProcessRedirection)
-        );
-        assert_eq!(*e.location.code.value.borrow(), ">(foo)\n");
-        assert_eq!(e.location.code.start_line_number.get(), 1);
+        assert_matches!(e.cause,
+            ErrorCause::Syntax(SyntaxError::UnclosedProcessRedirection { opening_location }) => {
+            assert_eq!(*opening_location.code.value.borrow(), "<(foo\n");
+            assert_eq!(opening_location.range, 0..2);
+        });
+        assert_eq!(*e.location.code.value.borrow(), "<(foo\n");
         assert_eq!(*e.location.code.source, Source::Unknown);
-        assert_eq!(e.location.range, 0..2);
+        assert_eq!(e.location.range, 6..6);
     }
 
     #[test]
@@ -391,6 +473,68 @@ mod tests {
         assert_eq!(next.id, Operator(Newline));
     }
 
+    #[test]
+    fn parser_redirection_with_single_digit_io_number() {
+        let mut lexer = Lexer::with_code("2>file\n");
+        let mut parser = Parser::new(&mut lexer);
+
+        let result = parser.redirection().now_or_never().unwrap();
+        let redir = result.unwrap().unwrap();
+        assert_eq!(redir.fd, Some(Fd(2)));
+        assert_matches!(redir.body, RedirBody::Normal { operator, operand } => {
+            assert_eq!(operator, RedirOp::FileOut);
+            assert_eq!(operand.to_string(), "file")
+        });
+    }
+
+    #[test]
+    fn parser_redirection_with_multi_digit_io_number() {
+        let mut lexer = Lexer::with_code("10>file\n");
+        let mut parser = Parser::new(&mut lexer);
+
+        let result = parser.redirection().now_or_never().unwrap();
+        let redir = result.unwrap().unwrap();
+        assert_eq!(redir.fd, Some(Fd(10)));
+        assert_matches!(redir.body, RedirBody::Normal { operator, operand } => {
+            assert_eq!(operator, RedirOp::FileOut);
+            assert_eq!(operand.to_string(), "file")
+        });
+    }
+
+    #[test]
+    fn parser_redirection_with_io_number_and_here_doc() {
+        let mut lexer = Lexer::with_code("9<<END\nEND\n");
+        let mut parser = Parser::new(&mut lexer);
+
+        let result = parser.redirection().now_or_never().unwrap();
+        let redir = result.unwrap().unwrap();
+        assert_eq!(redir.fd, Some(Fd(9)));
+        let here_doc = assert_matches!(redir.body, RedirBody::HereDoc(here_doc) => here_doc);
+        assert_eq!(here_doc.delimiter.to_string(), "END");
+    }
+
+    #[test]
+    fn parser_redirection_digits_not_adjacent_to_operator() {
+        // "2" is a normal word, not an IO_NUMBER, if followed by a blank.
+        let mut lexer = Lexer::with_code("2 >file\n");
+        let mut parser = Parser::new(&mut lexer);
+
+        let result = parser.redirection().now_or_never().unwrap();
+        assert_eq!(result, Ok(None));
+
+        let token = parser.take_token_raw().now_or_never().unwrap().unwrap();
+        assert_eq!(token.id, Token(None));
+        assert_eq!(token.word.to_string(), "2");
+
+        let result = parser.redirection().now_or_never().unwrap();
+        let redir = result.unwrap().unwrap();
+        assert_eq!(redir.fd, None);
+        assert_matches!(redir.body, RedirBody::Normal { operator, operand } => {
+            assert_eq!(operator, RedirOp::FileOut);
+            assert_eq!(operand.to_string(), "file")
+        });
+    }
+
     #[test]
     fn parser_redirection_fd_out_of_range() {
         let mut lexer = Lexer::with_code("9999999999999999999999999999999999999999< x");
@@ -367,6 +367,23 @@ mod tests {
         assert_eq!(result, Ok(Rec::Parsed(None)));
     }
 
+    #[test]
+    fn parser_simple_command_keywords_as_arguments() {
+        let mut lexer = Lexer::with_code("echo done then if { } !");
+        let mut parser = Parser::new(&mut lexer);
+
+        let result = parser.simple_command().now_or_never().unwrap();
+        let sc = result.unwrap().unwrap().unwrap();
+        assert_eq!(sc.assigns, []);
+        assert_eq!(*sc.redirs, []);
+        let words = sc
+            .words
+            .iter()
+            .map(|(w, _)| w.to_string())
+            .collect::<Vec<_>>();
+        assert_eq!(words, ["echo", "done", "then", "if", "{", "}", "!"]);
+    }
+
     #[test]
     fn parser_simple_command_one_assignment() {
         let mut lexer = Lexer::with_code("my=assignment");
@@ -497,6 +514,29 @@ mod tests {
         assert_eq!(next.id, EndOfInput);
     }
 
+    #[test]
+    fn parser_simple_command_process_redirections() {
+        let mut lexer = Lexer::with_code("diff <(a) <(b)");
+        let mut parser = Parser::new(&mut lexer);
+
+        let result = parser.simple_command().now_or_never().unwrap();
+        let sc = result.unwrap().unwrap().unwrap();
+        assert_eq!(sc.assigns, []);
+        assert_eq!(sc.words.len(), 1);
+        assert_eq!(sc.words[0].0.to_string(), "diff");
+        assert_eq!(sc.redirs.len(), 2);
+        assert_matches!(sc.redirs[0].body, RedirBody::Process { ref body, .. } => {
+            assert_eq!(body.to_string(), "a")
+        });
+        assert_matches!(sc.redirs[1].body, RedirBody::Process { ref body, .. } => {
+            assert_eq!(body.to_string(), "b")
+        });
+        assert_eq!(sc.to_string(), "diff <(a) <(b)");
+
+        let next = parser.peek_token().now_or_never().unwrap().unwrap();
+        assert_eq!(next.id, EndOfInput);
+    }
+
     #[test]
     fn parser_simple_command_assignment_word() {
         let mut lexer = Lexer::with_code("if=then else");
@@ -572,6 +612,48 @@ mod tests {
         });
     }
 
+    #[test]
+    fn parser_simple_command_redirection_word() {
+        let mut lexer = Lexer::with_code(">out echo");
+        let mut parser = Parser::new(&mut lexer);
+
+        let result = parser.simple_command().now_or_never().unwrap();
+        let sc = result.unwrap().unwrap().unwrap();
+        assert_eq!(sc.assigns, []);
+        assert_eq!(sc.words.len(), 1);
+        assert_eq!(sc.redirs.len(), 1);
+        assert_eq!(sc.words[0].0.to_string(), "echo");
+        assert_eq!(sc.words[0].1, ExpansionMode::Multiple);
+        assert_eq!(sc.redirs[0].fd, None);
+        assert_matches!(sc.redirs[0].body, RedirBody::Normal { ref operator, ref operand } => {
+            assert_eq!(operator, &RedirOp::FileOut);
+            assert_eq!(operand.to_string(), "out")
+        });
+    }
+
+    #[test]
+    fn parser_simple_command_assignments_words_redirection() {
+        let mut lexer = Lexer::with_code("a=1 b=2 echo hi >out");
+        let mut parser = Parser::new(&mut lexer);
+
+        let result = parser.simple_command().now_or_never().unwrap();
+        let sc = result.unwrap().unwrap().unwrap();
+        assert_eq!(sc.assigns.len(), 2);
+        assert_eq!(sc.words.len(), 2);
+        assert_eq!(sc.redirs.len(), 1);
+        assert_eq!(sc.assigns[0].name, "a");
+        assert_eq!(sc.assigns[0].value.to_string(), "1");
+        assert_eq!(sc.assigns[1].name, "b");
+        assert_eq!(sc.assigns[1].value.to_string(), "2");
+        assert_eq!(sc.words[0].0.to_string(), "echo");
+        assert_eq!(sc.words[1].0.to_string(), "hi");
+        assert_eq!(sc.redirs[0].fd, None);
+        assert_matches!(sc.redirs[0].body, RedirBody::Normal { ref operator, ref operand } => {
+            assert_eq!(operator, &RedirOp::FileOut);
+            assert_eq!(operand.to_string(), "out")
+        });
+    }
+
     #[test]
     fn parser_simple_command_array_assignment() {
         let mut lexer = Lexer::with_code("a=()");
@@ -245,6 +245,34 @@ pub struct Trim {
     pub pattern: Word,
 }
 
+/// Flag that specifies how a list of names is expanded in a
+/// [modifier](Modifier)
+///
+/// The flags correspond to the special parameters `*` and `@`, and the list is
+/// expanded in the same way as the positional parameters are.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum ListSuffix {
+    /// `*`
+    Asterisk,
+    /// `@`
+    At,
+}
+
+/// Array index that selects elements of a parameter value
+///
+/// A subscript follows the parameter name in a [braced parameter
+/// expansion](BracedParam), as in `${foo[1]}` or `${foo[@]}`.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum Subscript {
+    /// `[*]` or `[@]`, selecting all the elements
+    All(ListSuffix),
+    /// Arithmetic expression that selects a single element
+    ///
+    /// The text is expanded and evaluated as an arithmetic expression to
+    /// yield the index. A negative index counts from the end of the array.
+    Expression(Text),
+}
+
 /// Attribute that modifies a parameter expansion
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub enum Modifier {
@@ -252,6 +280,21 @@ pub enum Modifier {
     None,
     /// `#` prefix (`${#foo}`)
     Length,
+    /// `!` prefix (`${!foo}`)
+    ///
+    /// The value of the parameter is used as the name of another parameter to
+    /// expand. This is a non-POSIX extension.
+    Indirect,
+    /// `!` prefix with a `*` or `@` suffix (`${!foo*}` or `${!foo@}`)
+    ///
+    /// The expansion results in the names of variables that start with the
+    /// parameter name. This is a non-POSIX extension.
+    VariableNames(ListSuffix),
+    /// `!` prefix with a `[*]` or `[@]` suffix (`${!foo[*]}` or `${!foo[@]}`)
+    ///
+    /// The expansion results in the indices of the elements of the array
+    /// variable. This is a non-POSIX extension.
+    Indices(ListSuffix),
     /// `+`, `-`, `=` or `?` suffix, optionally with `:` (`${foo:-bar}`)
     Switch(Switch),
     /// `#`, `##`, `%` or `%%` suffix
@@ -269,7 +312,8 @@ pub struct BracedParam {
     // TODO recursive expansion
     /// Parameter to be expanded
     pub param: Param,
-    // TODO index
+    /// Array index that selects elements of the parameter value
+    pub subscript: Option<Subscript>,
     /// Modifier
     pub modifier: Modifier,
     /// Position of this parameter expansion in the source code
@@ -477,7 +521,7 @@ pub struct Assign {
 /// Redirection operators
 ///
 /// This enum defines the redirection operator types except here-document and
-/// process redirection.
+/// [process redirection](ProcessSubstDirection).
 #[derive(Clone, Copy, Debug, Eq, PartialEq)]
 pub enum RedirOp {
     /// `<` (open a file for input)
@@ -500,6 +544,18 @@ pub enum RedirOp {
     String,
 }
 
+/// Direction of a process redirection
+///
+/// This enum tells which end of the pipe between the shell and the process
+/// redirection body is connected to the redirected file descriptor.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum ProcessSubstDirection {
+    /// `<(` (read the output of the body)
+    In,
+    /// `>(` (write to the input of the body)
+    Out,
+}
+
 /// Here-document
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub struct HereDoc {
@@ -534,15 +590,39 @@ pub enum RedirBody {
     Normal { operator: RedirOp, operand: Word },
     /// Here-document
     HereDoc(Rc<HereDoc>),
-    // TODO process redirection
+    /// Process redirection (`<(list)` or `>(list)`)
+    Process {
+        /// Whether the body's output or input is connected to the file descriptor
+        direction: ProcessSubstDirection,
+        /// Commands run in the process
+        body: Rc<List>,
+        /// Location of the opening `<(` or `>(`
+        location: Location,
+    },
 }
 
 impl RedirBody {
     /// Returns the operand word of the redirection.
-    pub fn operand(&self) -> &Word {
+    ///
+    /// A process redirection has no operand word, in which case this function
+    /// returns `None`.
+    pub fn operand(&self) -> Option<&Word> {
         match self {
-            RedirBody::Normal { operand, .. } => operand,
-            RedirBody::HereDoc(here_doc) => &here_doc.delimiter,
+            RedirBody::Normal { operand, .. } => Some(operand),
+            RedirBody::HereDoc(here_doc) => Some(&here_doc.delimiter),
+            RedirBody::Process { .. } => None,
+        }
+    }
+
+    /// Returns the location of the redirection body.
+    ///
+    /// This is the location of the [operand](Self::operand) if any, or that of
+    /// the opening `<(` or `>(` of a process redirection.
+    pub fn location(&self) -> &Location {
+        match self {
+            RedirBody::Normal { operand, .. } => &operand.location,
+            RedirBody::HereDoc(here_doc) => &here_doc.delimiter.location,
+            RedirBody::Process { location, .. } => location,
         }
     }
 }
@@ -569,6 +649,10 @@ impl Redir {
                 FileOut | FileAppend | FileClobber | FdOut | Pipe => Fd::STDOUT,
             },
             RedirBody::HereDoc { .. } => Fd::STDIN,
+            RedirBody::Process { direction, .. } => match direction {
+                ProcessSubstDirection::In => Fd::STDIN,
+                ProcessSubstDirection::Out => Fd::STDOUT,
+            },
         })
     }
 }
@@ -673,6 +757,17 @@ pub enum CompoundCommand {
         values: Option<Vec<Word>>,
         body: List,
     },
+    /// Arithmetic for loop
+    ///
+    /// This is the C-style for loop of the form `for ((init; cond; update))`.
+    /// The `location` is that of the `((...))` part.
+    ArithmeticFor {
+        init: Text,
+        cond: Text,
+        update: Text,
+        body: List,
+        location: Location,
+    },
     /// While loop
     While { condition: List, body: List },
     /// Until loop
@@ -773,5 +868,7 @@ pub struct List(pub Vec<Item>);
 mod conversions;
 /// Implementations of [std::fmt::Display] for the shell language syntax types
 mod impl_display;
+#[cfg(test)]
+mod round_trip;
 
 pub use conversions::{MaybeLiteral, NotLiteral, NotSpecialParam, Unquote};
@@ -231,29 +231,74 @@ impl Unquote for Trim {
     }
 }
 
+impl Unquote for Subscript {
+    fn write_unquoted<W: fmt::Write>(&self, w: &mut W) -> UnquoteResult {
+        match self {
+            Subscript::All(suffix) => {
+                write!(w, "[{suffix}]")?;
+                Ok(false)
+            }
+            Subscript::Expression(text) => {
+                w.write_char('[')?;
+                let quoted = text.write_unquoted(w)?;
+                w.write_char(']')?;
+                Ok(quoted)
+            }
+        }
+    }
+}
+
 impl Unquote for BracedParam {
     fn write_unquoted<W: fmt::Write>(&self, w: &mut W) -> UnquoteResult {
         use Modifier::*;
+        /// Writes the parameter name and the subscript, if any.
+        fn write_name<W: fmt::Write>(param: &BracedParam, w: &mut W) -> UnquoteResult {
+            write!(w, "{}", param.param)?;
+            match &param.subscript {
+                Option::None => Ok(false),
+                Some(subscript) => subscript.write_unquoted(w),
+            }
+        }
         match self.modifier {
             None => {
-                write!(w, "${{{}}}", self.param)?;
-                Ok(false)
+                w.write_str("${")?;
+                let quoted = write_name(self, w)?;
+                w.write_char('}')?;
+                Ok(quoted)
             }
             Length => {
-                write!(w, "${{#{}}}", self.param)?;
+                w.write_str("${#")?;
+                let quoted = write_name(self, w)?;
+                w.write_char('}')?;
+                Ok(quoted)
+            }
+            Indirect => {
+                w.write_str("${!")?;
+                let quoted = write_name(self, w)?;
+                w.write_char('}')?;
+                Ok(quoted)
+            }
+            VariableNames(suffix) => {
+                write!(w, "${{!{}{}}}", self.param, suffix)?;
+                Ok(false)
+            }
+            Indices(suffix) => {
+                write!(w, "${{!{}[{}]}}", self.param, suffix)?;
                 Ok(false)
             }
             Switch(ref switch) => {
-                write!(w, "${{{}", self.param)?;
+                w.write_str("${")?;
+                let name_quoted = write_name(self, w)?;
                 let quoted = switch.write_unquoted(w)?;
                 w.write_char('}')?;
-                Ok(quoted)
+                Ok(name_quoted || quoted)
             }
             Trim(ref trim) => {
-                write!(w, "${{{}", self.param)?;
+                w.write_str("${")?;
+                let name_quoted = write_name(self, w)?;
                 let quoted = trim.write_unquoted(w)?;
                 w.write_char('}')?;
-                Ok(quoted)
+                Ok(name_quoted || quoted)
             }
         }
     }
@@ -556,6 +601,26 @@ impl From<RedirOp> for Operator {
     }
 }
 
+impl TryFrom<Operator> for ProcessSubstDirection {
+    type Error = TryFromOperatorError;
+    fn try_from(op: Operator) -> Result<ProcessSubstDirection, TryFromOperatorError> {
+        match op {
+            Operator::LessOpenParen => Ok(ProcessSubstDirection::In),
+            Operator::GreaterOpenParen => Ok(ProcessSubstDirection::Out),
+            _ => Err(TryFromOperatorError {}),
+        }
+    }
+}
+
+impl From<ProcessSubstDirection> for Operator {
+    fn from(direction: ProcessSubstDirection) -> Operator {
+        match direction {
+            ProcessSubstDirection::In => Operator::LessOpenParen,
+            ProcessSubstDirection::Out => Operator::GreaterOpenParen,
+        }
+    }
+}
+
 impl<T: Into<Rc<HereDoc>>> From<T> for RedirBody {
     fn from(t: T) -> Self {
         RedirBody::HereDoc(t.into())
@@ -707,6 +772,7 @@ mod tests {
     fn braced_param_unquote() {
         let param = BracedParam {
             param: Param::variable("foo"),
+            subscript: None,
             modifier: Modifier::None,
             location: Location::dummy(""),
         };
@@ -722,6 +788,30 @@ mod tests {
         assert_eq!(unquoted, "${#foo}");
         assert_eq!(is_quoted, false);
 
+        let param = BracedParam {
+            modifier: Modifier::Indirect,
+            ..param
+        };
+        let (unquoted, is_quoted) = param.unquote();
+        assert_eq!(unquoted, "${!foo}");
+        assert_eq!(is_quoted, false);
+
+        let param = BracedParam {
+            modifier: Modifier::VariableNames(ListSuffix::At),
+            ..param
+        };
+        let (unquoted, is_quoted) = param.unquote();
+        assert_eq!(unquoted, "${!foo@}");
+        assert_eq!(is_quoted, false);
+
+        let param = BracedParam {
+            modifier: Modifier::Indices(ListSuffix::Asterisk),
+            ..param
+        };
+        let (unquoted, is_quoted) = param.unquote();
+        assert_eq!(unquoted, "${!foo[*]}");
+        assert_eq!(is_quoted, false);
+
         let switch = Switch {
             action: SwitchAction::Assign,
             condition: SwitchCondition::UnsetOrEmpty,
@@ -747,6 +837,15 @@ mod tests {
         let (unquoted, is_quoted) = param.unquote();
         assert_eq!(unquoted, "${foo%baz bar}");
         assert_eq!(is_quoted, true);
+
+        let param = BracedParam {
+            subscript: Some(Subscript::Expression(r"\1".parse().unwrap())),
+            modifier: Modifier::None,
+            ..param
+        };
+        let (unquoted, is_quoted) = param.unquote();
+        assert_eq!(unquoted, "${foo[1]}");
+        assert_eq!(is_quoted, true);
     }
 
     #[test]
@@ -989,6 +1088,82 @@ mod tests {
         });
     }
 
+    #[test]
+    fn assign_try_from_word_tilde_only() {
+        let word = Word::from_str("a=~").unwrap();
+        let assign = Assign::try_from(word).unwrap();
+        assert_matches!(assign.value, Scalar(value) => {
+            assert_eq!(
+                value.units,
+                [WordUnit::Tilde {
+                    name: "".to_string(),
+                    followed_by_slash: false,
+                }]
+            );
+        });
+    }
+
+    #[test]
+    fn assign_try_from_word_tilde_followed_by_slash() {
+        let word = Word::from_str("a=~/x").unwrap();
+        let assign = Assign::try_from(word).unwrap();
+        assert_matches!(assign.value, Scalar(value) => {
+            assert_eq!(
+                value.units,
+                [
+                    WordUnit::Tilde {
+                        name: "".to_string(),
+                        followed_by_slash: true,
+                    },
+                    WordUnit::Unquoted(TextUnit::Literal('/')),
+                    WordUnit::Unquoted(TextUnit::Literal('x')),
+                ]
+            );
+        });
+    }
+
+    #[test]
+    fn assign_try_from_word_tilde_after_colon() {
+        let word = Word::from_str("a=x:~/y").unwrap();
+        let assign = Assign::try_from(word).unwrap();
+        assert_matches!(assign.value, Scalar(value) => {
+            assert_eq!(
+                value.units,
+                [
+                    WordUnit::Unquoted(TextUnit::Literal('x')),
+                    WordUnit::Unquoted(TextUnit::Literal(':')),
+                    WordUnit::Tilde {
+                        name: "".to_string(),
+                        followed_by_slash: true,
+                    },
+                    WordUnit::Unquoted(TextUnit::Literal('/')),
+                    WordUnit::Unquoted(TextUnit::Literal('y')),
+                ]
+            );
+        });
+    }
+
+    #[test]
+    fn assign_try_from_word_quoted_tilde() {
+        let word = Word::from_str(r#"a="~""#).unwrap();
+        let assign = Assign::try_from(word).unwrap();
+        assert_matches!(assign.value, Scalar(value) => {
+            assert_eq!(value.to_string(), r#""~""#);
+            assert_matches!(value.units[..], [WordUnit::DoubleQuote(_)]);
+        });
+
+        // A quoted colon does not delimit a tilde expansion
+        let word = Word::from_str(r#"a=x":"~/y"#).unwrap();
+        let assign = Assign::try_from(word).unwrap();
+        assert_matches!(assign.value, Scalar(value) => {
+            assert!(
+                !value.units.iter().any(|unit| matches!(unit, WordUnit::Tilde { .. })),
+                "{:?}",
+                value.units
+            );
+        });
+    }
+
     #[test]
     fn redir_op_conversions() {
         use RedirOp::*;
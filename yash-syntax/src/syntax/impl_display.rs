@@ -71,6 +71,17 @@ impl fmt::Display for TrimSide {
     }
 }
 
+impl fmt::Display for ListSuffix {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        use ListSuffix::*;
+        let c = match self {
+            Asterisk => '*',
+            At => '@',
+        };
+        f.write_char(c)
+    }
+}
+
 impl fmt::Display for Trim {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         self.side.fmt(f)?;
@@ -82,14 +93,27 @@ impl fmt::Display for Trim {
     }
 }
 
+impl fmt::Display for Subscript {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Subscript::All(suffix) => write!(f, "[{suffix}]"),
+            Subscript::Expression(text) => write!(f, "[{text}]"),
+        }
+    }
+}
+
 impl fmt::Display for BracedParam {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         use Modifier::*;
+        let subscript = self.subscript.iter().format("");
         match self.modifier {
-            None => write!(f, "${{{}}}", self.param),
-            Length => write!(f, "${{#{}}}", self.param),
-            Switch(ref switch) => write!(f, "${{{}{}}}", self.param, switch),
-            Trim(ref trim) => write!(f, "${{{}{}}}", self.param, trim),
+            None => write!(f, "${{{}{}}}", self.param, subscript),
+            Length => write!(f, "${{#{}{}}}", self.param, subscript),
+            Indirect => write!(f, "${{!{}{}}}", self.param, subscript),
+            VariableNames(suffix) => write!(f, "${{!{}{}}}", self.param, suffix),
+            Indices(suffix) => write!(f, "${{!{}[{}]}}", self.param, suffix),
+            Switch(ref switch) => write!(f, "${{{}{}{}}}", self.param, subscript, switch),
+            Trim(ref trim) => write!(f, "${{{}{}{}}}", self.param, subscript, trim),
         }
     }
 }
@@ -197,6 +221,12 @@ impl fmt::Display for RedirOp {
     }
 }
 
+impl fmt::Display for ProcessSubstDirection {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        Operator::from(*self).fmt(f)
+    }
+}
+
 impl fmt::Display for HereDoc {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         f.write_str(if self.remove_tabs { "<<-" } else { "<<" })?;
@@ -215,6 +245,9 @@ impl fmt::Display for RedirBody {
         match self {
             RedirBody::Normal { operator, operand } => write!(f, "{operator}{operand}"),
             RedirBody::HereDoc(h) => write!(f, "{h}"),
+            RedirBody::Process {
+                direction, body, ..
+            } => write!(f, "{direction}{body})"),
         }
     }
 }
@@ -292,6 +325,13 @@ impl fmt::Display for CompoundCommand {
                 }
                 write!(f, " do {body:#} done")
             }
+            ArithmeticFor {
+                init,
+                cond,
+                update,
+                body,
+                ..
+            } => write!(f, "for (({init};{cond};{update})) do {body:#} done"),
             While { condition, body } => write!(f, "while {condition:#} do {body:#} done"),
             Until { condition, body } => write!(f, "until {condition:#} do {body:#} done"),
             If {
@@ -484,6 +524,7 @@ mod tests {
     fn braced_param_display() {
         let param = BracedParam {
             param: Param::variable("foo"),
+            subscript: None,
             modifier: Modifier::None,
             location: Location::dummy(""),
         };
@@ -495,6 +536,36 @@ mod tests {
         };
         assert_eq!(param.to_string(), "${#foo}");
 
+        let param = BracedParam {
+            modifier: Modifier::Indirect,
+            ..param
+        };
+        assert_eq!(param.to_string(), "${!foo}");
+
+        let param = BracedParam {
+            modifier: Modifier::VariableNames(ListSuffix::Asterisk),
+            ..param
+        };
+        assert_eq!(param.to_string(), "${!foo*}");
+
+        let param = BracedParam {
+            modifier: Modifier::VariableNames(ListSuffix::At),
+            ..param
+        };
+        assert_eq!(param.to_string(), "${!foo@}");
+
+        let param = BracedParam {
+            modifier: Modifier::Indices(ListSuffix::Asterisk),
+            ..param
+        };
+        assert_eq!(param.to_string(), "${!foo[*]}");
+
+        let param = BracedParam {
+            modifier: Modifier::Indices(ListSuffix::At),
+            ..param
+        };
+        assert_eq!(param.to_string(), "${!foo[@]}");
+
         let switch = Switch {
             action: SwitchAction::Assign,
             condition: SwitchCondition::UnsetOrEmpty,
@@ -516,6 +587,19 @@ mod tests {
             ..param
         };
         assert_eq!(param.to_string(), "${foo%baz' 'bar}");
+
+        let param = BracedParam {
+            subscript: Some(Subscript::Expression("i+1".parse().unwrap())),
+            ..param
+        };
+        assert_eq!(param.to_string(), "${foo[i+1]%baz' 'bar}");
+
+        let param = BracedParam {
+            subscript: Some(Subscript::All(ListSuffix::At)),
+            modifier: Modifier::Length,
+            ..param
+        };
+        assert_eq!(param.to_string(), "${#foo[@]}");
     }
 
     #[test]
@@ -723,6 +807,29 @@ mod tests {
         assert_eq!(redir.to_string(), "9<<END");
     }
 
+    #[test]
+    fn process_redir_display() {
+        let redir = Redir {
+            fd: None,
+            body: RedirBody::Process {
+                direction: ProcessSubstDirection::In,
+                body: Rc::new("foo; bar".parse().unwrap()),
+                location: Location::dummy("<("),
+            },
+        };
+        assert_eq!(redir.to_string(), "<(foo; bar)");
+
+        let redir = Redir {
+            fd: Some(Fd(3)),
+            body: RedirBody::Process {
+                direction: ProcessSubstDirection::Out,
+                body: Rc::new("cat".parse().unwrap()),
+                location: Location::dummy(">("),
+            },
+        };
+        assert_eq!(redir.to_string(), "3>(cat)");
+    }
+
     #[test]
     fn simple_command_display() {
         let mut command = SimpleCommand {
@@ -875,6 +982,23 @@ mod tests {
         assert_eq!(r#for.to_string(), "for V in a b; do one; two& done");
     }
 
+    #[test]
+    fn arithmetic_for_display() {
+        let init = Text::from_str("i=0").unwrap();
+        let cond = Text::from_str("i<3").unwrap();
+        let update = Text::from_str("i++").unwrap();
+        let body = "echo $i".parse::<List>().unwrap();
+        let location = Location::dummy("");
+        let r#for = CompoundCommand::ArithmeticFor {
+            init,
+            cond,
+            update,
+            body,
+            location,
+        };
+        assert_eq!(r#for.to_string(), "for ((i=0;i<3;i++)) do echo $i; done");
+    }
+
     #[test]
     fn while_display() {
         let condition = "true& false".parse::<List>().unwrap();
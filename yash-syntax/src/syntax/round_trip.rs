@@ -0,0 +1,289 @@
+// This file is part of yash, an extended POSIX shell.
+// Copyright (C) 2026 WATANABE Yuki
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+//! Round-trip tests for displaying and parsing ASTs
+//!
+//! The tests in this module generate random ASTs, convert them to strings with
+//! [`Display`](std::fmt::Display), parse the strings back, and check that the
+//! re-parsed ASTs are the same as the originals except for locations.
+//!
+//! The generator is a small hand-written one driven by a pseudo-random number
+//! generator with a fixed seed, so the tests are deterministic. Leaf elements
+//! (words, assignments, and redirections) are taken from the corpora defined
+//! below, and the generator combines them into simple commands, pipelines,
+//! and-or lists, and lists.
+//!
+//! To extend the tests:
+//!
+//! - To cover more lexical elements, add source strings to [`WORDS`],
+//!   [`ASSIGNS`], or [`REDIRS`]. Words must not be reserved words, assignments,
+//!   or names of declaration utilities because those change how the
+//!   surrounding simple command is parsed.
+//! - To cover another kind of AST node, write a generator function for it,
+//!   implement [`SameModuloLocation`] for it, and make the generator of the
+//!   enclosing node call the new function. For example, a generator for
+//!   compound commands would be called from [`command`].
+
+use super::*;
+
+/// Words used as the words of simple commands
+const WORDS: &[&str] = &[
+    "echo",
+    "foo",
+    "-n",
+    "'single quoted'",
+    "\"double $quoted\"",
+    "\\&escaped",
+    "$var",
+    "${param:-default}",
+    "${#length}",
+    "$(nested; list& other)",
+    "`backquoted`",
+    "$((1 + 2))",
+    "~/tilde",
+    "*.glob",
+];
+
+/// Assignments used in simple commands
+const ASSIGNS: &[&str] = &[
+    "a=1",
+    "b=",
+    "c=$x",
+    "d='quoted value'",
+    "e=()",
+    "f=(array $elements)",
+];
+
+/// Redirections used in simple commands
+const REDIRS: &[&str] = &[">out", "2>&1", "<in", ">>log", "<>rw", ">|clobber", "3<&-"];
+
+/// Number of ASTs generated in each test
+const ITERATIONS: usize = 500;
+
+/// Pseudo-random number generator (xorshift64)
+struct Rng(u64);
+
+impl Rng {
+    fn next_u64(&mut self) -> u64 {
+        let mut x = self.0;
+        x ^= x << 13;
+        x ^= x >> 7;
+        x ^= x << 17;
+        self.0 = x;
+        x
+    }
+
+    /// Returns a number in the range `0..n`.
+    fn below(&mut self, n: usize) -> usize {
+        (self.next_u64() % n as u64) as usize
+    }
+
+    /// Returns `true` with the probability of 1/`n`.
+    fn one_in(&mut self, n: usize) -> bool {
+        self.below(n) == 0
+    }
+
+    /// Returns a random element of `items`.
+    fn pick<'a>(&mut self, items: &[&'a str]) -> &'a str {
+        items[self.below(items.len())]
+    }
+}
+
+fn simple_command(rng: &mut Rng) -> SimpleCommand {
+    let count = rng.below(3);
+    let assigns = (0..count)
+        .map(|_| rng.pick(ASSIGNS).parse().unwrap())
+        .collect();
+    let count = 1 + rng.below(3);
+    let words = (0..count)
+        .map(|_| (rng.pick(WORDS).parse().unwrap(), ExpansionMode::Multiple))
+        .collect();
+    let count = rng.below(3);
+    let redirs = (0..count)
+        .map(|_| rng.pick(REDIRS).parse().unwrap())
+        .collect();
+    SimpleCommand {
+        assigns,
+        words,
+        redirs: Rc::new(redirs),
+    }
+}
+
+fn command(rng: &mut Rng) -> Command {
+    Command::Simple(simple_command(rng))
+}
+
+fn pipeline(rng: &mut Rng) -> Pipeline {
+    let count = 1 + rng.below(3);
+    let commands = (0..count).map(|_| Rc::new(command(rng))).collect();
+    let negation = rng.one_in(4);
+    Pipeline { commands, negation }
+}
+
+fn and_or_list(rng: &mut Rng) -> AndOrList {
+    let first = pipeline(rng);
+    let count = rng.below(3);
+    let rest = (0..count)
+        .map(|_| {
+            let and_or = if rng.one_in(2) {
+                AndOr::AndThen
+            } else {
+                AndOr::OrElse
+            };
+            (and_or, pipeline(rng))
+        })
+        .collect();
+    AndOrList { first, rest }
+}
+
+fn list(rng: &mut Rng) -> List {
+    let count = 1 + rng.below(3);
+    let items = (0..count)
+        .map(|_| {
+            let and_or = Rc::new(and_or_list(rng));
+            let async_flag = rng.one_in(4).then(|| Location::dummy("&"));
+            Item { and_or, async_flag }
+        })
+        .collect();
+    List(items)
+}
+
+/// Structural equality that ignores locations
+///
+/// Leaf elements are compared by their string representations.
+trait SameModuloLocation {
+    fn same(&self, other: &Self) -> bool;
+}
+
+impl SameModuloLocation for SimpleCommand {
+    fn same(&self, other: &Self) -> bool {
+        fn same_strings<T: std::fmt::Display>(left: &[T], right: &[T]) -> bool {
+            left.len() == right.len()
+                && left
+                    .iter()
+                    .zip(right)
+                    .all(|(l, r)| l.to_string() == r.to_string())
+        }
+
+        same_strings(&self.assigns, &other.assigns)
+            && self.words.len() == other.words.len()
+            && self
+                .words
+                .iter()
+                .zip(&other.words)
+                .all(|((lw, lm), (rw, rm))| lw.to_string() == rw.to_string() && lm == rm)
+            && same_strings(&self.redirs[..], &other.redirs[..])
+    }
+}
+
+impl SameModuloLocation for Command {
+    fn same(&self, other: &Self) -> bool {
+        match (self, other) {
+            (Command::Simple(l), Command::Simple(r)) => l.same(r),
+            // Other kinds of commands are not generated yet.
+            _ => self.to_string() == other.to_string(),
+        }
+    }
+}
+
+impl SameModuloLocation for Pipeline {
+    fn same(&self, other: &Self) -> bool {
+        self.negation == other.negation
+            && self.commands.len() == other.commands.len()
+            && self
+                .commands
+                .iter()
+                .zip(&other.commands)
+                .all(|(l, r)| l.same(r))
+    }
+}
+
+impl SameModuloLocation for AndOrList {
+    fn same(&self, other: &Self) -> bool {
+        self.first.same(&other.first)
+            && self.rest.len() == other.rest.len()
+            && self
+                .rest
+                .iter()
+                .zip(&other.rest)
+                .all(|((lc, lp), (rc, rp))| lc == rc && lp.same(rp))
+    }
+}
+
+impl SameModuloLocation for List {
+    fn same(&self, other: &Self) -> bool {
+        self.0.len() == other.0.len()
+            && self.0.iter().zip(&other.0).all(|(l, r)| {
+                l.async_flag.is_some() == r.async_flag.is_some() && l.and_or.same(&r.and_or)
+            })
+    }
+}
+
+/// Generates ASTs and checks that they survive a display-parse round trip.
+fn check_round_trip<T, F>(seed: u64, mut generate: F)
+where
+    T: SameModuloLocation + std::fmt::Display + FromStr,
+    T::Err: std::fmt::Debug,
+    F: FnMut(&mut Rng) -> T,
+{
+    let mut rng = Rng(seed);
+    for _ in 0..ITERATIONS {
+        let ast = generate(&mut rng);
+        let source = ast.to_string();
+        let reparsed = source.parse::<T>().unwrap();
+        assert!(
+            ast.same(&reparsed),
+            "round trip mismatch: {source:?} became {:?}",
+            reparsed.to_string(),
+        );
+    }
+}
+
+#[test]
+fn simple_command_round_trip() {
+    check_round_trip(0x5EED_0001, simple_command);
+}
+
+#[test]
+fn pipeline_round_trip() {
+    check_round_trip(0x5EED_0002, pipeline);
+}
+
+#[test]
+fn and_or_list_round_trip() {
+    check_round_trip(0x5EED_0003, and_or_list);
+}
+
+#[test]
+fn list_round_trip() {
+    check_round_trip(0x5EED_0004, list);
+}
+
+#[test]
+fn same_modulo_location_detects_differences() {
+    let left: List = "foo | bar && baz&".parse().unwrap();
+    let right: List = "foo | bar && baz&".parse().unwrap();
+    assert!(left.same(&right));
+
+    for other in [
+        "foo | bar && baz",
+        "foo | bar || baz&",
+        "! foo | bar && baz&",
+    ] {
+        let right: List = other.parse().unwrap();
+        assert!(!left.same(&right), "{other:?}");
+    }
+}